@@ -0,0 +1,3 @@
+-- Add approval_policy column to repos table
+-- JSON-encoded allow/deny/ask rules evaluated before asking the user to approve a tool call
+ALTER TABLE repos ADD COLUMN approval_policy TEXT;
@@ -0,0 +1,13 @@
+-- Task dependencies: task_id cannot start until depends_on_task_id is done
+CREATE TABLE task_dependencies (
+    id                 BLOB PRIMARY KEY,
+    task_id            BLOB NOT NULL,
+    depends_on_task_id BLOB NOT NULL,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (task_id, depends_on_task_id),
+    CHECK (task_id != depends_on_task_id)
+);
+CREATE INDEX idx_task_dependencies_task_id ON task_dependencies(task_id);
+CREATE INDEX idx_task_dependencies_depends_on_task_id ON task_dependencies(depends_on_task_id);
@@ -0,0 +1,13 @@
+-- Follow-up messages waiting for the current execution of a session to finish,
+-- consumed in position order
+CREATE TABLE queued_messages (
+    id                  BLOB PRIMARY KEY,
+    session_id          BLOB NOT NULL,
+    position            INTEGER NOT NULL,
+    message             TEXT NOT NULL,
+    executor_profile_id TEXT NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_queued_messages_session_position ON queued_messages(session_id, position);
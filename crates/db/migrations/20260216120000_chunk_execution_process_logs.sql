@@ -0,0 +1,69 @@
+-- Store execution logs as batches of JSONL lines instead of one row per line.
+-- Chunks written by the server are zstd-compressed; rows compacted here are stored
+-- uncompressed and recompressed by the server after startup.
+CREATE TABLE execution_process_log_chunks (
+    execution_id BLOB NOT NULL,
+    seq          INTEGER NOT NULL,   -- chunk index within the execution, starting at 0
+    first_line   INTEGER NOT NULL,   -- index of the first line stored in this chunk
+    line_count   INTEGER NOT NULL,
+    byte_size    INTEGER NOT NULL,   -- uncompressed size
+    compression  TEXT NOT NULL DEFAULT 'zstd'
+                 CHECK (compression IN ('none', 'zstd')),
+    data         BLOB NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (execution_id, seq),
+    FOREIGN KEY (execution_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+-- Compact existing rows into chunks of up to 256 rows. Older databases stored a whole
+-- JSONL document per row, so lines are counted by newline rather than by row.
+INSERT INTO execution_process_log_chunks (
+    execution_id,
+    seq,
+    first_line,
+    line_count,
+    byte_size,
+    compression,
+    data,
+    created_at
+)
+SELECT
+    execution_id,
+    seq,
+    COALESCE(
+        SUM(line_count) OVER (
+            PARTITION BY execution_id
+            ORDER BY seq
+            ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING
+        ),
+        0
+    ),
+    line_count,
+    LENGTH(data),
+    'none',
+    data,
+    created_at
+FROM (
+    SELECT
+        execution_id,
+        seq,
+        SUM(LENGTH(logs) - LENGTH(REPLACE(logs, char(10), ''))) AS line_count,
+        CAST(group_concat(logs, '' ORDER BY inserted_at, row_id) AS BLOB) AS data,
+        MIN(inserted_at) AS created_at
+    FROM (
+        SELECT
+            execution_id,
+            rowid AS row_id,
+            inserted_at,
+            CASE WHEN substr(logs, -1) = char(10) THEN logs ELSE logs || char(10) END AS logs,
+            (ROW_NUMBER() OVER (
+                PARTITION BY execution_id
+                ORDER BY inserted_at, rowid
+            ) - 1) / 256 AS seq
+        FROM execution_process_logs
+        WHERE logs != ''
+    )
+    GROUP BY execution_id, seq
+);
+
+DROP TABLE execution_process_logs;
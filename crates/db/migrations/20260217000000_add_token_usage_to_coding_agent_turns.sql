@@ -0,0 +1,6 @@
+-- Token usage reported by the coding agent for each turn. NULL until the agent reports it.
+ALTER TABLE coding_agent_turns ADD COLUMN model TEXT;
+ALTER TABLE coding_agent_turns ADD COLUMN input_tokens INTEGER;
+ALTER TABLE coding_agent_turns ADD COLUMN output_tokens INTEGER;
+ALTER TABLE coding_agent_turns ADD COLUMN cache_read_tokens INTEGER;
+ALTER TABLE coding_agent_turns ADD COLUMN cache_write_tokens INTEGER;
@@ -0,0 +1,3 @@
+-- Add default_merge_strategy column to repos table
+-- One of squash, merge_commit, rebase, fast_forward; NULL means squash
+ALTER TABLE repos ADD COLUMN default_merge_strategy TEXT;
@@ -0,0 +1,63 @@
+-- Add check_script column to repos table
+-- This script verifies a workspace before the merge queue lands it
+ALTER TABLE repos ADD COLUMN check_script TEXT;
+
+-- Add 'checkscript' to the run_reason CHECK constraint
+
+-- 1. Add the replacement column with the wider CHECK
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                               'cleanupscript',
+                               'archivescript',
+                               'checkscript',
+                               'codingagent',
+                               'devserver'));
+
+-- 2. Copy existing values across
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop any indexes that reference run_reason
+DROP INDEX IF EXISTS idx_execution_processes_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_status_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_run_reason_created;
+
+-- 4. Remove the old column (requires 3.35+)
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 5. Rename the new column back to the canonical name
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+
+-- 6. Re-create all indexes
+CREATE INDEX idx_execution_processes_run_reason
+        ON execution_processes(run_reason);
+
+CREATE INDEX idx_execution_processes_session_status_run_reason
+        ON execution_processes (session_id, status, run_reason);
+
+CREATE INDEX idx_execution_processes_session_run_reason_created
+        ON execution_processes (session_id, run_reason, created_at DESC);
+
+-- Workspaces waiting to be rebased, checked and merged into a repo's target branch,
+-- processed in position order. Entries are removed once merged; a failed or
+-- conflicted entry pauses the rest of its repo's queue.
+CREATE TABLE merge_queue_entries (
+    id                   BLOB PRIMARY KEY,
+    repo_id              BLOB NOT NULL,
+    workspace_id         BLOB NOT NULL,
+    position             INTEGER NOT NULL,
+    status               TEXT NOT NULL DEFAULT 'queued'
+                           CHECK (status IN ('queued', 'checking', 'failed', 'conflict')),
+    execution_process_id BLOB,
+    error                TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+CREATE INDEX idx_merge_queue_entries_repo_position ON merge_queue_entries(repo_id, position);
+CREATE UNIQUE INDEX idx_merge_queue_entries_workspace_repo
+        ON merge_queue_entries(workspace_id, repo_id);
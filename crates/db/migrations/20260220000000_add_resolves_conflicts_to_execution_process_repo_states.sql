@@ -0,0 +1,4 @@
+-- Add resolves_conflicts column to execution_process_repo_states table
+-- Set on coding agent turns started to resolve a repo's in-progress conflicts
+ALTER TABLE execution_process_repo_states
+    ADD COLUMN resolves_conflicts BOOLEAN NOT NULL DEFAULT FALSE;
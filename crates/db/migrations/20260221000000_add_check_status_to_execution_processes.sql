@@ -0,0 +1,7 @@
+-- Add check_status and check_fix_attempt columns to execution_processes table
+-- check_status records the outcome of the repo check scripts run after a coding agent turn
+ALTER TABLE execution_processes
+    ADD COLUMN check_status TEXT CHECK (check_status IN ('pending', 'passed', 'failed'));
+-- Number of automatic follow-ups in a row that were sent to fix failing checks
+ALTER TABLE execution_processes
+    ADD COLUMN check_fix_attempt INTEGER NOT NULL DEFAULT 0;
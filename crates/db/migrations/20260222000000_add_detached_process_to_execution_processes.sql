@@ -0,0 +1,4 @@
+-- Add pid and spool_dir columns to execution_processes table
+-- Detached processes keep running across server restarts; these record how to reattach to them
+ALTER TABLE execution_processes ADD COLUMN pid INTEGER;
+ALTER TABLE execution_processes ADD COLUMN spool_dir TEXT;
@@ -0,0 +1,14 @@
+-- Add fan_out_workspaces table
+-- Groups the workspaces started together on one task with different executor profiles,
+-- in the order they were requested, so their results can be compared side by side.
+CREATE TABLE fan_out_workspaces (
+    workspace_id        BLOB PRIMARY KEY,
+    fan_out_id          BLOB NOT NULL,
+    task_id             BLOB NOT NULL,
+    position            INTEGER NOT NULL,
+    executor_profile_id TEXT NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+CREATE INDEX idx_fan_out_workspaces_fan_out_position ON fan_out_workspaces(fan_out_id, position);
@@ -0,0 +1,4 @@
+-- Add checked_process_id column to execution_processes table
+-- Links a check script to the coding agent turn whose changes it verifies; merge queue checks have none
+ALTER TABLE execution_processes
+    ADD COLUMN checked_process_id BLOB REFERENCES execution_processes(id) ON DELETE SET NULL;
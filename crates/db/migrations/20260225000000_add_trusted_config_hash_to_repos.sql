@@ -0,0 +1,3 @@
+-- Add trusted_config_hash column to repos table
+-- Hash of the committed .vibe-kanban.toml whose scripts the user approved to run
+ALTER TABLE repos ADD COLUMN trusted_config_hash TEXT;
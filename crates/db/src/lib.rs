@@ -8,6 +8,8 @@ use sqlx::{
 use utils::assets::asset_dir;
 
 pub mod models;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), Error> {
     use std::collections::HashSet;
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
+use utils::log_msg::TokenUsage;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -215,6 +216,40 @@ impl CodingAgentTurn {
         Ok(())
     }
 
+    /// Record the token usage reported by the agent, replacing any earlier report
+    pub async fn update_token_usage(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        usage: &TokenUsage,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let input_tokens = usage.input_tokens as i64;
+        let output_tokens = usage.output_tokens as i64;
+        let cache_read_tokens = usage.cache_read_tokens as i64;
+        let cache_write_tokens = usage.cache_write_tokens as i64;
+        sqlx::query!(
+            r#"UPDATE coding_agent_turns
+               SET model = COALESCE($1, model),
+                   input_tokens = $2,
+                   output_tokens = $3,
+                   cache_read_tokens = $4,
+                   cache_write_tokens = $5,
+                   updated_at = $6
+               WHERE execution_process_id = $7"#,
+            usage.model,
+            input_tokens,
+            output_tokens,
+            cache_read_tokens,
+            cache_write_tokens,
+            now,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Mark all coding agent turns for a workspace as seen
     pub async fn mark_seen_by_workspace_id(
         pool: &SqlitePool,
@@ -43,6 +43,7 @@ pub enum ExecutionProcessError {
 #[serde(rename_all = "lowercase")]
 #[ts(use_ts_enum)]
 pub enum ExecutionProcessStatus {
+    Queued,
     Running,
     Completed,
     Failed,
@@ -56,10 +57,21 @@ pub enum ExecutionProcessRunReason {
     SetupScript,
     CleanupScript,
     ArchiveScript,
+    CheckScript,
     CodingAgent,
     DevServer,
 }
 
+/// Outcome of the repo check scripts run after a coding agent turn
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "check_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pending,
+    Passed,
+    Failed,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcess {
     pub id: Uuid,
@@ -73,6 +85,18 @@ pub struct ExecutionProcess {
     /// history view (due to restore/trimming). Hidden from logs/timeline;
     /// still listed in the Processes tab.
     pub dropped: bool,
+    /// Result of the check scripts run on this coding agent turn's changes, if any ran
+    pub check_status: Option<CheckStatus>,
+    /// Automatic follow-ups in a row, including this turn, sent to fix failing checks
+    pub check_fix_attempt: i64,
+    /// Process group of a detached process, used to reattach to it after a restart
+    #[serde(skip)]
+    #[ts(skip)]
+    pub pid: Option<i64>,
+    /// Directory a detached process spools its output and exit code to
+    #[serde(skip)]
+    #[ts(skip)]
+    pub spool_dir: Option<String>,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -84,6 +108,9 @@ pub struct CreateExecutionProcess {
     pub session_id: Uuid,
     pub executor_action: ExecutorAction,
     pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    /// The coding agent turn whose changes a check script verifies
+    pub checked_process_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -145,6 +172,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -219,6 +250,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -246,6 +281,10 @@ impl ExecutionProcess {
                       ep.status          as "status!: ExecutionProcessStatus",
                       ep.exit_code,
                       ep.dropped as "dropped!: bool",
+                      ep.check_status as "check_status: CheckStatus",
+                      ep.check_fix_attempt as "check_fix_attempt!: i64",
+                      ep.pid,
+                      ep.spool_dir,
                       ep.started_at      as "started_at!: DateTime<Utc>",
                       ep.completed_at    as "completed_at?: DateTime<Utc>",
                       ep.created_at      as "created_at!: DateTime<Utc>",
@@ -273,6 +312,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -283,6 +326,48 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find queued execution processes, oldest first
+    pub async fn find_queued(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                    ep.id as "id!: Uuid",
+                    ep.session_id as "session_id!: Uuid",
+                    ep.run_reason as "run_reason!: ExecutionProcessRunReason",
+                    ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                    ep.status as "status!: ExecutionProcessStatus",
+                    ep.exit_code,
+                    ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
+                    ep.started_at as "started_at!: DateTime<Utc>",
+                    ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.created_at as "created_at!: DateTime<Utc>",
+                    ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep WHERE ep.status = 'queued' ORDER BY ep.created_at ASC"#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a queued process to running. Returns false if it was no longer queued,
+    /// e.g. because it was stopped or already started elsewhere.
+    pub async fn mark_dequeued(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET status = 'running', started_at = $2, updated_at = $2
+               WHERE id = $1 AND status = 'queued'"#,
+            id,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
@@ -292,7 +377,7 @@ impl ExecutionProcess {
             ExecutionProcess,
             r#"SELECT ep.id as "id!: Uuid", ep.session_id as "session_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
                       ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
-                      ep.dropped as "dropped!: bool", ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+                      ep.dropped as "dropped!: bool", ep.check_status as "check_status: CheckStatus", ep.check_fix_attempt as "check_fix_attempt!: i64", ep.pid, ep.spool_dir, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_processes ep
                JOIN sessions s ON ep.session_id = s.id
                JOIN workspaces w ON s.workspace_id = w.id
@@ -305,7 +390,7 @@ impl ExecutionProcess {
         .await
     }
 
-    /// Check if there are running processes (excluding dev servers) for a workspace (across all sessions)
+    /// Check if there are running or queued processes (excluding dev servers) for a workspace (across all sessions)
     pub async fn has_running_non_dev_server_processes_for_workspace(
         pool: &SqlitePool,
         workspace_id: Uuid,
@@ -315,7 +400,7 @@ impl ExecutionProcess {
                FROM execution_processes ep
                JOIN sessions s ON ep.session_id = s.id
                WHERE s.workspace_id = $1
-                 AND ep.status = 'running'
+                 AND ep.status IN ('running', 'queued')
                  AND ep.run_reason != 'devserver'"#,
             workspace_id
         )
@@ -340,6 +425,10 @@ impl ExecutionProcess {
             ep.status as "status!: ExecutionProcessStatus",
             ep.exit_code,
             ep.dropped as "dropped!: bool",
+            ep.check_status as "check_status: CheckStatus",
+            ep.check_fix_attempt as "check_fix_attempt!: i64",
+            ep.pid,
+            ep.spool_dir,
             ep.started_at as "started_at!: DateTime<Utc>",
             ep.completed_at as "completed_at?: DateTime<Utc>",
             ep.created_at as "created_at!: DateTime<Utc>",
@@ -373,6 +462,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -403,6 +496,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -437,14 +534,16 @@ impl ExecutionProcess {
         sqlx::query!(
             r#"INSERT INTO execution_processes (
                     id, session_id, run_reason, executor_action,
-                    status, exit_code, started_at, completed_at, created_at, updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                    status, exit_code, checked_process_id,
+                    started_at, completed_at, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
             process_id,
             data.session_id,
             data.run_reason,
             executor_action_json,
-            ExecutionProcessStatus::Running,
+            data.status,
             None::<i64>,
+            data.checked_process_id,
             now,
             None::<DateTime<Utc>>,
             now,
@@ -479,7 +578,10 @@ impl ExecutionProcess {
         status: ExecutionProcessStatus,
         exit_code: Option<i64>,
     ) -> Result<(), sqlx::Error> {
-        let completed_at = if matches!(status, ExecutionProcessStatus::Running) {
+        let completed_at = if matches!(
+            status,
+            ExecutionProcessStatus::Queued | ExecutionProcessStatus::Running
+        ) {
             None
         } else {
             Some(Utc::now())
@@ -500,6 +602,112 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Record the process group and spool directory of a detached process
+    pub async fn set_detached(
+        pool: &SqlitePool,
+        id: Uuid,
+        pid: i64,
+        spool_dir: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET pid = $1, spool_dir = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $3"#,
+            pid,
+            spool_dir,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of the check scripts run on a coding agent turn
+    pub async fn set_check_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        check_status: Option<CheckStatus>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET check_status = $1, updated_at = datetime('now', 'subsec')
+               WHERE id = $2"#,
+            check_status,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_check_fix_attempt(
+        pool: &SqlitePool,
+        id: Uuid,
+        check_fix_attempt: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_processes
+               SET check_fix_attempt = $1, updated_at = datetime('now', 'subsec')
+               WHERE id = $2"#,
+            check_fix_attempt,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The coding agent turn a check script verifies, if it runs on one
+    pub async fn find_checked_process_id(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let checked_process_id = sqlx::query_scalar!(
+            r#"SELECT checked_process_id as "checked_process_id: Uuid"
+               FROM execution_processes
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(checked_process_id.flatten())
+    }
+
+    /// Find the coding agent turn a check script verifies, while its checks are pending.
+    /// Checks the merge queue runs have no such turn.
+    pub async fn find_pending_check_for_process(
+        pool: &SqlitePool,
+        check_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                    ep.id as "id!: Uuid",
+                    ep.session_id as "session_id!: Uuid",
+                    ep.run_reason as "run_reason!: ExecutionProcessRunReason",
+                    ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                    ep.status as "status!: ExecutionProcessStatus",
+                    ep.exit_code,
+                    ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
+                    ep.started_at as "started_at!: DateTime<Utc>",
+                    ep.completed_at as "completed_at?: DateTime<Utc>",
+                    ep.created_at as "created_at!: DateTime<Utc>",
+                    ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN execution_processes check_ep ON check_ep.checked_process_id = ep.id
+               WHERE check_ep.id = $1
+                 AND ep.check_status = 'pending'
+                 AND ep.dropped = FALSE"#,
+            check_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub fn executor_action(&self) -> Result<&ExecutorAction, anyhow::Error> {
         match &self.executor_action.0 {
             ExecutorActionField::ExecutorAction(action) => Ok(action),
@@ -630,6 +838,10 @@ impl ExecutionProcess {
                     ep.status as "status!: ExecutionProcessStatus",
                     ep.exit_code,
                     ep.dropped as "dropped!: bool",
+                    ep.check_status as "check_status: CheckStatus",
+                    ep.check_fix_attempt as "check_fix_attempt!: i64",
+                    ep.pid,
+                    ep.spool_dir,
                     ep.started_at as "started_at!: DateTime<Utc>",
                     ep.completed_at as "completed_at?: DateTime<Utc>",
                     ep.created_at as "created_at!: DateTime<Utc>",
@@ -1,69 +1,332 @@
+use std::ops::Range;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
 use ts_rs::TS;
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+/// Flush a chunk once it holds this many lines
+pub const LOG_CHUNK_MAX_LINES: usize = 256;
+/// Flush a chunk once its uncompressed size reaches this many bytes
+pub const LOG_CHUNK_MAX_BYTES: usize = 256 * 1024;
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Error)]
+pub enum ExecutionProcessLogsError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Failed to (de)compress log chunk: {0}")]
+    Compression(#[from] std::io::Error),
+    #[error("Log chunk is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Copy, Type, PartialEq, Eq)]
+#[sqlx(type_name = "log_compression", rename_all = "lowercase")]
+pub enum LogCompression {
+    /// Rows compacted by the migration, waiting to be recompressed
+    None,
+    Zstd,
+}
+
+/// A batch of consecutive JSONL log lines for one execution process
+#[derive(Debug, Clone, FromRow)]
 pub struct ExecutionProcessLogs {
     pub execution_id: Uuid,
-    pub logs: String, // JSONL format
-    pub byte_size: i64,
-    pub inserted_at: DateTime<Utc>,
+    pub seq: i64,        // Chunk index within the execution, starting at 0
+    pub first_line: i64, // Index of the first line stored in this chunk
+    pub line_count: i64,
+    pub byte_size: i64, // Uncompressed size
+    pub compression: LogCompression,
+    pub data: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Size information used by clients to paginate through stored logs
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessLogStats {
+    #[ts(type = "number")]
+    pub line_count: u64,
+    #[ts(type = "number")]
+    pub chunk_count: u64,
+    /// Uncompressed size of all lines
+    #[ts(type = "number")]
+    pub byte_size: u64,
+    /// Size on disk after compression
+    #[ts(type = "number")]
+    pub stored_size: u64,
 }
 
 impl ExecutionProcessLogs {
-    /// Find logs by execution process ID
+    /// Find every log chunk of an execution process, in order
     pub async fn find_by_execution_id(
         pool: &SqlitePool,
         execution_id: Uuid,
     ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             ExecutionProcessLogs,
-            r#"SELECT 
+            r#"SELECT
                 execution_id as "execution_id!: Uuid",
-                logs,
+                seq,
+                first_line,
+                line_count,
                 byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>"
-               FROM execution_process_logs 
+                compression as "compression!: LogCompression",
+                data,
+                created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_log_chunks
                WHERE execution_id = $1
-               ORDER BY inserted_at ASC"#,
+               ORDER BY seq ASC"#,
             execution_id
         )
         .fetch_all(pool)
         .await
     }
 
-    /// Parse JSONL logs back into Vec<LogMsg>
-    pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, serde_json::Error> {
+    /// Find the chunks that contain any of the lines in `lines`
+    pub async fn find_by_line_range(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        lines: Range<i64>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessLogs,
+            r#"SELECT
+                execution_id as "execution_id!: Uuid",
+                seq,
+                first_line,
+                line_count,
+                byte_size,
+                compression as "compression!: LogCompression",
+                data,
+                created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1
+                 AND first_line + line_count > $2
+                 AND first_line < $3
+               ORDER BY seq ASC"#,
+            execution_id,
+            lines.start,
+            lines.end
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn stats(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+    ) -> Result<ExecutionProcessLogStats, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT
+                COALESCE(SUM(line_count), 0) as "line_count!: i64",
+                COUNT(*) as "chunk_count!: i64",
+                COALESCE(SUM(byte_size), 0) as "byte_size!: i64",
+                COALESCE(SUM(LENGTH(data)), 0) as "stored_size!: i64"
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1"#,
+            execution_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(ExecutionProcessLogStats {
+            line_count: row.line_count as u64,
+            chunk_count: row.chunk_count as u64,
+            byte_size: row.byte_size as u64,
+            stored_size: row.stored_size as u64,
+        })
+    }
+
+    /// Decompress the chunk into its JSONL text
+    pub fn decode(&self) -> Result<String, ExecutionProcessLogsError> {
+        let bytes = match self.compression {
+            LogCompression::None => self.data.clone(),
+            LogCompression::Zstd => zstd::decode_all(self.data.as_slice())?,
+        };
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Parse JSONL chunks back into Vec<LogMsg>
+    pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, ExecutionProcessLogsError> {
         let mut messages = Vec::new();
-        for line in records.iter().flat_map(|record| record.logs.lines()) {
-            if !line.trim().is_empty() {
-                let msg: LogMsg = serde_json::from_str(line)?;
-                messages.push(msg);
+        for record in records {
+            for line in record.decode()?.lines() {
+                if !line.trim().is_empty() {
+                    messages.push(serde_json::from_str(line)?);
+                }
             }
         }
         Ok(messages)
     }
 
-    /// Append a JSONL line to the logs for an execution process
-    pub async fn append_log_line(
+    /// Read `limit` lines starting at line `offset`, decompressing only the chunks
+    /// that overlap the range. Reads to the end when `limit` is None.
+    pub async fn read_lines(
         pool: &SqlitePool,
         execution_id: Uuid,
-        jsonl_line: &str,
-    ) -> Result<(), sqlx::Error> {
-        let byte_size = jsonl_line.len() as i64;
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogMsg>, ExecutionProcessLogsError> {
+        let start = offset as i64;
+        let end = limit.map_or(i64::MAX, |limit| start.saturating_add(limit as i64));
+        let chunks = Self::find_by_line_range(pool, execution_id, start..end).await?;
+
+        let mut messages = Vec::new();
+        for chunk in &chunks {
+            for (i, line) in chunk.decode()?.lines().enumerate() {
+                let index = chunk.first_line + i as i64;
+                if index < start || index >= end || line.trim().is_empty() {
+                    continue;
+                }
+                messages.push(serde_json::from_str(line)?);
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Store `lines` (each a JSONL line ending in a newline) as the next chunk of
+    /// an execution process
+    pub async fn append_chunk(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        lines: &[String],
+    ) -> Result<(), ExecutionProcessLogsError> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let text = lines.concat();
+        let byte_size = text.len() as i64;
+        let line_count = lines.len() as i64;
+        let data = zstd::encode_all(text.as_bytes(), ZSTD_LEVEL)?;
+        let compression = LogCompression::Zstd;
+
         sqlx::query!(
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, datetime('now', 'subsec'))"#,
+            r#"INSERT INTO execution_process_log_chunks
+                   (execution_id, seq, first_line, line_count, byte_size, compression, data)
+               SELECT $1,
+                      COALESCE(MAX(seq) + 1, 0),
+                      COALESCE(MAX(first_line + line_count), 0),
+                      $2, $3, $4, $5
+               FROM execution_process_log_chunks
+               WHERE execution_id = $1"#,
             execution_id,
-            jsonl_line,
-            byte_size
+            line_count,
+            byte_size,
+            compression,
+            data
         )
         .execute(pool)
         .await?;
 
         Ok(())
     }
+
+    /// Append a single JSONL line as its own chunk. Prefer [`LogChunkWriter`] for
+    /// streams of lines.
+    pub async fn append_log_line(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        jsonl_line: &str,
+    ) -> Result<(), ExecutionProcessLogsError> {
+        Self::append_chunk(pool, execution_id, &[jsonl_line.to_string()]).await
+    }
+
+    /// Delete every stored log chunk of an execution process
+    pub async fn delete_by_execution_id(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM execution_process_log_chunks WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Compress chunks left uncompressed by the log compaction migration.
+    /// Returns the number of chunks compressed.
+    pub async fn compress_pending_chunks(
+        pool: &SqlitePool,
+    ) -> Result<u64, ExecutionProcessLogsError> {
+        let mut compressed = 0;
+        loop {
+            let pending = sqlx::query!(
+                r#"SELECT execution_id as "execution_id!: Uuid", seq, data
+                   FROM execution_process_log_chunks
+                   WHERE compression = 'none'
+                   LIMIT 64"#
+            )
+            .fetch_all(pool)
+            .await?;
+            if pending.is_empty() {
+                return Ok(compressed);
+            }
+
+            for chunk in pending {
+                let data = zstd::encode_all(chunk.data.as_slice(), ZSTD_LEVEL)?;
+                sqlx::query!(
+                    r#"UPDATE execution_process_log_chunks
+                       SET data = $3, compression = 'zstd'
+                       WHERE execution_id = $1 AND seq = $2"#,
+                    chunk.execution_id,
+                    chunk.seq,
+                    data
+                )
+                .execute(pool)
+                .await?;
+                compressed += 1;
+            }
+        }
+    }
+}
+
+/// Buffers JSONL lines for one execution process and writes them as compressed
+/// chunks once enough have accumulated
+pub struct LogChunkWriter {
+    execution_id: Uuid,
+    lines: Vec<String>,
+    bytes: usize,
+}
+
+impl LogChunkWriter {
+    pub fn new(execution_id: Uuid) -> Self {
+        Self {
+            execution_id,
+            lines: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Buffer a line, flushing when the chunk is full
+    pub async fn push(
+        &mut self,
+        pool: &SqlitePool,
+        jsonl_line: String,
+    ) -> Result<(), ExecutionProcessLogsError> {
+        self.bytes += jsonl_line.len();
+        self.lines.push(jsonl_line);
+        if self.lines.len() >= LOG_CHUNK_MAX_LINES || self.bytes >= LOG_CHUNK_MAX_BYTES {
+            self.flush(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Write any buffered lines as a chunk
+    pub async fn flush(&mut self, pool: &SqlitePool) -> Result<(), ExecutionProcessLogsError> {
+        if self.lines.is_empty() {
+            return Ok(());
+        }
+        ExecutionProcessLogs::append_chunk(pool, self.execution_id, &self.lines).await?;
+        self.lines.clear();
+        self.bytes = 0;
+        Ok(())
+    }
 }
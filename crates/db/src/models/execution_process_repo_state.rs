@@ -12,6 +12,8 @@ pub struct ExecutionProcessRepoState {
     pub before_head_commit: Option<String>,
     pub after_head_commit: Option<String>,
     pub merge_commit: Option<String>,
+    /// Whether the process was started to resolve the repo's in-progress conflicts
+    pub resolves_conflicts: bool,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -133,6 +135,26 @@ impl ExecutionProcessRepoState {
         Ok(())
     }
 
+    pub async fn set_resolves_conflicts(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE execution_process_repo_states
+               SET resolves_conflicts = TRUE, updated_at = $1
+             WHERE execution_process_id = $2
+               AND repo_id = $3"#,
+            now,
+            execution_process_id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn find_by_execution_process_id(
         pool: &SqlitePool,
         execution_process_id: Uuid,
@@ -146,6 +168,7 @@ impl ExecutionProcessRepoState {
                     before_head_commit,
                     after_head_commit,
                     merge_commit,
+                    resolves_conflicts as "resolves_conflicts!: bool",
                     created_at as "created_at!: DateTime<Utc>",
                     updated_at as "updated_at!: DateTime<Utc>"
                FROM execution_process_repo_states
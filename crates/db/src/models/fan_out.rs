@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A workspace started as one of several attempts at a task, each with a different
+/// executor profile, so that their results can be compared
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct FanOutWorkspace {
+    pub workspace_id: Uuid,
+    pub fan_out_id: Uuid,
+    pub task_id: Uuid,
+    pub position: i64,
+    pub executor_profile_id: sqlx::types::Json<ExecutorProfileId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FanOutWorkspace {
+    pub async fn create(
+        pool: &SqlitePool,
+        fan_out_id: Uuid,
+        task_id: Uuid,
+        workspace_id: Uuid,
+        position: i64,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = sqlx::types::Json(executor_profile_id);
+        sqlx::query_as!(
+            FanOutWorkspace,
+            r#"INSERT INTO fan_out_workspaces (workspace_id, fan_out_id, task_id, position, executor_profile_id)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING workspace_id as "workspace_id!: Uuid",
+                         fan_out_id as "fan_out_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         position as "position!: i64",
+                         executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            workspace_id,
+            fan_out_id,
+            task_id,
+            position,
+            executor_profile_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FanOutWorkspace,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      fan_out_id as "fan_out_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      position as "position!: i64",
+                      executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM fan_out_workspaces
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Workspaces of a fan-out in the order they were requested
+    pub async fn find_by_fan_out_id(
+        pool: &SqlitePool,
+        fan_out_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FanOutWorkspace,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      fan_out_id as "fan_out_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      position as "position!: i64",
+                      executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM fan_out_workspaces
+               WHERE fan_out_id = $1
+               ORDER BY position ASC"#,
+            fan_out_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+    use crate::models::{
+        project::{CreateProject, Project},
+        task::{CreateTask, Task},
+        workspace::{CreateWorkspace, Workspace},
+    };
+
+    async fn pool() -> SqlitePool {
+        crate::test_support::db().await.pool
+    }
+
+    async fn task(pool: &SqlitePool) -> Task {
+        let project_id = Uuid::new_v4();
+        let project = CreateProject {
+            name: "project".to_string(),
+            repositories: vec![],
+        };
+        Project::create(pool, &project, project_id).await.unwrap();
+        let task = CreateTask::from_title_description(project_id, "task".to_string(), None);
+        Task::create(pool, &task, Uuid::new_v4()).await.unwrap()
+    }
+
+    async fn workspace(pool: &SqlitePool, task: &Task) -> Workspace {
+        let id = Uuid::new_v4();
+        let data = CreateWorkspace {
+            branch: format!("vk/{id}"),
+            agent_working_dir: None,
+        };
+        Workspace::create(pool, &data, id, task.id).await.unwrap()
+    }
+
+    fn profile(executor: BaseCodingAgent) -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor,
+            variant: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn members_are_listed_in_requested_order() {
+        let pool = pool().await;
+        let task = task(&pool).await;
+        let fan_out_id = Uuid::new_v4();
+        let profiles = [
+            profile(BaseCodingAgent::ClaudeCode),
+            profile(BaseCodingAgent::Codex),
+            profile(BaseCodingAgent::Gemini),
+        ];
+
+        let mut workspace_ids = Vec::new();
+        for (position, executor_profile_id) in profiles.iter().enumerate().rev() {
+            let workspace = workspace(&pool, &task).await;
+            FanOutWorkspace::create(
+                &pool,
+                fan_out_id,
+                task.id,
+                workspace.id,
+                position as i64,
+                executor_profile_id,
+            )
+            .await
+            .unwrap();
+            workspace_ids.insert(0, workspace.id);
+        }
+
+        let members = FanOutWorkspace::find_by_fan_out_id(&pool, fan_out_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            members.iter().map(|m| m.workspace_id).collect::<Vec<_>>(),
+            workspace_ids
+        );
+        assert_eq!(
+            members
+                .iter()
+                .map(|m| m.executor_profile_id.0.clone())
+                .collect::<Vec<_>>(),
+            profiles
+        );
+
+        let member = FanOutWorkspace::find_by_workspace_id(&pool, workspace_ids[1])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(member.fan_out_id, fan_out_id);
+        assert_eq!(member.task_id, task.id);
+        assert_eq!(member.position, 1);
+
+        assert!(
+            FanOutWorkspace::find_by_fan_out_id(&pool, Uuid::new_v4())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn deleting_a_workspace_removes_it_from_the_fan_out() {
+        let pool = pool().await;
+        let task = task(&pool).await;
+        let fan_out_id = Uuid::new_v4();
+        let kept = workspace(&pool, &task).await;
+        let discarded = workspace(&pool, &task).await;
+        for (position, workspace) in [&kept, &discarded].into_iter().enumerate() {
+            FanOutWorkspace::create(
+                &pool,
+                fan_out_id,
+                task.id,
+                workspace.id,
+                position as i64,
+                &profile(BaseCodingAgent::ClaudeCode),
+            )
+            .await
+            .unwrap();
+        }
+
+        Workspace::delete(&pool, discarded.id).await.unwrap();
+
+        assert!(
+            FanOutWorkspace::find_by_workspace_id(&pool, discarded.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let members = FanOutWorkspace::find_by_fan_out_id(&pool, fan_out_id)
+            .await
+            .unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].workspace_id, kept.id);
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum MergeQueueError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Merge queue entry not found")]
+    NotFound,
+    #[error("Workspace is already queued for this repository")]
+    AlreadyQueued,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "merge_queue_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum MergeQueueStatus {
+    /// Waiting to be rebased onto the target branch
+    Queued,
+    /// Rebased; the repo's check script is running
+    Checking,
+    /// The check script or merge failed; the queue is paused
+    Failed,
+    /// Rebasing hit conflicts that must be resolved in the workspace; the queue is paused
+    Conflict,
+}
+
+impl MergeQueueStatus {
+    /// Whether an entry in this status holds up the rest of its repo's queue
+    pub fn is_paused(&self) -> bool {
+        matches!(self, MergeQueueStatus::Failed | MergeQueueStatus::Conflict)
+    }
+}
+
+/// A workspace waiting to be rebased, checked and merged into a repo's target branch
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct MergeQueueEntry {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    pub workspace_id: Uuid,
+    pub status: MergeQueueStatus,
+    /// The check script run for the latest attempt, if any
+    pub execution_process_id: Option<Uuid>,
+    /// Why the entry failed or which files conflict
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MergeQueueEntry {
+    /// Entries of a repo's queue in the order they will be merged
+    pub async fn find_by_repo_id(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            MergeQueueEntry,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      status as "status!: MergeQueueStatus",
+                      execution_process_id as "execution_process_id: Uuid",
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM merge_queue_entries
+               WHERE repo_id = $1
+               ORDER BY position ASC"#,
+            repo_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            MergeQueueEntry,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      status as "status!: MergeQueueStatus",
+                      execution_process_id as "execution_process_id: Uuid",
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM merge_queue_entries
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Repos with at least one queued workspace
+    pub async fn find_repo_ids(pool: &SqlitePool) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT repo_id as "repo_id!: Uuid" FROM merge_queue_entries"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Append a workspace to the end of a repo's queue
+    pub async fn create(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        workspace_id: Uuid,
+    ) -> Result<Self, MergeQueueError> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            MergeQueueEntry,
+            r#"INSERT INTO merge_queue_entries (id, repo_id, workspace_id, position)
+               VALUES (
+                   $1,
+                   $2,
+                   $3,
+                   (SELECT COALESCE(MAX(position) + 1, 0) FROM merge_queue_entries WHERE repo_id = $2)
+               )
+               RETURNING id as "id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         status as "status!: MergeQueueStatus",
+                         execution_process_id as "execution_process_id: Uuid",
+                         error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            repo_id,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                MergeQueueError::AlreadyQueued
+            }
+            e => MergeQueueError::Database(e),
+        })
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: MergeQueueStatus,
+        execution_process_id: Option<Uuid>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merge_queue_entries
+               SET status = $2,
+                   execution_process_id = $3,
+                   error = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status,
+            execution_process_id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM merge_queue_entries WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -2,15 +2,20 @@ pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod fan_out;
 pub mod image;
 pub mod merge;
+pub mod merge_queue;
 pub mod migration_state;
 pub mod project;
 pub mod project_repo;
+pub mod queued_message;
 pub mod repo;
 pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_dependency;
+pub mod token_usage;
 pub mod workspace;
 pub mod workspace_repo;
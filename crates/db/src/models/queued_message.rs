@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, types::Json};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::scratch::DraftFollowUpData;
+
+#[derive(Debug, Error)]
+pub enum QueuedMessageError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Queued message not found")]
+    NotFound,
+    #[error("Reorder must list every queued message of the session exactly once")]
+    InvalidOrder,
+}
+
+/// A follow-up message waiting for the current execution of a session to finish
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QueuedMessage {
+    pub id: Uuid,
+    /// The session this message is queued for
+    pub session_id: Uuid,
+    /// The follow-up data (message + variant)
+    pub data: DraftFollowUpData,
+    /// Timestamp when the message was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+struct QueuedMessageRow {
+    id: Uuid,
+    session_id: Uuid,
+    message: String,
+    executor_profile_id: Json<ExecutorProfileId>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<QueuedMessageRow> for QueuedMessage {
+    fn from(row: QueuedMessageRow) -> Self {
+        Self {
+            id: row.id,
+            session_id: row.session_id,
+            data: DraftFollowUpData {
+                message: row.message,
+                executor_profile_id: row.executor_profile_id.0,
+            },
+            queued_at: row.created_at,
+        }
+    }
+}
+
+impl QueuedMessage {
+    /// Queued messages of a session in the order they will be sent
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let rows = sqlx::query_as!(
+            QueuedMessageRow,
+            r#"SELECT id as "id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      message,
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM queued_messages
+               WHERE session_id = $1
+               ORDER BY position ASC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            QueuedMessageRow,
+            r#"SELECT id as "id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      message,
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM queued_messages
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn exists_for_session(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM queued_messages WHERE session_id = $1) as "exists!: bool""#,
+            session_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Append a message to the end of the session's queue
+    pub async fn create(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        data: &DraftFollowUpData,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let row = sqlx::query_as!(
+            QueuedMessageRow,
+            r#"INSERT INTO queued_messages (id, session_id, position, message, executor_profile_id)
+               VALUES (
+                   $1,
+                   $2,
+                   (SELECT COALESCE(MAX(position) + 1, 0) FROM queued_messages WHERE session_id = $2),
+                   $3,
+                   $4
+               )
+               RETURNING id as "id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         message,
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            session_id,
+            data.message,
+            executor_profile_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(row.into())
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &DraftFollowUpData,
+    ) -> Result<Self, QueuedMessageError> {
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let row = sqlx::query_as!(
+            QueuedMessageRow,
+            r#"UPDATE queued_messages
+               SET message = $2, executor_profile_id = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         message,
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.message,
+            executor_profile_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        row.map(Into::into).ok_or(QueuedMessageError::NotFound)
+    }
+
+    /// Rewrite the positions of a session's queue to match `ordered_ids`
+    pub async fn reorder(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        ordered_ids: &[Uuid],
+    ) -> Result<Vec<Self>, QueuedMessageError> {
+        let current: HashSet<Uuid> = Self::find_by_session_id(pool, session_id)
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        let requested: HashSet<Uuid> = ordered_ids.iter().copied().collect();
+        if requested.len() != ordered_ids.len() || requested != current {
+            return Err(QueuedMessageError::InvalidOrder);
+        }
+
+        let mut tx = pool.begin().await?;
+        for (position, id) in ordered_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE queued_messages SET position = $1 WHERE id = $2 AND session_id = $3",
+                position,
+                id,
+                session_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(Self::find_by_session_id(pool, session_id).await?)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM queued_messages WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM queued_messages WHERE session_id = $1",
+            session_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Remove and return the message at the head of the session's queue
+    pub async fn take_next(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            QueuedMessageRow,
+            r#"DELETE FROM queued_messages
+               WHERE id = (
+                   SELECT id FROM queued_messages
+                   WHERE session_id = $1
+                   ORDER BY position ASC
+                   LIMIT 1
+               )
+               RETURNING id as "id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         message,
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+}
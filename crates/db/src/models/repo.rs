@@ -30,6 +30,13 @@ pub struct Repo {
     pub dev_server_script: Option<String>,
     pub default_target_branch: Option<String>,
     pub default_working_dir: Option<String>,
+    /// JSON-encoded approval policy applied to coding agents running in this repo.
+    pub approval_policy: Option<String>,
+    /// How local merges land task branches when the request doesn't pick a strategy.
+    #[ts(type = "MergeStrategy | null")]
+    pub default_merge_strategy: Option<String>,
+    /// Verifies a workspace before the merge queue lands it; a non-zero exit blocks the merge.
+    pub check_script: Option<String>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -109,6 +116,30 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub default_working_dir: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub approval_policy: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "MergeStrategy | null")]
+    pub default_merge_strategy: Option<Option<String>>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "double_option"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub check_script: Option<Option<String>>,
 }
 
 impl Repo {
@@ -129,6 +160,9 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      approval_policy,
+                      default_merge_strategy,
+                      check_script,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -170,6 +204,9 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      approval_policy,
+                      default_merge_strategy,
+                      check_script,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -228,6 +265,9 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         approval_policy,
+                         default_merge_strategy,
+                         check_script,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -265,6 +305,9 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      approval_policy,
+                      default_merge_strategy,
+                      check_script,
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -291,6 +334,9 @@ impl Repo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.approval_policy,
+                      r.default_merge_strategy,
+                      r.check_script,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -353,6 +399,18 @@ impl Repo {
             None => existing.default_working_dir,
             Some(v) => v.clone(),
         };
+        let approval_policy = match &payload.approval_policy {
+            None => existing.approval_policy,
+            Some(v) => v.clone(),
+        };
+        let default_merge_strategy = match &payload.default_merge_strategy {
+            None => existing.default_merge_strategy,
+            Some(v) => v.clone(),
+        };
+        let check_script = match &payload.check_script {
+            None => existing.check_script,
+            Some(v) => v.clone(),
+        };
 
         sqlx::query_as!(
             Repo,
@@ -366,8 +424,11 @@ impl Repo {
                    dev_server_script = $7,
                    default_target_branch = $8,
                    default_working_dir = $9,
+                   approval_policy = $10,
+                   default_merge_strategy = $11,
+                   check_script = $12,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $10
+               WHERE id = $13
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -380,6 +441,9 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         approval_policy,
+                         default_merge_strategy,
+                         check_script,
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -391,10 +455,40 @@ impl Repo {
             dev_server_script,
             default_target_branch,
             default_working_dir,
+            approval_policy,
+            default_merge_strategy,
+            check_script,
             id
         )
         .fetch_one(pool)
         .await
         .map_err(RepoError::from)
     }
+
+    /// Hash of the config file whose scripts the user approved, if any
+    pub async fn find_trusted_config_hash(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let hash =
+            sqlx::query_scalar!(r#"SELECT trusted_config_hash FROM repos WHERE id = $1"#, id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(hash.flatten())
+    }
+
+    pub async fn set_trusted_config_hash(
+        pool: &SqlitePool,
+        id: Uuid,
+        hash: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE repos SET trusted_config_hash = $1, updated_at = datetime('now', 'subsec') WHERE id = $2",
+            hash,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
 }
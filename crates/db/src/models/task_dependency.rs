@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskStatus};
+
+#[derive(Debug, Error)]
+pub enum TaskDependencyError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("A task cannot depend on itself")]
+    SelfDependency,
+    #[error("Tasks must belong to the same project")]
+    DifferentProject,
+    #[error("Dependency already exists")]
+    AlreadyExists,
+    #[error("Dependency would create a cycle")]
+    Cycle,
+    #[error("Dependency not found")]
+    NotFound,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDependency {
+    pub id: Uuid,
+    pub task_id: Uuid,            // The blocked task
+    pub depends_on_task_id: Uuid, // The task that must be done first
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskDependency {
+    pub depends_on_task_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskDependencies {
+    pub blocked_by: Vec<Task>, // Tasks this task waits on
+    pub blocking: Vec<Task>,   // Tasks waiting on this task
+}
+
+impl TaskDependency {
+    /// Tasks that must be done before `task_id` can start
+    pub async fn find_blockers(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.depends_on_task_id
+               WHERE td.task_id = $1
+               ORDER BY td.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks that wait on `task_id`
+    pub async fn find_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE td.depends_on_task_id = $1
+               ORDER BY td.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskDependencies, sqlx::Error> {
+        Ok(TaskDependencies {
+            blocked_by: Self::find_blockers(pool, task_id).await?,
+            blocking: Self::find_dependents(pool, task_id).await?,
+        })
+    }
+
+    /// Dependents of `task_id` that are still in todo and whose blockers are all done
+    pub async fn find_unblocked_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE td.depends_on_task_id = $1
+                 AND t.status = 'todo'
+                 AND NOT EXISTS (
+                     SELECT 1
+                       FROM task_dependencies other
+                       JOIN tasks blocker ON blocker.id = other.depends_on_task_id
+                      WHERE other.task_id = t.id
+                        AND blocker.status != 'done'
+                 )
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether `task_id` is reachable by following dependencies from `from_task_id`
+    async fn is_reachable(
+        pool: &SqlitePool,
+        from_task_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let found = sqlx::query_scalar!(
+            r#"WITH RECURSIVE reachable(id) AS (
+                   SELECT depends_on_task_id FROM task_dependencies WHERE task_id = $1
+                   UNION
+                   SELECT td.depends_on_task_id
+                     FROM task_dependencies td
+                     JOIN reachable r ON td.task_id = r.id
+               )
+               SELECT EXISTS(SELECT 1 FROM reachable WHERE id = $2) as "found!: bool""#,
+            from_task_id,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(found)
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task: &Task,
+        depends_on: &Task,
+    ) -> Result<Self, TaskDependencyError> {
+        if task.id == depends_on.id {
+            return Err(TaskDependencyError::SelfDependency);
+        }
+        if task.project_id != depends_on.project_id {
+            return Err(TaskDependencyError::DifferentProject);
+        }
+        if Self::find_blockers(pool, task.id)
+            .await?
+            .iter()
+            .any(|t| t.id == depends_on.id)
+        {
+            return Err(TaskDependencyError::AlreadyExists);
+        }
+        // Adding task -> depends_on closes a cycle if task is already upstream of depends_on
+        if Self::is_reachable(pool, depends_on.id, task.id).await? {
+            return Err(TaskDependencyError::Cycle);
+        }
+
+        let id = Uuid::new_v4();
+        let dependency = sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (id, task_id, depends_on_task_id)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", depends_on_task_id as "depends_on_task_id!: Uuid", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task.id,
+            depends_on.id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(dependency)
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<(), TaskDependencyError> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND depends_on_task_id = $2",
+            task_id,
+            depends_on_task_id
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(TaskDependencyError::NotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Which coding agent turns to aggregate token usage over
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TokenUsageScope {
+    #[default]
+    All,
+    Workspace(Uuid),
+    Task(Uuid),
+    Project(Uuid),
+}
+
+/// Summed token usage of the turns in a scope that ran with one executor and model
+#[derive(Debug, Clone)]
+pub struct TokenUsageTotals {
+    pub executor: String,
+    pub model: Option<String>,
+    pub turns: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+}
+
+impl TokenUsageTotals {
+    /// Token usage of every turn in `scope` that reported it, grouped by executor and model
+    pub async fn find_by_scope(
+        pool: &SqlitePool,
+        scope: TokenUsageScope,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let (workspace_id, task_id, project_id) = match scope {
+            TokenUsageScope::All => (None, None, None),
+            TokenUsageScope::Workspace(id) => (Some(id), None, None),
+            TokenUsageScope::Task(id) => (None, Some(id), None),
+            TokenUsageScope::Project(id) => (None, None, Some(id)),
+        };
+
+        sqlx::query_as!(
+            TokenUsageTotals,
+            r#"SELECT
+                COALESCE(
+                    json_extract(ep.executor_action, '$.typ.executor_profile_id.executor'),
+                    s.executor,
+                    'UNKNOWN'
+                ) as "executor!: String",
+                cat.model,
+                COUNT(*) as "turns!: i64",
+                COALESCE(SUM(cat.input_tokens), 0) as "input_tokens!: i64",
+                COALESCE(SUM(cat.output_tokens), 0) as "output_tokens!: i64",
+                COALESCE(SUM(cat.cache_read_tokens), 0) as "cache_read_tokens!: i64",
+                COALESCE(SUM(cat.cache_write_tokens), 0) as "cache_write_tokens!: i64"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON ep.id = cat.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE cat.input_tokens IS NOT NULL
+                 AND ($1 IS NULL OR w.id = $1)
+                 AND ($2 IS NULL OR t.id = $2)
+                 AND ($3 IS NULL OR t.project_id = $3)
+               GROUP BY 1, cat.model
+               ORDER BY 1, cat.model"#,
+            workspace_id,
+            task_id,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -57,6 +57,8 @@ pub struct WorkspaceWithStatus {
     pub workspace: Workspace,
     pub is_running: bool,
     pub is_errored: bool,
+    /// 1-based position in the global coding agent queue, if waiting for a slot
+    pub queue_position: Option<u32>,
 }
 
 impl std::ops::Deref for WorkspaceWithStatus {
@@ -66,6 +68,11 @@ impl std::ops::Deref for WorkspaceWithStatus {
     }
 }
 
+/// The status queries report 0 when nothing is queued for the workspace
+fn queue_position(count: i64) -> Option<u32> {
+    u32::try_from(count).ok().filter(|position| *position > 0)
+}
+
 /// GitHub PR creation parameters
 pub struct CreatePrParams<'a> {
     pub workspace_id: Uuid,
@@ -550,7 +557,20 @@ impl Workspace {
                       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
-                ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
+                ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64",
+
+                (
+                    SELECT COUNT(*)
+                    FROM execution_processes q
+                    WHERE q.status = 'queued'
+                      AND q.created_at <= (
+                          SELECT MIN(ep.created_at)
+                          FROM sessions s
+                          JOIN execution_processes ep ON ep.session_id = s.id
+                          WHERE s.workspace_id = w.id
+                            AND ep.status = 'queued'
+                      )
+                ) AS "queue_position!: i64"
 
             FROM workspaces w
             ORDER BY w.updated_at DESC"#
@@ -576,6 +596,7 @@ impl Workspace {
                 },
                 is_running: rec.is_running != 0,
                 is_errored: rec.is_errored != 0,
+                queue_position: queue_position(rec.queue_position),
             })
             // Apply archived filter if provided
             .filter(|ws| archived.is_none_or(|a| ws.workspace.archived == a))
@@ -651,7 +672,20 @@ impl Workspace {
                       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
                     ORDER BY ep.created_at DESC
                     LIMIT 1
-                ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64"
+                ) IN ('failed','killed') THEN 1 ELSE 0 END AS "is_errored!: i64",
+
+                (
+                    SELECT COUNT(*)
+                    FROM execution_processes q
+                    WHERE q.status = 'queued'
+                      AND q.created_at <= (
+                          SELECT MIN(ep.created_at)
+                          FROM sessions s
+                          JOIN execution_processes ep ON ep.session_id = s.id
+                          WHERE s.workspace_id = w.id
+                            AND ep.status = 'queued'
+                      )
+                ) AS "queue_position!: i64"
 
             FROM workspaces w
             WHERE w.id = $1"#,
@@ -680,6 +714,7 @@ impl Workspace {
             },
             is_running: rec.is_running != 0,
             is_errored: rec.is_errored != 0,
+            queue_position: queue_position(rec.queue_position),
         };
 
         if ws.workspace.name.is_none()
@@ -122,6 +122,9 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.approval_policy,
+                      r.default_merge_strategy,
+                      r.check_script,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -151,6 +154,9 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.approval_policy,
+                      r.default_merge_strategy,
+                      r.check_script,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -179,6 +185,9 @@ impl WorkspaceRepo {
                     dev_server_script: row.dev_server_script,
                     default_target_branch: row.default_target_branch,
                     default_working_dir: row.default_working_dir,
+                    approval_policy: row.approval_policy,
+                    default_merge_strategy: row.default_merge_strategy,
+                    check_script: row.check_script,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -268,6 +277,9 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.approval_policy,
+                      r.default_merge_strategy,
+                      r.check_script,
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
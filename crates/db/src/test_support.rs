@@ -0,0 +1,70 @@
+//! Fixtures for the tests of the database and of the crates built on it
+
+use chrono::Utc;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType,
+    script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+};
+use sqlx::sqlite::SqlitePoolOptions;
+use uuid::Uuid;
+
+use crate::{
+    DBService,
+    models::execution_process::{
+        ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
+    },
+};
+
+/// A fresh in-memory database with every migration applied
+pub async fn db() -> DBService {
+    // A single connection, since every connection gets its own in-memory database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+    crate::run_migrations(&pool)
+        .await
+        .expect("Failed to run migrations");
+    DBService { pool }
+}
+
+/// A bash script action running `cargo test`
+pub fn script_action(context: ScriptContext) -> ExecutorAction {
+    ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script: "cargo test".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context,
+            working_dir: None,
+        }),
+        None,
+    )
+}
+
+/// An execution process that is not stored anywhere, started now with fresh ids
+pub fn execution_process(
+    run_reason: ExecutionProcessRunReason,
+    action: ExecutorAction,
+    status: ExecutionProcessStatus,
+    exit_code: Option<i64>,
+) -> ExecutionProcess {
+    let now = Utc::now();
+    ExecutionProcess {
+        id: Uuid::new_v4(),
+        session_id: Uuid::new_v4(),
+        run_reason,
+        executor_action: sqlx::types::Json(ExecutorActionField::ExecutorAction(action)),
+        status,
+        exit_code,
+        dropped: false,
+        check_status: None,
+        check_fix_attempt: 0,
+        pid: None,
+        spool_dir: None,
+        started_at: now,
+        completed_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
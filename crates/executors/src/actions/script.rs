@@ -24,6 +24,7 @@ pub enum ScriptContext {
     SetupScript,
     CleanupScript,
     ArchiveScript,
+    CheckScript,
     DevServer,
     ToolInstallScript,
 }
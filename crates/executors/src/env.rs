@@ -1,9 +1,9 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, ffi::OsStr, path::PathBuf};
 
 use git::GitService;
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{command::CmdOverrides, executors::ExecutorError, sandbox::Sandbox, spool::Spool};
 
 /// Repository context for executor operations
 #[derive(Debug, Clone, Default)]
@@ -81,6 +81,10 @@ pub struct ExecutionEnv {
     pub repo_context: RepoContext,
     pub commit_reminder: bool,
     pub commit_reminder_prompt: String,
+    /// Confine the executor process when set
+    pub sandbox: Option<Sandbox>,
+    /// Spool the executor process's output so it can outlive the server when set
+    pub spool: Option<Spool>,
 }
 
 impl ExecutionEnv {
@@ -94,9 +98,38 @@ impl ExecutionEnv {
             repo_context,
             commit_reminder,
             commit_reminder_prompt,
+            sandbox: None,
+            spool: None,
         }
     }
 
+    /// Command that runs `program` with `args`, inside the sandbox and with its output
+    /// spooled when those are configured
+    pub fn command(
+        &self,
+        program: impl AsRef<OsStr>,
+        args: &[String],
+    ) -> Result<Command, ExecutorError> {
+        let command = match &self.sandbox {
+            Some(sandbox) => sandbox.command(program.as_ref(), args, &self.repo_context)?,
+            None => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+        };
+        match &self.spool {
+            Some(spool) => spool.wrap(command),
+            None => Ok(command),
+        }
+    }
+
+    /// Whether dropping the handle of a command built by [`Self::command`] should kill it.
+    /// Spooled commands have to outlive the server.
+    pub fn kill_on_drop(&self) -> bool {
+        self.spool.is_none()
+    }
+
     /// Insert an environment variable
     pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
         self.vars.insert(key.into(), value.into());
@@ -9,7 +9,7 @@ use agent_client_protocol as proto;
 use agent_client_protocol::Agent as _;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::StreamExt;
-use tokio::{io::AsyncWriteExt, process::Command, sync::mpsc};
+use tokio::{io::AsyncWriteExt, sync::mpsc};
 use tokio_util::{
     compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt},
     io::ReaderStream,
@@ -79,7 +79,7 @@ impl AcpAgentHarness {
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
+        let mut command = env.command(program_path, &args)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -87,8 +87,7 @@ impl AcpAgentHarness {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .env("NPM_CONFIG_LOGLEVEL", "error")
-            .env("NODE_NO_WARNINGS", "1")
-            .args(&args);
+            .env("NODE_NO_WARNINGS", "1");
 
         env.clone()
             .with_profile(cmd_overrides)
@@ -132,7 +131,7 @@ impl AcpAgentHarness {
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
+        let mut command = env.command(program_path, &args)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -140,8 +139,7 @@ impl AcpAgentHarness {
             .stderr(Stdio::piped())
             .current_dir(current_dir)
             .env("NPM_CONFIG_LOGLEVEL", "error")
-            .env("NODE_NO_WARNINGS", "1")
-            .args(&args);
+            .env("NODE_NO_WARNINGS", "1");
 
         env.clone()
             .with_profile(cmd_overrides)
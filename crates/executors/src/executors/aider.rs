@@ -0,0 +1,384 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::{msg_store::MsgStore, shell::resolve_executable_path_blocking};
+
+use crate::{
+    command::{CmdOverrides, CommandBuildError, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
+    logs::utils::EntryIndexProvider,
+    stdout_dup::tee_stdout_with_appender,
+};
+
+pub mod normalize_logs;
+
+use normalize_logs::{SESSION_MARKER, normalize_logs};
+
+const SESSION_NAMESPACE: &str = "aider_sessions";
+
+/// Aider executor configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Aider {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "Model to use (e.g., sonnet, gpt-4o, deepseek/deepseek-chat)"
+    )]
+    pub model: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Edit Format",
+        description = "Edit format the model uses: diff, whole, udiff or architect"
+    )]
+    pub edit_format: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Auto Commits",
+        description = "Let aider commit its own edits instead of leaving them uncommitted"
+    )]
+    pub auto_commits: Option<bool>,
+
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Aider {
+    fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
+        let mut builder = CommandBuilder::new("aider").params([
+            "--yes-always",
+            "--no-pretty",
+            "--no-stream",
+            "--no-check-update",
+            "--no-show-release-notes",
+        ]);
+        if !self.auto_commits.unwrap_or(false) {
+            builder = builder.extend_params(["--no-auto-commits"]);
+        }
+        if let Some(model) = &self.model {
+            builder = builder.extend_params(["--model", model.as_str()]);
+        }
+        if let Some(edit_format) = &self.edit_format {
+            builder = builder.extend_params(["--edit-format", edit_format.as_str()]);
+        }
+
+        apply_overrides(builder, &self.cmd)
+    }
+
+    /// Arguments that run a single message against the session's chat history
+    fn session_args(history_file: &Path, prompt: &str) -> Vec<String> {
+        vec![
+            "--chat-history-file".to_string(),
+            history_file.to_string_lossy().to_string(),
+            "--restore-chat-history".to_string(),
+            "--message".to_string(),
+            prompt.to_string(),
+        ]
+    }
+}
+
+/// Directory the chat history of each aider session is kept in, which may not exist yet
+pub(crate) fn sessions_path() -> Option<PathBuf> {
+    let mut vk_dir = dirs::home_dir()?.join(".vibe-kanban");
+    if cfg!(debug_assertions) {
+        vk_dir = vk_dir.join("dev");
+    }
+    Some(vk_dir.join(SESSION_NAMESPACE))
+}
+
+fn sessions_dir() -> io::Result<PathBuf> {
+    let dir =
+        sessions_path().ok_or_else(|| io::Error::other("Could not determine home directory"))?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn history_file(sessions_dir: &Path, session_id: &str) -> PathBuf {
+    sessions_dir.join(format!("{session_id}.md"))
+}
+
+async fn spawn_aider(
+    command_parts: CommandParts,
+    session_id: &str,
+    current_dir: &Path,
+    env: &ExecutionEnv,
+    cmd_overrides: &CmdOverrides,
+) -> Result<SpawnedChild, ExecutorError> {
+    let (program_path, args) = command_parts.into_resolved().await?;
+
+    let mut command = env.command(program_path, &args)?;
+    command
+        .kill_on_drop(env.kill_on_drop())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(current_dir)
+        .env("AIDER_CHECK_UPDATE", "false");
+
+    env.clone()
+        .with_profile(cmd_overrides)
+        .apply_to_command(&mut command);
+
+    let mut child = command.group_spawn()?;
+
+    // Aider has no notion of session ids, so report the one the history file is keyed by
+    let (_, appender) = tee_stdout_with_appender(&mut child)?;
+    appender.append_line(format!("{SESSION_MARKER}{session_id}"));
+
+    Ok(child.into())
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Aider {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let history = history_file(&sessions_dir().map_err(ExecutorError::Io)?, &session_id);
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let aider_command = self
+            .build_command_builder()?
+            .build_follow_up(&Self::session_args(&history, &combined_prompt))?;
+
+        spawn_aider(aider_command, &session_id, current_dir, env, &self.cmd).await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        _reset_to_message_id: Option<&str>,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        // Continue in a copy of the history so earlier turns can be followed up again
+        let sessions_dir = sessions_dir().map_err(ExecutorError::Io)?;
+        let new_session_id = uuid::Uuid::new_v4().to_string();
+        let history = history_file(&sessions_dir, &new_session_id);
+        let previous = history_file(&sessions_dir, session_id);
+        if previous.exists() {
+            tokio::fs::copy(&previous, &history)
+                .await
+                .map_err(ExecutorError::Io)?;
+        }
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let aider_command = self
+            .build_command_builder()?
+            .build_follow_up(&Self::session_args(&history, &combined_prompt))?;
+
+        spawn_aider(aider_command, &new_session_id, current_dir, env, &self.cmd).await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        normalize_logs(
+            msg_store.clone(),
+            current_dir,
+            EntryIndexProvider::start_from(&msg_store),
+        );
+    }
+
+    fn default_mcp_config_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let program = self
+            .cmd
+            .base_command_override
+            .as_deref()
+            .and_then(|base| base.split_whitespace().next())
+            .unwrap_or("aider");
+
+        if resolve_executable_path_blocking(program).is_some() {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{os::unix::fs::PermissionsExt, time::Duration};
+
+    use tokio::io::AsyncReadExt;
+    use workspace_utils::log_msg::LogMsg;
+
+    use super::*;
+    use crate::{
+        env::RepoContext,
+        logs::{
+            ActionType, NormalizedEntryType, ToolStatus,
+            utils::patch::extract_normalized_entry_from_patch,
+        },
+        sandbox::{Sandbox, SandboxConfig},
+    };
+
+    /// Stands in for aider: echoes the history file it was given and replies with an edit
+    const FAKE_AIDER: &str = r#"#!/bin/sh
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --chat-history-file) history="$2"; shift ;;
+  esac
+  shift
+done
+echo "Aider v0.0.0-fake"
+echo "history: $history"
+echo "greet the world" >> "$history" || exit 1
+echo "Updating the greeting."
+echo
+echo "hello.txt"
+echo '```'
+echo "<<<<<<< SEARCH"
+echo "hello"
+echo "======="
+echo "hello world"
+echo ">>>>>>> REPLACE"
+echo '```'
+echo "Tokens: 10 sent, 5 received."
+echo "Applied edit to hello.txt"
+"#;
+
+    fn fake_aider(dir: &Path) -> Aider {
+        let script = dir.join("fake-aider");
+        std::fs::write(&script, FAKE_AIDER).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        Aider {
+            append_prompt: AppendPrompt::default(),
+            model: None,
+            edit_format: None,
+            auto_commits: None,
+            cmd: CmdOverrides {
+                base_command_override: Some(script.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Run `aider` to completion, returning its stdout and whether it succeeded
+    async fn run(aider: &Aider, dir: &Path, env: &ExecutionEnv) -> (String, bool) {
+        let mut spawned = aider.spawn(dir, "greet the world", env).await.unwrap();
+
+        let mut stdout = String::new();
+        spawned
+            .child
+            .inner()
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut stdout)
+            .await
+            .unwrap();
+        let success = spawned.child.wait().await.unwrap().success();
+        (stdout, success)
+    }
+
+    fn session_id(stdout: &str) -> &str {
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(SESSION_MARKER))
+            .expect("session id is reported")
+    }
+
+    fn applied_edit(history: &[LogMsg]) -> bool {
+        history.iter().any(|msg| match msg {
+            LogMsg::JsonPatch(patch) => {
+                extract_normalized_entry_from_patch(patch).is_some_and(|(_, entry)| {
+                    matches!(
+                        entry.entry_type,
+                        NormalizedEntryType::ToolUse {
+                            action_type: ActionType::FileEdit { ref path, .. },
+                            status: ToolStatus::Success,
+                            ..
+                        } if path == "hello.txt"
+                    )
+                })
+            }
+            _ => false,
+        })
+    }
+
+    #[tokio::test]
+    async fn runs_a_scripted_aider_and_normalizes_its_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let aider = fake_aider(dir.path());
+        let env = ExecutionEnv::new(RepoContext::default(), false, String::new());
+        let (stdout, success) = run(&aider, dir.path(), &env).await;
+        assert!(success);
+
+        let session_id = session_id(&stdout);
+        assert!(stdout.contains(&format!("{session_id}.md")));
+
+        let msg_store = Arc::new(MsgStore::new());
+        msg_store.push_stdout(stdout.clone());
+        msg_store.push_finished();
+        aider.normalize_logs(msg_store.clone(), dir.path());
+
+        let history = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let history = msg_store.get_history();
+                let has_session = history
+                    .iter()
+                    .any(|msg| matches!(msg, LogMsg::SessionId(id) if id == session_id));
+                if has_session && applied_edit(&history) {
+                    return history;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await;
+        assert!(
+            history.is_ok(),
+            "expected the session id and an applied edit to hello.txt"
+        );
+        let _ = std::fs::remove_file(history_file(&sessions_dir().unwrap(), session_id));
+    }
+
+    #[tokio::test]
+    async fn sandboxed_aider_can_write_its_chat_history() {
+        if !cfg!(target_os = "linux") || resolve_executable_path_blocking("bwrap").is_none() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let aider = fake_aider(dir.path());
+        let mut env = ExecutionEnv::new(
+            RepoContext::new(dir.path().to_path_buf(), vec![]),
+            false,
+            String::new(),
+        );
+        env.sandbox = Some(Sandbox::new(
+            SandboxConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            None,
+        ));
+        let (stdout, success) = run(&aider, dir.path(), &env).await;
+        assert!(success, "aider failed inside the sandbox: {stdout}");
+
+        let history = history_file(&sessions_dir().unwrap(), session_id(&stdout));
+        let contents = std::fs::read_to_string(&history).unwrap();
+        assert!(contents.contains("greet the world"));
+        std::fs::remove_file(history).unwrap();
+    }
+}
@@ -0,0 +1,589 @@
+use std::{path::Path, sync::Arc};
+
+use futures::{StreamExt, future::ready};
+use json_patch::Patch;
+use workspace_utils::{diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative};
+
+use crate::logs::{
+    ActionType, CommandRunResult, FileChange, NormalizedEntry, NormalizedEntryError,
+    NormalizedEntryType, ToolStatus,
+    plain_text_processor::PlainTextLogProcessor,
+    utils::{EntryIndexProvider, patch::ConversationPatch},
+};
+
+/// Line written ahead of aider's own output to report the session id
+pub const SESSION_MARKER: &str = "[vibe-kanban] aider session: ";
+
+/// Status lines aider prints outside of model replies
+const STATUS_PREFIXES: &[&str] = &[
+    "Aider v",
+    "Main model:",
+    "Weak model:",
+    "Editor model:",
+    "Model:",
+    "Git repo:",
+    "Repo-map:",
+    "Added ",
+    "Restored previous conversation history",
+    "Use /help",
+    "https://aider.chat/",
+    "Warning:",
+];
+
+const ERROR_PREFIXES: &[&str] = &[
+    "The LLM did not conform to the edit format",
+    "litellm.",
+    "Error:",
+];
+
+const SEARCH_MARKER: &str = "<<<<<<< SEARCH";
+const DIVIDER_MARKER: &str = "=======";
+const REPLACE_MARKER: &str = ">>>>>>> REPLACE";
+
+pub fn normalize_logs(
+    msg_store: Arc<MsgStore>,
+    worktree_path: &Path,
+    entry_index_provider: EntryIndexProvider,
+) {
+    normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+    let worktree_path = worktree_path.to_string_lossy().to_string();
+    tokio::spawn(async move {
+        let mut processor = AiderLogProcessor::new(worktree_path, entry_index_provider);
+        let mut lines = msg_store
+            .stdout_lines_stream()
+            .filter_map(|res| ready(res.ok()));
+
+        while let Some(line) = lines.next().await {
+            if let Some(session_id) = line.trim().strip_prefix(SESSION_MARKER) {
+                msg_store.push_session_id(session_id.to_string());
+                continue;
+            }
+            for patch in processor.process_line(&line) {
+                msg_store.push_patch(patch);
+            }
+        }
+        for patch in processor.finish() {
+            msg_store.push_patch(patch);
+        }
+    });
+}
+
+fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: EntryIndexProvider) {
+    tokio::spawn(async move {
+        let mut stderr = msg_store.stderr_chunked_stream();
+
+        let mut processor = PlainTextLogProcessor::builder()
+            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                content,
+                metadata: None,
+            }))
+            .transform_lines(Box::new(|lines| {
+                lines.iter_mut().for_each(|line| {
+                    *line = strip_ansi_escapes::strip_str(&line);
+                });
+            }))
+            .time_gap(std::time::Duration::from_secs(2))
+            .index_provider(entry_index_provider)
+            .build();
+
+        while let Some(Ok(chunk)) = stderr.next().await {
+            for patch in processor.process(chunk) {
+                msg_store.push_patch(patch);
+            }
+        }
+    });
+}
+
+/// Model reply text, streamed into a single assistant message
+struct Reply {
+    index: Option<usize>,
+    lines: Vec<String>,
+}
+
+/// A SEARCH/REPLACE block being read out of a model reply
+struct EditBlock {
+    path: String,
+    search: Vec<String>,
+    replace: Vec<String>,
+    in_replace: bool,
+}
+
+/// A shell command aider is running, with the output seen so far
+struct Command {
+    index: usize,
+    command: String,
+    output: Vec<String>,
+}
+
+struct PendingEdit {
+    index: usize,
+    entry: NormalizedEntry,
+}
+
+/// Turns aider's plain-text output (`--no-pretty --no-stream`) into normalized entries.
+///
+/// Model replies become assistant messages, with any SEARCH/REPLACE blocks split out into file
+/// edits that are marked applied once aider reports `Applied edit to <path>`. Commands aider
+/// runs become command runs carrying their output. Other status lines become system messages.
+pub struct AiderLogProcessor {
+    worktree_path: String,
+    index_provider: EntryIndexProvider,
+    reply: Option<Reply>,
+    edit_block: Option<EditBlock>,
+    command: Option<Command>,
+    pending_edits: Vec<(String, PendingEdit)>,
+    skip_closing_fence: bool,
+}
+
+impl AiderLogProcessor {
+    pub fn new(worktree_path: String, index_provider: EntryIndexProvider) -> Self {
+        Self {
+            worktree_path,
+            index_provider,
+            reply: None,
+            edit_block: None,
+            command: None,
+            pending_edits: Vec::new(),
+            skip_closing_fence: false,
+        }
+    }
+
+    pub fn process_line(&mut self, line: &str) -> Vec<Patch> {
+        let line = strip_ansi_escapes::strip_str(line.trim_end_matches(['\r', '\n']));
+        let trimmed = line.trim();
+        let mut patches = Vec::new();
+
+        if let Some(block) = &mut self.edit_block {
+            match trimmed {
+                DIVIDER_MARKER if !block.in_replace => block.in_replace = true,
+                REPLACE_MARKER => {
+                    let block = self.edit_block.take().expect("edit block is set");
+                    self.skip_closing_fence = true;
+                    patches.push(self.add_edit(block));
+                }
+                _ if block.in_replace => block.replace.push(line.clone()),
+                _ => block.search.push(line.clone()),
+            }
+            return patches;
+        }
+
+        if self.skip_closing_fence {
+            self.skip_closing_fence = false;
+            if trimmed.starts_with("```") {
+                return patches;
+            }
+        }
+
+        if self.reply.is_some() {
+            if trimmed == SEARCH_MARKER {
+                let path = self.take_edit_path();
+                patches.extend(self.end_reply());
+                self.edit_block = Some(EditBlock {
+                    path,
+                    search: Vec::new(),
+                    replace: Vec::new(),
+                    in_replace: false,
+                });
+                return patches;
+            }
+            if trimmed.starts_with("Tokens: ") {
+                patches.extend(self.end_reply());
+                return patches;
+            }
+            let reply = self.reply.as_mut().expect("reply is set");
+            reply.lines.push(line.clone());
+            patches.extend(self.update_reply(false));
+            return patches;
+        }
+
+        if let Some(path) = trimmed.strip_prefix("Applied edit to ") {
+            patches.extend(self.end_command());
+            patches.extend(self.mark_applied(path));
+        } else if trimmed.starts_with("Tokens: ") || is_prompt(trimmed) {
+            patches.extend(self.end_command());
+        } else if let Some(command) = trimmed.strip_prefix("Running ") {
+            patches.extend(self.end_command());
+            patches.push(self.start_command(command));
+        } else if ERROR_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+            || trimmed.ends_with("failed to match!")
+        {
+            patches.extend(self.end_command());
+            patches.extend(self.fail_pending_edits());
+            patches.push(self.add_entry(error_entry(trimmed)));
+        } else if STATUS_PREFIXES.iter().any(|p| trimmed.starts_with(p)) || is_commit(trimmed) {
+            patches.extend(self.end_command());
+            patches.push(self.add_entry(system_entry(trimmed)));
+        } else if let Some(command) = &mut self.command {
+            command.output.push(line.clone());
+            let (index, entry) = (command.index, command_entry(command, ToolStatus::Created));
+            patches.push(ConversationPatch::replace(index, entry));
+        } else if !trimmed.is_empty() {
+            self.reply = Some(Reply {
+                index: None,
+                lines: vec![line.clone()],
+            });
+            patches.extend(self.update_reply(false));
+        }
+
+        patches
+    }
+
+    /// Flush whatever is still open once the output ends
+    pub fn finish(&mut self) -> Vec<Patch> {
+        let mut patches = self.end_reply();
+        patches.extend(self.end_command());
+        patches
+    }
+
+    fn add_entry(&self, entry: NormalizedEntry) -> Patch {
+        ConversationPatch::add_normalized_entry(self.index_provider.next(), entry)
+    }
+
+    /// Show the reply so far. The last two lines are held back until more text arrives, as they
+    /// may turn out to be the file path and opening fence of a SEARCH/REPLACE block.
+    fn update_reply(&mut self, flush: bool) -> Option<Patch> {
+        let reply = self.reply.as_mut()?;
+        let visible = if flush {
+            reply.lines.len()
+        } else {
+            reply.lines.len().saturating_sub(2)
+        };
+        let content = reply.lines[..visible].join("\n").trim().to_string();
+        if content.is_empty() {
+            return None;
+        }
+        let entry = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content,
+            metadata: None,
+        };
+        match reply.index {
+            Some(index) => Some(ConversationPatch::replace(index, entry)),
+            None => {
+                let index = self.index_provider.next();
+                reply.index = Some(index);
+                Some(ConversationPatch::add_normalized_entry(index, entry))
+            }
+        }
+    }
+
+    fn end_reply(&mut self) -> Vec<Patch> {
+        let patch = self.update_reply(true);
+        self.reply = None;
+        patch.into_iter().collect()
+    }
+
+    /// Pop the opening fence and file path that precede a SEARCH marker off the reply
+    fn take_edit_path(&mut self) -> String {
+        let Some(reply) = self.reply.as_mut() else {
+            return String::new();
+        };
+        if reply
+            .lines
+            .last()
+            .is_some_and(|line| line.trim().starts_with("```"))
+        {
+            reply.lines.pop();
+        }
+        reply
+            .lines
+            .pop()
+            .map(|line| line.trim().trim_matches('`').to_string())
+            .unwrap_or_default()
+    }
+
+    fn add_edit(&mut self, block: EditBlock) -> Patch {
+        let path = make_path_relative(&block.path, &self.worktree_path);
+        let replace = join_lines(&block.replace);
+        let change = if block.search.iter().all(|line| line.trim().is_empty()) {
+            FileChange::Write { content: replace }
+        } else {
+            FileChange::Edit {
+                unified_diff: create_unified_diff(&path, &join_lines(&block.search), &replace),
+                has_line_numbers: false,
+            }
+        };
+        let entry = edit_entry(&path, vec![change], ToolStatus::Created);
+        let index = self.index_provider.next();
+        self.pending_edits.push((
+            path,
+            PendingEdit {
+                index,
+                entry: entry.clone(),
+            },
+        ));
+        ConversationPatch::add_normalized_entry(index, entry)
+    }
+
+    fn mark_applied(&mut self, path: &str) -> Vec<Patch> {
+        let path = make_path_relative(path, &self.worktree_path);
+        let (applied, pending) = std::mem::take(&mut self.pending_edits)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(edit_path, _)| *edit_path == path);
+        self.pending_edits = pending;
+
+        if applied.is_empty() {
+            return vec![self.add_entry(edit_entry(&path, Vec::new(), ToolStatus::Success))];
+        }
+        applied
+            .into_iter()
+            .filter_map(|(_, edit)| {
+                edit.entry
+                    .with_tool_status(ToolStatus::Success)
+                    .map(|entry| ConversationPatch::replace(edit.index, entry))
+            })
+            .collect()
+    }
+
+    fn fail_pending_edits(&mut self) -> Vec<Patch> {
+        std::mem::take(&mut self.pending_edits)
+            .into_iter()
+            .filter_map(|(_, edit)| {
+                edit.entry
+                    .with_tool_status(ToolStatus::Failed)
+                    .map(|entry| ConversationPatch::replace(edit.index, entry))
+            })
+            .collect()
+    }
+
+    fn start_command(&mut self, command: &str) -> Patch {
+        let command = Command {
+            index: self.index_provider.next(),
+            command: command.trim().to_string(),
+            output: Vec::new(),
+        };
+        let patch = ConversationPatch::add_normalized_entry(
+            command.index,
+            command_entry(&command, ToolStatus::Created),
+        );
+        self.command = Some(command);
+        patch
+    }
+
+    fn end_command(&mut self) -> Option<Patch> {
+        let command = self.command.take()?;
+        Some(ConversationPatch::replace(
+            command.index,
+            command_entry(&command, ToolStatus::Success),
+        ))
+    }
+}
+
+fn join_lines(lines: &[String]) -> String {
+    let mut text = lines.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// Confirmation prompts, answered automatically under `--yes-always`
+fn is_prompt(line: &str) -> bool {
+    line.contains("(Y)es/(N)o")
+}
+
+/// `Commit <hash> <message>`, printed when aider commits its own edits
+fn is_commit(line: &str) -> bool {
+    line.strip_prefix("Commit ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .is_some_and(|hash| hash.len() >= 7 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn system_entry(content: &str) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type: NormalizedEntryType::SystemMessage,
+        content: content.to_string(),
+        metadata: None,
+    }
+}
+
+fn error_entry(content: &str) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type: NormalizedEntryType::ErrorMessage {
+            error_type: NormalizedEntryError::Other,
+        },
+        content: content.to_string(),
+        metadata: None,
+    }
+}
+
+fn edit_entry(path: &str, changes: Vec<FileChange>, status: ToolStatus) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type: NormalizedEntryType::ToolUse {
+            tool_name: "edit".to_string(),
+            action_type: ActionType::FileEdit {
+                path: path.to_string(),
+                changes,
+            },
+            status,
+        },
+        content: path.to_string(),
+        metadata: None,
+    }
+}
+
+fn command_entry(command: &Command, status: ToolStatus) -> NormalizedEntry {
+    let output = command.output.join("\n");
+    NormalizedEntry {
+        timestamp: None,
+        entry_type: NormalizedEntryType::ToolUse {
+            tool_name: "bash".to_string(),
+            action_type: ActionType::CommandRun {
+                command: command.command.clone(),
+                result: (!output.trim().is_empty()).then(|| CommandRunResult {
+                    exit_status: None,
+                    output: Some(output),
+                }),
+            },
+            status,
+        },
+        content: command.command.clone(),
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::utils::patch::extract_normalized_entry_from_patch;
+
+    /// Final state of each entry after applying every patch in order
+    fn process(lines: &[&str]) -> Vec<NormalizedEntry> {
+        let mut processor =
+            AiderLogProcessor::new("/tmp/worktree".to_string(), EntryIndexProvider::test_new());
+        let mut patches = Vec::new();
+        for line in lines {
+            patches.extend(processor.process_line(line));
+        }
+        patches.extend(processor.finish());
+
+        let mut entries: Vec<Option<NormalizedEntry>> = Vec::new();
+        for patch in &patches {
+            if let Some((index, entry)) = extract_normalized_entry_from_patch(patch) {
+                if entries.len() <= index {
+                    entries.resize(index + 1, None);
+                }
+                entries[index] = Some(entry);
+            }
+        }
+        entries.into_iter().flatten().collect()
+    }
+
+    #[test]
+    fn splits_edits_out_of_the_reply() {
+        let entries = process(&[
+            "Aider v0.86.1",
+            "Main model: gpt-4o with diff edit format",
+            "I'll rename the function.",
+            "",
+            "src/lib.rs",
+            "```rust",
+            "<<<<<<< SEARCH",
+            "fn old() {}",
+            "=======",
+            "fn new() {}",
+            ">>>>>>> REPLACE",
+            "```",
+            "",
+            "Done.",
+            "Tokens: 1.2k sent, 80 received.",
+            "Applied edit to src/lib.rs",
+        ]);
+
+        assert_eq!(entries.len(), 5);
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::SystemMessage
+        ));
+        assert!(matches!(
+            entries[2].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(entries[2].content, "I'll rename the function.");
+        match &entries[3].entry_type {
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::FileEdit { path, changes },
+                status: ToolStatus::Success,
+                ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert!(matches!(
+                    &changes[..],
+                    [FileChange::Edit { unified_diff, .. }]
+                        if unified_diff.contains("-fn old() {}")
+                            && unified_diff.contains("+fn new() {}")
+                ));
+            }
+            other => panic!("expected an applied file edit, got {other:?}"),
+        }
+        assert_eq!(entries[4].content, "Done.");
+    }
+
+    #[test]
+    fn failed_edits_are_marked_failed() {
+        let entries = process(&[
+            "new.txt",
+            "```",
+            "<<<<<<< SEARCH",
+            "=======",
+            "hello",
+            ">>>>>>> REPLACE",
+            "```",
+            "Tokens: 1k sent, 10 received.",
+            "# 1 SEARCH/REPLACE block failed to match!",
+        ]);
+
+        assert!(matches!(
+            &entries[0].entry_type,
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::FileEdit { changes, .. },
+                status: ToolStatus::Failed,
+                ..
+            } if matches!(&changes[..], [FileChange::Write { content }] if content == "hello\n")
+        ));
+        assert!(matches!(
+            entries[1].entry_type,
+            NormalizedEntryType::ErrorMessage { .. }
+        ));
+    }
+
+    #[test]
+    fn commands_capture_their_output() {
+        let entries = process(&[
+            "Tokens: 1k sent, 10 received.",
+            "Run shell command? (Y)es/(N)o/(D)on't ask again [Yes]: y",
+            "Running cargo test",
+            "test result: ok. 3 passed",
+            "Add command output to the chat? (Y)es/(N)o/(D)on't ask again [Yes]: y",
+            "Added 1 line of output to the chat.",
+        ]);
+
+        match &entries[0].entry_type {
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::CommandRun { command, result },
+                status: ToolStatus::Success,
+                ..
+            } => {
+                assert_eq!(command, "cargo test");
+                assert_eq!(
+                    result.as_ref().and_then(|r| r.output.as_deref()),
+                    Some("test result: ok. 3 passed")
+                );
+            }
+            other => panic!("expected a command run, got {other:?}"),
+        }
+        assert!(matches!(
+            entries[1].entry_type,
+            NormalizedEntryType::SystemMessage
+        ));
+    }
+}
@@ -56,15 +56,14 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, &args)?;
         command
-            .kill_on_drop(true)
+            .kill_on_drop(env.kill_on_drop())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .args(&args);
+            .env("NPM_CONFIG_LOGLEVEL", "error");
 
         env.clone()
             .with_profile(&self.cmd)
@@ -132,15 +131,14 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(continue_program);
+        let mut command = env.command(continue_program, &continue_args)?;
         command
-            .kill_on_drop(true)
+            .kill_on_drop(env.kill_on_drop())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .args(&continue_args);
+            .env("NPM_CONFIG_LOGLEVEL", "error");
 
         env.clone()
             .with_profile(&self.cmd)
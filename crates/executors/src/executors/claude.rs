@@ -16,11 +16,13 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use tokio_util::sync::CancellationToken;
 use ts_rs::TS;
 use workspace_utils::{
-    approvals::ApprovalStatus, diff::create_unified_diff, log_msg::LogMsg, msg_store::MsgStore,
+    approvals::ApprovalStatus,
+    diff::create_unified_diff,
+    log_msg::{LogMsg, TokenUsage},
+    msg_store::MsgStore,
     path::make_path_relative,
 };
 
@@ -301,15 +303,14 @@ impl ClaudeCode {
         let (program_path, args) = command_parts.into_resolved().await?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(program_path);
+        let mut command = env.command(program_path, &args)?;
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .args(&args);
+            .env("NPM_CONFIG_LOGLEVEL", "error");
 
         env.clone()
             .with_profile(&self.cmd)
@@ -455,6 +456,7 @@ impl ClaudeLogProcessor {
                     LogMsg::JsonPatch(_)
                     | LogMsg::SessionId(_)
                     | LogMsg::MessageId(_)
+                    | LogMsg::TokenUsage(_)
                     | LogMsg::Stderr(_)
                     | LogMsg::Ready => continue,
                     LogMsg::Finished => break,
@@ -505,10 +507,19 @@ impl ClaudeLogProcessor {
                                 ClaudeJson::Assistant { uuid, .. } => {
                                     pending_assistant_uuid = uuid.clone();
                                 }
-                                ClaudeJson::Result { .. } => {
+                                ClaudeJson::Result {
+                                    model_usage, usage, ..
+                                } => {
                                     if let Some(uuid) = pending_assistant_uuid.take() {
                                         msg_store.push_message_id(uuid);
                                     }
+                                    if let Some(token_usage) = Self::extract_token_usage(
+                                        model_usage.as_ref(),
+                                        usage.as_ref(),
+                                        processor.main_model_name.as_deref(),
+                                    ) {
+                                        msg_store.push_token_usage(token_usage);
+                                    }
                                 }
                                 _ => {}
                             }
@@ -579,6 +590,45 @@ impl ClaudeLogProcessor {
         }
     }
 
+    /// Token usage of the whole run from a result message. Per-model usage includes
+    /// subagents, so it is preferred over the top-level usage when present.
+    fn extract_token_usage(
+        model_usage: Option<&HashMap<String, ClaudeModelUsage>>,
+        usage: Option<&ClaudeUsage>,
+        main_model: Option<&str>,
+    ) -> Option<TokenUsage> {
+        let model = main_model.map(str::to_string);
+        if let Some(model_usage) = model_usage.filter(|m| !m.is_empty()) {
+            let model = model.or_else(|| {
+                model_usage
+                    .iter()
+                    .max_by_key(|(_, usage)| usage.output_tokens)
+                    .map(|(name, _)| name.clone())
+            });
+            return Some(model_usage.values().fold(
+                TokenUsage {
+                    model,
+                    ..Default::default()
+                },
+                |mut total, usage| {
+                    total.input_tokens += usage.input_tokens;
+                    total.output_tokens += usage.output_tokens;
+                    total.cache_read_tokens += usage.cache_read_input_tokens;
+                    total.cache_write_tokens += usage.cache_creation_input_tokens;
+                    total
+                },
+            ));
+        }
+
+        usage.map(|usage| TokenUsage {
+            model,
+            input_tokens: usage.input_tokens.unwrap_or(0),
+            output_tokens: usage.output_tokens.unwrap_or(0),
+            cache_read_tokens: usage.cache_read_input_tokens.unwrap_or(0),
+            cache_write_tokens: usage.cache_creation_input_tokens.unwrap_or(0),
+        })
+    }
+
     /// Generate warning entry if API key source is ANTHROPIC_API_KEY
     fn warn_if_unmanaged_key(src: &Option<String>) -> Option<NormalizedEntry> {
         match src.as_deref() {
@@ -1964,6 +2014,14 @@ pub struct ClaudeUsage {
 pub struct ClaudeModelUsage {
     #[serde(default)]
     pub context_window: Option<u32>,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
 }
 
 /// Structured tool data for Claude tools based on real samples
@@ -2706,4 +2764,37 @@ mod tests {
         let parsed: ClaudeJson = serde_json::from_str(control_request_json).unwrap();
         assert!(matches!(parsed, ClaudeJson::ControlRequest { .. }));
     }
+
+    #[test]
+    fn test_result_token_usage_sums_model_usage() {
+        let result_json = r#"{"type":"result","subtype":"success","is_error":false,"session_id":"abc123","usage":{"input_tokens":10,"output_tokens":20},"modelUsage":{"claude-sonnet-4-5":{"inputTokens":100,"outputTokens":200,"cacheReadInputTokens":3000,"cacheCreationInputTokens":400,"contextWindow":200000},"claude-haiku-4-5":{"inputTokens":5,"outputTokens":6}}}"#;
+        let parsed: ClaudeJson = serde_json::from_str(result_json).unwrap();
+        let ClaudeJson::Result {
+            model_usage, usage, ..
+        } = parsed
+        else {
+            panic!("expected a result message");
+        };
+
+        let token_usage = ClaudeLogProcessor::extract_token_usage(
+            model_usage.as_ref(),
+            usage.as_ref(),
+            Some("claude-sonnet-4-5"),
+        )
+        .unwrap();
+        assert_eq!(
+            token_usage,
+            TokenUsage {
+                model: Some("claude-sonnet-4-5".to_string()),
+                input_tokens: 105,
+                output_tokens: 206,
+                cache_read_tokens: 3000,
+                cache_write_tokens: 400,
+            }
+        );
+
+        let fallback = ClaudeLogProcessor::extract_token_usage(None, usage.as_ref(), None).unwrap();
+        assert_eq!(fallback.input_tokens, 10);
+        assert_eq!(fallback.output_tokens, 20);
+    }
 }
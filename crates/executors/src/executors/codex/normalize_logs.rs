@@ -25,8 +25,8 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use workspace_utils::{
-    approvals::ApprovalStatus, diff::normalize_unified_diff, msg_store::MsgStore,
-    path::make_path_relative,
+    approvals::ApprovalStatus, diff::normalize_unified_diff, log_msg::TokenUsage,
+    msg_store::MsgStore, path::make_path_relative,
 };
 
 use crate::{
@@ -433,6 +433,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
     let worktree_path_str = worktree_path.to_string_lossy().to_string();
     tokio::spawn(async move {
         let mut state = LogState::new(entry_index.clone());
+        let mut model: Option<String> = None;
         let mut stdout_lines = msg_store.stdout_lines_stream();
 
         while let Some(Ok(line)) = stdout_lines.next().await {
@@ -458,6 +459,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                     server_notification
                 {
                     msg_store.push_session_id(session_configured.session_id.to_string());
+                    model = Some(session_configured.model.clone());
                     handle_model_params(
                         session_configured.model,
                         session_configured.reasoning_effort,
@@ -496,6 +498,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
             match event {
                 EventMsg::SessionConfigured(payload) => {
                     msg_store.push_session_id(payload.session_id.to_string());
+                    model = Some(payload.model.clone());
                     handle_model_params(
                         payload.model,
                         payload.reasoning_effort,
@@ -1066,6 +1069,16 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                 }
                 EventMsg::TokenCount(payload) => {
                     if let Some(info) = payload.info {
+                        // Cached input is reported as part of the input tokens
+                        let usage = &info.total_token_usage;
+                        msg_store.push_token_usage(TokenUsage {
+                            model: model.clone(),
+                            input_tokens: (usage.input_tokens - usage.cached_input_tokens).max(0)
+                                as u64,
+                            output_tokens: usage.output_tokens.max(0) as u64,
+                            cache_read_tokens: usage.cached_input_tokens.max(0) as u64,
+                            cache_write_tokens: 0,
+                        });
                         add_normalized_entry(
                             &msg_store,
                             &entry_index,
@@ -6,7 +6,7 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::{
     diff::{create_unified_diff, normalize_unified_diff},
@@ -85,15 +85,14 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, &args)?;
         command
-            .kill_on_drop(true)
+            .kill_on_drop(env.kill_on_drop())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .args(&args);
+            .env("NPM_CONFIG_LOGLEVEL", "error");
 
         env.clone()
             .with_profile(&self.cmd)
@@ -126,15 +125,14 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, &args)?;
         command
-            .kill_on_drop(true)
+            .kill_on_drop(env.kill_on_drop())
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .args(&args);
+            .env("NPM_CONFIG_LOGLEVEL", "error");
 
         env.clone()
             .with_profile(&self.cmd)
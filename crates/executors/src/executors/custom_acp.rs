@@ -0,0 +1,230 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use derivative::Derivative;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::{
+    msg_store::MsgStore, path::expand_tilde, shell::resolve_executable_path_blocking,
+};
+
+use crate::{
+    approvals::ExecutorApprovalService,
+    command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        acp::AcpAgentHarness,
+    },
+};
+
+const DEFAULT_SESSION_NAMESPACE: &str = "custom_acp_sessions";
+
+/// Any agent that speaks the Agent Client Protocol over stdio
+#[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
+#[derivative(Debug, PartialEq)]
+pub struct CustomAcp {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[serde(default)]
+    #[schemars(
+        title = "Command",
+        description = "Command that starts the agent in ACP mode (e.g., \"npx -y my-agent --acp\")"
+    )]
+    pub command: String,
+    #[serde(default)]
+    #[schemars(
+        title = "Arguments",
+        description = "Arguments passed to the command, one per entry"
+    )]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Session Namespace",
+        description = "Directory name under the app's data directory where session history is kept"
+    )]
+    pub session_namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "Model to select via ACP once the session starts"
+    )]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Mode",
+        description = "Session mode to select via ACP once the session starts"
+    )]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "MCP Config Path",
+        description = "Path to the agent's MCP configuration file (uses the mcpServers format)"
+    )]
+    pub mcp_config_path: Option<String>,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
+}
+
+impl CustomAcp {
+    fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
+        let builder = CommandBuilder::new(self.command.trim()).extend_params(self.args.clone());
+        apply_overrides(builder, &self.cmd)
+    }
+
+    fn harness(&self) -> AcpAgentHarness {
+        let namespace = self
+            .session_namespace
+            .as_deref()
+            .map(str::trim)
+            .filter(|ns| !ns.is_empty())
+            .unwrap_or(DEFAULT_SESSION_NAMESPACE);
+        let mut harness = AcpAgentHarness::with_session_namespace(namespace);
+        if let Some(model) = &self.model {
+            harness = harness.with_model(model);
+        }
+        if let Some(mode) = &self.mode {
+            harness = harness.with_mode(mode);
+        }
+        harness
+    }
+
+    /// Program the agent is launched with, honouring a base command override
+    fn program(&self) -> Option<String> {
+        let base = self
+            .cmd
+            .base_command_override
+            .as_deref()
+            .unwrap_or(&self.command);
+        base.split_whitespace().next().map(str::to_string)
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for CustomAcp {
+    fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
+        self.approvals = Some(approvals);
+    }
+
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let command = self.build_command_builder()?.build_initial()?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        self.harness()
+            .spawn_with_command(
+                current_dir,
+                combined_prompt,
+                command,
+                env,
+                &self.cmd,
+                self.approvals.clone(),
+            )
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        _reset_to_message_id: Option<&str>,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let command = self.build_command_builder()?.build_follow_up(&[])?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        self.harness()
+            .spawn_follow_up_with_command(
+                current_dir,
+                combined_prompt,
+                session_id,
+                command,
+                env,
+                &self.cmd,
+                self.approvals.clone(),
+            )
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+        crate::executors::acp::normalize_logs(msg_store, worktree_path);
+    }
+
+    // MCP configuration methods
+    fn default_mcp_config_path(&self) -> Option<PathBuf> {
+        self.mcp_config_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(expand_tilde)
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let binary_found = self
+            .program()
+            .and_then(|program| resolve_executable_path_blocking(&program))
+            .is_some();
+
+        if binary_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_profile_config() {
+        let agent: CustomAcp = serde_json::from_value(serde_json::json!({
+            "command": "npx -y my-agent",
+            "args": ["--acp"],
+            "model": "fast",
+            "mcp_config_path": "~/.my-agent/mcp.json",
+        }))
+        .unwrap();
+
+        assert_eq!(agent.program().as_deref(), Some("npx"));
+        assert_eq!(agent.model.as_deref(), Some("fast"));
+        assert!(
+            agent
+                .build_command_builder()
+                .unwrap()
+                .build_initial()
+                .is_ok()
+        );
+        assert!(
+            agent
+                .default_mcp_config_path()
+                .is_some_and(|path| path.ends_with(".my-agent/mcp.json"))
+        );
+    }
+
+    #[test]
+    fn empty_command_is_rejected() {
+        let agent: CustomAcp = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(agent.program().is_none());
+        assert!(
+            agent
+                .build_command_builder()
+                .unwrap()
+                .build_initial()
+                .is_err()
+        );
+        assert!(agent.default_mcp_config_path().is_none());
+    }
+}
@@ -21,19 +21,22 @@ use crate::{
     command::CommandBuildError,
     env::ExecutionEnv,
     executors::{
-        amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        aider::Aider, amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot,
+        cursor::CursorAgent, custom_acp::CustomAcp, droid::Droid, gemini::Gemini,
+        opencode::Opencode, qwen::QwenCode,
     },
     logs::utils::patch,
     mcp_config::McpConfig,
 };
 
 pub mod acp;
+pub mod aider;
 pub mod amp;
 pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod custom_acp;
 pub mod droid;
 pub mod gemini;
 pub mod opencode;
@@ -59,6 +62,8 @@ pub enum BaseAgentCapability {
     SetupHelper,
     /// Agent reports context/token usage information
     ContextUsage,
+    /// Agent keeps running while the server restarts
+    SurvivesRestart,
 }
 
 #[derive(Debug, Error)]
@@ -87,6 +92,8 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("Sandbox error: {0}")]
+    Sandbox(String),
 }
 
 #[enum_dispatch]
@@ -117,10 +124,26 @@ pub enum CodingAgent {
     QwenCode,
     Copilot,
     Droid,
+    CustomAcp,
+    Aider,
     #[cfg(feature = "qa-mode")]
     QaMock(QaMockExecutor),
 }
 
+impl BaseCodingAgent {
+    /// Whether the agent can keep running while the server restarts. Agents that the server
+    /// keeps talking to over stdin or a local connection after the start cannot.
+    pub fn can_detach(&self) -> bool {
+        matches!(
+            self,
+            BaseCodingAgent::Amp
+                | BaseCodingAgent::CursorAgent
+                | BaseCodingAgent::Droid
+                | BaseCodingAgent::Aider
+        )
+    }
+}
+
 impl CodingAgent {
     pub fn get_mcp_config(&self) -> McpConfig {
         match self {
@@ -173,7 +196,7 @@ impl CodingAgent {
     }
 
     pub fn capabilities(&self) -> Vec<BaseAgentCapability> {
-        match self {
+        let mut capabilities = match self {
             Self::ClaudeCode(_) => vec![
                 BaseAgentCapability::SessionFork,
                 BaseAgentCapability::ContextUsage,
@@ -187,14 +210,18 @@ impl CodingAgent {
                 BaseAgentCapability::SetupHelper,
                 BaseAgentCapability::ContextUsage,
             ],
-            Self::Amp(_) | Self::Gemini(_) | Self::QwenCode(_) => {
+            Self::Amp(_) | Self::Gemini(_) | Self::QwenCode(_) | Self::CustomAcp(_) => {
                 vec![BaseAgentCapability::SessionFork]
             }
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Copilot(_) | Self::Droid(_) => vec![],
+            Self::Copilot(_) | Self::Droid(_) | Self::Aider(_) => vec![],
             #[cfg(feature = "qa-mode")]
             Self::QaMock(_) => vec![], // QA mock doesn't need special capabilities
+        };
+        if BaseCodingAgent::from(self).can_detach() {
+            capabilities.push(BaseAgentCapability::SurvivesRestart);
         }
+        capabilities
     }
 }
 
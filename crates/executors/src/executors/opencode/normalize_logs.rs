@@ -3,7 +3,9 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 use futures::StreamExt;
 use serde::Deserialize;
 use serde_json::Value;
-use workspace_utils::{approvals::ApprovalStatus, msg_store::MsgStore, path::make_path_relative};
+use workspace_utils::{
+    approvals::ApprovalStatus, log_msg::TokenUsage, msg_store::MsgStore, path::make_path_relative,
+};
 
 use super::types::{
     MessageInfo, MessageRole, OpencodeExecutorEvent, Part, PermissionAskedEvent, SdkEvent, SdkTodo,
@@ -169,6 +171,8 @@ struct LogState {
     entry_index: EntryIndexProvider,
     msg_store: Arc<MsgStore>,
     message_roles: HashMap<String, MessageRole>,
+    /// OpenCode reports tokens per assistant message, so the run's usage is their sum
+    message_tokens: HashMap<String, TokenUsage>,
     assistant_text: HashMap<String, StreamingText>,
     thinking_text: HashMap<String, StreamingText>,
     tool_states: HashMap<String, ToolCallState>,
@@ -185,6 +189,7 @@ impl LogState {
             entry_index,
             msg_store,
             message_roles: HashMap::new(),
+            message_tokens: HashMap::new(),
             assistant_text: HashMap::new(),
             thinking_text: HashMap::new(),
             tool_states: HashMap::new(),
@@ -211,6 +216,7 @@ impl LogState {
             SdkEvent::MessageUpdated(event) => {
                 let info = event.info;
                 self.maybe_emit_model_system_message(&info);
+                self.record_token_usage(&info);
                 self.message_roles.insert(info.id, info.role);
             }
             SdkEvent::MessagePartUpdated(event) => {
@@ -353,6 +359,42 @@ impl LogState {
         self.model_system_message_emitted = true;
     }
 
+    fn record_token_usage(&mut self, info: &MessageInfo) {
+        if info.role != MessageRole::Assistant {
+            return;
+        }
+        let Some(tokens) = &info.tokens else {
+            return;
+        };
+
+        let usage = TokenUsage {
+            model: info.model_id().map(str::to_string),
+            input_tokens: tokens.input as u64,
+            output_tokens: tokens.output as u64,
+            cache_read_tokens: tokens.cache.as_ref().map_or(0, |c| c.read as u64),
+            cache_write_tokens: tokens.cache.as_ref().map_or(0, |c| c.write as u64),
+        };
+        if self.message_tokens.get(&info.id) == Some(&usage) {
+            return;
+        }
+        self.message_tokens.insert(info.id.clone(), usage.clone());
+
+        let total =
+            self.message_tokens
+                .values()
+                .fold(TokenUsage::default(), |mut total, message| {
+                    total.input_tokens += message.input_tokens;
+                    total.output_tokens += message.output_tokens;
+                    total.cache_read_tokens += message.cache_read_tokens;
+                    total.cache_write_tokens += message.cache_write_tokens;
+                    total
+                });
+        self.msg_store.push_token_usage(TokenUsage {
+            model: usage.model,
+            ..total
+        });
+    }
+
     fn handle_part_update(
         &mut self,
         part: Part,
@@ -1238,3 +1280,65 @@ fn extract_file_path_from_permission_metadata(metadata: &Value) -> Option<&str>
         Some(trimmed)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use workspace_utils::log_msg::LogMsg;
+
+    use super::*;
+
+    fn message_updated(id: &str, input: u32, output: u32, cache_read: u32) -> Value {
+        json!({
+            "type": "message.updated",
+            "properties": {
+                "info": {
+                    "id": id,
+                    "role": "assistant",
+                    "providerID": "anthropic",
+                    "modelID": "claude-sonnet-4",
+                    "tokens": {
+                        "input": input,
+                        "output": output,
+                        "cache": { "read": cache_read, "write": 0 }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn token_usage_is_summed_over_assistant_messages() {
+        let msg_store = Arc::new(MsgStore::new());
+        let mut state = LogState::new(
+            EntryIndexProvider::start_from(&msg_store),
+            msg_store.clone(),
+        );
+        let worktree = Path::new("/tmp");
+
+        state.handle_sdk_event(&message_updated("m1", 100, 10, 5), worktree, &msg_store);
+        // Repeated updates with unchanged counts are not reported again
+        state.handle_sdk_event(&message_updated("m1", 100, 10, 5), worktree, &msg_store);
+        state.handle_sdk_event(&message_updated("m2", 50, 20, 0), worktree, &msg_store);
+
+        let usages: Vec<TokenUsage> = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::TokenUsage(usage) => Some(usage),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(usages.len(), 2);
+        assert_eq!(
+            usages[1],
+            TokenUsage {
+                model: Some("claude-sonnet-4".to_string()),
+                input_tokens: 150,
+                output_tokens: 30,
+                cache_read_tokens: 5,
+                cache_write_tokens: 0,
+            }
+        );
+    }
+}
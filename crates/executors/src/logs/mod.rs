@@ -146,6 +146,10 @@ pub enum ToolStatus {
         requested_at: DateTime<Utc>,
         timeout_at: DateTime<Utc>,
     },
+    /// Approved without asking the user because an approval policy rule matched.
+    AutoApproved {
+        rule: String,
+    },
     TimedOut,
 }
 
@@ -0,0 +1,611 @@
+//! Opt-in confinement of coding agents with bubblewrap on Linux.
+//!
+//! A sandboxed agent sees the whole filesystem read-only except for its workspace, the git
+//! directories its worktrees point into, the per-user state directories agents keep their
+//! sessions and caches in, and any extra paths configured by the user. Git hooks and config
+//! stay read-only, since git outside the sandbox would otherwise run whatever the agent
+//! plants there. Network access can be
+//! left alone, cut off entirely, or restricted to an allowlist of hosts; in the latter case the
+//! agent runs in its own network namespace and reaches the outside world only through an
+//! allowlisting HTTP proxy served over a unix socket.
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, UnixListener, UnixStream},
+    process::Command,
+    sync::mpsc,
+    task::JoinHandle,
+};
+use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path_blocking;
+
+use crate::{
+    env::RepoContext,
+    executors::{ExecutorError, aider},
+};
+
+/// Prefix of the log lines that report sandbox violations
+pub const SANDBOX_LOG_PREFIX: &str = "[sandbox]";
+
+/// Port the in-sandbox proxy listens on
+const PROXY_PORT: u16 = 3128;
+const PROXY_SOCKET_NAME: &str = "proxy.sock";
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+/// Longest excerpt of agent output quoted in a violation report
+const MAX_VIOLATION_EXCERPT: usize = 200;
+
+/// Directories under `$HOME` that agents write their sessions, credentials and caches to
+const AGENT_STATE_DIRS: &[&str] = &[
+    ".claude",
+    ".claude.json",
+    ".codex",
+    ".gemini",
+    ".qwen",
+    ".copilot",
+    ".cursor",
+    ".factory",
+    ".cache",
+    ".local/state",
+    ".npm",
+];
+
+/// Agents that keep their state under `~/.config/<name>` and `~/.local/share/<name>`
+const XDG_AGENT_NAMES: &[&str] = &[
+    "amp",
+    "claude",
+    "cursor",
+    "gemini",
+    "github-copilot",
+    "opencode",
+    "qwen",
+];
+
+/// Files of the shared git directory that git outside the sandbox executes or obeys
+const GIT_PROTECTED_PATHS: &[&str] = &["hooks", "config"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SandboxNetwork {
+    /// Unrestricted network access
+    #[default]
+    Allow,
+    /// No network access at all
+    Deny,
+    /// HTTP(S) access to the listed hosts only; `*.example.com` also matches subdomains
+    Allowlist { hosts: Vec<String> },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct SandboxConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub network: SandboxNetwork,
+    /// Extra paths the agent may write to besides its workspace
+    #[serde(default)]
+    pub writable_paths: Vec<String>,
+}
+
+impl SandboxConfig {
+    /// Describe the sandbox violation `line` of agent output points at, if any
+    pub fn detect_violation(&self, line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(SANDBOX_LOG_PREFIX) {
+            return None;
+        }
+        let excerpt = || -> String {
+            let mut excerpt: String = line.chars().take(MAX_VIOLATION_EXCERPT).collect();
+            if excerpt.len() < line.len() {
+                excerpt.push('…');
+            }
+            excerpt
+        };
+        if line.contains("Read-only file system") || line.contains("EROFS") {
+            return Some(format!(
+                "{SANDBOX_LOG_PREFIX} Write outside the workspace was blocked: {}",
+                excerpt()
+            ));
+        }
+        let network_blocked = [
+            "Network is unreachable",
+            "ENETUNREACH",
+            "Could not resolve host",
+            "EAI_AGAIN",
+            "Temporary failure in name resolution",
+        ];
+        if self.network != SandboxNetwork::Allow
+            && network_blocked.iter().any(|needle| line.contains(needle))
+        {
+            return Some(format!(
+                "{SANDBOX_LOG_PREFIX} Network access was blocked: {}",
+                excerpt()
+            ));
+        }
+        None
+    }
+}
+
+/// Whether `host` is covered by one of the `allowlist` patterns
+pub fn host_allowed(allowlist: &[String], host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowlist.iter().any(|pattern| {
+        let pattern = pattern.trim().trim_end_matches('.').to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+            None => host == pattern,
+        }
+    })
+}
+
+/// Sandbox settings for one execution
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    pub config: SandboxConfig,
+    /// Socket of the allowlisting proxy, required for [`SandboxNetwork::Allowlist`]
+    pub proxy_socket: Option<PathBuf>,
+}
+
+impl Sandbox {
+    pub fn new(config: SandboxConfig, proxy_socket: Option<PathBuf>) -> Self {
+        Self {
+            config,
+            proxy_socket,
+        }
+    }
+
+    /// Build a command that runs `program` with `args` inside the sandbox
+    pub fn command(
+        &self,
+        program: &OsStr,
+        args: &[String],
+        repo_context: &RepoContext,
+    ) -> Result<Command, ExecutorError> {
+        if !cfg!(target_os = "linux") {
+            return Err(ExecutorError::Sandbox(
+                "sandboxed execution is only supported on Linux".to_string(),
+            ));
+        }
+        let bwrap = resolve_executable_path_blocking("bwrap").ok_or_else(|| {
+            ExecutorError::Sandbox("bubblewrap (`bwrap`) is not installed".to_string())
+        })?;
+        let socat = match &self.config.network {
+            SandboxNetwork::Allowlist { .. } => {
+                Some(resolve_executable_path_blocking("socat").ok_or_else(|| {
+                    ExecutorError::Sandbox(
+                        "`socat` is required for the network allowlist".to_string(),
+                    )
+                })?)
+            }
+            _ => None,
+        };
+
+        let mut command = Command::new(bwrap);
+        command.args(self.bwrap_args(program, args, repo_context, socat.as_deref())?);
+        if socat.is_some() {
+            let proxy = format!("http://127.0.0.1:{PROXY_PORT}");
+            for key in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+                command.env(key, &proxy);
+            }
+            command.env("NO_PROXY", "localhost,127.0.0.1");
+            command.env("no_proxy", "localhost,127.0.0.1");
+        }
+        Ok(command)
+    }
+
+    fn bwrap_args(
+        &self,
+        program: &OsStr,
+        args: &[String],
+        repo_context: &RepoContext,
+        socat: Option<&Path>,
+    ) -> Result<Vec<OsString>, ExecutorError> {
+        let workspace_root = &repo_context.workspace_root;
+        if workspace_root.as_os_str().is_empty() {
+            return Err(ExecutorError::Sandbox(
+                "cannot sandbox an execution without a workspace".to_string(),
+            ));
+        }
+
+        let mut out: Vec<OsString> = [
+            "--die-with-parent",
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--tmpfs",
+            "/tmp",
+        ]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+
+        let mut bind = |flag: &str, path: &Path| {
+            out.push(flag.into());
+            out.push(path.into());
+            out.push(path.into());
+        };
+
+        bind("--bind", workspace_root);
+        for repo_path in repo_context.repo_paths() {
+            if let Some(git_dir) = worktree_common_dir(&repo_path) {
+                bind("--bind", &git_dir);
+                // A missing hooks dir could be created from inside, so make sure it exists
+                let _ = std::fs::create_dir_all(git_dir.join("hooks"));
+                // Later mounts win, so these stay read-only inside the writable git dir
+                for path in GIT_PROTECTED_PATHS {
+                    bind("--ro-bind-try", &git_dir.join(path));
+                }
+                // Repointing the worktree at another git dir would sidestep the above
+                bind("--ro-bind-try", &repo_path.join(".git"));
+            }
+        }
+        if let Some(home) = dirs::home_dir() {
+            for dir in AGENT_STATE_DIRS {
+                bind("--bind-try", &home.join(dir));
+            }
+            for name in XDG_AGENT_NAMES {
+                bind("--bind-try", &home.join(".config").join(name));
+                bind("--bind-try", &home.join(".local/share").join(name));
+            }
+        }
+        // Aider's history sits next to our own data, which must otherwise stay read-only
+        if let Some(sessions) = aider::sessions_path() {
+            let _ = std::fs::create_dir_all(&sessions);
+            bind("--bind-try", &sessions);
+        }
+        for path in &self.config.writable_paths {
+            bind("--bind-try", Path::new(path));
+        }
+
+        match (&self.config.network, socat) {
+            (SandboxNetwork::Allow, _) => {}
+            (SandboxNetwork::Deny, _) => out.push("--unshare-net".into()),
+            (SandboxNetwork::Allowlist { .. }, Some(socat)) => {
+                let socket = self.proxy_socket.as_deref().ok_or_else(|| {
+                    ExecutorError::Sandbox("network allowlist proxy is not running".to_string())
+                })?;
+                if let Some(socket_dir) = socket.parent() {
+                    out.push("--bind".into());
+                    out.push(socket_dir.into());
+                    out.push(socket_dir.into());
+                }
+                out.push("--unshare-net".into());
+                out.push("--".into());
+                // Forward the namespace-local proxy port to the proxy outside, then exec the agent
+                out.push("sh".into());
+                out.push("-c".into());
+                out.push(
+                    format!(
+                        "\"$0\" TCP-LISTEN:{PROXY_PORT},bind=127.0.0.1,fork,reuseaddr UNIX-CONNECT:\"$1\" & shift 2; exec \"$@\""
+                    )
+                    .into(),
+                );
+                out.push(socat.into());
+                out.push(socket.into());
+                out.push(program.into());
+                out.extend(args.iter().map(OsString::from));
+                return Ok(out);
+            }
+            (SandboxNetwork::Allowlist { .. }, None) => {
+                return Err(ExecutorError::Sandbox(
+                    "`socat` is required for the network allowlist".to_string(),
+                ));
+            }
+        }
+
+        out.push("--".into());
+        out.push(program.into());
+        out.extend(args.iter().map(OsString::from));
+        Ok(out)
+    }
+}
+
+/// The shared git directory a worktree checked out at `repo_path` writes objects and refs to
+fn worktree_common_dir(repo_path: &Path) -> Option<PathBuf> {
+    let dot_git = repo_path.join(".git");
+    if !dot_git.is_file() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(&dot_git).ok()?;
+    let git_dir = contents.trim().strip_prefix("gitdir:")?.trim();
+    let git_dir = repo_path.join(git_dir);
+    let common_dir = match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir,
+    };
+    common_dir.canonicalize().ok()
+}
+
+/// Allowlisting HTTP proxy for a sandboxed execution, served on a unix socket that is bound
+/// into the sandbox. Blocked hosts are reported on the receiver returned by [`Self::start`].
+/// The proxy stops and its socket is removed when dropped.
+#[derive(Debug)]
+pub struct SandboxProxy {
+    socket_path: PathBuf,
+    dir: PathBuf,
+    task: JoinHandle<()>,
+}
+
+impl SandboxProxy {
+    pub fn start(
+        allowlist: Vec<String>,
+    ) -> std::io::Result<(Self, mpsc::UnboundedReceiver<String>)> {
+        let dir = std::env::temp_dir().join(format!("vk-sandbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let socket_path = dir.join(PROXY_SOCKET_NAME);
+        let listener = UnixListener::bind(&socket_path)?;
+        let (denied_tx, denied_rx) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let allowlist = allowlist.clone();
+                let denied_tx = denied_tx.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = proxy_connection(stream, &allowlist, &denied_tx).await {
+                        tracing::debug!("Sandbox proxy connection failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok((
+            Self {
+                socket_path,
+                dir,
+                task,
+            },
+            denied_rx,
+        ))
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+}
+
+impl Drop for SandboxProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn proxy_connection(
+    mut client: UnixStream,
+    allowlist: &[String],
+    denied_tx: &mpsc::UnboundedSender<String>,
+) -> std::io::Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 4096];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD {
+            return client
+                .write_all(b"HTTP/1.1 431 Request Header Fields Too Large\r\n\r\n")
+                .await;
+        }
+        let n = client.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let Some((method, host, port)) = parse_request_line(&head) else {
+        return client.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n").await;
+    };
+    if !host_allowed(allowlist, &host) {
+        let _ = denied_tx.send(host);
+        return client
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+            .await;
+    }
+
+    let mut upstream = match TcpStream::connect((host.as_str(), port)).await {
+        Ok(upstream) => upstream,
+        Err(_) => return client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await,
+    };
+    if method.eq_ignore_ascii_case("CONNECT") {
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+        // Anything sent after the CONNECT head belongs to the tunnel
+        let end = head.windows(4).position(|w| w == b"\r\n\r\n").unwrap_or(0) + 4;
+        upstream.write_all(&head[end..]).await?;
+    } else {
+        upstream.write_all(&head).await?;
+    }
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Method, host and port of a proxied request: `CONNECT host:port` or `GET http://host[:port]/`
+fn parse_request_line(head: &[u8]) -> Option<(String, String, u16)> {
+    let line_end = head.windows(2).position(|w| w == b"\r\n")?;
+    let line = std::str::from_utf8(&head[..line_end]).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+
+    let (authority, default_port) = if method.eq_ignore_ascii_case("CONNECT") {
+        (target, 443)
+    } else {
+        let rest = target.strip_prefix("http://")?;
+        (rest.split('/').next()?, 80)
+    };
+    let (host, port) = match authority.strip_prefix('[') {
+        // Bracketed IPv6 literal
+        Some(rest) => {
+            let (host, port) = rest.split_once(']')?;
+            match port.strip_prefix(':') {
+                Some(port) => (host, port.parse().ok()?),
+                None => (host, default_port),
+            }
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().ok()?),
+            None => (authority, default_port),
+        },
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((method, host.to_string(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist_matches_exact_hosts_and_wildcard_subdomains() {
+        let allowlist = vec!["api.anthropic.com".to_string(), "*.github.com".to_string()];
+        assert!(host_allowed(&allowlist, "api.anthropic.com"));
+        assert!(host_allowed(&allowlist, "API.Anthropic.com."));
+        assert!(host_allowed(&allowlist, "github.com"));
+        assert!(host_allowed(&allowlist, "codeload.github.com"));
+        assert!(!host_allowed(&allowlist, "anthropic.com"));
+        assert!(!host_allowed(&allowlist, "evilgithub.com"));
+    }
+
+    #[test]
+    fn parses_connect_and_absolute_form_requests() {
+        assert_eq!(
+            parse_request_line(b"CONNECT api.openai.com:443 HTTP/1.1\r\nHost: x\r\n\r\n"),
+            Some(("CONNECT".to_string(), "api.openai.com".to_string(), 443))
+        );
+        assert_eq!(
+            parse_request_line(b"GET http://example.com/path HTTP/1.1\r\n\r\n"),
+            Some(("GET".to_string(), "example.com".to_string(), 80))
+        );
+        assert_eq!(
+            parse_request_line(b"GET http://example.com:8080/ HTTP/1.1\r\n\r\n"),
+            Some(("GET".to_string(), "example.com".to_string(), 8080))
+        );
+        assert_eq!(parse_request_line(b"GET /relative HTTP/1.1\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn detects_filesystem_and_network_violations() {
+        let deny = SandboxConfig {
+            enabled: true,
+            network: SandboxNetwork::Deny,
+            writable_paths: vec![],
+        };
+        assert!(
+            deny.detect_violation("touch: cannot touch '/etc/x': Read-only file system")
+                .is_some()
+        );
+        assert!(
+            deny.detect_violation("curl: (6) Could not resolve host: example.com")
+                .is_some()
+        );
+        assert!(
+            deny.detect_violation("[sandbox] Write outside the workspace was blocked")
+                .is_none()
+        );
+
+        let allow = SandboxConfig {
+            network: SandboxNetwork::Allow,
+            ..deny
+        };
+        assert!(
+            allow
+                .detect_violation("curl: (6) Could not resolve host: example.com")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn bwrap_binds_workspace_and_unshares_network() {
+        let sandbox = Sandbox::new(
+            SandboxConfig {
+                enabled: true,
+                network: SandboxNetwork::Deny,
+                writable_paths: vec!["/opt/cache".to_string()],
+            },
+            None,
+        );
+        let repo_context = RepoContext::new(PathBuf::from("/work/ws"), vec![]);
+        let args = sandbox
+            .bwrap_args(
+                OsStr::new("claude"),
+                &["-p".to_string()],
+                &repo_context,
+                None,
+            )
+            .unwrap();
+        let args: Vec<_> = args.iter().map(|a| a.to_string_lossy()).collect();
+        let joined = args.join(" ");
+
+        assert!(joined.starts_with("--die-with-parent --ro-bind / /"));
+        assert!(joined.contains("--bind /work/ws /work/ws"));
+        assert!(joined.contains("--bind-try /opt/cache /opt/cache"));
+        let aider_sessions = aider::sessions_path().unwrap();
+        let aider_sessions = aider_sessions.display();
+        assert!(joined.contains(&format!("--bind-try {aider_sessions} {aider_sessions}")));
+        assert!(joined.contains("--unshare-net"));
+        assert!(joined.ends_with("-- claude -p"));
+    }
+
+    #[test]
+    fn bwrap_keeps_git_hooks_config_and_user_config_read_only() {
+        // A worktree at ws/repo whose .git file points into main/.git/worktrees/repo
+        let tmp = tempfile::tempdir().unwrap();
+        let common_dir = tmp.path().join("main/.git");
+        let worktree_git_dir = common_dir.join("worktrees/repo");
+        std::fs::create_dir_all(common_dir.join("hooks")).unwrap();
+        std::fs::write(common_dir.join("config"), "[core]\n").unwrap();
+        std::fs::create_dir_all(&worktree_git_dir).unwrap();
+        std::fs::write(worktree_git_dir.join("commondir"), "../..\n").unwrap();
+        let workspace_root = tmp.path().join("ws");
+        std::fs::create_dir_all(workspace_root.join("repo")).unwrap();
+        std::fs::write(
+            workspace_root.join("repo/.git"),
+            format!("gitdir: {}\n", worktree_git_dir.display()),
+        )
+        .unwrap();
+
+        let sandbox = Sandbox::new(SandboxConfig::default(), None);
+        let repo_context = RepoContext::new(workspace_root.clone(), vec!["repo".to_string()]);
+        let args = sandbox
+            .bwrap_args(OsStr::new("codex"), &[], &repo_context, None)
+            .unwrap();
+        let args: Vec<String> = args
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+
+        let common_dir = common_dir.canonicalize().unwrap();
+        let mount = |flag: &str, path: &Path| {
+            let path = path.to_string_lossy().into_owned();
+            args.windows(3)
+                .position(|w| w[0] == flag && w[1] == path && w[2] == path)
+        };
+        let writable = mount("--bind", &common_dir).expect("git dir is writable");
+        for protected in GIT_PROTECTED_PATHS {
+            let read_only = mount("--ro-bind-try", &common_dir.join(protected))
+                .unwrap_or_else(|| panic!("{protected} is read-only"));
+            assert!(
+                read_only > writable,
+                "{protected} is mounted over the git dir"
+            );
+        }
+        let workspace = mount("--bind", &workspace_root).unwrap();
+        assert!(mount("--ro-bind-try", &workspace_root.join("repo/.git")).unwrap() > workspace);
+
+        let home = dirs::home_dir().unwrap();
+        assert!(mount("--bind-try", &home.join(".config")).is_none());
+        assert!(mount("--bind-try", &home.join(".local/share")).is_none());
+        assert!(mount("--bind-try", &home.join(".config/opencode")).is_some());
+        assert!(mount("--bind-try", &home.join(".local/share/opencode")).is_some());
+    }
+}
@@ -0,0 +1,161 @@
+//! Output spooling for executions that must outlive the server.
+//!
+//! A spooled command runs under a small `sh` wrapper that appends its stdout and stderr to
+//! files in the spool directory while still passing them through, and records its exit code
+//! once it finishes. SIGPIPE is ignored inside the wrapper, so when the server goes away the
+//! process keeps running and its output keeps landing in the spool, where a restarted server
+//! can pick it up again.
+
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::executors::ExecutorError;
+
+const STDOUT_FILE: &str = "stdout";
+const STDERR_FILE: &str = "stderr";
+const EXIT_CODE_FILE: &str = "exit_code";
+const LEADER_FILE: &str = "leader";
+
+/// Runs `"$@"` with stdout and stderr teed into the spool directory given as `$1`
+const SPOOL_SCRIPT: &str = r#"spool="$1"
+shift
+trap '' PIPE
+{ { "$@"; echo $? > "$spool/exit_code"; } 2>&1 1>&3 3>&- | tee -a "$spool/stderr" >&2 3>&-; } 3>&1 | tee -a "$spool/stdout"
+code=$(cat "$spool/exit_code" 2>/dev/null) || code=1
+exit "$code""#;
+
+/// Files a detached execution writes its output and exit code to
+#[derive(Debug, Clone)]
+pub struct Spool {
+    dir: PathBuf,
+}
+
+impl Spool {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn stdout_path(&self) -> PathBuf {
+        self.dir.join(STDOUT_FILE)
+    }
+
+    pub fn stderr_path(&self) -> PathBuf {
+        self.dir.join(STDERR_FILE)
+    }
+
+    /// Exit code recorded by the spooled process, once it has exited
+    pub fn exit_code(&self) -> Option<i32> {
+        std::fs::read_to_string(self.dir.join(EXIT_CODE_FILE))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Remember the start time of the spooled process, which tells it apart from another
+    /// process that reuses its pid later
+    pub fn record_leader_start_time(&self, start_time: &str) -> std::io::Result<()> {
+        std::fs::write(self.dir.join(LEADER_FILE), start_time)
+    }
+
+    pub fn leader_start_time(&self) -> Option<String> {
+        let start_time = std::fs::read_to_string(self.dir.join(LEADER_FILE)).ok()?;
+        Some(start_time.trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    /// Wrap `command` so that its output is spooled. The program, arguments, environment and
+    /// working directory of `command` carry over to the wrapper.
+    pub fn wrap(&self, command: Command) -> Result<Command, ExecutorError> {
+        if !cfg!(unix) {
+            return Err(ExecutorError::Io(std::io::Error::other(
+                "spooled execution is only supported on Unix",
+            )));
+        }
+        std::fs::create_dir_all(&self.dir).map_err(ExecutorError::Io)?;
+
+        let inner = command.as_std();
+        let mut wrapped = Command::new("sh");
+        wrapped
+            .arg("-c")
+            .arg(SPOOL_SCRIPT)
+            .arg("vk-spool")
+            .arg(&self.dir)
+            .arg(inner.get_program())
+            .args(inner.get_args());
+        for (key, value) in inner.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        if let Some(dir) = inner.get_current_dir() {
+            wrapped.current_dir(dir);
+        }
+        Ok(wrapped)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::Stdio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn wrapped_command_spools_output_and_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path().join("spool"));
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 3"]);
+        let output = spool
+            .wrap(command)
+            .unwrap()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "err\n");
+        assert_eq!(
+            std::fs::read_to_string(spool.stdout_path()).unwrap(),
+            "out\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(spool.stderr_path()).unwrap(),
+            "err\n"
+        );
+        assert_eq!(spool.exit_code(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn spooled_command_outlives_its_reader() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path().join("spool"));
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 0.5; echo after"]);
+        let mut child = spool
+            .wrap(command)
+            .unwrap()
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        drop(child.stdout.take());
+
+        let status = child.wait().await.unwrap();
+        assert!(status.success());
+        assert_eq!(
+            std::fs::read_to_string(spool.stdout_path()).unwrap(),
+            "after\n"
+        );
+    }
+}
@@ -615,8 +615,12 @@ impl GitCli {
                 "No rebase in progress".to_string(),
             ));
         }
-        self.git(worktree_path, ["rebase", "--continue"])
-            .map(|_| ())
+        // Keep the recorded commit messages rather than waiting on an editor
+        self.git(
+            worktree_path,
+            ["-c", "core.editor=true", "rebase", "--continue"],
+        )
+        .map(|_| ())
     }
 
     /// Return true if there are staged changes (index differs from HEAD)
@@ -659,6 +663,46 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Checkout base branch and merge from_branch with a merge commit, even when a
+    /// fast-forward is possible. Returns new HEAD sha.
+    pub fn merge_no_ff_commit(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+        message: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(
+            repo_path,
+            ["merge", "--no-ff", "--no-edit", "-m", message, from_branch],
+        )
+        .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
+    /// Checkout base branch and fast-forward it to from_branch; fails if that is not
+    /// possible. Returns new HEAD sha.
+    pub fn merge_ff_only(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--ff-only", from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -692,6 +736,20 @@ impl GitCli {
         self.git(worktree_path, ["revert", "--abort"]).map(|_| ())
     }
 
+    /// One-line summary (`<short sha> <subject>`) of a commit, or None if the
+    /// revision does not exist.
+    pub fn commit_summary(
+        &self,
+        worktree_path: &Path,
+        rev: &str,
+    ) -> Result<Option<String>, GitCliError> {
+        match self.git(worktree_path, ["log", "-1", "--format=%h %s", rev, "--"]) {
+            Ok(out) => Ok(Some(out.trim().to_string())),
+            Err(GitCliError::CommandFailed(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// List files currently in a conflicted (unmerged) state in the worktree.
     pub fn get_conflicted_files(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
         // `--diff-filter=U` lists paths with unresolved conflicts
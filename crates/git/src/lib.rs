@@ -71,6 +71,68 @@ pub enum ConflictOp {
     Revert,
 }
 
+impl ConflictOp {
+    /// Pseudo-ref git keeps for the commit being applied while the operation is stopped
+    fn incoming_ref(&self) -> &'static str {
+        match self {
+            ConflictOp::Rebase => "REBASE_HEAD",
+            ConflictOp::Merge => "MERGE_HEAD",
+            ConflictOp::CherryPick => "CHERRY_PICK_HEAD",
+            ConflictOp::Revert => "REVERT_HEAD",
+        }
+    }
+}
+
+/// Both sides of an in-progress conflict as one-line commit summaries
+#[derive(Debug, Clone)]
+pub struct ConflictSides {
+    /// The commit checked out in the worktree (the new base during a rebase)
+    pub head: Option<String>,
+    /// The commit being applied onto it (the task's commit during a rebase)
+    pub incoming: Option<String>,
+}
+
+/// How a task branch is landed on its base branch by a local merge
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// A single new commit on the base branch with all of the task's changes
+    #[default]
+    Squash,
+    /// A merge commit with the base and task branches as parents
+    MergeCommit,
+    /// Replay the task's commits onto the base branch, then fast-forward it
+    Rebase,
+    /// Fast-forward the base branch; fails if it has moved ahead of the task branch
+    FastForward,
+}
+
+impl MergeStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeStrategy::Squash => "squash",
+            MergeStrategy::MergeCommit => "merge_commit",
+            MergeStrategy::Rebase => "rebase",
+            MergeStrategy::FastForward => "fast_forward",
+        }
+    }
+}
+
+impl std::str::FromStr for MergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "squash" => Ok(MergeStrategy::Squash),
+            "merge_commit" => Ok(MergeStrategy::MergeCommit),
+            "rebase" => Ok(MergeStrategy::Rebase),
+            "fast_forward" => Ok(MergeStrategy::FastForward),
+            other => Err(format!("Unknown merge strategy: {other}")),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GitBranch {
     pub name: String,
@@ -822,22 +884,55 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_strategy(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+            MergeStrategy::Squash,
+        )
+    }
+
+    /// Land a task branch on its base branch using `strategy` and return the new base commit.
+    /// `commit_message` is used for the squash and merge commits; rebased commits keep theirs.
+    pub fn merge_changes_with_strategy(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+        strategy: MergeStrategy,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
         let base_repo = self.open_repo(base_worktree_path)?;
 
-        // Check if base branch is ahead of task branch - this indicates the base has moved
-        // ahead since the task was created, which should block the merge
+        // Squash and fast-forward merges need the task branch to contain the base branch; a
+        // base that has moved ahead since the task was created blocks them
         let (_, task_behind) =
             self.get_branch_status(base_worktree_path, task_branch_name, base_branch_name)?;
 
-        if task_behind > 0 {
+        if task_behind > 0 && matches!(strategy, MergeStrategy::Squash | MergeStrategy::FastForward)
+        {
             return Err(GitServiceError::BranchesDiverged(format!(
                 "Cannot merge: base branch '{base_branch_name}' is {task_behind} commits ahead of task branch '{task_branch_name}'. The base branch has moved forward since the task was created.",
             )));
         }
 
+        // Rebasing rewrites the task branch, so its worktree must be clean
+        if strategy == MergeStrategy::Rebase {
+            self.check_worktree_clean(&task_repo)?;
+            if GitCli::new()
+                .is_rebase_in_progress(task_worktree_path)
+                .unwrap_or(false)
+            {
+                return Err(GitServiceError::RebaseInProgress);
+            }
+        }
+
         // Check where base branch is checked out (if anywhere)
         match self.find_checkout_path_for_branch(base_worktree_path, base_branch_name)? {
             Some(base_checkout_path) => {
@@ -859,24 +954,75 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
-                    .merge_squash_commit(
-                        &base_checkout_path,
-                        base_branch_name,
-                        task_branch_name,
-                        commit_message,
-                    )
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
-                    })?;
+                let sha = match strategy {
+                    MergeStrategy::Squash => git_cli
+                        .merge_squash_commit(
+                            &base_checkout_path,
+                            base_branch_name,
+                            task_branch_name,
+                            commit_message,
+                        )
+                        .map_err(|e| {
+                            GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
+                        })?,
+                    MergeStrategy::MergeCommit => git_cli
+                        .merge_no_ff_commit(
+                            &base_checkout_path,
+                            base_branch_name,
+                            task_branch_name,
+                            commit_message,
+                        )
+                        .map_err(|e| {
+                            let conflicted_files = git_cli
+                                .get_conflicted_files(&base_checkout_path)
+                                .unwrap_or_default();
+                            let _ = git_cli.abort_merge(&base_checkout_path);
+                            Self::cli_merge_error("Merge", e, conflicted_files)
+                        })?,
+                    MergeStrategy::Rebase => {
+                        self.ensure_cli_commit_identity(task_worktree_path)?;
+                        git_cli
+                            .rebase_onto(
+                                task_worktree_path,
+                                base_branch_name,
+                                base_branch_name,
+                                task_branch_name,
+                            )
+                            .map_err(|e| {
+                                let conflicted_files = git_cli
+                                    .get_conflicted_files(task_worktree_path)
+                                    .unwrap_or_default();
+                                let _ = git_cli.abort_rebase(task_worktree_path);
+                                Self::cli_merge_error("Rebase", e, conflicted_files)
+                            })?;
+                        git_cli
+                            .merge_ff_only(&base_checkout_path, base_branch_name, task_branch_name)
+                            .map_err(|e| {
+                                GitServiceError::InvalidRepository(format!(
+                                    "Fast-forward after rebase failed: {e}"
+                                ))
+                            })?
+                    }
+                    MergeStrategy::FastForward => git_cli
+                        .merge_ff_only(&base_checkout_path, base_branch_name, task_branch_name)
+                        .map_err(|e| {
+                            GitServiceError::InvalidRepository(format!(
+                                "Fast-forward merge failed: {e}"
+                            ))
+                        })?,
+                };
 
                 // Update task branch ref for continuity
-                let task_refname = format!("refs/heads/{task_branch_name}");
-                git_cli
-                    .update_ref(base_worktree_path, &task_refname, &sha)
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("git update-ref failed: {e}"))
-                    })?;
+                if strategy == MergeStrategy::Squash {
+                    let task_refname = format!("refs/heads/{task_branch_name}");
+                    git_cli
+                        .update_ref(base_worktree_path, &task_refname, &sha)
+                        .map_err(|e| {
+                            GitServiceError::InvalidRepository(format!(
+                                "git update-ref failed: {e}"
+                            ))
+                        })?;
+                }
 
                 Ok(sha)
             }
@@ -888,32 +1034,122 @@ impl GitService {
                 // Resolve commits
                 let base_commit = base_branch.get().peel_to_commit()?;
                 let task_commit = task_branch.get().peel_to_commit()?;
-
-                // Create the squash commit in-memory (no checkout) and update the base branch ref
                 let signature = self.signature_with_fallback(&task_repo)?;
-                let squash_commit_id = self.perform_squash_merge(
-                    &task_repo,
-                    &base_commit,
-                    &task_commit,
-                    &signature,
-                    commit_message,
-                    base_branch_name,
-                )?;
-
-                // Update the task branch to the new squash commit so follow-up
-                // work can continue from the merged state without conflicts.
                 let task_refname = format!("refs/heads/{task_branch_name}");
-                base_repo.reference(
-                    &task_refname,
-                    squash_commit_id,
-                    true,
-                    "Reset task branch after squash merge",
-                )?;
 
-                Ok(squash_commit_id.to_string())
+                match strategy {
+                    MergeStrategy::Squash => {
+                        // Create the squash commit in-memory (no checkout) and update the base
+                        // branch ref
+                        let squash_commit_id = self.perform_squash_merge(
+                            &task_repo,
+                            &base_commit,
+                            &task_commit,
+                            &signature,
+                            commit_message,
+                            base_branch_name,
+                        )?;
+
+                        // Update the task branch to the new squash commit so follow-up
+                        // work can continue from the merged state without conflicts.
+                        base_repo.reference(
+                            &task_refname,
+                            squash_commit_id,
+                            true,
+                            "Reset task branch after squash merge",
+                        )?;
+
+                        Ok(squash_commit_id.to_string())
+                    }
+                    MergeStrategy::MergeCommit => {
+                        let merge_commit_id = self.perform_merge_commit(
+                            &task_repo,
+                            &base_commit,
+                            &task_commit,
+                            &signature,
+                            commit_message,
+                            base_branch_name,
+                        )?;
+                        Ok(merge_commit_id.to_string())
+                    }
+                    MergeStrategy::Rebase => {
+                        let rebased_id = self.replay_commits(
+                            &task_repo,
+                            &base_commit,
+                            &task_commit,
+                            &signature,
+                        )?;
+                        let rebased = task_repo.find_commit(rebased_id)?;
+
+                        // Bring the task worktree along before moving the branch it has
+                        // checked out; a safe checkout refuses to clobber local files
+                        task_repo.checkout_tree(
+                            rebased.as_object(),
+                            Some(git2::build::CheckoutBuilder::new().safe()),
+                        )?;
+                        task_repo.reference(
+                            &task_refname,
+                            rebased_id,
+                            true,
+                            "Rebase task branch onto base",
+                        )?;
+                        let base_refname = format!("refs/heads/{base_branch_name}");
+                        task_repo.reference(
+                            &base_refname,
+                            rebased_id,
+                            true,
+                            "Fast-forward after rebase",
+                        )?;
+                        Ok(rebased_id.to_string())
+                    }
+                    MergeStrategy::FastForward => {
+                        let base_refname = format!("refs/heads/{base_branch_name}");
+                        task_repo.reference(
+                            &base_refname,
+                            task_commit.id(),
+                            true,
+                            "Fast-forward merge",
+                        )?;
+                        Ok(task_commit.id().to_string())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Map a failed CLI merge or rebase to a conflict error when the output shows conflicts
+    fn cli_merge_error(
+        operation: &str,
+        err: GitCliError,
+        conflicted_files: Vec<String>,
+    ) -> GitServiceError {
+        let looks_like_conflict = match &err {
+            GitCliError::CommandFailed(stderr) => {
+                stderr.contains("CONFLICT")
+                    || stderr.contains("could not apply")
+                    || stderr.to_lowercase().contains("resolve all conflicts")
             }
+            _ => false,
+        };
+        if looks_like_conflict || !conflicted_files.is_empty() {
+            let files_part = if conflicted_files.is_empty() {
+                String::new()
+            } else {
+                format!(" Conflicted files: {}.", conflicted_files.join(", "))
+            };
+            return GitServiceError::MergeConflicts {
+                message: format!(
+                    "{operation} failed due to conflicts and was aborted.{files_part} Rebase the task branch and resolve them first."
+                ),
+                conflicted_files,
+            };
         }
+        GitServiceError::InvalidRepository(format!(
+            "CLI {} failed: {err}",
+            operation.to_lowercase()
+        ))
     }
+
     fn get_branch_status_inner(
         &self,
         repo: &Repository,
@@ -1343,6 +1579,32 @@ impl GitService {
         commit_message: &str,
         base_branch_name: &str,
     ) -> Result<git2::Oid, GitServiceError> {
+        let tree = self.merged_tree(repo, base_commit, task_commit)?;
+
+        // Create a squash commit: use merged tree with base_commit as sole parent
+        let squash_commit_id = repo.commit(
+            None,           // Don't update any reference yet
+            signature,      // Author
+            signature,      // Committer
+            commit_message, // Custom message
+            &tree,          // Merged tree content
+            &[base_commit], // Single parent: base branch commit
+        )?;
+
+        // Update the base branch reference to point to the new commit
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
+
+        Ok(squash_commit_id)
+    }
+
+    /// Merge two commits in memory (no checkout) into the tree of their merge result
+    fn merged_tree<'r>(
+        &self,
+        repo: &'r Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+    ) -> Result<git2::Tree<'r>, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
         // Safety and correctness options
@@ -1361,23 +1623,105 @@ impl GitService {
 
         // Write the merged tree back to the repository
         let tree_id = index.write_tree_to(repo)?;
-        let tree = repo.find_tree(tree_id)?;
+        Ok(repo.find_tree(tree_id)?)
+    }
 
-        // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
-            None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
-            commit_message, // Custom message
-            &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
+    fn perform_merge_commit(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+        commit_message: &str,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let tree = self.merged_tree(repo, base_commit, task_commit)?;
+
+        // Both branch tips become parents, keeping the task's commits in history
+        let merge_commit_id = repo.commit(
+            None,
+            signature,
+            signature,
+            commit_message,
+            &tree,
+            &[base_commit, task_commit],
         )?;
 
-        // Update the base branch reference to point to the new commit
         let refname = format!("refs/heads/{base_branch_name}");
-        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
+        repo.reference(&refname, merge_commit_id, true, "Merge commit")?;
 
-        Ok(squash_commit_id)
+        Ok(merge_commit_id)
+    }
+
+    /// Replay the commits of `task_commit` that `base_commit` lacks on top of it, in memory,
+    /// and return the new tip. Like `git rebase`, merge commits and commits whose changes
+    /// are already on the base are dropped, and authors are preserved.
+    fn replay_commits(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(task_commit.id())?;
+        revwalk.hide(base_commit.id())?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+        // Conflicts are left in the index so the conflicted paths can be reported
+        let mut merge_opts = git2::MergeOptions::new();
+        merge_opts.find_renames(true);
+
+        let mut onto = base_commit.clone();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let mut index = repo.cherrypick_commit(&commit, &onto, 0, Some(&merge_opts))?;
+            if index.has_conflicts() {
+                let conflicted_files = Self::index_conflicted_paths(&index)?;
+                return Err(GitServiceError::MergeConflicts {
+                    message: format!(
+                        "Rebase failed due to conflicts in {} while applying {}. Please resolve conflicts manually.",
+                        conflicted_files.join(", "),
+                        commit.id()
+                    ),
+                    conflicted_files,
+                });
+            }
+            let tree_id = index.write_tree_to(repo)?;
+            if tree_id == onto.tree_id() {
+                continue;
+            }
+            let tree = repo.find_tree(tree_id)?;
+            let new_id = repo.commit(
+                None,
+                &commit.author(),
+                signature,
+                commit.message().unwrap_or_default(),
+                &tree,
+                &[&onto],
+            )?;
+            onto = repo.find_commit(new_id)?;
+        }
+        Ok(onto.id())
+    }
+
+    /// Paths with conflict entries in an in-memory merge index
+    fn index_conflicted_paths(index: &git2::Index) -> Result<Vec<String>, GitServiceError> {
+        let mut paths = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+            if let Some(entry) = entry {
+                let path = String::from_utf8_lossy(&entry.path).into_owned();
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        Ok(paths)
     }
 
     /// Rebase a worktree branch onto a new base
@@ -1572,6 +1916,43 @@ impl GitService {
         })
     }
 
+    /// Summarize the commits on both sides of the worktree's in-progress operation.
+    pub fn get_conflict_sides(
+        &self,
+        worktree_path: &Path,
+        op: &ConflictOp,
+    ) -> Result<ConflictSides, GitServiceError> {
+        let git = GitCli::new();
+        let summary = |rev: &str| {
+            git.commit_summary(worktree_path, rev).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git log for {rev} failed: {e}"))
+            })
+        };
+        Ok(ConflictSides {
+            head: summary("HEAD")?,
+            incoming: summary(op.incoming_ref())?,
+        })
+    }
+
+    /// Stage the worktree once no conflicted file still contains conflict markers.
+    /// Returns the files that do; nothing is staged while any remain.
+    pub fn stage_resolved_conflicts(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let unresolved: Vec<String> = self
+            .get_conflicted_files(worktree_path)?
+            .into_iter()
+            .filter(|file| has_conflict_markers(&worktree_path.join(file)))
+            .collect();
+        if unresolved.is_empty() {
+            GitCli::new()
+                .add_all(worktree_path)
+                .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
+        }
+        Ok(unresolved)
+    }
+
     /// Abort an in-progress rebase in this worktree (no-op if none).
     pub fn abort_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
@@ -1583,6 +1964,7 @@ impl GitService {
     /// Continue an in-progress rebase. Fails if there are unresolved conflicts.
     pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
+        self.ensure_cli_commit_identity(worktree_path)?;
         git.continue_rebase(worktree_path).map_err(|e| {
             GitServiceError::InvalidRepository(format!("git rebase --continue failed: {e}"))
         })
@@ -1962,3 +2344,15 @@ impl GitService {
         Ok(stats)
     }
 }
+
+/// Whether a file still contains conflict markers. Missing or unreadable files (e.g.
+/// deleted as the resolution) count as resolved.
+fn has_conflict_markers(path: &Path) -> bool {
+    std::fs::read(path)
+        .map(|bytes| {
+            String::from_utf8_lossy(&bytes)
+                .lines()
+                .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+        })
+        .unwrap_or(false)
+}
@@ -4,7 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use git::{GitCli, GitCliError, GitService};
+use git::{GitCli, GitCliError, GitService, MergeStrategy};
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
@@ -713,6 +713,58 @@ fn rebase_refuses_to_abort_existing_rebase() {
     // Note: We do not auto-abort; user should resolve or abort explicitly
 }
 
+#[test]
+fn stage_resolved_conflicts_waits_for_markers_to_be_removed() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    let svc = GitService::new();
+    let _ = svc
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .expect_err("rebase should stop on conflicts");
+
+    let op = svc
+        .detect_conflict_op(&worktree_path)
+        .unwrap()
+        .expect("rebase in progress");
+    let sides = svc.get_conflict_sides(&worktree_path, &op).unwrap();
+    assert!(sides.head.unwrap().ends_with("new-base change"));
+    assert!(
+        sides
+            .incoming
+            .unwrap()
+            .ends_with("feature conflicting change")
+    );
+
+    // Markers still present: nothing is staged and the file stays conflicted
+    let unresolved = svc.stage_resolved_conflicts(&worktree_path).unwrap();
+    assert_eq!(unresolved, vec!["conflict.txt".to_string()]);
+    assert_eq!(
+        svc.get_conflicted_files(&worktree_path).unwrap(),
+        vec!["conflict.txt".to_string()]
+    );
+
+    write_file(&worktree_path, "conflict.txt", "resolved version\n");
+    let unresolved = svc.stage_resolved_conflicts(&worktree_path).unwrap();
+    assert!(unresolved.is_empty());
+    assert!(svc.get_conflicted_files(&worktree_path).unwrap().is_empty());
+
+    svc.continue_rebase(&worktree_path).unwrap();
+    assert!(!svc.is_rebase_in_progress(&worktree_path).unwrap());
+
+    let content = fs::read_to_string(worktree_path.join("conflict.txt")).unwrap();
+    assert_eq!(content, "resolved version\n");
+    let repo = Repository::open(&worktree_path).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary(), Some("feature conflicting change"));
+}
+
 #[test]
 fn rebase_fast_forwards_when_no_unique_commits() {
     let td = TempDir::new().unwrap();
@@ -1406,3 +1458,286 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+// Feature has two commits; main has moved ahead with a non-conflicting commit.
+// When `checkout_main` is false, the main repo sits on another branch so merges take the
+// libgit2 path.
+fn setup_base_ahead_repo(root: &TempDir, checkout_main: bool) -> (PathBuf, PathBuf) {
+    let repo_path = root.path().join("repo");
+    let worktree_path = root.path().join("wt-feature");
+
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&repo_path)
+        .expect("init repo");
+    let repo = Repository::open(&repo_path).unwrap();
+    configure_user(&repo);
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "base.txt", "base\n");
+    commit_all(&repo, "initial commit");
+
+    create_branch_from_head(&repo, "feature");
+    create_branch_from_head(&repo, "parking");
+    service
+        .add_worktree(&repo_path, &worktree_path, "feature", false)
+        .expect("create worktree");
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    write_file(&worktree_path, "feature_a.txt", "a\n");
+    commit_all(&wt_repo, "feature commit a");
+    write_file(&worktree_path, "feature_b.txt", "b\n");
+    commit_all(&wt_repo, "feature commit b");
+
+    write_file(&repo_path, "main.txt", "main advanced\n");
+    commit_all(&repo, "main advances");
+
+    if !checkout_main {
+        checkout_branch(&repo, "parking");
+    }
+    (repo_path, worktree_path)
+}
+
+fn first_parent_messages(repo_path: &Path, branch: &str, count: usize) -> Vec<String> {
+    let repo = Repository::open(repo_path).unwrap();
+    let mut commit = repo
+        .find_branch(branch, git2::BranchType::Local)
+        .unwrap()
+        .get()
+        .peel_to_commit()
+        .unwrap();
+    let mut messages = vec![commit.summary().unwrap_or_default().to_string()];
+    while messages.len() < count {
+        commit = commit.parent(0).unwrap();
+        messages.push(commit.summary().unwrap_or_default().to_string());
+    }
+    messages
+}
+
+#[test]
+fn merge_commit_strategy_merges_when_base_ahead() {
+    for checkout_main in [true, false] {
+        let td = TempDir::new().unwrap();
+        let (repo_path, worktree_path) = setup_base_ahead_repo(&td, checkout_main);
+        let s = GitService::new();
+        let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+        let sha = s
+            .merge_changes_with_strategy(
+                &repo_path,
+                &worktree_path,
+                "feature",
+                "main",
+                "merge feature",
+                MergeStrategy::MergeCommit,
+            )
+            .expect("merge commit should succeed when base is ahead");
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let merge = repo
+            .find_commit(git2::Oid::from_str(&sha).unwrap())
+            .unwrap();
+        assert_eq!(merge.parent_count(), 2);
+        assert_eq!(merge.parent_id(1).unwrap().to_string(), feature_before);
+        assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+        // The task branch keeps its own commits
+        assert_eq!(
+            s.get_branch_oid(&repo_path, "feature").unwrap(),
+            feature_before
+        );
+        assert!(!GitCli::new().has_staged_changes(&repo_path).unwrap());
+    }
+}
+
+#[test]
+fn rebase_strategy_keeps_individual_commits_in_linear_history() {
+    for checkout_main in [true, false] {
+        let td = TempDir::new().unwrap();
+        let (repo_path, worktree_path) = setup_base_ahead_repo(&td, checkout_main);
+        let s = GitService::new();
+
+        let sha = s
+            .merge_changes_with_strategy(
+                &repo_path,
+                &worktree_path,
+                "feature",
+                "main",
+                "unused for rebase",
+                MergeStrategy::Rebase,
+            )
+            .expect("rebase merge should succeed when base is ahead");
+
+        assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), sha);
+        assert_eq!(s.get_branch_oid(&repo_path, "feature").unwrap(), sha);
+        assert_eq!(
+            first_parent_messages(&repo_path, "main", 4),
+            vec![
+                "feature commit b",
+                "feature commit a",
+                "main advances",
+                "initial commit"
+            ]
+        );
+        let repo = Repository::open(&repo_path).unwrap();
+        let tip = repo
+            .find_commit(git2::Oid::from_str(&sha).unwrap())
+            .unwrap();
+        assert_eq!(tip.parent_count(), 1);
+
+        // The task worktree follows the rebased branch and stays clean
+        assert!(s.is_worktree_clean(&worktree_path).unwrap());
+        assert!(worktree_path.join("main.txt").exists());
+    }
+}
+
+#[test]
+fn rebase_strategy_conflict_leaves_branches_untouched() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let s = GitService::new();
+    let repo = Repository::open(&repo_path).unwrap();
+    checkout_branch(&repo, "new-base");
+
+    let base_before = s.get_branch_oid(&repo_path, "new-base").unwrap();
+    let feature_before = s.get_branch_oid(&repo_path, "feature").unwrap();
+    let res = s.merge_changes_with_strategy(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "new-base",
+        "rebase conflict",
+        MergeStrategy::Rebase,
+    );
+
+    assert!(matches!(
+        res,
+        Err(git::GitServiceError::MergeConflicts { .. })
+    ));
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "new-base").unwrap(),
+        base_before
+    );
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "feature").unwrap(),
+        feature_before
+    );
+    assert!(!GitCli::new().is_rebase_in_progress(&worktree_path).unwrap());
+}
+
+#[test]
+fn rebase_strategy_reports_conflicted_files_when_base_not_checked_out() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let s = GitService::new();
+    let repo = Repository::open(&repo_path).unwrap();
+    // With new-base checked out nowhere the commits are replayed in memory
+    checkout_branch(&repo, "main");
+
+    let base_before = s.get_branch_oid(&repo_path, "new-base").unwrap();
+    let res = s.merge_changes_with_strategy(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "new-base",
+        "rebase conflict",
+        MergeStrategy::Rebase,
+    );
+
+    match res {
+        Err(git::GitServiceError::MergeConflicts {
+            conflicted_files, ..
+        }) => assert_eq!(conflicted_files, vec!["conflict.txt".to_string()]),
+        other => panic!("expected merge conflicts, got {other:?}"),
+    }
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "new-base").unwrap(),
+        base_before
+    );
+    assert!(s.is_worktree_clean(&worktree_path).unwrap());
+}
+
+#[test]
+fn merge_commit_strategy_conflict_aborts_merge_on_base() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let s = GitService::new();
+    let repo = Repository::open(&repo_path).unwrap();
+    checkout_branch(&repo, "new-base");
+
+    let base_before = s.get_branch_oid(&repo_path, "new-base").unwrap();
+    let res = s.merge_changes_with_strategy(
+        &repo_path,
+        &worktree_path,
+        "feature",
+        "new-base",
+        "merge conflict",
+        MergeStrategy::MergeCommit,
+    );
+
+    assert!(matches!(
+        res,
+        Err(git::GitServiceError::MergeConflicts { .. })
+    ));
+    assert_eq!(
+        s.get_branch_oid(&repo_path, "new-base").unwrap(),
+        base_before
+    );
+    let git = GitCli::new();
+    assert!(!git.is_merge_in_progress(&repo_path).unwrap());
+    assert!(!git.has_staged_changes(&repo_path).unwrap());
+}
+
+#[test]
+fn fast_forward_strategy_refuses_when_base_ahead() {
+    for checkout_main in [true, false] {
+        let td = TempDir::new().unwrap();
+        let (repo_path, worktree_path) = setup_base_ahead_repo(&td, checkout_main);
+        let s = GitService::new();
+        let main_before = s.get_branch_oid(&repo_path, "main").unwrap();
+
+        let res = s.merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            "ff",
+            MergeStrategy::FastForward,
+        );
+
+        assert!(matches!(
+            res,
+            Err(git::GitServiceError::BranchesDiverged(_))
+        ));
+        assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), main_before);
+    }
+}
+
+#[test]
+fn fast_forward_strategy_moves_base_to_task_tip() {
+    for checkout_main in [true, false] {
+        let td = TempDir::new().unwrap();
+        let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+        let s = GitService::new();
+        let repo = Repository::open(&repo_path).unwrap();
+        checkout_branch(&repo, if checkout_main { "old-base" } else { "main" });
+        let feature_tip = s.get_branch_oid(&repo_path, "feature").unwrap();
+
+        let sha = s
+            .merge_changes_with_strategy(
+                &repo_path,
+                &worktree_path,
+                "feature",
+                "old-base",
+                "ff",
+                MergeStrategy::FastForward,
+            )
+            .expect("fast-forward should succeed");
+
+        assert_eq!(sha, feature_tip);
+        assert_eq!(
+            s.get_branch_oid(&repo_path, "old-base").unwrap(),
+            feature_tip
+        );
+        if checkout_main {
+            assert!(repo_path.join("feat.txt").exists());
+        }
+    }
+}
@@ -1,8 +1,15 @@
 use command_group::AsyncGroupChild;
 use services::services::container::ContainerError;
+use utils::process::DetachedProcessGroup;
 
 pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
     utils::process::kill_process_group(child)
         .await
         .map_err(ContainerError::KillFailed)
 }
+
+pub async fn kill_detached_process_group(
+    group: &DetachedProcessGroup,
+) -> Result<(), ContainerError> {
+    group.kill().await.map_err(ContainerError::KillFailed)
+}
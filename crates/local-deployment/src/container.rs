@@ -1,7 +1,8 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io,
     path::{Path, PathBuf},
+    process::ExitStatus,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -14,8 +15,10 @@ use db::{
     models::{
         coding_agent_turn::CodingAgentTurn,
         execution_process::{
-            ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            CheckStatus, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
+            ExecutionProcessStatus,
         },
+        execution_process_logs::ExecutionProcessLogs,
         execution_process_repo_state::ExecutionProcessRepoState,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
@@ -31,19 +34,29 @@ use executors::{
         Executable, ExecutorAction, ExecutorActionType,
         coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
+        script::{ScriptContext, ScriptRequest},
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::{ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, CancellationToken, ExecutorExitResult, ExecutorExitSignal},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    profile::ExecutorProfileId,
+    sandbox::{SANDBOX_LOG_PREFIX, Sandbox, SandboxProxy},
+    spool::Spool,
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
-use git::GitService;
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
+use git::{ConflictOp, GitService};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
-    approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::{Config, DEFAULT_COMMIT_REMINDER_PROMPT},
+    approvals::{
+        Approvals,
+        executor_approvals::ExecutorApprovalBridge,
+        policy::{ApprovalPolicy, ApprovalPolicyEngine},
+    },
+    config::{
+        ConcurrencyConfig, Config, DEFAULT_COMMIT_REMINDER_PROMPT, SandboxConfig, SandboxNetwork,
+    },
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
     image::ImageService,
@@ -51,22 +64,74 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::RemoteClient,
     remote_sync,
+    repo_config::RepoConfigFile,
     webhook_notification::WebhookMetadata,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{Mutex, RwLock, mpsc, oneshot},
+    task::JoinHandle,
+};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
-    text::{git_branch_id, short_uuid, truncate_to_char_boundary},
+    path::VIBE_SPOOL_DIR,
+    process::DetachedProcessGroup,
+    text::{git_branch_id, short_uuid, tail_to_char_boundary, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, copy, detached, pty::PtyService};
 
 const WORKSPACE_TOUCH_DEBOUNCE: Duration = Duration::from_mins(2);
 
+/// What happens once a check script run on a coding agent turn's changes exits
+#[derive(Debug, PartialEq, Eq)]
+enum CheckStep {
+    /// Another check script follows in the chain
+    NextCheck,
+    /// Every check passed; the task is finalized unless a cleanup script still runs
+    Passed { finalize: bool },
+    /// A check failed; `auto_fix` is the fix attempt to send back to the agent, if any
+    Failed { auto_fix: Option<i64> },
+}
+
+fn check_step(
+    check: &ExecutionProcess,
+    agent: &ExecutionProcess,
+    auto_fix_attempts: i64,
+) -> CheckStep {
+    let next_action = check
+        .executor_action()
+        .ok()
+        .and_then(|action| action.next_action());
+    let passed = check.status == ExecutionProcessStatus::Completed && check.exit_code == Some(0);
+
+    if passed {
+        let more_checks = next_action.is_some_and(|next| {
+            matches!(
+                next.typ(),
+                ExecutorActionType::ScriptRequest(ScriptRequest {
+                    context: ScriptContext::CheckScript,
+                    ..
+                })
+            )
+        });
+        if more_checks {
+            return CheckStep::NextCheck;
+        }
+        return CheckStep::Passed {
+            finalize: next_action.is_none(),
+        };
+    }
+
+    let auto_fix = (check.status != ExecutionProcessStatus::Killed
+        && agent.check_fix_attempt < auto_fix_attempts)
+        .then_some(agent.check_fix_attempt + 1);
+    CheckStep::Failed { auto_fix }
+}
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -77,7 +142,11 @@ pub struct LocalContainerService {
     /// When stopping execution, we await these to ensure logs are fully persisted.
     db_stream_handles: Arc<RwLock<HashMap<Uuid, JoinHandle<()>>>>,
     exit_monitor_handles: Arc<RwLock<HashMap<Uuid, JoinHandle<()>>>>,
+    /// Process groups of detached executions reattached after a restart, which have no
+    /// child handle
+    detached_processes: Arc<RwLock<HashMap<Uuid, DetachedProcessGroup>>>,
     workspace_touch_times: Arc<RwLock<HashMap<Uuid, Instant>>>,
+    execution_queue_lock: Arc<Mutex<()>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
@@ -86,6 +155,7 @@ pub struct LocalContainerService {
     queued_message_service: QueuedMessageService,
     notification_service: NotificationService,
     remote_client: Option<RemoteClient>,
+    pty: PtyService,
 }
 
 impl LocalContainerService {
@@ -100,12 +170,15 @@ impl LocalContainerService {
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
         remote_client: Option<RemoteClient>,
+        pty: PtyService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let cancellation_tokens = Arc::new(RwLock::new(HashMap::new()));
         let db_stream_handles = Arc::new(RwLock::new(HashMap::new()));
         let exit_monitor_handles = Arc::new(RwLock::new(HashMap::new()));
+        let detached_processes = Arc::new(RwLock::new(HashMap::new()));
         let workspace_touch_times = Arc::new(RwLock::new(HashMap::new()));
+        let execution_queue_lock = Arc::new(Mutex::new(()));
         let notification_service = NotificationService::new(config.clone());
 
         let container = LocalContainerService {
@@ -115,7 +188,9 @@ impl LocalContainerService {
             msg_stores,
             db_stream_handles,
             exit_monitor_handles,
+            detached_processes,
             workspace_touch_times,
+            execution_queue_lock,
             config,
             git,
             image_service,
@@ -124,6 +199,7 @@ impl LocalContainerService {
             queued_message_service,
             notification_service,
             remote_client,
+            pty,
         };
 
         container.spawn_workspace_cleanup();
@@ -332,6 +408,21 @@ impl LocalContainerService {
         for repo in repos {
             let worktree_path = workspace_root.join(&repo.name);
 
+            // Committing mid-rebase or over unresolved conflicts would bake a half-finished
+            // operation into the branch; leave those changes for the user
+            if git.is_rebase_in_progress(&worktree_path).unwrap_or(false)
+                || !git
+                    .get_conflicted_files(&worktree_path)
+                    .unwrap_or_default()
+                    .is_empty()
+            {
+                tracing::info!(
+                    "Skipping commit in repo '{}': rebase or conflict resolution in progress",
+                    repo.name
+                );
+                continue;
+            }
+
             match git.get_worktree_status(&worktree_path) {
                 Ok(ws) if !ws.entries.is_empty() => {
                     repos_with_changes.push((repo.clone(), worktree_path));
@@ -381,6 +472,241 @@ impl LocalContainerService {
         Ok(false)
     }
 
+    /// Stage the repos a conflict resolution turn was started for once no conflict markers
+    /// remain, continuing an in-progress rebase. Merges, cherry-picks and reverts are
+    /// concluded by the auto-commit that follows. Repos with markers left are untouched.
+    async fn try_complete_resolved_conflicts(&self, ctx: &ExecutionContext) {
+        let repo_states = match ExecutionProcessRepoState::find_by_execution_process_id(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(states) => states,
+            Err(e) => {
+                tracing::warn!("Failed to load repo states for conflict resolution: {}", e);
+                return;
+            }
+        };
+
+        let workspace_root = self.workspace_to_current_dir(&ctx.workspace);
+        for state in repo_states.iter().filter(|s| s.resolves_conflicts) {
+            let Some(repo) = ctx.repos.iter().find(|r| r.id == state.repo_id) else {
+                continue;
+            };
+            let worktree_path = workspace_root.join(&repo.name);
+            let Ok(Some(op)) = self.git().detect_conflict_op(&worktree_path) else {
+                continue;
+            };
+
+            match self.git().stage_resolved_conflicts(&worktree_path) {
+                Ok(unresolved) if unresolved.is_empty() => {}
+                Ok(unresolved) => {
+                    tracing::info!(
+                        "Conflict markers remain in repo '{}': {}",
+                        repo.name,
+                        unresolved.join(", ")
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to stage resolved conflicts in repo '{}': {}",
+                        repo.name,
+                        e
+                    );
+                    continue;
+                }
+            }
+
+            if op == ConflictOp::Rebase {
+                match self.git().continue_rebase(&worktree_path) {
+                    Ok(()) => tracing::info!("Continued rebase in repo '{}'", repo.name),
+                    Err(e) => {
+                        tracing::warn!("Failed to continue rebase in repo '{}': {}", repo.name, e)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Start the repos' check scripts on a coding agent turn's changes, followed by the
+    /// turn's own next action. Returns whether the checks were started.
+    async fn try_start_checks(&self, ctx: &ExecutionContext) -> bool {
+        let Some(checks) = self.check_actions_for_repos(&ctx.repos).await else {
+            return false;
+        };
+        let action = match ctx
+            .execution_process
+            .executor_action()
+            .ok()
+            .and_then(|action| action.next_action.clone())
+        {
+            Some(next_action) => checks.append_action(*next_action),
+            None => checks,
+        };
+
+        // Mark the turn before the checks start so their exit always finds it
+        if let Err(e) = ExecutionProcess::set_check_status(
+            &self.db.pool,
+            ctx.execution_process.id,
+            Some(CheckStatus::Pending),
+        )
+        .await
+        {
+            tracing::error!("Failed to mark checks as pending: {}", e);
+            return false;
+        }
+
+        match self
+            .start_execution(
+                &ctx.workspace,
+                &ctx.session,
+                &action,
+                &ExecutionProcessRunReason::CheckScript,
+                Some(ctx.execution_process.id),
+            )
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::error!("Failed to start check scripts: {}", e);
+                if let Err(e) = ExecutionProcess::set_check_status(
+                    &self.db.pool,
+                    ctx.execution_process.id,
+                    None,
+                )
+                .await
+                {
+                    tracing::warn!("Failed to clear pending check status: {}", e);
+                }
+                false
+            }
+        }
+    }
+
+    /// Record the outcome of a check script started after a coding agent turn. Failing
+    /// output is sent back to the agent while auto-fix attempts remain. Returns whether
+    /// the task should be finalized.
+    async fn finish_check(&self, ctx: &ExecutionContext) -> bool {
+        let agent = match ExecutionProcess::find_pending_check_for_process(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(Some(agent)) => agent,
+            // Merge queue checks are handled by the queue
+            Ok(None) => return false,
+            Err(e) => {
+                tracing::error!("Failed to find coding agent turn for check: {}", e);
+                return false;
+            }
+        };
+
+        let auto_fix_attempts = self.config.read().await.checks.auto_fix_attempts as i64;
+        let attempt = match check_step(&ctx.execution_process, &agent, auto_fix_attempts) {
+            CheckStep::NextCheck => return false,
+            CheckStep::Passed { finalize } => {
+                if let Err(e) = ExecutionProcess::set_check_status(
+                    &self.db.pool,
+                    agent.id,
+                    Some(CheckStatus::Passed),
+                )
+                .await
+                {
+                    tracing::error!("Failed to record passed checks: {}", e);
+                }
+                return finalize;
+            }
+            CheckStep::Failed { auto_fix } => {
+                if let Err(e) = ExecutionProcess::set_check_status(
+                    &self.db.pool,
+                    agent.id,
+                    Some(CheckStatus::Failed),
+                )
+                .await
+                {
+                    tracing::error!("Failed to record failed checks: {}", e);
+                }
+                match auto_fix {
+                    Some(attempt) => attempt,
+                    None => return true,
+                }
+            }
+        };
+
+        let executor_profile_id = match ExecutionProcess::latest_executor_profile_for_session(
+            &self.db.pool,
+            ctx.session.id,
+        )
+        .await
+        {
+            Ok(Some(profile)) => profile,
+            Ok(None) => return true,
+            Err(e) => {
+                tracing::error!("Failed to load executor profile for check fix: {}", e);
+                return true;
+            }
+        };
+
+        let prompt = self.check_failure_prompt(ctx).await;
+        match self.start_follow_up(ctx, prompt, executor_profile_id).await {
+            Ok(follow_up) => {
+                tracing::info!(
+                    "Sent failing checks back to the agent for session {} (attempt {} of {})",
+                    ctx.session.id,
+                    attempt,
+                    auto_fix_attempts
+                );
+                if let Err(e) =
+                    ExecutionProcess::set_check_fix_attempt(&self.db.pool, follow_up.id, attempt)
+                        .await
+                {
+                    tracing::warn!("Failed to record check fix attempt: {}", e);
+                }
+                false
+            }
+            Err(e) => {
+                tracing::error!("Failed to start check fix follow-up: {}", e);
+                true
+            }
+        }
+    }
+
+    /// Follow-up prompt asking the agent to fix a failing check script
+    async fn check_failure_prompt(&self, ctx: &ExecutionContext) -> String {
+        const MAX_CHECK_OUTPUT_LEN: usize = 8000;
+
+        let (script, repo_name) = match ctx.execution_process.executor_action().map(|a| a.typ()) {
+            Ok(ExecutorActionType::ScriptRequest(request)) => (
+                request.script.clone(),
+                request.working_dir.clone().unwrap_or_default(),
+            ),
+            _ => (String::new(), String::new()),
+        };
+
+        let output = match self.get_msg_store_by_id(&ctx.execution_process.id).await {
+            Some(store) => store
+                .get_history()
+                .into_iter()
+                .filter_map(|msg| match msg {
+                    LogMsg::Stdout(chunk) | LogMsg::Stderr(chunk) => Some(chunk),
+                    _ => None,
+                })
+                .collect::<String>(),
+            None => String::new(),
+        };
+        let output = tail_to_char_boundary(output.trim_end(), MAX_CHECK_OUTPUT_LEN);
+
+        format!(
+            "The check script of the `{repo_name}` repository failed on your changes.\n\n\
+             Script:\n```\n{script}\n```\n\n\
+             Output (last {MAX_CHECK_OUTPUT_LEN} bytes at most):\n```\n{output}\n```\n\n\
+             Fix the cause of the failure so that the check passes."
+        )
+    }
+
     /// Commit changes to each repo. Logs failures but continues with other repos.
     fn commit_repos(&self, repos_with_changes: Vec<(Repo, PathBuf)>, message: &str) -> bool {
         let mut any_committed = false;
@@ -409,12 +735,13 @@ impl LocalContainerService {
         any_committed
     }
 
-    /// Spawn a background task that polls the child process for completion and
-    /// cleans up the execution entry when it exits.
+    /// Spawn a background task that waits for the process to exit and cleans up the
+    /// execution entry when it does.
     pub fn spawn_exit_monitor(
         &self,
         exec_id: &Uuid,
         exit_signal: Option<ExecutorExitSignal>,
+        mut process_exit_rx: oneshot::Receiver<io::Result<ExitStatus>>,
     ) -> JoinHandle<()> {
         let exec_id = *exec_id;
         let child_store = self.child_store.clone();
@@ -424,8 +751,6 @@ impl LocalContainerService {
         let container = self.clone();
         let analytics = self.analytics.clone();
 
-        let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
-
         tokio::spawn(async move {
             let mut exit_signal_future = exit_signal
                 .map(|rx| rx.boxed()) // wait for result
@@ -480,6 +805,11 @@ impl LocalContainerService {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
 
+            // A coding agent slot may have freed up
+            if let Err(e) = container.start_queued_executions().await {
+                tracing::error!("Failed to start queued executions: {}", e);
+            }
+
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
@@ -499,7 +829,15 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Running
                 );
 
+                let mut checks_started = false;
                 if success || cleanup_done {
+                    if matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    ) {
+                        container.try_complete_resolved_conflicts(&ctx).await;
+                    }
+
                     // Commit changes (if any) and get feedback about whether changes were made
                     let changes_committed = match container.try_commit_changes(&ctx).await {
                         Ok(committed) => committed,
@@ -525,8 +863,18 @@ impl LocalContainerService {
                     };
 
                     if should_start_next {
+                        // Check the coding agent's changes before anything else runs
+                        if matches!(
+                            ctx.execution_process.run_reason,
+                            ExecutionProcessRunReason::CodingAgent
+                        ) {
+                            checks_started = container.try_start_checks(&ctx).await;
+                        }
+
                         // If the process exited successfully, start the next action
-                        if let Err(e) = container.try_start_next_action(&ctx).await {
+                        if !checks_started
+                            && let Err(e) = container.try_start_next_action(&ctx).await
+                        {
                             tracing::error!("Failed to start next action after completion: {}", e);
                         }
                     } else {
@@ -540,7 +888,16 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                let finalize = if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CheckScript
+                ) {
+                    container.finish_check(&ctx).await
+                } else {
+                    !checks_started && container.should_finalize(&ctx)
+                };
+
+                if finalize {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
                     let should_execute_queued = !matches!(
@@ -548,9 +905,23 @@ impl LocalContainerService {
                         ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed
                     );
 
-                    if let Some(queued_msg) =
-                        container.queued_message_service.take_queued(ctx.session.id)
+                    let queued_msg = match container
+                        .queued_message_service
+                        .take_next(ctx.session.id)
+                        .await
                     {
+                        Ok(queued_msg) => queued_msg,
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to read queued messages for session {}: {}",
+                                ctx.session.id,
+                                e
+                            );
+                            None
+                        }
+                    };
+
+                    if let Some(queued_msg) = queued_msg {
                         if should_execute_queued {
                             tracing::info!(
                                 "Found queued message for session {}, starting follow-up execution",
@@ -581,12 +952,23 @@ impl LocalContainerService {
                                 container.finalize_task(&ctx).await;
                             }
                         } else {
-                            // Execution failed or was killed - discard the queued message and finalize
+                            // Execution failed or was killed - discard the whole queue and finalize
                             tracing::info!(
-                                "Discarding queued message for session {} due to execution status {:?}",
+                                "Discarding queued messages for session {} due to execution status {:?}",
                                 ctx.session.id,
                                 ctx.execution_process.status
                             );
+                            if let Err(e) = container
+                                .queued_message_service
+                                .cancel_queued(ctx.session.id)
+                                .await
+                            {
+                                tracing::warn!(
+                                    "Failed to clear queued messages for session {}: {}",
+                                    ctx.session.id,
+                                    e
+                                );
+                            }
                             container.finalize_task(&ctx).await;
                         }
                     } else {
@@ -661,14 +1043,23 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
+            container.detached_processes.write().await.remove(&exec_id);
+
+            // The spool is no longer needed once the logs are persisted
+            if let Ok(Some(process)) = ExecutionProcess::find_by_id(&db.pool, exec_id).await
+                && let Some(spool_dir) = process.spool_dir
+                && let Err(e) = tokio::fs::remove_dir_all(&spool_dir).await
+            {
+                tracing::debug!("Failed to remove spool {}: {}", spool_dir, e);
+            }
         })
     }
 
     pub fn spawn_os_exit_watcher(
         &self,
         exec_id: Uuid,
-    ) -> tokio::sync::oneshot::Receiver<std::io::Result<std::process::ExitStatus>> {
-        let (tx, rx) = tokio::sync::oneshot::channel::<std::io::Result<std::process::ExitStatus>>();
+    ) -> oneshot::Receiver<io::Result<ExitStatus>> {
+        let (tx, rx) = oneshot::channel::<io::Result<ExitStatus>>();
         let child_store = self.child_store.clone();
         tokio::spawn(async move {
             loop {
@@ -706,6 +1097,70 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
+    /// Compile the approval policies of the workspace's repos, if any are configured.
+    fn approval_policy_engine(
+        repos: &[Repo],
+        workspace_dir: &Path,
+    ) -> Option<ApprovalPolicyEngine> {
+        let policy = ApprovalPolicy::from_repos(repos);
+        if policy.is_empty() {
+            return None;
+        }
+        policy
+            .compile(workspace_dir)
+            .inspect_err(|e| tracing::warn!("Failed to compile approval policy: {}", e))
+            .ok()
+    }
+
+    /// Report sandbox violations of a running execution as error entries in its log: hosts the
+    /// network allowlist proxy refused, and agent output that shows a blocked write or
+    /// connection. Owns the proxy, which stops once the execution finishes.
+    async fn spawn_sandbox_monitor(
+        &self,
+        id: Uuid,
+        config: SandboxConfig,
+        proxy: Option<(SandboxProxy, mpsc::UnboundedReceiver<String>)>,
+    ) {
+        let Some(store) = self.msg_stores().read().await.get(&id).cloned() else {
+            return;
+        };
+        let (proxy, mut denied_rx) = proxy.unzip();
+
+        tokio::spawn(async move {
+            let _proxy = proxy;
+            let mut logs = store.history_plus_stream();
+            let mut reported = HashSet::new();
+            loop {
+                let denied = async {
+                    match denied_rx.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                };
+                tokio::select! {
+                    Some(host) = denied => {
+                        let note = format!("{SANDBOX_LOG_PREFIX} Blocked network access to {host}");
+                        if reported.insert(note.clone()) {
+                            store.push_stderr(format!("{note}\n"));
+                        }
+                    }
+                    msg = logs.next() => match msg {
+                        Some(Ok(LogMsg::Stdout(chunk) | LogMsg::Stderr(chunk))) => {
+                            let notes = chunk.lines().filter_map(|line| config.detect_violation(line));
+                            for note in notes {
+                                if reported.insert(note.clone()) {
+                                    store.push_stderr(format!("{note}\n"));
+                                }
+                            }
+                        }
+                        Some(Ok(LogMsg::Finished)) | None => break,
+                        _ => {}
+                    },
+                }
+            }
+        });
+    }
+
     async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
         let store = Arc::new(MsgStore::new());
 
@@ -801,7 +1256,17 @@ impl LocalContainerService {
         let repos = WorkspaceRepo::find_repos_with_copy_files(&self.db.pool, workspace.id).await?;
 
         for repo in &repos {
-            if let Some(copy_files) = &repo.copy_files
+            let copy_files = match repo
+                .copy_files
+                .clone()
+                .filter(|files| !files.trim().is_empty())
+            {
+                Some(copy_files) => Some(copy_files),
+                None => RepoConfigFile::load_or_default(&repo.path)
+                    .await
+                    .copy_files(),
+            };
+            if let Some(copy_files) = &copy_files
                 && !copy_files.trim().is_empty()
             {
                 let worktree_path = workspace_dir.join(&repo.name);
@@ -894,8 +1359,21 @@ impl LocalContainerService {
         ctx: &ExecutionContext,
         queued_data: &DraftFollowUpData,
     ) -> Result<ExecutionProcess, ContainerError> {
-        let executor_profile_id = queued_data.executor_profile_id.clone();
+        self.start_follow_up(
+            ctx,
+            queued_data.message.clone(),
+            queued_data.executor_profile_id.clone(),
+        )
+        .await
+    }
 
+    /// Start a coding agent follow-up in the context's session, followed by cleanup
+    async fn start_follow_up(
+        &self,
+        ctx: &ExecutionContext,
+        prompt: String,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<ExecutionProcess, ContainerError> {
         // Validate executor matches session if session has prior executions
         let expected_executor: Option<String> =
             ExecutionProcess::latest_executor_profile_for_session(&self.db.pool, ctx.session.id)
@@ -925,7 +1403,7 @@ impl LocalContainerService {
 
         let repos =
             WorkspaceRepo::find_repos_for_workspace(&self.db.pool, ctx.workspace.id).await?;
-        let cleanup_action = self.cleanup_actions_for_repos(&repos);
+        let cleanup_action = self.cleanup_actions_for_repos(&repos).await;
 
         let working_dir = ctx
             .workspace
@@ -936,16 +1414,16 @@ impl LocalContainerService {
 
         let action_type = if let Some(info) = latest_session_info {
             ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-                prompt: queued_data.message.clone(),
+                prompt,
                 session_id: info.session_id,
                 reset_to_message_id: None,
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir: working_dir.clone(),
+                executor_profile_id,
+                working_dir,
             })
         } else {
             ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt: queued_data.message.clone(),
-                executor_profile_id: executor_profile_id.clone(),
+                prompt,
+                executor_profile_id,
                 working_dir,
             })
         };
@@ -957,6 +1435,7 @@ impl LocalContainerService {
             &ctx.session,
             &action,
             &ExecutionProcessRunReason::CodingAgent,
+            None,
         )
         .await
     }
@@ -993,6 +1472,10 @@ impl ContainerService for LocalContainerService {
         &self.notification_service
     }
 
+    fn execution_queue_lock(&self) -> &Arc<Mutex<()>> {
+        &self.execution_queue_lock
+    }
+
     async fn touch(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         let now = Instant::now();
 
@@ -1038,6 +1521,17 @@ impl ContainerService for LocalContainerService {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn auto_start_executor_profile(&self) -> Option<ExecutorProfileId> {
+        let config = self.config.read().await;
+        config
+            .auto_start_dependent_tasks
+            .then(|| config.executor_profile.clone())
+    }
+
+    async fn concurrency_config(&self) -> ConcurrencyConfig {
+        self.config.read().await.concurrency.clone()
+    }
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
         PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
     }
@@ -1105,10 +1599,15 @@ impl ContainerService for LocalContainerService {
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         self.try_stop(workspace, true).await;
+        self.pty.close_workspace_sessions(workspace.id).await;
         Self::cleanup_workspace(&self.db, workspace).await;
         Ok(())
     }
 
+    async fn close_workspace_terminals(&self, workspace_id: Uuid) {
+        self.pty.close_workspace_sessions(workspace_id).await;
+    }
+
     async fn ensure_container_exists(
         &self,
         workspace: &Workspace,
@@ -1194,6 +1693,8 @@ impl ContainerService for LocalContainerService {
             )))?;
         let current_dir = PathBuf::from(container_ref);
 
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
+
         let approvals_service: Arc<dyn ExecutorApprovalService> =
             match executor_action.base_executor() {
                 Some(
@@ -1201,17 +1702,18 @@ impl ContainerService for LocalContainerService {
                     | BaseCodingAgent::ClaudeCode
                     | BaseCodingAgent::Gemini
                     | BaseCodingAgent::QwenCode
-                    | BaseCodingAgent::Opencode,
+                    | BaseCodingAgent::Opencode
+                    | BaseCodingAgent::CustomAcp,
                 ) => ExecutorApprovalBridge::new(
                     self.approvals.clone(),
                     self.db.clone(),
                     self.notification_service.clone(),
                     execution_process.id,
+                    Self::approval_policy_engine(&repos, &current_dir),
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
-        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
         let repo_names: Vec<String> = repos.iter().map(|r| r.name.clone()).collect();
         let repo_context = RepoContext::new(current_dir.clone(), repo_names);
 
@@ -1221,6 +1723,7 @@ impl ContainerService for LocalContainerService {
             .commit_reminder_prompt
             .clone()
             .unwrap_or_else(|| DEFAULT_COMMIT_REMINDER_PROMPT.to_string());
+        let sandbox_config = config.sandbox.clone();
         drop(config);
         let mut env = ExecutionEnv::new(
             repo_context,
@@ -1228,6 +1731,37 @@ impl ContainerService for LocalContainerService {
             commit_reminder_prompt,
         );
 
+        // Confine the agent to its workspace when the sandbox is enabled
+        let mut sandbox_proxy = None;
+        if sandbox_config.enabled {
+            if let SandboxNetwork::Allowlist { hosts } = &sandbox_config.network {
+                let proxy = SandboxProxy::start(hosts.clone()).map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to start sandbox proxy: {e}"))
+                })?;
+                sandbox_proxy = Some(proxy);
+            }
+            env.sandbox = Some(Sandbox::new(
+                sandbox_config.clone(),
+                sandbox_proxy
+                    .as_ref()
+                    .map(|(proxy, _)| proxy.socket_path().to_path_buf()),
+            ));
+        }
+
+        // Detach coding agents so they keep running across server restarts, unless the
+        // server has to keep talking to them
+        if cfg!(unix)
+            && executor_action
+                .base_executor()
+                .is_some_and(|executor| executor.can_detach())
+        {
+            env.spool = Some(Spool::new(
+                current_dir
+                    .join(VIBE_SPOOL_DIR)
+                    .join(execution_process.id.to_string()),
+            ));
+        }
+
         // Always inject workspace/session context
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
@@ -1245,9 +1779,38 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
+        // Executors that do not build their command through the env are not spooled
+        if let Some(spool) = env.spool.as_ref().filter(|spool| spool.dir().exists())
+            && let Some(group) = spawned
+                .child
+                .inner()
+                .id()
+                .and_then(DetachedProcessGroup::of_leader)
+        {
+            let recorded = match spool.record_leader_start_time(&group.leader_start_time) {
+                Ok(()) => ExecutionProcess::set_detached(
+                    &self.db.pool,
+                    execution_process.id,
+                    group.pgid as i64,
+                    &spool.dir().to_string_lossy(),
+                )
+                .await
+                .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = recorded {
+                tracing::warn!("Failed to record detached process: {}", e);
+            }
+        }
+
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
 
+        if sandbox_config.enabled {
+            self.spawn_sandbox_monitor(execution_process.id, sandbox_config, sandbox_proxy)
+                .await;
+        }
+
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
 
@@ -1258,7 +1821,11 @@ impl ContainerService for LocalContainerService {
         }
 
         // Spawn unified exit monitor: watches OS exit and optional executor signal
-        let hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
+        let hn = self.spawn_exit_monitor(
+            &execution_process.id,
+            spawned.exit_signal,
+            self.spawn_os_exit_watcher(execution_process.id),
+        );
         self.add_exit_monitor_handle(execution_process.id, hn).await;
 
         // Notify execution started (webhook only, no sound)
@@ -1277,12 +1844,31 @@ impl ContainerService for LocalContainerService {
         execution_process: &ExecutionProcess,
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError> {
-        let child = self
-            .get_child_from_store(&execution_process.id)
+        // Queued processes were never spawned, so just take them out of the queue
+        if execution_process.status == ExecutionProcessStatus::Queued {
+            ExecutionProcess::update_completion(&self.db.pool, execution_process.id, status, None)
+                .await?;
+            if let Some((workspace, _)) = execution_process
+                .parent_workspace_and_session(&self.db.pool)
+                .await?
+            {
+                Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::InReview).await?;
+            }
+            return Ok(());
+        }
+
+        let child = self.get_child_from_store(&execution_process.id).await;
+        let detached_group = self
+            .detached_processes
+            .read()
             .await
-            .ok_or_else(|| {
-                ContainerError::Other(anyhow!("Child process not found for execution"))
-            })?;
+            .get(&execution_process.id)
+            .cloned();
+        if child.is_none() && detached_group.is_none() {
+            return Err(ContainerError::Other(anyhow!(
+                "Child process not found for execution"
+            )));
+        }
         let exit_code = if status == ExecutionProcessStatus::Completed {
             Some(0)
         } else {
@@ -1313,16 +1899,21 @@ impl ContainerService for LocalContainerService {
             }
         }
 
-        {
-            let mut child_guard = child.write().await;
-            if let Err(e) = command::kill_process_group(&mut child_guard).await {
-                tracing::error!(
-                    "Failed to stop execution process {}: {}",
-                    execution_process.id,
-                    e
-                );
-                return Err(e);
+        let killed = match (child, detached_group) {
+            (Some(child), _) => {
+                let mut child_guard = child.write().await;
+                command::kill_process_group(&mut child_guard).await
             }
+            (None, Some(group)) => command::kill_detached_process_group(&group).await,
+            (None, None) => Ok(()),
+        };
+        if let Err(e) = killed {
+            tracing::error!(
+                "Failed to stop execution process {}: {}",
+                execution_process.id,
+                e
+            );
+            return Err(e);
         }
         self.remove_child_from_store(&execution_process.id).await;
 
@@ -1339,7 +1930,7 @@ impl ContainerService for LocalContainerService {
         if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
             && !matches!(
                 ctx.execution_process.run_reason,
-                ExecutionProcessRunReason::DevServer
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::CheckScript
             )
             && let Err(e) =
                 Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
@@ -1482,16 +2073,23 @@ impl ContainerService for LocalContainerService {
         .map_err(|e| ContainerError::Other(anyhow!("Copy files task failed: {e}")))?
     }
 
-    async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
-        tracing::info!("Killing all running processes");
+    async fn shutdown_running_processes(&self) -> Result<(), ContainerError> {
+        tracing::info!("Stopping running processes");
         let running_processes = ExecutionProcess::find_running(&self.db.pool).await?;
 
         tracing::info!(
-            "Found {} running processes to kill",
+            "Found {} running processes to stop",
             running_processes.len()
         );
 
         for process in running_processes {
+            if process.pid.is_some() && process.spool_dir.is_some() {
+                // Spooled commands are not killed on drop, so the handle can simply go
+                self.remove_child_from_store(&process.id).await;
+                tracing::info!("Leaving detached process running: id={}", process.id);
+                continue;
+            }
+
             tracing::info!(
                 "Killing process: id={}, run_reason={:?}",
                 process.id,
@@ -1513,6 +2111,60 @@ impl ContainerService for LocalContainerService {
 
         Ok(())
     }
+
+    async fn reattach_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<bool, ContainerError> {
+        let (Some(pid), Some(spool_dir)) = (execution_process.pid, &execution_process.spool_dir)
+        else {
+            return Ok(false);
+        };
+        let spool = Spool::new(spool_dir);
+        // Without the leader's start time the pid may belong to an unrelated process by now
+        let group = spool
+            .leader_start_time()
+            .map(|leader_start_time| DetachedProcessGroup {
+                pgid: pid as u32,
+                leader_start_time,
+            })
+            .filter(DetachedProcessGroup::is_alive);
+        if group.is_none() && spool.exit_code().is_none() {
+            // Killed along with the machine or without getting to record its exit
+            let _ = tokio::fs::remove_dir_all(spool.dir()).await;
+            return Ok(false);
+        }
+        let Some((workspace, _)) = execution_process
+            .parent_workspace_and_session(&self.db.pool)
+            .await?
+        else {
+            return Ok(false);
+        };
+        let executor_action = execution_process.executor_action()?;
+
+        // The spool holds the complete output, so it replaces what was persisted before
+        ExecutionProcessLogs::delete_by_execution_id(&self.db.pool, execution_process.id).await?;
+
+        let store = Arc::new(MsgStore::new());
+        self.msg_stores
+            .write()
+            .await
+            .insert(execution_process.id, store.clone());
+        if let Some(group) = &group {
+            self.detached_processes
+                .write()
+                .await
+                .insert(execution_process.id, group.clone());
+        }
+        self.start_log_processing(&workspace, execution_process, executor_action)
+            .await;
+
+        let process_exit_rx = detached::spawn_spool_follower(group, spool, store);
+        let hn = self.spawn_exit_monitor(&execution_process.id, None, process_exit_rx);
+        self.add_exit_monitor_handle(execution_process.id, hn).await;
+
+        Ok(true)
+    }
 }
 fn success_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
@@ -1526,3 +2178,96 @@ fn success_exit_status() -> std::process::ExitStatus {
         ExitStatusExt::from_raw(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use db::test_support::{execution_process as process, script_action as script};
+
+    use super::*;
+
+    fn check(action: ExecutorAction, exit_code: i64) -> ExecutionProcess {
+        process(
+            ExecutionProcessRunReason::CheckScript,
+            action,
+            ExecutionProcessStatus::Completed,
+            Some(exit_code),
+        )
+    }
+
+    fn agent(check_fix_attempt: i64) -> ExecutionProcess {
+        ExecutionProcess {
+            check_fix_attempt,
+            ..process(
+                ExecutionProcessRunReason::CodingAgent,
+                script(ScriptContext::SetupScript),
+                ExecutionProcessStatus::Completed,
+                Some(0),
+            )
+        }
+    }
+
+    #[test]
+    fn passing_checks_finalize_after_the_last_one() {
+        let last = check(script(ScriptContext::CheckScript), 0);
+        assert_eq!(
+            check_step(&last, &agent(0), 2),
+            CheckStep::Passed { finalize: true }
+        );
+
+        let chained = check(
+            script(ScriptContext::CheckScript).append_action(script(ScriptContext::CheckScript)),
+            0,
+        );
+        assert_eq!(check_step(&chained, &agent(0), 2), CheckStep::NextCheck);
+
+        // The turn's cleanup script runs next and finalizes the task itself
+        let before_cleanup = check(
+            script(ScriptContext::CheckScript).append_action(script(ScriptContext::CleanupScript)),
+            0,
+        );
+        assert_eq!(
+            check_step(&before_cleanup, &agent(0), 2),
+            CheckStep::Passed { finalize: false }
+        );
+    }
+
+    #[test]
+    fn failing_check_is_sent_back_while_attempts_remain() {
+        let failed = check(
+            script(ScriptContext::CheckScript).append_action(script(ScriptContext::CheckScript)),
+            1,
+        );
+        assert_eq!(
+            check_step(&failed, &agent(0), 2),
+            CheckStep::Failed { auto_fix: Some(1) }
+        );
+        assert_eq!(
+            check_step(&failed, &agent(1), 2),
+            CheckStep::Failed { auto_fix: Some(2) }
+        );
+    }
+
+    #[test]
+    fn failing_check_stops_at_the_retry_limit() {
+        let failed = check(script(ScriptContext::CheckScript), 1);
+        assert_eq!(
+            check_step(&failed, &agent(2), 2),
+            CheckStep::Failed { auto_fix: None }
+        );
+        assert_eq!(
+            check_step(&failed, &agent(0), 0),
+            CheckStep::Failed { auto_fix: None }
+        );
+
+        let killed = process(
+            ExecutionProcessRunReason::CheckScript,
+            script(ScriptContext::CheckScript),
+            ExecutionProcessStatus::Killed,
+            None,
+        );
+        assert_eq!(
+            check_step(&killed, &agent(0), 2),
+            CheckStep::Failed { auto_fix: None }
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    sync::Arc,
+    time::Duration,
+};
+
+use executors::spool::Spool;
+use tokio::{fs::File, io::AsyncReadExt, sync::oneshot};
+use utils::{msg_store::MsgStore, process::DetachedProcessGroup};
+
+const SPOOL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A spool file read incrementally as the detached process appends to it
+struct SpoolFile {
+    path: PathBuf,
+    file: Option<File>,
+    /// Trailing bytes of a UTF-8 character split across reads
+    pending: Vec<u8>,
+}
+
+impl SpoolFile {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            file: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Text appended since the last read, if any
+    async fn read_appended(&mut self) -> Option<String> {
+        if self.file.is_none() {
+            self.file = File::open(&self.path).await.ok();
+        }
+        let file = self.file.as_mut()?;
+
+        let start = self.pending.len();
+        if file.read_to_end(&mut self.pending).await.is_err() || self.pending.len() == start {
+            return None;
+        }
+
+        let complete = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        let rest = self.pending.split_off(complete);
+        let text = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending = rest;
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+/// Replay a detached process's spooled output into `store` and keep following it until the
+/// process group exits, or just replay it when `group` has already exited. Resolves with the
+/// exit code the process recorded in its spool.
+pub(crate) fn spawn_spool_follower(
+    group: Option<DetachedProcessGroup>,
+    spool: Spool,
+    store: Arc<MsgStore>,
+) -> oneshot::Receiver<io::Result<ExitStatus>> {
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let mut stdout = SpoolFile::new(&spool.stdout_path());
+        let mut stderr = SpoolFile::new(&spool.stderr_path());
+        loop {
+            // Check before reading so output written right before the exit is not missed
+            let alive = group.as_ref().is_some_and(DetachedProcessGroup::is_alive);
+            let mut read_any = false;
+            if let Some(chunk) = stdout.read_appended().await {
+                store.push_stdout(chunk);
+                read_any = true;
+            }
+            if let Some(chunk) = stderr.read_appended().await {
+                store.push_stderr(chunk);
+                read_any = true;
+            }
+            if !alive && !read_any {
+                break;
+            }
+            if !read_any {
+                tokio::time::sleep(SPOOL_POLL_INTERVAL).await;
+            }
+        }
+
+        let _ = tx.send(Ok(exit_status_from_code(spool.exit_code().unwrap_or(1))));
+    });
+    rx
+}
+
+fn exit_status_from_code(code: i32) -> ExitStatus {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatusExt::from_raw((code & 0xff) << 8)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatusExt::from_raw(code as u32)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::Stdio;
+
+    use tokio::process::Command;
+    use utils::log_msg::LogMsg;
+
+    use super::*;
+
+    fn output(store: &MsgStore) -> (String, String) {
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        for msg in store.get_history() {
+            match msg {
+                LogMsg::Stdout(chunk) => stdout.push_str(&chunk),
+                LogMsg::Stderr(chunk) => stderr.push_str(&chunk),
+                _ => {}
+            }
+        }
+        (stdout, stderr)
+    }
+
+    #[tokio::test]
+    async fn reattached_follower_replays_the_spool_until_the_group_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path().join("spool"));
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo before; sleep 1; echo after >&2; exit 4"]);
+        let mut command = spool.wrap(command).unwrap();
+        command
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .process_group(0);
+        let mut child = command.spawn().unwrap();
+        let group = DetachedProcessGroup::of_leader(child.id().unwrap()).unwrap();
+        // After a restart the process is no longer our child; something else reaps it
+        tokio::spawn(async move { child.wait().await });
+
+        let store = Arc::new(MsgStore::new());
+        let status = spawn_spool_follower(Some(group), spool, store.clone())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(status.code(), Some(4));
+        assert_eq!(
+            output(&store),
+            ("before\n".to_string(), "after\n".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn follower_does_not_wait_on_a_reused_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path().join("spool"));
+        std::fs::create_dir_all(spool.dir()).unwrap();
+        std::fs::write(spool.stdout_path(), "partial\n").unwrap();
+
+        // An unrelated process that got the recorded pid
+        let mut unrelated = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let group = DetachedProcessGroup {
+            pgid: unrelated.id().unwrap(),
+            leader_start_time: "another boot".to_string(),
+        };
+
+        let store = Arc::new(MsgStore::new());
+        let status = tokio::time::timeout(
+            Duration::from_secs(5),
+            spawn_spool_follower(Some(group), spool, store.clone()),
+        )
+        .await
+        .expect("follower stops right away")
+        .unwrap()
+        .unwrap();
+        unrelated.kill().await.unwrap();
+
+        // No exit code was recorded, so the execution counts as failed
+        assert_eq!(status.code(), Some(1));
+        assert_eq!(output(&store).0, "partial\n");
+    }
+}
@@ -16,6 +16,7 @@ use services::services::{
     file_search::FileSearchCache,
     filesystem::FilesystemService,
     image::ImageService,
+    merge_queue::MergeQueueService,
     oauth_credentials::OAuthCredentials,
     pr_monitor::PrMonitorService,
     project::ProjectService,
@@ -35,6 +36,7 @@ use crate::{container::LocalContainerService, pty::PtyService};
 mod command;
 pub mod container;
 mod copy;
+mod detached;
 pub mod pty;
 
 #[derive(Clone)]
@@ -132,7 +134,7 @@ impl Deployment for LocalDeployment {
         }
 
         let approvals = Approvals::new(msg_stores.clone());
-        let queued_message_service = QueuedMessageService::new();
+        let queued_message_service = QueuedMessageService::new(db.clone().pool);
 
         let oauth_credentials = Arc::new(OAuthCredentials::new(credentials_path()));
         if let Err(e) = oauth_credentials.load().await {
@@ -171,6 +173,7 @@ impl Deployment for LocalDeployment {
             user_id: user_id.clone(),
             analytics_service: s.clone(),
         });
+        let pty = PtyService::new();
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
@@ -181,6 +184,7 @@ impl Deployment for LocalDeployment {
             approvals.clone(),
             queued_message_service.clone(),
             remote_client.clone().ok(),
+            pty.clone(),
         )
         .await;
 
@@ -188,7 +192,6 @@ impl Deployment for LocalDeployment {
 
         let file_search_cache = Arc::new(FileSearchCache::new());
 
-        let pty = PtyService::new();
         {
             let db = db.clone();
             let analytics = analytics.as_ref().map(|s| AnalyticsContext {
@@ -199,6 +202,7 @@ impl Deployment for LocalDeployment {
             let rc = remote_client.clone().ok();
             PrMonitorService::spawn(db, analytics, container, rc).await;
         }
+        MergeQueueService::spawn(db.clone(), container.clone()).await;
 
         let deployment = Self {
             config,
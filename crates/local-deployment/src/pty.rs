@@ -1,17 +1,24 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     io::{Read, Write},
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
 };
 
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use chrono::{DateTime, Utc};
+use portable_pty::{ChildKiller, CommandBuilder, NativePtySystem, PtySize, PtySystem};
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::broadcast;
 use utils::shell::get_interactive_shell;
 use uuid::Uuid;
 
+/// Bytes of output kept per session and replayed to viewers when they attach
+const SCROLLBACK_LIMIT: usize = 256 * 1024;
+
+/// Output chunks buffered per viewer before a slow viewer starts skipping output
+const VIEWER_BUFFER: usize = 1024;
+
 #[derive(Debug, Error)]
 pub enum PtyError {
     #[error("Failed to create PTY: {0}")]
@@ -22,17 +29,79 @@ pub enum PtyError {
     WriteFailed(String),
     #[error("Failed to resize PTY: {0}")]
     ResizeFailed(String),
-    #[error("Session already closed")]
-    SessionClosed,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerminalSessionInfo {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// Number of websockets currently attached
+    pub viewers: usize,
+}
+
+/// What a viewer gets when attaching to a session
+pub struct TerminalAttachment {
+    /// Recent output, to replay before anything received on `output`
+    pub scrollback: Vec<u8>,
+    /// Live output; `None` when the shell already exited
+    pub output: Option<broadcast::Receiver<Vec<u8>>>,
+}
+
+/// Bounded history of a session's output
+struct Scrollback {
+    buf: VecDeque<u8>,
+    limit: usize,
+}
+
+impl Scrollback {
+    fn new(limit: usize) -> Self {
+        Self {
+            buf: VecDeque::new(),
+            limit,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        self.buf.extend(data);
+        if self.buf.len() <= self.limit {
+            return;
+        }
+
+        let cut = self.buf.len() - self.limit;
+        let cut_at_line_start = self.buf[cut - 1] == b'\n';
+        self.buf.drain(..cut);
+        // Start the replay at a line boundary rather than halfway through a line or an escape
+        // sequence
+        if !cut_at_line_start && let Some(newline) = self.buf.iter().position(|&b| b == b'\n') {
+            self.buf.drain(..=newline);
+        }
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.buf.iter().copied().collect()
+    }
+}
+
+/// Scrollback and live channel of a session, updated under one lock so that an attaching viewer
+/// neither misses nor repeats output
+struct SessionOutput {
+    scrollback: Scrollback,
+    /// Dropped once the shell exits, which ends every viewer's stream
+    sender: Option<broadcast::Sender<Vec<u8>>>,
 }
 
 struct PtySession {
+    workspace_id: Uuid,
+    created_at: DateTime<Utc>,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
-    _output_handle: thread::JoinHandle<()>,
-    closed: bool,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    output: Arc<Mutex<SessionOutput>>,
 }
 
+/// Shell sessions of workspace terminals. Sessions outlive the websockets viewing them and are
+/// ended by closing them, by their workspace being archived, or by the shell exiting.
 #[derive(Clone)]
 pub struct PtyService {
     sessions: Arc<Mutex<HashMap<Uuid, PtySession>>>,
@@ -47,12 +116,12 @@ impl PtyService {
 
     pub async fn create_session(
         &self,
+        workspace_id: Uuid,
         working_dir: PathBuf,
         cols: u16,
         rows: u16,
-    ) -> Result<(Uuid, mpsc::UnboundedReceiver<Vec<u8>>), PtyError> {
+    ) -> Result<Uuid, PtyError> {
         let session_id = Uuid::new_v4();
-        let (output_tx, output_rx) = mpsc::unbounded_channel();
         let shell = get_interactive_shell().await;
 
         let result = tokio::task::spawn_blocking(move || {
@@ -98,6 +167,7 @@ impl PtyService {
                 .slave
                 .spawn_command(cmd)
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
+            let killer = child.clone_killer();
 
             let mut writer = pty_pair
                 .master
@@ -111,39 +181,31 @@ impl PtyService {
                 let _ = writer.flush();
             }
 
-            let mut reader = pty_pair
+            let reader = pty_pair
                 .master
                 .try_clone_reader()
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
 
-            let output_handle = thread::spawn(move || {
-                let mut buf = [0u8; 4096];
-                loop {
-                    match reader.read(&mut buf) {
-                        Ok(0) => break,
-                        Ok(n) => {
-                            if output_tx.send(buf[..n].to_vec()).is_err() {
-                                break;
-                            }
-                        }
-                        Err(_) => break,
-                    }
-                }
-                drop(child);
-            });
-
-            Ok::<_, PtyError>((pty_pair.master, writer, output_handle))
+            Ok::<_, PtyError>((pty_pair.master, writer, reader, child, killer))
         })
         .await
         .map_err(|e| PtyError::CreateFailed(e.to_string()))??;
 
-        let (master, writer, output_handle) = result;
+        let (master, writer, mut reader, mut child, killer) = result;
+
+        let (sender, _) = broadcast::channel(VIEWER_BUFFER);
+        let output = Arc::new(Mutex::new(SessionOutput {
+            scrollback: Scrollback::new(SCROLLBACK_LIMIT),
+            sender: Some(sender),
+        }));
 
         let session = PtySession {
+            workspace_id,
+            created_at: Utc::now(),
             writer,
             master,
-            _output_handle: output_handle,
-            closed: false,
+            killer,
+            output: Arc::clone(&output),
         };
 
         self.sessions
@@ -151,7 +213,98 @@ impl PtyService {
             .map_err(|e| PtyError::CreateFailed(e.to_string()))?
             .insert(session_id, session);
 
-        Ok((session_id, output_rx))
+        // Started once the session is registered, so a shell that exits right away is still
+        // removed again
+        let sessions = Arc::clone(&self.sessions);
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        let Ok(mut output) = output.lock() else {
+                            break;
+                        };
+                        output.scrollback.push(&buf[..n]);
+                        if let Some(sender) = &output.sender {
+                            // No viewers attached is fine, the output is in the scrollback
+                            let _ = sender.send(buf[..n].to_vec());
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            if let Ok(mut output) = output.lock() {
+                output.sender = None;
+            }
+            if let Ok(mut sessions) = sessions.lock() {
+                sessions.remove(&session_id);
+            }
+            let _ = child.wait();
+        });
+
+        Ok(session_id)
+    }
+
+    /// Start viewing a session. Any number of viewers can be attached at the same time.
+    pub fn attach(&self, session_id: Uuid) -> Result<TerminalAttachment, PtyError> {
+        let output = {
+            let sessions = self
+                .sessions
+                .lock()
+                .map_err(|_| PtyError::SessionNotFound(session_id))?;
+            let session = sessions
+                .get(&session_id)
+                .ok_or(PtyError::SessionNotFound(session_id))?;
+            Arc::clone(&session.output)
+        };
+
+        let output = output
+            .lock()
+            .map_err(|_| PtyError::SessionNotFound(session_id))?;
+        Ok(TerminalAttachment {
+            scrollback: output.scrollback.snapshot(),
+            output: output.sender.as_ref().map(broadcast::Sender::subscribe),
+        })
+    }
+
+    pub fn list_sessions(&self, workspace_id: Uuid) -> Vec<TerminalSessionInfo> {
+        let Ok(sessions) = self.sessions.lock() else {
+            return Vec::new();
+        };
+
+        let mut infos: Vec<TerminalSessionInfo> = sessions
+            .iter()
+            .filter(|(_, session)| session.workspace_id == workspace_id)
+            .map(|(id, session)| TerminalSessionInfo {
+                id: *id,
+                workspace_id: session.workspace_id,
+                created_at: session.created_at,
+                viewers: session
+                    .output
+                    .lock()
+                    .ok()
+                    .and_then(|output| output.sender.as_ref().map(|s| s.receiver_count()))
+                    .unwrap_or(0),
+            })
+            .collect();
+        infos.sort_by_key(|info| info.created_at);
+        infos
+    }
+
+    pub fn session_info(&self, session_id: Uuid) -> Result<TerminalSessionInfo, PtyError> {
+        let workspace_id = self
+            .sessions
+            .lock()
+            .ok()
+            .and_then(|sessions| sessions.get(&session_id).map(|s| s.workspace_id))
+            .ok_or(PtyError::SessionNotFound(session_id))?;
+
+        self.list_sessions(workspace_id)
+            .into_iter()
+            .find(|info| info.id == session_id)
+            .ok_or(PtyError::SessionNotFound(session_id))
     }
 
     pub async fn write(&self, session_id: Uuid, data: &[u8]) -> Result<(), PtyError> {
@@ -163,10 +316,6 @@ impl PtyService {
             .get_mut(&session_id)
             .ok_or(PtyError::SessionNotFound(session_id))?;
 
-        if session.closed {
-            return Err(PtyError::SessionClosed);
-        }
-
         session
             .writer
             .write_all(data)
@@ -189,10 +338,6 @@ impl PtyService {
             .get(&session_id)
             .ok_or(PtyError::SessionNotFound(session_id))?;
 
-        if session.closed {
-            return Err(PtyError::SessionClosed);
-        }
-
         session
             .master
             .resize(PtySize {
@@ -206,23 +351,52 @@ impl PtyService {
         Ok(())
     }
 
+    /// End a session, terminating its shell and disconnecting its viewers
     pub async fn close_session(&self, session_id: Uuid) -> Result<(), PtyError> {
-        if let Some(mut session) = self
+        let session = self
             .sessions
             .lock()
-            .map_err(|_| PtyError::SessionClosed)?
+            .map_err(|_| PtyError::SessionNotFound(session_id))?
             .remove(&session_id)
-        {
-            session.closed = true;
-        }
+            .ok_or(PtyError::SessionNotFound(session_id))?;
+
+        terminate(session_id, session);
         Ok(())
     }
 
-    pub fn session_exists(&self, session_id: &Uuid) -> bool {
-        self.sessions
-            .lock()
-            .map(|s| s.contains_key(session_id))
-            .unwrap_or(false)
+    /// End every session of a workspace, e.g. when the workspace is archived
+    pub async fn close_workspace_sessions(&self, workspace_id: Uuid) {
+        let closed: Vec<(Uuid, PtySession)> = match self.sessions.lock() {
+            Ok(mut sessions) => {
+                let ids: Vec<Uuid> = sessions
+                    .iter()
+                    .filter(|(_, session)| session.workspace_id == workspace_id)
+                    .map(|(id, _)| *id)
+                    .collect();
+                ids.into_iter()
+                    .filter_map(|id| sessions.remove(&id).map(|session| (id, session)))
+                    .collect()
+            }
+            Err(_) => return,
+        };
+
+        for (session_id, session) in closed {
+            terminate(session_id, session);
+        }
+    }
+}
+
+fn terminate(session_id: Uuid, mut session: PtySession) {
+    if let Err(e) = session.killer.kill() {
+        tracing::debug!(
+            "Failed to kill shell of terminal session {}: {}",
+            session_id,
+            e
+        );
+    }
+    // Viewers are disconnected right away rather than when the output thread notices the exit
+    if let Ok(mut output) = session.output.lock() {
+        output.sender = None;
     }
 }
 
@@ -231,3 +405,38 @@ impl Default for PtyService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrollback_keeps_everything_below_the_limit() {
+        let mut scrollback = Scrollback::new(16);
+        scrollback.push(b"hello\n");
+        scrollback.push(b"world");
+        assert_eq!(scrollback.snapshot(), b"hello\nworld");
+    }
+
+    #[test]
+    fn scrollback_drops_oldest_output_from_a_line_boundary() {
+        let mut scrollback = Scrollback::new(16);
+        scrollback.push(b"first line\n");
+        scrollback.push(b"second\nthird\n");
+        // The last 16 bytes start inside "first line", so the rest of that line goes too
+        assert_eq!(scrollback.snapshot(), b"second\nthird\n");
+
+        let mut scrollback = Scrollback::new(13);
+        scrollback.push(b"first line\n");
+        scrollback.push(b"second\nthird\n");
+        // A cut that falls on a line boundary keeps the whole limit
+        assert_eq!(scrollback.snapshot(), b"second\nthird\n");
+    }
+
+    #[test]
+    fn scrollback_without_newlines_keeps_the_tail() {
+        let mut scrollback = Scrollback::new(4);
+        scrollback.push(b"abcdefgh");
+        assert_eq!(scrollback.snapshot(), b"efgh");
+    }
+}
@@ -7,17 +7,20 @@ use tracing::instrument;
 use crate::{
     AppState,
     analytics::{AnalyticsConfig, AnalyticsService},
-    attachments::cleanup::spawn_cleanup_task,
+    attachments::{
+        cleanup::spawn_cleanup_task,
+        storage::{AttachmentStorage, LocalStorage, S3Storage},
+    },
     auth::{
         GitHubOAuthProvider, GoogleOAuthProvider, JwtService, OAuthHandoffService,
-        OAuthTokenValidator, ProviderRegistry,
+        OAuthTokenValidator, OidcClaimMapping, OidcProvider, ProviderRegistry,
     },
     azure_blob::AzureBlobService,
     billing::BillingService,
-    config::RemoteServerConfig,
+    config::{AttachmentStorageConfig, MailConfig, RemoteServerConfig},
     db,
     github_app::GitHubAppService,
-    mail::LoopsMailer,
+    mail::{LogMailer, LoopsMailer, Mailer, SmtpMailer},
     r2::R2Service,
     routes,
 };
@@ -64,6 +67,23 @@ impl Server {
             )?);
         }
 
+        if let Some(oidc) = auth_config.oidc() {
+            let provider = OidcProvider::discover(
+                &oidc.discovery_url,
+                oidc.client_id.clone(),
+                oidc.client_secret.clone(),
+                oidc.scopes.clone(),
+                OidcClaimMapping {
+                    email: oidc.email_claim.clone(),
+                    name: oidc.name_claim.clone(),
+                    avatar_url: oidc.avatar_claim.clone(),
+                },
+            )
+            .await
+            .context("failed to set up OIDC login")?;
+            registry.register(provider);
+        }
+
         if registry.is_empty() {
             bail!("no OAuth providers configured");
         }
@@ -80,9 +100,15 @@ impl Server {
         let oauth_token_validator =
             Arc::new(OAuthTokenValidator::new(pool.clone(), registry.clone()));
 
-        let api_key = std::env::var("LOOPS_EMAIL_API_KEY")
-            .context("LOOPS_EMAIL_API_KEY environment variable is required")?;
-        let mailer = Arc::new(LoopsMailer::new(api_key));
+        let mailer: Arc<dyn Mailer> = match &config.mail {
+            MailConfig::Smtp(smtp) => {
+                Arc::new(SmtpMailer::new(smtp).context("failed to set up SMTP mailer")?)
+            }
+            MailConfig::Loops { api_key } => {
+                Arc::new(LoopsMailer::new(api_key.expose_secret().to_string()))
+            }
+            MailConfig::Log => Arc::new(LogMailer),
+        };
 
         let server_public_base_url = config.server_public_base_url.clone().ok_or_else(|| {
             anyhow::anyhow!(
@@ -99,20 +125,40 @@ impl Server {
             );
         }
 
-        let azure_blob = config.azure_blob.as_ref().map(AzureBlobService::new);
-        if azure_blob.is_some() {
-            tracing::info!("Azure Blob storage service initialized");
-        } else {
-            tracing::info!(
-                "Azure Blob storage not configured. Set AZURE_STORAGE_ACCOUNT_NAME and AZURE_STORAGE_ACCOUNT_KEY to enable issue attachments."
-            );
-        }
-
         let http_client = reqwest::Client::builder()
             .user_agent("VibeKanbanRemote/1.0")
             .build()
             .context("failed to create HTTP client")?;
 
+        let mut local_attachment_storage = None;
+        let storage_config = config.attachment_storage.as_ref();
+        let attachment_storage: Option<Arc<dyn AttachmentStorage>> = match storage_config {
+            Some(AttachmentStorageConfig::Azure(azure)) => {
+                tracing::info!("Azure Blob attachment storage initialized");
+                Some(Arc::new(AzureBlobService::new(azure)))
+            }
+            Some(AttachmentStorageConfig::S3(s3)) => {
+                tracing::info!(bucket = %s3.bucket, "S3 attachment storage initialized");
+                Some(Arc::new(S3Storage::new(s3, http_client.clone())))
+            }
+            Some(AttachmentStorageConfig::Local(local)) => {
+                tracing::info!(root = %local.root.display(), "Local attachment storage initialized");
+                let storage = Arc::new(LocalStorage::new(
+                    local,
+                    &server_public_base_url,
+                    auth_config.jwt_secret(),
+                ));
+                local_attachment_storage = Some(Arc::clone(&storage));
+                Some(storage)
+            }
+            None => {
+                tracing::info!(
+                    "Attachment storage not configured. Set AZURE_STORAGE_ACCOUNT_NAME, ATTACHMENTS_S3_BUCKET or ATTACHMENTS_LOCAL_DIR to enable issue attachments."
+                );
+                None
+            }
+        };
+
         let github_app = match &config.github_app {
             Some(github_config) => {
                 match GitHubAppService::new(github_config, http_client.clone()) {
@@ -156,8 +202,8 @@ impl Server {
             }
         };
 
-        if let Some(ref azure_blob_service) = azure_blob {
-            spawn_cleanup_task(pool.clone(), azure_blob_service.clone());
+        if let Some(ref storage) = attachment_storage {
+            spawn_cleanup_task(pool.clone(), Arc::clone(storage));
         }
 
         let state = AppState::new(
@@ -170,7 +216,8 @@ impl Server {
             server_public_base_url,
             http_client,
             r2,
-            azure_blob,
+            attachment_storage,
+            local_attachment_storage,
             github_app,
             billing,
             analytics,
@@ -1,11 +1,11 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use sqlx::PgPool;
 use tokio::task::JoinHandle;
 use tracing::{info, instrument, warn};
 
 use crate::{
-    azure_blob::AzureBlobService,
+    attachments::storage::AttachmentStorage,
     db::attachments::AttachmentRepository,
     db::blobs::BlobRepository,
     db::pending_uploads::PendingUploadRepository,
@@ -16,7 +16,7 @@ const DEFAULT_INTERVAL: Duration = Duration::from_secs(3600);
 
 /// Spawns a background task that periodically cleans up orphan attachments and
 /// expired pending uploads. Call once during server startup.
-pub fn spawn_cleanup_task(pool: PgPool, azure: AzureBlobService) -> JoinHandle<()> {
+pub fn spawn_cleanup_task(pool: PgPool, storage: Arc<dyn AttachmentStorage>) -> JoinHandle<()> {
     let interval = std::env::var("ATTACHMENT_CLEANUP_INTERVAL_SECS")
         .ok()
         .and_then(|v| v.parse::<u64>().ok())
@@ -35,18 +35,18 @@ pub fn spawn_cleanup_task(pool: PgPool, azure: AzureBlobService) -> JoinHandle<(
 
         loop {
             ticker.tick().await;
-            run_sweep(&pool, &azure).await;
+            run_sweep(&pool, storage.as_ref()).await;
         }
     })
 }
 
 #[instrument(name = "attachment_cleanup.sweep", skip_all)]
-async fn run_sweep(pool: &PgPool, azure: &AzureBlobService) {
+async fn run_sweep(pool: &PgPool, storage: &dyn AttachmentStorage) {
     info!("Starting attachment cleanup sweep");
 
     let (expired, pending) = tokio::join!(
-        cleanup_expired_attachments(pool, azure),
-        cleanup_expired_pending_uploads(pool, azure),
+        cleanup_expired_attachments(pool, storage),
+        cleanup_expired_pending_uploads(pool, storage),
     );
 
     match expired {
@@ -62,7 +62,7 @@ async fn run_sweep(pool: &PgPool, azure: &AzureBlobService) {
 
 async fn cleanup_expired_attachments(
     pool: &PgPool,
-    azure: &AzureBlobService,
+    storage: &dyn AttachmentStorage,
 ) -> anyhow::Result<u32> {
     let expired = AttachmentRepository::find_expired(pool, EXPIRED_BATCH_SIZE).await?;
     let mut deleted_count: u32 = 0;
@@ -79,12 +79,12 @@ async fn cleanup_expired_attachments(
         match AttachmentRepository::count_by_blob_id(pool, blob_id).await {
             Ok(0) => {
                 if let Ok(Some(blob)) = BlobRepository::delete(pool, blob_id).await {
-                    if let Err(e) = azure.delete_blob(&blob.blob_path).await {
-                        warn!(blob_path = %blob.blob_path, error = %e, "Failed to delete Azure blob");
+                    if let Err(e) = storage.delete_blob(&blob.blob_path).await {
+                        warn!(blob_path = %blob.blob_path, error = %e, "Failed to delete blob");
                     }
                     if let Some(thumb_path) = &blob.thumbnail_blob_path {
-                        if let Err(e) = azure.delete_blob(thumb_path).await {
-                            warn!(blob_path = %thumb_path, error = %e, "Failed to delete Azure thumbnail");
+                        if let Err(e) = storage.delete_blob(thumb_path).await {
+                            warn!(blob_path = %thumb_path, error = %e, "Failed to delete thumbnail");
                         }
                     }
                 }
@@ -103,14 +103,14 @@ async fn cleanup_expired_attachments(
 
 async fn cleanup_expired_pending_uploads(
     pool: &PgPool,
-    azure: &AzureBlobService,
+    storage: &dyn AttachmentStorage,
 ) -> anyhow::Result<u32> {
     let expired = PendingUploadRepository::delete_expired(pool).await?;
     let mut deleted_count: u32 = 0;
 
     for pending in expired {
-        if let Err(e) = azure.delete_blob(&pending.blob_path).await {
-            warn!(blob_path = %pending.blob_path, error = %e, "Failed to delete Azure blob for expired pending upload");
+        if let Err(e) = storage.delete_blob(&pending.blob_path).await {
+            warn!(blob_path = %pending.blob_path, error = %e, "Failed to delete blob for expired pending upload");
         }
         deleted_count += 1;
     }
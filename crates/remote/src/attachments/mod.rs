@@ -1,2 +1,3 @@
 pub mod thumbnail;
+pub mod storage;
 pub(crate) mod cleanup;
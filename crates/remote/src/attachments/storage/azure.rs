@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+
+use super::{AttachmentStorage, BlobProperties, PresignedUpload, StorageError};
+use crate::azure_blob::{AzureBlobError, AzureBlobService};
+
+impl From<AzureBlobError> for StorageError {
+    fn from(err: AzureBlobError) -> Self {
+        match err {
+            AzureBlobError::Storage(e) => StorageError::Backend(e),
+            AzureBlobError::NotFound(path) => StorageError::NotFound(path),
+            AzureBlobError::SasToken(e) => StorageError::Presign(e),
+        }
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for AzureBlobService {
+    async fn create_upload_url(&self, blob_path: &str) -> Result<PresignedUpload, StorageError> {
+        Ok(AzureBlobService::create_upload_url(self, blob_path)?)
+    }
+
+    async fn create_read_url(&self, blob_path: &str) -> Result<String, StorageError> {
+        Ok(AzureBlobService::create_read_url(self, blob_path)?)
+    }
+
+    async fn get_blob_properties(&self, blob_path: &str) -> Result<BlobProperties, StorageError> {
+        Ok(AzureBlobService::get_blob_properties(self, blob_path).await?)
+    }
+
+    async fn download_blob(&self, blob_path: &str) -> Result<Vec<u8>, StorageError> {
+        Ok(AzureBlobService::download_blob(self, blob_path).await?)
+    }
+
+    async fn upload_blob(
+        &self,
+        blob_path: &str,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), StorageError> {
+        Ok(AzureBlobService::upload_blob(self, blob_path, data, content_type).await?)
+    }
+
+    async fn delete_blob(&self, blob_path: &str) -> Result<(), StorageError> {
+        Ok(AzureBlobService::delete_blob(self, blob_path).await?)
+    }
+}
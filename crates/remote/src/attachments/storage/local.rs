@@ -0,0 +1,409 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+
+use super::{
+    AttachmentStorage, BlobProperties, PresignedUpload, READ_URL_EXPIRY, StorageError, expires_at,
+};
+use crate::config::LocalStorageConfig;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Route that serves local blobs, relative to the server's public base URL.
+const LOCAL_STORAGE_ROUTE: &str = "/v1/attachment-storage";
+
+/// Suffix of the file next to each blob that records the content type it was uploaded with.
+const CONTENT_TYPE_SUFFIX: &str = ".content-type";
+
+/// Content types served inline. Anything else is served as a download so uploaded HTML or SVG
+/// never runs on the server's origin.
+const INLINE_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Attachment storage in a directory on the server's disk.
+///
+/// Clients upload and download through the server itself, using URLs signed with a key derived
+/// from the JWT secret, so they keep working without a session like the cloud backends' URLs.
+pub struct LocalStorage {
+    root: PathBuf,
+    public_base_url: String,
+    signing_key: Vec<u8>,
+    presign_expiry: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedOperation {
+    Read,
+    Write,
+}
+
+impl SignedOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            SignedOperation::Read => "read",
+            SignedOperation::Write => "write",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LocalBlob {
+    pub data: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+impl LocalBlob {
+    /// Content type to serve the blob with, or `None` if it should be downloaded rather than
+    /// displayed.
+    pub fn inline_content_type(&self) -> Option<&str> {
+        self.content_type
+            .as_deref()
+            .filter(|content_type| INLINE_CONTENT_TYPES.contains(content_type))
+    }
+}
+
+impl LocalStorage {
+    pub fn new(
+        config: &LocalStorageConfig,
+        public_base_url: &str,
+        signing_secret: &SecretString,
+    ) -> Self {
+        let mut mac = HmacSha256::new_from_slice(signing_secret.expose_secret().as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(b"vibe-kanban attachment storage");
+
+        Self {
+            root: config.root.clone(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+            signing_key: mac.finalize().into_bytes().to_vec(),
+            presign_expiry: Duration::from_secs(config.presign_expiry_secs),
+        }
+    }
+
+    fn signed_url(
+        &self,
+        operation: SignedOperation,
+        blob_path: &str,
+        expires: DateTime<Utc>,
+    ) -> Result<String, StorageError> {
+        validate_blob_path(blob_path)?;
+
+        let encoded_path = blob_path
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        let expires = expires.timestamp();
+        let signature = hex::encode(
+            self.mac(operation, blob_path, expires)
+                .finalize()
+                .into_bytes(),
+        );
+
+        Ok(format!(
+            "{}{LOCAL_STORAGE_ROUTE}/{encoded_path}?expires={expires}&sig={signature}",
+            self.public_base_url
+        ))
+    }
+
+    fn mac(&self, operation: SignedOperation, blob_path: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.signing_key).expect("HMAC can take key of any size");
+        mac.update(format!("{}\n{blob_path}\n{expires}", operation.as_str()).as_bytes());
+        mac
+    }
+
+    /// Check the `expires` and `sig` query parameters of a signed URL.
+    pub fn verify(
+        &self,
+        operation: SignedOperation,
+        blob_path: &str,
+        expires: i64,
+        signature: &str,
+    ) -> bool {
+        if expires < Utc::now().timestamp() {
+            return false;
+        }
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.mac(operation, blob_path, expires)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    fn resolve(&self, blob_path: &str) -> Result<PathBuf, StorageError> {
+        validate_blob_path(blob_path)?;
+        Ok(self.root.join(blob_path))
+    }
+
+    pub async fn read(&self, blob_path: &str) -> Result<LocalBlob, StorageError> {
+        let path = self.resolve(blob_path)?;
+        let data = tokio::fs::read(&path)
+            .await
+            .map_err(|e| io_error(e, blob_path))?;
+        let content_type = tokio::fs::read_to_string(content_type_path(&path))
+            .await
+            .ok()
+            .map(|content_type| content_type.trim().to_string());
+
+        Ok(LocalBlob { data, content_type })
+    }
+
+    pub async fn write(
+        &self,
+        blob_path: &str,
+        data: &[u8],
+        content_type: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let path = self.resolve(blob_path)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io_error(e, blob_path))?;
+        }
+
+        tokio::fs::write(&path, data)
+            .await
+            .map_err(|e| io_error(e, blob_path))?;
+
+        let content_type_path = content_type_path(&path);
+        match content_type {
+            Some(content_type) => tokio::fs::write(content_type_path, content_type)
+                .await
+                .map_err(|e| io_error(e, blob_path)),
+            None => remove_if_exists(&content_type_path, blob_path).await,
+        }
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalStorage {
+    async fn create_upload_url(&self, blob_path: &str) -> Result<PresignedUpload, StorageError> {
+        let expires_at = expires_at(self.presign_expiry);
+        let upload_url = self.signed_url(SignedOperation::Write, blob_path, expires_at)?;
+
+        Ok(PresignedUpload {
+            upload_url,
+            blob_path: blob_path.to_string(),
+            expires_at,
+        })
+    }
+
+    async fn create_read_url(&self, blob_path: &str) -> Result<String, StorageError> {
+        self.signed_url(
+            SignedOperation::Read,
+            blob_path,
+            expires_at(READ_URL_EXPIRY),
+        )
+    }
+
+    async fn get_blob_properties(&self, blob_path: &str) -> Result<BlobProperties, StorageError> {
+        let metadata = tokio::fs::metadata(self.resolve(blob_path)?)
+            .await
+            .map_err(|e| io_error(e, blob_path))?;
+
+        Ok(BlobProperties {
+            content_length: metadata.len() as i64,
+        })
+    }
+
+    async fn download_blob(&self, blob_path: &str) -> Result<Vec<u8>, StorageError> {
+        Ok(self.read(blob_path).await?.data)
+    }
+
+    async fn upload_blob(
+        &self,
+        blob_path: &str,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), StorageError> {
+        self.write(blob_path, &data, Some(&content_type)).await
+    }
+
+    async fn delete_blob(&self, blob_path: &str) -> Result<(), StorageError> {
+        let path = self.resolve(blob_path)?;
+        remove_if_exists(&path, blob_path).await?;
+        remove_if_exists(&content_type_path(&path), blob_path).await
+    }
+}
+
+/// Blob paths are relative, `/` separated and may not step outside the storage directory.
+fn validate_blob_path(blob_path: &str) -> Result<(), StorageError> {
+    let valid = !blob_path.is_empty()
+        && blob_path.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && !segment.contains(['\\', ':', '\0'])
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidPath(blob_path.to_string()))
+    }
+}
+
+fn content_type_path(path: &Path) -> PathBuf {
+    let mut content_type_path = path.as_os_str().to_owned();
+    content_type_path.push(CONTENT_TYPE_SUFFIX);
+    PathBuf::from(content_type_path)
+}
+
+async fn remove_if_exists(path: &Path, blob_path: &str) -> Result<(), StorageError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_error(e, blob_path)),
+    }
+}
+
+fn io_error(err: io::Error, blob_path: &str) -> StorageError {
+    match err.kind() {
+        io::ErrorKind::NotFound => StorageError::NotFound(blob_path.to_string()),
+        _ => StorageError::Backend(format!("{blob_path}: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(root: &Path) -> LocalStorage {
+        LocalStorage::new(
+            &LocalStorageConfig {
+                root: root.to_path_buf(),
+                presign_expiry_secs: 3600,
+            },
+            "https://kanban.example.com/",
+            &SecretString::new("test-jwt-secret".into()),
+        )
+    }
+
+    fn query_param(url: &str, name: &str) -> String {
+        url::Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signed_urls_verify_only_for_their_operation_and_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(dir.path());
+        let blob_path = "attachments/p1/abc_photo one.png";
+
+        let upload = storage.create_upload_url(blob_path).await.unwrap();
+        assert!(upload.upload_url.starts_with(
+            "https://kanban.example.com/v1/attachment-storage/attachments/p1/abc_photo%20one.png?"
+        ));
+
+        let expires: i64 = query_param(&upload.upload_url, "expires").parse().unwrap();
+        let signature = query_param(&upload.upload_url, "sig");
+
+        assert!(storage.verify(SignedOperation::Write, blob_path, expires, &signature));
+        assert!(!storage.verify(SignedOperation::Read, blob_path, expires, &signature));
+        assert!(!storage.verify(
+            SignedOperation::Write,
+            "attachments/p1/other.png",
+            expires,
+            &signature
+        ));
+        assert!(!storage.verify(SignedOperation::Write, blob_path, expires + 1, &signature));
+        assert!(!storage.verify(SignedOperation::Write, blob_path, expires, "not-hex"));
+    }
+
+    #[test]
+    fn expired_signatures_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(dir.path());
+        let expires = Utc::now() - chrono::Duration::minutes(1);
+
+        let url = storage
+            .signed_url(SignedOperation::Read, "attachments/a.png", expires)
+            .unwrap();
+        let signature = query_param(&url, "sig");
+
+        assert!(!storage.verify(
+            SignedOperation::Read,
+            "attachments/a.png",
+            expires.timestamp(),
+            &signature
+        ));
+    }
+
+    #[test]
+    fn paths_outside_the_storage_directory_are_rejected() {
+        for path in [
+            "",
+            "/etc/passwd",
+            "../secret",
+            "attachments/../../secret",
+            "attachments//a.png",
+            "attachments/./a.png",
+            "attachments\\..\\a.png",
+            "C:/a.png",
+        ] {
+            assert!(
+                matches!(validate_blob_path(path), Err(StorageError::InvalidPath(_))),
+                "{path:?} should be rejected"
+            );
+        }
+
+        assert!(validate_blob_path("thumbnails/attachments/p1/abc_a.b-c.png").is_ok());
+    }
+
+    #[tokio::test]
+    async fn blobs_round_trip_with_their_content_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(dir.path());
+
+        storage
+            .write(
+                "attachments/p1/a.html",
+                b"<script></script>",
+                Some("text/html"),
+            )
+            .await
+            .unwrap();
+        storage
+            .upload_blob("thumbnails/a.png", vec![1, 2, 3], "image/jpeg".to_string())
+            .await
+            .unwrap();
+
+        let html = storage.read("attachments/p1/a.html").await.unwrap();
+        assert_eq!(html.content_type.as_deref(), Some("text/html"));
+        assert_eq!(html.inline_content_type(), None);
+
+        let thumbnail = storage.read("thumbnails/a.png").await.unwrap();
+        assert_eq!(thumbnail.data, vec![1, 2, 3]);
+        assert_eq!(thumbnail.inline_content_type(), Some("image/jpeg"));
+        assert_eq!(
+            storage
+                .get_blob_properties("thumbnails/a.png")
+                .await
+                .unwrap()
+                .content_length,
+            3
+        );
+
+        storage.delete_blob("thumbnails/a.png").await.unwrap();
+        assert!(matches!(
+            storage.read("thumbnails/a.png").await,
+            Err(StorageError::NotFound(_))
+        ));
+        assert!(!dir.path().join("thumbnails/a.png.content-type").exists());
+        // Deleting a blob that is already gone is not an error
+        storage.delete_blob("thumbnails/a.png").await.unwrap();
+    }
+}
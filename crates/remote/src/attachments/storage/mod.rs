@@ -0,0 +1,66 @@
+//! Backends that hold attachment blobs. Clients upload and download through presigned URLs,
+//! while the server reads uploads back to generate thumbnails and removes unused blobs.
+
+mod azure;
+mod local;
+mod s3;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+pub use local::{LocalBlob, LocalStorage, SignedOperation};
+pub use s3::S3Storage;
+
+/// How long read URLs handed to clients stay valid.
+pub const READ_URL_EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug)]
+pub struct PresignedUpload {
+    pub upload_url: String,
+    pub blob_path: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct BlobProperties {
+    pub content_length: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("storage backend error: {0}")]
+    Backend(String),
+    #[error("blob not found: {0}")]
+    NotFound(String),
+    #[error("presign error: {0}")]
+    Presign(String),
+    #[error("invalid blob path: {0}")]
+    InvalidPath(String),
+}
+
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    /// URL the client uploads the file to with a single `PUT`.
+    async fn create_upload_url(&self, blob_path: &str) -> Result<PresignedUpload, StorageError>;
+
+    /// Short lived URL the client downloads the blob from.
+    async fn create_read_url(&self, blob_path: &str) -> Result<String, StorageError>;
+
+    async fn get_blob_properties(&self, blob_path: &str) -> Result<BlobProperties, StorageError>;
+
+    async fn download_blob(&self, blob_path: &str) -> Result<Vec<u8>, StorageError>;
+
+    async fn upload_blob(
+        &self,
+        blob_path: &str,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), StorageError>;
+
+    async fn delete_blob(&self, blob_path: &str) -> Result<(), StorageError>;
+}
+
+fn expires_at(expiry: Duration) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(expiry).unwrap_or(chrono::Duration::hours(1))
+}
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aws_credential_types::Credentials;
+use aws_sdk_s3::{
+    Client,
+    config::{Builder as S3ConfigBuilder, IdentityCache, Region, StalledStreamProtectionConfig},
+    presigning::{PresignedRequest, PresigningConfig},
+};
+use reqwest::StatusCode;
+use secrecy::ExposeSecret;
+
+use super::{
+    AttachmentStorage, BlobProperties, PresignedUpload, READ_URL_EXPIRY, StorageError, expires_at,
+};
+use crate::config::S3StorageConfig;
+
+/// Attachment storage in an S3 compatible bucket (AWS S3, MinIO, Garage, ...).
+///
+/// Every request is presigned. Server side operations run the presigned request with the
+/// shared HTTP client, so no separate SDK transport has to be configured.
+#[derive(Clone)]
+pub struct S3Storage {
+    /// Signs requests the server sends itself
+    client: Client,
+    /// Signs URLs handed to browsers, which may reach the bucket under another host
+    public_client: Client,
+    http: reqwest::Client,
+    bucket: String,
+    presign_expiry: Duration,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3StorageConfig, http: reqwest::Client) -> Self {
+        let client = build_client(config, config.endpoint.as_deref());
+        let public_client = match &config.public_endpoint {
+            Some(public_endpoint) => build_client(config, Some(public_endpoint)),
+            None => client.clone(),
+        };
+
+        Self {
+            client,
+            public_client,
+            http,
+            bucket: config.bucket.clone(),
+            presign_expiry: Duration::from_secs(config.presign_expiry_secs),
+        }
+    }
+
+    fn presigning_config(&self, expiry: Duration) -> Result<PresigningConfig, StorageError> {
+        PresigningConfig::builder()
+            .expires_in(expiry)
+            .build()
+            .map_err(|e| StorageError::Presign(e.to_string()))
+    }
+
+    async fn send(
+        &self,
+        presigned: PresignedRequest,
+        body: Option<Vec<u8>>,
+        blob_path: &str,
+    ) -> Result<reqwest::Response, StorageError> {
+        let method = reqwest::Method::from_bytes(presigned.method().as_bytes())
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+
+        let mut request = self.http.request(method, presigned.uri());
+        for (name, value) in presigned.headers() {
+            request = request.header(name, value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::NOT_FOUND => Err(StorageError::NotFound(blob_path.to_string())),
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                Err(StorageError::Backend(format!(
+                    "S3 request failed with status {status}: {body}"
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for S3Storage {
+    async fn create_upload_url(&self, blob_path: &str) -> Result<PresignedUpload, StorageError> {
+        let presigned = self
+            .public_client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .presigned(self.presigning_config(self.presign_expiry)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        Ok(PresignedUpload {
+            upload_url: presigned.uri().to_string(),
+            blob_path: blob_path.to_string(),
+            expires_at: expires_at(self.presign_expiry),
+        })
+    }
+
+    async fn create_read_url(&self, blob_path: &str) -> Result<String, StorageError> {
+        let presigned = self
+            .public_client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .presigned(self.presigning_config(READ_URL_EXPIRY)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        Ok(presigned.uri().to_string())
+    }
+
+    async fn get_blob_properties(&self, blob_path: &str) -> Result<BlobProperties, StorageError> {
+        let presigned = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .presigned(self.presigning_config(READ_URL_EXPIRY)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        let response = self.send(presigned, None, blob_path).await?;
+        let content_length = response
+            .headers()
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+
+        Ok(BlobProperties { content_length })
+    }
+
+    async fn download_blob(&self, blob_path: &str) -> Result<Vec<u8>, StorageError> {
+        let presigned = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .presigned(self.presigning_config(READ_URL_EXPIRY)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        let bytes = self
+            .send(presigned, None, blob_path)
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    async fn upload_blob(
+        &self,
+        blob_path: &str,
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<(), StorageError> {
+        let presigned = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .content_type(content_type)
+            .presigned(self.presigning_config(READ_URL_EXPIRY)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        self.send(presigned, Some(data), blob_path).await?;
+        Ok(())
+    }
+
+    async fn delete_blob(&self, blob_path: &str) -> Result<(), StorageError> {
+        let presigned = self
+            .client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(blob_path)
+            .presigned(self.presigning_config(READ_URL_EXPIRY)?)
+            .await
+            .map_err(|e| StorageError::Presign(e.to_string()))?;
+
+        self.send(presigned, None, blob_path).await?;
+        Ok(())
+    }
+}
+
+fn build_client(config: &S3StorageConfig, endpoint: Option<&str>) -> Client {
+    let credentials = Credentials::new(
+        &config.access_key_id,
+        config.secret_access_key.expose_secret(),
+        None,
+        None,
+        "attachments-static",
+    );
+
+    let mut builder = S3ConfigBuilder::new()
+        .region(Region::new(config.region.clone()))
+        .credentials_provider(credentials)
+        .force_path_style(config.force_path_style)
+        .stalled_stream_protection(StalledStreamProtectionConfig::disabled())
+        .identity_cache(IdentityCache::no_cache());
+    if let Some(endpoint) = endpoint {
+        builder = builder.endpoint_url(endpoint);
+    }
+
+    Client::from_conf(builder.build())
+}
@@ -3,9 +3,10 @@ use std::{collections::HashMap, sync::Arc};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::Duration;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 use tracing::info;
 use url::Url;
@@ -60,7 +61,7 @@ pub struct ProviderTokenDetails {
 #[async_trait]
 pub trait AuthorizationProvider: Send + Sync {
     fn name(&self) -> &'static str;
-    fn scopes(&self) -> &[&str];
+    fn scopes(&self) -> Vec<&str>;
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url>;
     async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant>;
     async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser>;
@@ -163,8 +164,8 @@ impl AuthorizationProvider for GitHubOAuthProvider {
         "github"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["read:user", "user:email"]
+    fn scopes(&self) -> Vec<&str> {
+        vec!["read:user", "user:email"]
     }
 
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
@@ -496,8 +497,8 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         "google"
     }
 
-    fn scopes(&self) -> &[&str] {
-        &["openid", "email", "profile"]
+    fn scopes(&self) -> Vec<&str> {
+        vec!["openid", "email", "profile"]
     }
 
     fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
@@ -690,3 +691,680 @@ impl AuthorizationProvider for GoogleOAuthProvider {
         }
     }
 }
+
+/// Names of the userinfo claims that fill in a user's profile
+#[derive(Debug, Clone)]
+pub struct OidcClaimMapping {
+    pub email: String,
+    pub name: String,
+    pub avatar_url: String,
+}
+
+impl Default for OidcClaimMapping {
+    fn default() -> Self {
+        Self {
+            email: "email".to_string(),
+            name: "name".to_string(),
+            avatar_url: "picture".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcDiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    userinfo_endpoint: Option<Url>,
+    #[serde(default)]
+    token_endpoint_auth_methods_supported: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OidcTokenResponse {
+    Success {
+        access_token: String,
+        token_type: String,
+        scope: Option<String>,
+        expires_in: Option<i64>,
+        refresh_token: Option<String>,
+        id_token: Option<String>,
+    },
+    Error {
+        error: String,
+        error_description: Option<String>,
+    },
+}
+
+/// Any OpenID Connect issuer (Keycloak, Okta, Authentik, ...), configured through its discovery
+/// document.
+pub struct OidcProvider {
+    client: Client,
+    client_id: String,
+    client_secret: SecretString,
+    scopes: Vec<String>,
+    claims: OidcClaimMapping,
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    userinfo_endpoint: Url,
+    client_secret_basic: bool,
+}
+
+impl OidcProvider {
+    /// Fetch the issuer's discovery document. `discovery_url` may be the issuer URL or the full
+    /// `.well-known/openid-configuration` URL.
+    pub async fn discover(
+        discovery_url: &str,
+        client_id: String,
+        client_secret: SecretString,
+        scopes: Vec<String>,
+        claims: OidcClaimMapping,
+    ) -> Result<Self> {
+        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let discovery_url = discovery_document_url(discovery_url)?;
+
+        let document: OidcDiscoveryDocument = client
+            .get(discovery_url.clone())
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch OIDC discovery document {discovery_url}"))?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse OIDC discovery document")?;
+
+        let userinfo_endpoint = document
+            .userinfo_endpoint
+            .context("OIDC issuer does not advertise a userinfo endpoint")?;
+
+        // Issuers that list no methods support client_secret_basic, the default in the spec
+        let methods = &document.token_endpoint_auth_methods_supported;
+        let client_secret_basic =
+            methods.is_empty() || methods.iter().any(|method| method == "client_secret_basic");
+
+        let mut scopes: Vec<String> = scopes
+            .into_iter()
+            .map(|scope| scope.trim().to_string())
+            .filter(|scope| !scope.is_empty())
+            .collect();
+        if !scopes.iter().any(|scope| scope == "openid") {
+            scopes.insert(0, "openid".to_string());
+        }
+
+        info!(issuer = %document.issuer, "OIDC provider discovered");
+
+        Ok(Self {
+            client,
+            client_id,
+            client_secret,
+            scopes,
+            claims,
+            authorization_endpoint: document.authorization_endpoint,
+            token_endpoint: document.token_endpoint,
+            userinfo_endpoint,
+            client_secret_basic,
+        })
+    }
+
+    fn token_request(&self, params: &[(&str, &str)]) -> RequestBuilder {
+        let request = self
+            .client
+            .post(self.token_endpoint.clone())
+            .header("Accept", "application/json");
+
+        if self.client_secret_basic {
+            request
+                .basic_auth(&self.client_id, Some(self.client_secret.expose_secret()))
+                .form(params)
+        } else {
+            let mut form = params.to_vec();
+            form.push(("client_id", self.client_id.as_str()));
+            form.push(("client_secret", self.client_secret.expose_secret()));
+            request.form(&form)
+        }
+    }
+
+    async fn try_refresh_access_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let response = self
+            .token_request(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ])
+            .send()
+            .await
+            .map_err(|err| {
+                TokenValidationError::temporary(format!("refresh request failed: {err}"))
+            })?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => match response
+                .json::<OidcTokenResponse>()
+                .await
+                .map_err(|err| TokenValidationError::temporary(format!("{err}")))?
+            {
+                OidcTokenResponse::Success {
+                    access_token,
+                    expires_in,
+                    refresh_token: new_refresh_token,
+                    ..
+                } => Ok(ProviderTokenDetails {
+                    provider: self.name().to_string(),
+                    access_token,
+                    refresh_token: Some(
+                        new_refresh_token.unwrap_or_else(|| refresh_token.to_string()),
+                    ),
+                    expires_at: expires_in.map(|secs| chrono::Utc::now().timestamp() + secs),
+                }),
+                OidcTokenResponse::Error { .. } => Err(TokenValidationError::InvalidOrRevoked),
+            },
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNAUTHORIZED => {
+                Err(TokenValidationError::InvalidOrRevoked)
+            }
+            status if status.is_server_error() => Err(TokenValidationError::temporary(format!(
+                "token refresh server error: {status}"
+            ))),
+            status => Err(TokenValidationError::temporary(format!(
+                "unexpected token refresh status: {status}"
+            ))),
+        }
+    }
+
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        max_retries: u32,
+    ) -> Result<ProviderTokenDetails, TokenValidationError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+
+            match self.try_refresh_access_token(refresh_token).await {
+                Ok(new_token_details) => return Ok(new_token_details),
+                Err(TokenValidationError::InvalidOrRevoked) => {
+                    return Err(TokenValidationError::InvalidOrRevoked);
+                }
+                Err(TokenValidationError::Temporary(err)) => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::Temporary(err));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+fn discovery_document_url(url: &str) -> Result<Url> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let url = if trimmed.ends_with("/.well-known/openid-configuration") {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed}/.well-known/openid-configuration")
+    };
+    Url::parse(&url).with_context(|| format!("invalid OIDC discovery URL `{url}`"))
+}
+
+fn string_claim(claims: &serde_json::Map<String, Value>, name: &str) -> Option<String> {
+    claims
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+#[async_trait]
+impl AuthorizationProvider for OidcProvider {
+    fn name(&self) -> &'static str {
+        "oidc"
+    }
+
+    fn scopes(&self) -> Vec<&str> {
+        self.scopes.iter().map(String::as_str).collect()
+    }
+
+    fn authorize_url(&self, state: &str, redirect_uri: &str) -> Result<Url> {
+        let mut url = self.authorization_endpoint.clone();
+        {
+            let mut qp = url.query_pairs_mut();
+            qp.append_pair("client_id", &self.client_id);
+            qp.append_pair("redirect_uri", redirect_uri);
+            qp.append_pair("response_type", "code");
+            qp.append_pair("scope", &self.scopes().join(" "));
+            qp.append_pair("state", state);
+        }
+        Ok(url)
+    }
+
+    async fn exchange_code(&self, code: &str, redirect_uri: &str) -> Result<AuthorizationGrant> {
+        let response = self
+            .token_request(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        match response.json::<OidcTokenResponse>().await? {
+            OidcTokenResponse::Success {
+                access_token,
+                token_type,
+                scope,
+                expires_in,
+                refresh_token,
+                id_token,
+            } => Ok(AuthorizationGrant {
+                access_token: SecretString::new(access_token.into()),
+                token_type,
+                scopes: scope
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                refresh_token: refresh_token.map(|v| SecretString::new(v.into())),
+                expires_in: expires_in.map(Duration::seconds),
+                id_token: id_token.map(|v| SecretString::new(v.into())),
+            }),
+            OidcTokenResponse::Error {
+                error,
+                error_description,
+            } => {
+                let detail = error_description.unwrap_or_else(|| error.clone());
+                anyhow::bail!("oidc token exchange failed: {detail}")
+            }
+        }
+    }
+
+    async fn fetch_user(&self, access_token: &SecretString) -> Result<ProviderUser> {
+        let claims: serde_json::Map<String, Value> = self
+            .client
+            .get(self.userinfo_endpoint.clone())
+            .header("Accept", "application/json")
+            .bearer_auth(access_token.expose_secret())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("failed to parse OIDC userinfo response")?;
+
+        let id = string_claim(&claims, "sub").context("OIDC userinfo response has no `sub`")?;
+
+        // An address the issuer has not verified could belong to somebody else
+        let email_unverified = self.claims.email == "email"
+            && claims.get("email_verified").and_then(Value::as_bool) == Some(false);
+        let email = string_claim(&claims, &self.claims.email).filter(|_| !email_unverified);
+
+        let name = string_claim(&claims, &self.claims.name).or_else(|| {
+            match (
+                string_claim(&claims, "given_name"),
+                string_claim(&claims, "family_name"),
+            ) {
+                (Some(first), Some(last)) => Some(format!("{first} {last}")),
+                (Some(first), None) => Some(first),
+                (None, Some(last)) => Some(last),
+                (None, None) => None,
+            }
+        });
+
+        Ok(ProviderUser {
+            id,
+            login: string_claim(&claims, "preferred_username"),
+            email,
+            name,
+            avatar_url: string_claim(&claims, &self.claims.avatar_url),
+        })
+    }
+
+    async fn validate_token(
+        &self,
+        token_details: &ProviderTokenDetails,
+        max_retries: u32,
+    ) -> Result<Option<ProviderTokenDetails>, TokenValidationError> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            if let Some(expires_at) = token_details.expires_at
+                && let now = chrono::Utc::now().timestamp()
+                && now >= expires_at - TOKEN_EXPIRATION_LEEWAY_SECONDS
+            {
+                let Some(refresh_token) = &token_details.refresh_token else {
+                    return Err(TokenValidationError::InvalidOrRevoked);
+                };
+
+                info!("Token expired, attempting refresh for OIDC");
+                return self
+                    .refresh_token(refresh_token, max_retries)
+                    .await
+                    .map(Some);
+            }
+
+            let response = match self
+                .client
+                .get(self.userinfo_endpoint.clone())
+                .bearer_auth(&token_details.access_token)
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(err) => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::temporary(format!(
+                            "userinfo request failed: {err}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                    continue;
+                }
+            };
+
+            match response.status() {
+                reqwest::StatusCode::OK => {
+                    return Ok(None);
+                }
+                reqwest::StatusCode::UNAUTHORIZED => {
+                    let Some(refresh_token) = &token_details.refresh_token else {
+                        return Err(TokenValidationError::InvalidOrRevoked);
+                    };
+                    info!("Token rejected during validation, attempting refresh");
+                    return self
+                        .refresh_token(refresh_token, max_retries)
+                        .await
+                        .map(Some);
+                }
+                status => {
+                    if attempt >= max_retries {
+                        return Err(TokenValidationError::temporary(format!(
+                            "unexpected userinfo status: {status}"
+                        )));
+                    }
+                    tokio::time::sleep(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECONDS))
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::{
+        Form, Json, Router,
+        extract::State,
+        http::{HeaderMap, StatusCode, header},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    const CLIENT_ID: &str = "vibe-kanban";
+    const CLIENT_SECRET: &str = "s3cret";
+
+    #[derive(Clone)]
+    struct MockIssuer {
+        base_url: String,
+        userinfo: Value,
+    }
+
+    async fn discovery(State(issuer): State<MockIssuer>) -> Json<Value> {
+        Json(json!({
+            "issuer": issuer.base_url,
+            "authorization_endpoint": format!("{}/authorize", issuer.base_url),
+            "token_endpoint": format!("{}/token", issuer.base_url),
+            "userinfo_endpoint": format!("{}/userinfo", issuer.base_url),
+            "token_endpoint_auth_methods_supported": ["client_secret_basic"],
+        }))
+    }
+
+    async fn token(headers: HeaderMap, Form(form): Form<HashMap<String, String>>) -> Response {
+        let expected_auth = format!(
+            "Basic {}",
+            base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                format!("{CLIENT_ID}:{CLIENT_SECRET}")
+            )
+        );
+        if headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            != Some(expected_auth.as_str())
+        {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": "invalid_client" })),
+            )
+                .into_response();
+        }
+
+        let access_token = match (
+            form.get("grant_type").map(String::as_str),
+            form.get("code")
+                .or_else(|| form.get("refresh_token"))
+                .map(String::as_str),
+        ) {
+            (Some("authorization_code"), Some("good-code")) => "access-1",
+            (Some("refresh_token"), Some("refresh-1")) => "access-2",
+            _ => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "error": "invalid_grant" })),
+                )
+                    .into_response();
+            }
+        };
+
+        Json(json!({
+            "access_token": access_token,
+            "token_type": "Bearer",
+            "expires_in": 300,
+            "refresh_token": "refresh-1",
+            "scope": "openid email profile",
+        }))
+        .into_response()
+    }
+
+    async fn userinfo(State(issuer): State<MockIssuer>, headers: HeaderMap) -> Response {
+        match headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        {
+            Some("Bearer access-1" | "Bearer access-2") => Json(issuer.userinfo).into_response(),
+            _ => StatusCode::UNAUTHORIZED.into_response(),
+        }
+    }
+
+    async fn spawn_issuer(userinfo: Value) -> String {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/realms/team", listener.local_addr().unwrap());
+        let issuer = MockIssuer {
+            base_url: base_url.clone(),
+            userinfo,
+        };
+        let realm = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/token", post(token))
+            .route("/userinfo", get(userinfo))
+            .with_state(issuer);
+        let app = Router::new().nest("/realms/team", realm);
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base_url
+    }
+
+    async fn provider(base_url: &str, claims: OidcClaimMapping) -> OidcProvider {
+        OidcProvider::discover(
+            base_url,
+            CLIENT_ID.to_string(),
+            SecretString::new(CLIENT_SECRET.into()),
+            vec!["email".to_string(), "profile".to_string()],
+            claims,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn alice() -> Value {
+        json!({
+            "sub": "f3b1c2d4",
+            "preferred_username": "alice",
+            "email": "alice@example.com",
+            "email_verified": true,
+            "name": "Alice Liddell",
+            "picture": "https://example.com/alice.png",
+        })
+    }
+
+    #[test]
+    fn discovery_url_accepts_issuer_or_document() {
+        assert_eq!(
+            discovery_document_url("https://sso.example.com/realms/team/")
+                .unwrap()
+                .as_str(),
+            "https://sso.example.com/realms/team/.well-known/openid-configuration"
+        );
+        assert_eq!(
+            discovery_document_url(
+                "https://sso.example.com/realms/team/.well-known/openid-configuration"
+            )
+            .unwrap()
+            .as_str(),
+            "https://sso.example.com/realms/team/.well-known/openid-configuration"
+        );
+    }
+
+    #[tokio::test]
+    async fn authorize_url_uses_discovered_endpoint() {
+        let base_url = spawn_issuer(alice()).await;
+        let provider = provider(&base_url, OidcClaimMapping::default()).await;
+
+        let url = provider
+            .authorize_url(
+                "state-1",
+                "https://kanban.example.com/v1/oauth/oidc/callback",
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str().split('?').next(),
+            Some(format!("{base_url}/authorize").as_str())
+        );
+
+        let params: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["client_id"], CLIENT_ID);
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(params["scope"], "openid email profile");
+        assert_eq!(params["state"], "state-1");
+    }
+
+    #[tokio::test]
+    async fn exchanges_code_and_maps_default_claims() {
+        let base_url = spawn_issuer(alice()).await;
+        let provider = provider(&base_url, OidcClaimMapping::default()).await;
+
+        let grant = provider
+            .exchange_code("good-code", "https://kanban.example.com/callback")
+            .await
+            .unwrap();
+        assert_eq!(grant.access_token.expose_secret(), "access-1");
+        assert_eq!(grant.expires_in, Some(Duration::seconds(300)));
+
+        let user = provider.fetch_user(&grant.access_token).await.unwrap();
+        assert_eq!(user.id, "f3b1c2d4");
+        assert_eq!(user.login.as_deref(), Some("alice"));
+        assert_eq!(user.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(user.name.as_deref(), Some("Alice Liddell"));
+        assert_eq!(
+            user.avatar_url.as_deref(),
+            Some("https://example.com/alice.png")
+        );
+
+        assert!(provider.exchange_code("bad-code", "x").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn maps_custom_claims_and_drops_unverified_email() {
+        let base_url = spawn_issuer(json!({
+            "sub": "f3b1c2d4",
+            "email": "alice@example.com",
+            "email_verified": false,
+            "mail": "alice@corp.example.com",
+            "given_name": "Alice",
+            "family_name": "Liddell",
+            "avatar": "https://example.com/a.png",
+        }))
+        .await;
+
+        let default_user = provider(&base_url, OidcClaimMapping::default())
+            .await
+            .fetch_user(&SecretString::new("access-1".into()))
+            .await
+            .unwrap();
+        assert_eq!(default_user.email, None);
+        assert_eq!(default_user.name.as_deref(), Some("Alice Liddell"));
+
+        let claims = OidcClaimMapping {
+            email: "mail".to_string(),
+            name: "display_name".to_string(),
+            avatar_url: "avatar".to_string(),
+        };
+        let mapped_user = provider(&base_url, claims)
+            .await
+            .fetch_user(&SecretString::new("access-1".into()))
+            .await
+            .unwrap();
+        assert_eq!(mapped_user.email.as_deref(), Some("alice@corp.example.com"));
+        assert_eq!(mapped_user.name.as_deref(), Some("Alice Liddell"));
+        assert_eq!(
+            mapped_user.avatar_url.as_deref(),
+            Some("https://example.com/a.png")
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_token_refreshes_expired_tokens() {
+        let base_url = spawn_issuer(alice()).await;
+        let provider = provider(&base_url, OidcClaimMapping::default()).await;
+
+        let valid = ProviderTokenDetails {
+            provider: "oidc".to_string(),
+            access_token: "access-1".to_string(),
+            refresh_token: Some("refresh-1".to_string()),
+            expires_at: Some(chrono::Utc::now().timestamp() + 300),
+        };
+        assert!(provider.validate_token(&valid, 1).await.unwrap().is_none());
+
+        let expired = ProviderTokenDetails {
+            expires_at: Some(chrono::Utc::now().timestamp() - 10),
+            ..valid.clone()
+        };
+        let refreshed = provider.validate_token(&expired, 1).await.unwrap().unwrap();
+        assert_eq!(refreshed.access_token, "access-2");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("refresh-1"));
+
+        let revoked = ProviderTokenDetails {
+            access_token: "revoked".to_string(),
+            refresh_token: None,
+            expires_at: None,
+            ..valid
+        };
+        assert!(matches!(
+            provider.validate_token(&revoked, 1).await,
+            Err(TokenValidationError::InvalidOrRevoked)
+        ));
+    }
+}
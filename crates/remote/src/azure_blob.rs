@@ -18,6 +18,7 @@ use time::OffsetDateTime;
 use url::form_urlencoded;
 
 use crate::{
+    attachments::storage::{BlobProperties, PresignedUpload},
     config::{AzureAuthMode, AzureBlobConfig},
     shared_key_auth::SharedKeyAuthorizationPolicy,
 };
@@ -33,18 +34,6 @@ pub struct AzureBlobService {
     presign_expiry: Duration,
 }
 
-#[derive(Debug)]
-pub struct PresignedUpload {
-    pub upload_url: String,
-    pub blob_path: String,
-    pub expires_at: DateTime<Utc>,
-}
-
-#[derive(Debug)]
-pub struct BlobProperties {
-    pub content_length: i64,
-}
-
 #[derive(Debug, thiserror::Error)]
 pub enum AzureBlobError {
     #[error("azure storage error: {0}")]
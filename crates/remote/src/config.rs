@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, path::PathBuf};
 
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use secrecy::SecretString;
@@ -14,10 +14,11 @@ pub struct RemoteServerConfig {
     pub electric_secret: Option<SecretString>,
     pub electric_role_password: Option<SecretString>,
     pub r2: Option<R2Config>,
-    pub azure_blob: Option<AzureBlobConfig>,
+    pub attachment_storage: Option<AttachmentStorageConfig>,
     pub review_worker_base_url: Option<String>,
     pub review_disabled: bool,
     pub github_app: Option<GitHubAppConfig>,
+    pub mail: MailConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +68,131 @@ impl R2Config {
     }
 }
 
+/// Where issue and comment attachments are stored. The first configured backend wins, in the
+/// order Azure Blob, S3, local directory.
+#[derive(Debug, Clone)]
+pub enum AttachmentStorageConfig {
+    Azure(AzureBlobConfig),
+    S3(S3StorageConfig),
+    Local(LocalStorageConfig),
+}
+
+impl AttachmentStorageConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        if let Some(azure) = AzureBlobConfig::from_env()? {
+            return Ok(Some(Self::Azure(azure)));
+        }
+        if let Some(s3) = S3StorageConfig::from_env()? {
+            return Ok(Some(Self::S3(s3)));
+        }
+        Ok(LocalStorageConfig::from_env()?.map(Self::Local))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct S3StorageConfig {
+    pub bucket: String,
+    pub region: String,
+    /// Custom endpoint for S3 compatible services such as MinIO.
+    pub endpoint: Option<String>,
+    /// Endpoint browsers reach the bucket at, when it differs from `endpoint`.
+    pub public_endpoint: Option<String>,
+    pub access_key_id: String,
+    pub secret_access_key: SecretString,
+    pub force_path_style: bool,
+    pub presign_expiry_secs: u64,
+}
+
+impl S3StorageConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let bucket = match env::var("ATTACHMENTS_S3_BUCKET") {
+            Ok(v) if !v.is_empty() => v,
+            _ => {
+                tracing::info!("ATTACHMENTS_S3_BUCKET not set, S3 attachment storage disabled");
+                return Ok(None);
+            }
+        };
+
+        let access_key_id = env::var("ATTACHMENTS_S3_ACCESS_KEY_ID")
+            .map_err(|_| ConfigError::MissingVar("ATTACHMENTS_S3_ACCESS_KEY_ID"))?;
+
+        let secret_access_key = env::var("ATTACHMENTS_S3_SECRET_ACCESS_KEY")
+            .map_err(|_| ConfigError::MissingVar("ATTACHMENTS_S3_SECRET_ACCESS_KEY"))?;
+
+        let region = env::var("ATTACHMENTS_S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+
+        let endpoint = env::var("ATTACHMENTS_S3_ENDPOINT")
+            .ok()
+            .filter(|v| !v.is_empty());
+        let public_endpoint = env::var("ATTACHMENTS_S3_PUBLIC_ENDPOINT")
+            .ok()
+            .filter(|v| !v.is_empty());
+
+        // Self-hosted S3 services rarely have per-bucket DNS names
+        let force_path_style = match env::var("ATTACHMENTS_S3_FORCE_PATH_STYLE") {
+            Ok(v) => match v.to_ascii_lowercase().as_str() {
+                "1" | "true" => true,
+                "0" | "false" => false,
+                _ => return Err(ConfigError::InvalidVar("ATTACHMENTS_S3_FORCE_PATH_STYLE")),
+            },
+            Err(_) => endpoint.is_some(),
+        };
+
+        let presign_expiry_secs = env::var("ATTACHMENTS_S3_PRESIGN_EXPIRY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+
+        tracing::info!(
+            bucket = %bucket,
+            region = %region,
+            endpoint = ?endpoint,
+            "S3 attachment storage config loaded successfully"
+        );
+
+        Ok(Some(Self {
+            bucket,
+            region,
+            endpoint,
+            public_endpoint,
+            access_key_id,
+            secret_access_key: SecretString::new(secret_access_key.into()),
+            force_path_style,
+            presign_expiry_secs,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalStorageConfig {
+    pub root: PathBuf,
+    pub presign_expiry_secs: u64,
+}
+
+impl LocalStorageConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let root = match env::var("ATTACHMENTS_LOCAL_DIR") {
+            Ok(v) if !v.is_empty() => PathBuf::from(v),
+            _ => {
+                tracing::info!("ATTACHMENTS_LOCAL_DIR not set, local attachment storage disabled");
+                return Ok(None);
+            }
+        };
+
+        let presign_expiry_secs = env::var("ATTACHMENTS_LOCAL_PRESIGN_EXPIRY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+
+        tracing::info!(root = %root.display(), "Local attachment storage config loaded successfully");
+
+        Ok(Some(Self {
+            root,
+            presign_expiry_secs,
+        }))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum AzureAuthMode {
     /// Entra ID via user-assigned managed identity (production).
@@ -90,8 +216,8 @@ pub struct AzureBlobConfig {
 impl AzureBlobConfig {
     pub fn from_env() -> Result<Option<Self>, ConfigError> {
         let account_name = match env::var("AZURE_STORAGE_ACCOUNT_NAME") {
-            Ok(v) => v,
-            Err(_) => {
+            Ok(v) if !v.is_empty() => v,
+            _ => {
                 tracing::info!("AZURE_STORAGE_ACCOUNT_NAME not set, Azure Blob storage disabled");
                 return Ok(None);
             }
@@ -187,6 +313,109 @@ impl GitHubAppConfig {
     }
 }
 
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTlsMode {
+    /// Upgrade a plain connection with STARTTLS (port 587 by default)
+    StartTls,
+    /// Connect over TLS from the start (port 465 by default)
+    Implicit,
+    /// Plain text, for local relays only (port 25 by default)
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<SecretString>,
+    pub tls: SmtpTlsMode,
+    pub from: String,
+}
+
+impl SmtpConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let host = match env::var("SMTP_HOST") {
+            Ok(v) if !v.is_empty() => v,
+            _ => return Ok(None),
+        };
+
+        let port = match env::var("SMTP_PORT") {
+            Ok(v) if !v.is_empty() => Some(
+                v.parse()
+                    .map_err(|_| ConfigError::InvalidVar("SMTP_PORT"))?,
+            ),
+            _ => None,
+        };
+
+        let tls = match env::var("SMTP_TLS")
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" | "starttls" => SmtpTlsMode::StartTls,
+            "tls" | "implicit" => SmtpTlsMode::Implicit,
+            "none" => SmtpTlsMode::None,
+            _ => return Err(ConfigError::InvalidVar("SMTP_TLS")),
+        };
+
+        let username = env::var("SMTP_USERNAME").ok().filter(|v| !v.is_empty());
+        let password = match &username {
+            Some(_) => Some(SecretString::new(
+                env::var("SMTP_PASSWORD")
+                    .map_err(|_| ConfigError::MissingVar("SMTP_PASSWORD"))?
+                    .into(),
+            )),
+            None => None,
+        };
+
+        let from = env::var("SMTP_FROM").map_err(|_| ConfigError::MissingVar("SMTP_FROM"))?;
+
+        tracing::info!(host = %host, port = ?port, tls = ?tls, "SMTP config loaded successfully");
+
+        Ok(Some(Self {
+            host,
+            port,
+            username,
+            password,
+            tls,
+            from,
+        }))
+    }
+}
+
+/// Where invitation and review mails go
+#[derive(Debug, Clone)]
+pub enum MailConfig {
+    Smtp(SmtpConfig),
+    Loops {
+        api_key: SecretString,
+    },
+    /// Mails are only written to the log
+    Log,
+}
+
+impl MailConfig {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        if let Some(smtp) = SmtpConfig::from_env()? {
+            return Ok(Self::Smtp(smtp));
+        }
+
+        match env::var("LOOPS_EMAIL_API_KEY") {
+            Ok(api_key) if !api_key.is_empty() => Ok(Self::Loops {
+                api_key: SecretString::new(api_key.into()),
+            }),
+            _ => {
+                tracing::info!(
+                    "Neither SMTP_HOST nor LOOPS_EMAIL_API_KEY set, mails will only be logged"
+                );
+                Ok(Self::Log)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("environment variable `{0}` is not set")]
@@ -222,7 +451,7 @@ impl RemoteServerConfig {
             .map(|s| SecretString::new(s.into()));
 
         let r2 = R2Config::from_env()?;
-        let azure_blob = AzureBlobConfig::from_env()?;
+        let attachment_storage = AttachmentStorageConfig::from_env()?;
 
         let review_worker_base_url = env::var("REVIEW_WORKER_BASE_URL").ok();
 
@@ -232,6 +461,8 @@ impl RemoteServerConfig {
 
         let github_app = GitHubAppConfig::from_env()?;
 
+        let mail = MailConfig::from_env()?;
+
         Ok(Self {
             database_url,
             listen_addr,
@@ -241,10 +472,11 @@ impl RemoteServerConfig {
             electric_secret,
             electric_role_password,
             r2,
-            azure_blob,
+            attachment_storage,
             review_worker_base_url,
             review_disabled,
             github_app,
+            mail,
         })
     }
 }
@@ -272,10 +504,65 @@ impl OAuthProviderConfig {
     }
 }
 
+/// A generic OpenID Connect issuer such as Keycloak or Okta
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub discovery_url: String,
+    pub client_id: String,
+    pub client_secret: SecretString,
+    pub scopes: Vec<String>,
+    pub email_claim: String,
+    pub name_claim: String,
+    pub avatar_claim: String,
+}
+
+impl OidcConfig {
+    fn from_env() -> Result<Option<Self>, ConfigError> {
+        let discovery_url = match env::var("OIDC_DISCOVERY_URL") {
+            Ok(v) if !v.is_empty() => v,
+            _ => return Ok(None),
+        };
+
+        let client_id =
+            env::var("OIDC_CLIENT_ID").map_err(|_| ConfigError::MissingVar("OIDC_CLIENT_ID"))?;
+        let client_secret = env::var("OIDC_CLIENT_SECRET")
+            .map_err(|_| ConfigError::MissingVar("OIDC_CLIENT_SECRET"))?;
+
+        let scopes = env::var("OIDC_SCOPES")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "openid email profile".to_string())
+            .split([' ', ','])
+            .filter(|scope| !scope.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let claim = |var: &str, default: &str| {
+            env::var(var)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+
+        tracing::info!(discovery_url = %discovery_url, "OIDC login configured");
+
+        Ok(Some(Self {
+            discovery_url,
+            client_id,
+            client_secret: SecretString::new(client_secret.into()),
+            scopes,
+            email_claim: claim("OIDC_EMAIL_CLAIM", "email"),
+            name_claim: claim("OIDC_NAME_CLAIM", "name"),
+            avatar_claim: claim("OIDC_AVATAR_CLAIM", "picture"),
+        }))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthConfig {
     github: Option<OAuthProviderConfig>,
     google: Option<OAuthProviderConfig>,
+    oidc: Option<OidcConfig>,
     jwt_secret: SecretString,
     public_base_url: String,
 }
@@ -311,7 +598,9 @@ impl AuthConfig {
             _ => None,
         };
 
-        if github.is_none() && google.is_none() {
+        let oidc = OidcConfig::from_env()?;
+
+        if github.is_none() && google.is_none() && oidc.is_none() {
             return Err(ConfigError::NoOAuthProviders);
         }
 
@@ -321,6 +610,7 @@ impl AuthConfig {
         Ok(Self {
             github,
             google,
+            oidc,
             jwt_secret,
             public_base_url,
         })
@@ -334,6 +624,10 @@ impl AuthConfig {
         self.google.as_ref()
     }
 
+    pub fn oidc(&self) -> Option<&OidcConfig> {
+        self.oidc.as_ref()
+    }
+
     pub fn jwt_secret(&self) -> &SecretString {
         &self.jwt_secret
     }
@@ -1,9 +1,12 @@
+mod smtp;
+mod templates;
+
 use std::time::Duration;
 
+use api_types::MemberRole;
 use async_trait::async_trait;
 use serde_json::json;
-
-use api_types::MemberRole;
+pub use smtp::SmtpMailer;
 
 const LOOPS_INVITE_TEMPLATE_ID: &str = "cmhvy2wgs3s13z70i1pxakij9";
 const LOOPS_REVIEW_READY_TEMPLATE_ID: &str = "cmj47k5ge16990iylued9by17";
@@ -25,6 +28,46 @@ pub trait Mailer: Send + Sync {
     async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str);
 }
 
+/// Writes mails to the log instead of sending them, for development and deployments without a
+/// mail service
+pub struct LogMailer;
+
+impl LogMailer {
+    fn log(email: &str, content: templates::MailContent) {
+        tracing::info!(
+            "Mail to {email} (not sent, no mail service configured)\n\
+             Subject: {}\n\n{}",
+            content.subject,
+            content.text
+        );
+    }
+}
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send_org_invitation(
+        &self,
+        org_name: &str,
+        email: &str,
+        accept_url: &str,
+        role: MemberRole,
+        invited_by: Option<&str>,
+    ) {
+        Self::log(
+            email,
+            templates::org_invitation(org_name, accept_url, role, invited_by),
+        );
+    }
+
+    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str) {
+        Self::log(email, templates::review_ready(review_url, pr_name));
+    }
+
+    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str) {
+        Self::log(email, templates::review_failed(pr_name, review_id));
+    }
+}
+
 pub struct LoopsMailer {
     client: reqwest::Client,
     api_key: String,
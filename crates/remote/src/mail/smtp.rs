@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use api_types::MemberRole;
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+use secrecy::ExposeSecret;
+
+use super::{
+    Mailer,
+    templates::{self, MailContent},
+};
+use crate::config::{SmtpConfig, SmtpTlsMode};
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends mails through an SMTP relay, rendering them from the local templates
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig) -> anyhow::Result<Self> {
+        let from: Mailbox = config
+            .from
+            .parse()
+            .with_context(|| format!("invalid SMTP_FROM address `{}`", config.from))?;
+
+        let mut builder = match config.tls {
+            SmtpTlsMode::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpTlsMode::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+            SmtpTlsMode::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+        }
+        .timeout(Some(SMTP_TIMEOUT));
+
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                password.expose_secret().to_string(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+
+    async fn send(&self, email: &str, content: MailContent, kind: &str) {
+        let to: Mailbox = match email.parse() {
+            Ok(to) => to,
+            Err(err) => {
+                tracing::warn!(error = %err, kind, "Invalid recipient address, mail not sent");
+                return;
+            }
+        };
+
+        let message = match Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(content.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                content.text,
+                content.html,
+            )) {
+            Ok(message) => message,
+            Err(err) => {
+                tracing::error!(error = ?err, kind, "Failed to build mail");
+                return;
+            }
+        };
+
+        match self.transport.send(message).await {
+            Ok(_) => tracing::debug!(kind, "Mail sent via SMTP to {email}"),
+            Err(err) => tracing::error!(error = ?err, kind, "SMTP send failed"),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send_org_invitation(
+        &self,
+        org_name: &str,
+        email: &str,
+        accept_url: &str,
+        role: MemberRole,
+        invited_by: Option<&str>,
+    ) {
+        let content = templates::org_invitation(org_name, accept_url, role, invited_by);
+        self.send(email, content, "org_invitation").await;
+    }
+
+    async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str) {
+        let content = templates::review_ready(review_url, pr_name);
+        self.send(email, content, "review_ready").await;
+    }
+
+    async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str) {
+        let content = templates::review_failed(pr_name, review_id);
+        self.send(email, content, "review_failed").await;
+    }
+}
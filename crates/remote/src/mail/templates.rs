@@ -0,0 +1,151 @@
+//! Message bodies for mailers that render mails themselves rather than through a hosted
+//! template service.
+
+use api_types::MemberRole;
+
+pub struct MailContent {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub fn org_invitation(
+    org_name: &str,
+    accept_url: &str,
+    role: MemberRole,
+    invited_by: Option<&str>,
+) -> MailContent {
+    let role = match role {
+        MemberRole::Admin => "an admin",
+        MemberRole::Member => "a member",
+    };
+    let inviter = invited_by.unwrap_or("Someone");
+
+    MailContent {
+        subject: format!("You've been invited to {org_name} on Vibe Kanban"),
+        text: format!(
+            "{inviter} invited you to join {org_name} on Vibe Kanban as {role}.\n\n\
+             Accept the invitation:\n{accept_url}\n\n\
+             If you weren't expecting this invitation, you can ignore this email."
+        ),
+        html: layout(&format!(
+            "<p>{} invited you to join <strong>{}</strong> on Vibe Kanban as {role}.</p>\
+             {}\
+             <p style=\"color:#6b7280\">If you weren't expecting this invitation, you can ignore this email.</p>",
+            escape_html(inviter),
+            escape_html(org_name),
+            button(accept_url, "Accept invitation"),
+        )),
+    }
+}
+
+pub fn review_ready(review_url: &str, pr_name: &str) -> MailContent {
+    MailContent {
+        subject: format!("Your review of \"{pr_name}\" is ready"),
+        text: format!("The review of {pr_name} is ready.\n\nRead it here:\n{review_url}"),
+        html: layout(&format!(
+            "<p>The review of <strong>{}</strong> is ready.</p>{}",
+            escape_html(pr_name),
+            button(review_url, "View review"),
+        )),
+    }
+}
+
+pub fn review_failed(pr_name: &str, review_id: &str) -> MailContent {
+    MailContent {
+        subject: format!("Your review of \"{pr_name}\" failed"),
+        text: format!(
+            "We couldn't finish reviewing {pr_name}. Please try again.\n\n\
+             If it keeps failing, contact support with review ID {review_id}."
+        ),
+        html: layout(&format!(
+            "<p>We couldn't finish reviewing <strong>{}</strong>. Please try again.</p>\
+             <p style=\"color:#6b7280\">If it keeps failing, contact support with review ID <code>{}</code>.</p>",
+            escape_html(pr_name),
+            escape_html(review_id),
+        )),
+    }
+}
+
+fn layout(body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\
+         <html><body style=\"margin:0;padding:24px;background:#f9fafb;font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;color:#111827\">\
+         <div style=\"max-width:560px;margin:0 auto;padding:32px;background:#ffffff;border-radius:8px\">\
+         {body}\
+         </div></body></html>"
+    )
+}
+
+fn button(url: &str, label: &str) -> String {
+    format!(
+        "<p style=\"margin:24px 0\"><a href=\"{}\" style=\"display:inline-block;padding:10px 18px;background:#111827;color:#ffffff;text-decoration:none;border-radius:6px\">{}</a></p>\
+         <p style=\"color:#6b7280;font-size:13px\">Or open this link: {}</p>",
+        escape_html(url),
+        escape_html(label),
+        escape_html(url),
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invitation_mentions_org_role_and_link() {
+        let content = org_invitation(
+            "Acme",
+            "https://kanban.example.com/invitations/abc/accept",
+            MemberRole::Admin,
+            Some("alice"),
+        );
+
+        assert_eq!(
+            content.subject,
+            "You've been invited to Acme on Vibe Kanban"
+        );
+        assert!(content.text.contains("alice invited you to join Acme"));
+        assert!(content.text.contains("as an admin"));
+        assert!(
+            content
+                .html
+                .contains("href=\"https://kanban.example.com/invitations/abc/accept\"")
+        );
+    }
+
+    #[test]
+    fn html_bodies_escape_user_input() {
+        let content = review_ready(
+            "https://kanban.example.com/review/1?a=1&b=2",
+            "<script>alert(1)</script>",
+        );
+
+        assert!(!content.html.contains("<script>"));
+        assert!(content.html.contains("&lt;script&gt;"));
+        assert!(content.html.contains("?a=1&amp;b=2"));
+        // Plain text bodies are sent as is
+        assert!(content.text.contains("<script>alert(1)</script>"));
+    }
+
+    #[test]
+    fn review_failed_includes_review_id() {
+        let content = review_failed("Fix login", "3f2a");
+        assert!(content.subject.contains("Fix login"));
+        assert!(content.text.contains("review ID 3f2a"));
+    }
+}
@@ -1,7 +1,8 @@
 use axum::{
     Json, Router,
-    extract::{Extension, Path, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
@@ -17,10 +18,10 @@ use api_types::{AttachmentUrlResponse, AttachmentWithBlob, AttachmentWithUrl, Li
 use crate::{
     AppState,
     auth::RequestContext,
-    azure_blob::AzureBlobError,
     db::attachments::{AttachmentError, AttachmentRepository},
     db::blobs::{BlobError, BlobRepository},
     db::pending_uploads::{PendingUploadError, PendingUploadRepository},
+    attachments::storage::{AttachmentStorage, SignedOperation, StorageError},
     attachments::thumbnail::ThumbnailService,
 };
 
@@ -37,6 +38,17 @@ pub fn router() -> Router<AppState> {
         .route("/comments/{comment_id}/attachments/commit", post(commit_comment_attachments))
 }
 
+/// Signed upload and download URLs of the local attachment storage. They carry their own
+/// authorization, so they don't need a session.
+pub fn public_router() -> Router<AppState> {
+    Router::new().route(
+        "/attachment-storage/{*path}",
+        get(read_local_blob)
+            .put(write_local_blob)
+            .layer(DefaultBodyLimit::max(MAX_FILE_SIZE as usize)),
+    )
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct InitUploadRequest {
@@ -90,10 +102,12 @@ pub struct CommitAttachmentsResponse {
 
 #[derive(Debug, thiserror::Error)]
 pub enum RouteError {
-    #[error("Azure Blob storage not configured")]
+    #[error("attachment storage not configured")]
     NotConfigured,
-    #[error("Azure Blob error: {0}")]
-    AzureBlob(#[from] AzureBlobError),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("invalid or expired signature")]
+    InvalidSignature,
     #[error("attachment error: {0}")]
     Attachment(#[from] AttachmentError),
     #[error("blob error: {0}")]
@@ -118,10 +132,13 @@ impl IntoResponse for RouteError {
     fn into_response(self) -> Response {
         let (status, message) = match &self {
             RouteError::NotConfigured => (StatusCode::SERVICE_UNAVAILABLE, "Attachment storage not available"),
-            RouteError::AzureBlob(e) => {
-                tracing::error!(error = %e, "Azure Blob error");
+            RouteError::Storage(StorageError::NotFound(_)) => (StatusCode::NOT_FOUND, "Blob not found"),
+            RouteError::Storage(StorageError::InvalidPath(_)) => (StatusCode::BAD_REQUEST, "Invalid blob path"),
+            RouteError::Storage(e) => {
+                tracing::error!(error = %e, "Storage error");
                 (StatusCode::INTERNAL_SERVER_ERROR, "Storage error")
             }
+            RouteError::InvalidSignature => (StatusCode::FORBIDDEN, "Invalid or expired signature"),
             RouteError::Attachment(e) => {
                 tracing::error!(error = %e, "Attachment error");
                 (StatusCode::INTERNAL_SERVER_ERROR, "Database error")
@@ -167,8 +184,8 @@ async fn init_upload(
     }
 
     if let Some(existing) = BlobRepository::find_by_hash(state.pool(), payload.project_id, &payload.hash).await? {
-        let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
-        let read_url = azure.create_read_url(&existing.blob_path)?;
+        let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
+        let read_url = storage.create_read_url(&existing.blob_path).await?;
 
         return Ok(Json(InitUploadResponse {
             upload_url: read_url,
@@ -179,10 +196,10 @@ async fn init_upload(
         }));
     }
 
-    let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
+    let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
     let sanitized_filename = sanitize_filename(&payload.filename);
     let blob_path = format!("attachments/{}/{}_{}", payload.project_id, Uuid::new_v4(), sanitized_filename);
-    let upload = azure.create_upload_url(&blob_path)?;
+    let upload = storage.create_upload_url(&blob_path).await?;
 
     let pending = PendingUploadRepository::create(
         state.pool(),
@@ -223,7 +240,7 @@ async fn confirm_upload(
             .map_err(|_| RouteError::AccessDenied)?;
     }
 
-    let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
+    let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
 
     let blob = if let Some(existing) = BlobRepository::find_by_hash(state.pool(), payload.project_id, &payload.hash).await? {
         existing
@@ -234,13 +251,13 @@ async fn confirm_upload(
 
         let blob_path = &pending.blob_path;
 
-        let props = azure.get_blob_properties(blob_path).await?;
+        let props = storage.get_blob_properties(blob_path).await?;
         if props.content_length > MAX_FILE_SIZE {
-            let _ = azure.delete_blob(blob_path).await;
+            let _ = storage.delete_blob(blob_path).await;
             return Err(RouteError::FileTooLarge);
         }
 
-        let blob_data = azure.download_blob(blob_path).await?;
+        let blob_data = storage.download_blob(blob_path).await?;
         let thumbnail_result = ThumbnailService::generate(&blob_data, payload.content_type.as_deref())
             .map_err(|e| RouteError::ThumbnailError(e.to_string()))?;
 
@@ -249,7 +266,7 @@ async fn confirm_upload(
         let (thumbnail_blob_path, width, height) = match thumbnail_result {
             Some(thumb) => {
                 let thumb_path = format!("thumbnails/{}", blob_path);
-                azure.upload_blob(&thumb_path, thumb.bytes, thumb.mime_type).await?;
+                storage.upload_blob(&thumb_path, thumb.bytes, thumb.mime_type).await?;
                 (Some(thumb_path), Some(thumb.original_width as i32), Some(thumb.original_height as i32))
             }
             None => (None, None, None),
@@ -334,15 +351,8 @@ async fn list_issue_attachments(
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
-    let azure = state.azure_blob();
-    let attachments = AttachmentRepository::find_by_issue_id(state.pool(), issue_id)
-        .await?
-        .into_iter()
-        .map(|a| {
-            let file_url = azure.and_then(|az| az.create_read_url(&a.blob_path).ok());
-            AttachmentWithUrl { attachment: a, file_url }
-        })
-        .collect();
+    let attachments = AttachmentRepository::find_by_issue_id(state.pool(), issue_id).await?;
+    let attachments = with_read_urls(state.attachment_storage(), attachments).await;
     Ok(Json(ListAttachmentsResponse { attachments }))
 }
 
@@ -356,15 +366,8 @@ async fn list_comment_attachments(
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
-    let azure = state.azure_blob();
-    let attachments = AttachmentRepository::find_by_comment_id(state.pool(), comment_id)
-        .await?
-        .into_iter()
-        .map(|a| {
-            let file_url = azure.and_then(|az| az.create_read_url(&a.blob_path).ok());
-            AttachmentWithUrl { attachment: a, file_url }
-        })
-        .collect();
+    let attachments = AttachmentRepository::find_by_comment_id(state.pool(), comment_id).await?;
+    let attachments = with_read_urls(state.attachment_storage(), attachments).await;
     Ok(Json(ListAttachmentsResponse { attachments }))
 }
 
@@ -380,8 +383,8 @@ async fn get_attachment_file(
 
     ensure_attachment_access(&state, ctx.user.id, &attachment).await?;
 
-    let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
-    let url = azure.create_read_url(&attachment.blob_path)?;
+    let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
+    let url = storage.create_read_url(&attachment.blob_path).await?;
     Ok(Json(AttachmentUrlResponse { url }))
 }
 
@@ -398,8 +401,8 @@ async fn get_attachment_thumbnail(
     ensure_attachment_access(&state, ctx.user.id, &attachment).await?;
 
     let thumbnail_path = attachment.thumbnail_blob_path.ok_or(RouteError::NoThumbnail)?;
-    let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
-    let url = azure.create_read_url(&thumbnail_path)?;
+    let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
+    let url = storage.create_read_url(&thumbnail_path).await?;
     Ok(Json(AttachmentUrlResponse { url }))
 }
 
@@ -421,12 +424,12 @@ async fn delete_attachment(
     let remaining = AttachmentRepository::count_by_blob_id(state.pool(), blob_id).await?;
     if remaining == 0 {
         if let Some(blob) = BlobRepository::delete(state.pool(), blob_id).await? {
-            let azure = state.azure_blob().ok_or(RouteError::NotConfigured)?;
-            if let Err(e) = azure.delete_blob(&blob.blob_path).await {
+            let storage = state.attachment_storage().ok_or(RouteError::NotConfigured)?;
+            if let Err(e) = storage.delete_blob(&blob.blob_path).await {
                 tracing::warn!(error = %e, blob_path = %blob.blob_path, "Failed to delete blob");
             }
             if let Some(thumb_path) = blob.thumbnail_blob_path {
-                if let Err(e) = azure.delete_blob(&thumb_path).await {
+                if let Err(e) = storage.delete_blob(&thumb_path).await {
                     tracing::warn!(error = %e, blob_path = %thumb_path, "Failed to delete thumbnail");
                 }
             }
@@ -455,6 +458,72 @@ async fn ensure_attachment_access(state: &AppState, user_id: Uuid, attachment: &
     Ok(())
 }
 
+async fn with_read_urls(storage: Option<&dyn AttachmentStorage>, attachments: Vec<AttachmentWithBlob>) -> Vec<AttachmentWithUrl> {
+    let mut with_urls = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        let file_url = match storage {
+            Some(storage) => storage.create_read_url(&attachment.blob_path).await.ok(),
+            None => None,
+        };
+        with_urls.push(AttachmentWithUrl { attachment, file_url });
+    }
+    with_urls
+}
+
+#[derive(Debug, Deserialize)]
+struct SignedUrlQuery {
+    expires: i64,
+    sig: String,
+}
+
+#[instrument(name = "attachments.local_storage.write", skip(state, query, headers, body), fields(blob_path = %path))]
+async fn write_local_blob(
+    State(state): State<AppState>,
+    Path(path): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, RouteError> {
+    let storage = state.local_attachment_storage().ok_or(RouteError::NotConfigured)?;
+    if !storage.verify(SignedOperation::Write, &path, query.expires, &query.sig) {
+        return Err(RouteError::InvalidSignature);
+    }
+
+    let content_type = headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok());
+    storage.write(&path, &body, content_type).await?;
+    Ok(StatusCode::CREATED)
+}
+
+#[instrument(name = "attachments.local_storage.read", skip(state, query), fields(blob_path = %path))]
+async fn read_local_blob(
+    State(state): State<AppState>,
+    Path(path): Path<String>,
+    Query(query): Query<SignedUrlQuery>,
+) -> Result<Response, RouteError> {
+    let storage = state.local_attachment_storage().ok_or(RouteError::NotConfigured)?;
+    if !storage.verify(SignedOperation::Read, &path, query.expires, &query.sig) {
+        return Err(RouteError::InvalidSignature);
+    }
+
+    let blob = storage.read(&path).await?;
+    let (content_type, disposition) = match blob.inline_content_type() {
+        Some(content_type) => (content_type.to_string(), "inline"),
+        None => ("application/octet-stream".to_string(), "attachment"),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, disposition.to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CONTENT_SECURITY_POLICY, "sandbox".to_string()),
+            (header::CACHE_CONTROL, "private, max-age=300".to_string()),
+        ],
+        blob.data,
+    )
+        .into_response())
+}
+
 fn sanitize_filename(filename: &str) -> String {
     filename
         .chars()
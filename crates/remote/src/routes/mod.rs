@@ -107,6 +107,7 @@ pub fn router(state: AppState) -> Router {
         .merge(tokens::public_router())
         .merge(review::public_router())
         .merge(github_app::public_router())
+        .merge(attachments::public_router())
         .merge(billing::public_router());
 
     let v1_protected = Router::<AppState>::new()
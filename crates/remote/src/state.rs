@@ -4,8 +4,8 @@ use sqlx::PgPool;
 
 use crate::{
     analytics::AnalyticsService,
+    attachments::storage::{AttachmentStorage, LocalStorage},
     auth::{JwtService, OAuthHandoffService, OAuthTokenValidator, ProviderRegistry},
-    azure_blob::AzureBlobService,
     billing::BillingService,
     config::RemoteServerConfig,
     github_app::GitHubAppService,
@@ -24,7 +24,9 @@ pub struct AppState {
     handoff: Arc<OAuthHandoffService>,
     oauth_token_validator: Arc<OAuthTokenValidator>,
     r2: Option<R2Service>,
-    azure_blob: Option<AzureBlobService>,
+    attachment_storage: Option<Arc<dyn AttachmentStorage>>,
+    /// Set when attachments live on local disk, to serve its signed URLs
+    local_attachment_storage: Option<Arc<LocalStorage>>,
     github_app: Option<Arc<GitHubAppService>>,
     billing: BillingService,
     analytics: Option<AnalyticsService>,
@@ -42,7 +44,8 @@ impl AppState {
         server_public_base_url: String,
         http_client: reqwest::Client,
         r2: Option<R2Service>,
-        azure_blob: Option<AzureBlobService>,
+        attachment_storage: Option<Arc<dyn AttachmentStorage>>,
+        local_attachment_storage: Option<Arc<LocalStorage>>,
         github_app: Option<Arc<GitHubAppService>>,
         billing: BillingService,
         analytics: Option<AnalyticsService>,
@@ -57,7 +60,8 @@ impl AppState {
             handoff,
             oauth_token_validator,
             r2,
-            azure_blob,
+            attachment_storage,
+            local_attachment_storage,
             github_app,
             billing,
             analytics,
@@ -92,8 +96,12 @@ impl AppState {
         self.r2.as_ref()
     }
 
-    pub fn azure_blob(&self) -> Option<&AzureBlobService> {
-        self.azure_blob.as_ref()
+    pub fn attachment_storage(&self) -> Option<&dyn AttachmentStorage> {
+        self.attachment_storage.as_deref()
+    }
+
+    pub fn local_attachment_storage(&self) -> Option<&LocalStorage> {
+        self.local_attachment_storage.as_deref()
     }
 
     pub fn github_app(&self) -> Option<&GitHubAppService> {
@@ -32,6 +32,9 @@ fn generate_types_content() -> String {
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_dependency::TaskDependency::decl(),
+        db::models::task_dependency::CreateTaskDependency::decl(),
+        db::models::task_dependency::TaskDependencies::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::DraftWorkspaceData::decl(),
         db::models::scratch::DraftWorkspaceLinkedIssue::decl(),
@@ -54,12 +57,16 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process::CheckStatus::decl(),
+        db::models::execution_process_logs::ExecutionProcessLogStats::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
+        db::models::merge_queue::MergeQueueStatus::decl(),
+        db::models::merge_queue::MergeQueueEntry::decl(),
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
@@ -98,6 +105,8 @@ fn generate_types_content() -> String {
         services::services::migration::EntityError::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
+        server::routes::repo::TrustRepoConfigRequest::decl(),
+        server::routes::merge_queue::EnqueueMergeRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
@@ -117,8 +126,12 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::PushTaskAttemptRequest::decl(),
         server::routes::task_attempts::RenameBranchRequest::decl(),
         server::routes::task_attempts::RenameBranchResponse::decl(),
+        server::routes::sessions::queue::ReorderQueueRequest::decl(),
         server::routes::sessions::review::StartReviewRequest::decl(),
         server::routes::sessions::review::ReviewError::decl(),
+        server::routes::token_usage::TokenUsageQuery::decl(),
+        server::routes::api_tokens::CreateApiTokenRequest::decl(),
+        server::routes::api_tokens::CreateApiTokenResponse::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::tasks::LinkedIssueInfo::decl(),
@@ -134,6 +147,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         server::routes::task_attempts::ContinueRebaseRequest::decl(),
         server::routes::task_attempts::AbortConflictsRequest::decl(),
+        server::routes::task_attempts::conflicts::ResolveConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::PushError::decl(),
         server::routes::task_attempts::pr::PrError::decl(),
@@ -157,9 +171,15 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
+        server::routes::task_attempts::fan_out::FanOutAttempt::decl(),
+        server::routes::task_attempts::fan_out::FanOutComparison::decl(),
+        server::routes::terminal::CreateTerminalSession::decl(),
+        server::routes::terminal::TerminalSession::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
+        services::services::repo_config::UnapprovedScript::decl(),
+        services::services::repo_config::EffectiveRepoConfig::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
         services::services::config::WebhookConfig::decl(),
@@ -173,10 +193,25 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::SendMessageShortcut::decl(),
+        services::services::config::ConcurrencyConfig::decl(),
+        services::services::config::ModelPrice::decl(),
+        services::services::config::SandboxConfig::decl(),
+        services::services::config::ChecksConfig::decl(),
+        services::services::config::ApiAuthConfig::decl(),
+        services::services::config::ApiToken::decl(),
+        services::services::config::ApiTokenScope::decl(),
+        services::services::config::SandboxNetwork::decl(),
         git::GitBranch::decl(),
-        services::services::queued_message::QueuedMessage::decl(),
+        db::models::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
+        services::services::merge_queue::MergeQueueItem::decl(),
+        services::services::transcript::TranscriptFormat::decl(),
+        services::services::token_usage::TokenUsageSummary::decl(),
+        services::services::token_usage::ModelTokenUsage::decl(),
+        services::services::token_usage::ExecutorTokenUsage::decl(),
+        services::services::token_usage::TokenUsageReport::decl(),
         git::ConflictOp::decl(),
+        git::MergeStrategy::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
@@ -208,6 +243,8 @@ fn generate_types_content() -> String {
         executors::executors::droid::Droid::decl(),
         executors::executors::droid::Autonomy::decl(),
         executors::executors::droid::ReasoningEffortLevel::decl(),
+        executors::executors::custom_acp::CustomAcp::decl(),
+        executors::executors::aider::Aider::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
@@ -310,6 +347,14 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "droid",
             generate_json_schema::<executors::executors::droid::Droid>()?,
         ),
+        (
+            "custom_acp",
+            generate_json_schema::<executors::executors::custom_acp::CustomAcp>()?,
+        ),
+        (
+            "aider",
+            generate_json_schema::<executors::executors::aider::Aider>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
@@ -0,0 +1,267 @@
+use anyhow::{Context, bail};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::{
+    client::IntoClientRequest, handshake::client::Request, http::header,
+};
+use utils::port_file::read_port_file;
+
+#[derive(Deserialize)]
+struct ApiResponseEnvelope {
+    success: bool,
+    data: Option<Value>,
+    error_data: Option<Value>,
+    message: Option<String>,
+}
+
+/// Talks to the HTTP API of a running Vibe Kanban server
+pub struct VkClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl VkClient {
+    /// Connect to `backend_url`, or to the locally running server found through its port file
+    pub async fn connect(
+        backend_url: Option<String>,
+        token: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let base_url = match backend_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => local_backend_url().await?,
+        };
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url,
+            token: token.filter(|token| !token.trim().is_empty()),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token.trim()),
+            None => request,
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        self.send(self.http.get(self.url(path))).await
+    }
+
+    pub async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> anyhow::Result<T> {
+        self.send(self.http.post(self.url(path)).json(body)).await
+    }
+
+    /// GET an endpoint that answers with a file rather than an `ApiResponse`
+    pub async fn get_raw(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        let request = self.authorized(self.http.get(self.url(path))).build()?;
+        let url = request.url().clone();
+        let response = self
+            .http
+            .execute(request)
+            .await
+            .with_context(|| format!("Failed to reach {}", url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await?;
+            parse_response::<()>(url.path(), status, &body)?;
+            bail!("{} returned {}", url.path(), status);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> anyhow::Result<T> {
+        let request = self.authorized(request).build()?;
+        let url = request.url().clone();
+        let response = self
+            .http
+            .execute(request)
+            .await
+            .with_context(|| format!("Failed to reach {}", url))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        parse_response(url.path(), status, &body)
+    }
+
+    /// Websocket handshake request for `path`, carrying the API token if one is set
+    pub fn websocket_request(&self, path: &str) -> anyhow::Result<Request> {
+        let url = self.url(path);
+        let url = match url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{rest}"),
+            Some((_, rest)) => format!("ws://{rest}"),
+            None => url,
+        };
+        let mut request = url.into_client_request()?;
+        if let Some(token) = &self.token {
+            request.headers_mut().insert(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token.trim()).parse()?,
+            );
+        }
+        Ok(request)
+    }
+}
+
+/// Unwrap the `ApiResponse` envelope the server answered a request for `path` with
+fn parse_response<T: DeserializeOwned>(
+    path: &str,
+    status: StatusCode,
+    body: &str,
+) -> anyhow::Result<T> {
+    let envelope = match serde_json::from_str::<ApiResponseEnvelope>(body) {
+        Ok(envelope) => envelope,
+        Err(_) if !status.is_success() => bail!("{} returned {}: {}", path, status, body),
+        Err(e) => bail!("Unexpected response from {}: {}", path, e),
+    };
+
+    if !envelope.success {
+        if let Some(message) = envelope.message {
+            bail!("{}", message);
+        }
+        match envelope.error_data {
+            Some(error) => bail!("{}", error),
+            None => bail!("{} returned {}", path, status),
+        }
+    }
+
+    // Endpoints returning `()` send `null` data
+    serde_json::from_value(envelope.data.unwrap_or(Value::Null))
+        .with_context(|| format!("Unexpected response from {}", path))
+}
+
+async fn local_backend_url() -> anyhow::Result<String> {
+    let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let port = match std::env::var("BACKEND_PORT").or_else(|_| std::env::var("PORT")) {
+        Ok(port) => port
+            .parse::<u16>()
+            .map_err(|e| anyhow::anyhow!("Invalid port value '{}': {}", port, e))?,
+        Err(_) => read_port_file("vibe-kanban")
+            .await
+            .context("No running Vibe Kanban server found; pass --backend-url")?,
+    };
+    // The server serves HTTPS when it was given a certificate
+    let scheme = if std::env::var_os("TLS_CERT_PATH").is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    Ok(format!("{}://{}:{}", scheme, host, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(base_url: &str, token: Option<&str>) -> VkClient {
+        VkClient {
+            http: reqwest::Client::new(),
+            base_url: base_url.to_string(),
+            token: token.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn successful_responses_unwrap_their_data() {
+        let names: Vec<String> = parse_response(
+            "/api/projects",
+            StatusCode::OK,
+            r#"{"success":true,"data":["a","b"],"error_data":null,"message":null}"#,
+        )
+        .unwrap();
+        assert_eq!(names, vec!["a", "b"]);
+
+        let _: () = parse_response(
+            "/api/task-attempts/1/push",
+            StatusCode::OK,
+            r#"{"success":true,"data":null,"error_data":null,"message":null}"#,
+        )
+        .unwrap();
+
+        let err = parse_response::<Vec<String>>(
+            "/api/projects",
+            StatusCode::OK,
+            r#"{"success":true,"data":{"id":1}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected response from /api/projects");
+    }
+
+    #[test]
+    fn failed_responses_become_errors() {
+        let err = parse_response::<()>(
+            "/api/tasks",
+            StatusCode::NOT_FOUND,
+            r#"{"success":false,"data":null,"error_data":null,"message":"Task not found"}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Task not found");
+
+        let err = parse_response::<()>(
+            "/api/task-attempts/1/merge",
+            StatusCode::CONFLICT,
+            r#"{"success":false,"data":null,"error_data":{"type":"merge_conflicts"}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), r#"{"type":"merge_conflicts"}"#);
+
+        let err = parse_response::<()>(
+            "/api/tasks",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{"success":false}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "/api/tasks returned 500 Internal Server Error"
+        );
+    }
+
+    #[test]
+    fn non_envelope_bodies_are_reported() {
+        let err = parse_response::<()>("/api/tasks", StatusCode::FORBIDDEN, "").unwrap_err();
+        assert_eq!(err.to_string(), "/api/tasks returned 403 Forbidden: ");
+
+        let err = parse_response::<()>("/api/tasks", StatusCode::OK, "<html></html>").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unexpected response from /api/tasks:")
+        );
+    }
+
+    #[test]
+    fn urls_join_paths_and_websockets_carry_the_token() {
+        let http = client("http://127.0.0.1:3000", None);
+        assert_eq!(
+            http.url("/api/projects"),
+            "http://127.0.0.1:3000/api/projects"
+        );
+        assert_eq!(
+            http.url("api/projects"),
+            "http://127.0.0.1:3000/api/projects"
+        );
+
+        let request = http.websocket_request("/api/events").unwrap();
+        assert_eq!(request.uri().to_string(), "ws://127.0.0.1:3000/api/events");
+        assert!(request.headers().get(header::AUTHORIZATION).is_none());
+
+        let https = client("https://vk.example.com", Some(" vk_token "));
+        let request = https.websocket_request("/api/events").unwrap();
+        assert_eq!(request.uri().to_string(), "wss://vk.example.com/api/events");
+        assert_eq!(
+            request.headers().get(header::AUTHORIZATION).unwrap(),
+            "Bearer vk_token"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::Context;
+use executors::logs::{NormalizedEntry, NormalizedEntryType, ToolStatus};
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use uuid::Uuid;
+
+use crate::client::VkClient;
+
+/// Prints conversation entries as they settle. An entry is printed once a later entry exists
+/// or the stream ends, since agents keep replacing the latest entry while they stream it.
+struct EntryPrinter {
+    process_id: Uuid,
+    json: bool,
+    entries: BTreeMap<usize, NormalizedEntry>,
+    next_to_print: usize,
+    announced_approvals: HashSet<String>,
+}
+
+impl EntryPrinter {
+    fn new(process_id: Uuid, json: bool) -> Self {
+        Self {
+            process_id,
+            json,
+            entries: BTreeMap::new(),
+            next_to_print: 0,
+            announced_approvals: HashSet::new(),
+        }
+    }
+
+    /// Apply the `add`/`replace` operations of a conversation patch
+    fn apply(&mut self, patch: &Value) {
+        for op in patch.as_array().into_iter().flatten() {
+            let Some(index) = op
+                .get("path")
+                .and_then(Value::as_str)
+                .and_then(|path| path.strip_prefix("/entries/"))
+                .and_then(|index| index.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let Some(value) = op.get("value") else {
+                continue;
+            };
+            if value.get("type").and_then(Value::as_str) != Some("NORMALIZED_ENTRY") {
+                continue;
+            }
+            let Some(entry) = value.get("content").and_then(|content| {
+                serde_json::from_value::<NormalizedEntry>(content.clone()).ok()
+            }) else {
+                continue;
+            };
+
+            self.announce_pending_approval(&entry);
+            self.entries.insert(index, entry);
+        }
+
+        let settled_until = self.entries.keys().next_back().copied().unwrap_or(0);
+        self.print_until(settled_until);
+    }
+
+    /// Approvals block the agent, so they are shown right away rather than once settled
+    fn announce_pending_approval(&mut self, entry: &NormalizedEntry) {
+        let NormalizedEntryType::ToolUse {
+            tool_name,
+            status: ToolStatus::PendingApproval { approval_id, .. },
+            ..
+        } = &entry.entry_type
+        else {
+            return;
+        };
+        if self.json || !self.announced_approvals.insert(approval_id.clone()) {
+            return;
+        }
+        println!("? {tool_name} needs approval: {}", entry.content);
+        println!(
+            "  vk approve {approval_id} --process {}   (add --deny to refuse)",
+            self.process_id
+        );
+    }
+
+    fn print_until(&mut self, end: usize) {
+        let pending: Vec<usize> = self
+            .entries
+            .range(self.next_to_print..end)
+            .map(|(index, _)| *index)
+            .collect();
+        for index in pending {
+            if let Some(entry) = self.entries.get(&index) {
+                self.print(entry);
+            }
+        }
+        self.next_to_print = self.next_to_print.max(end);
+    }
+
+    fn finish(&mut self) {
+        self.print_until(usize::MAX);
+    }
+
+    fn print(&self, entry: &NormalizedEntry) {
+        if self.json {
+            if let Ok(line) = serde_json::to_string(entry) {
+                println!("{line}");
+            }
+            return;
+        }
+        if let Some(line) = render_entry(entry) {
+            println!("{line}");
+        }
+    }
+}
+
+fn render_entry(entry: &NormalizedEntry) -> Option<String> {
+    let content = entry.content.trim_end();
+    let line = match &entry.entry_type {
+        NormalizedEntryType::UserMessage => format!("> {content}"),
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            format!("> (denied {denied_tool}) {content}")
+        }
+        NormalizedEntryType::AssistantMessage => content.to_string(),
+        NormalizedEntryType::ToolUse {
+            tool_name, status, ..
+        } => {
+            let status = match status {
+                ToolStatus::Created => "",
+                ToolStatus::Success | ToolStatus::AutoApproved { .. } => " ✓",
+                ToolStatus::Failed => " ✗ failed",
+                ToolStatus::Denied { .. } => " ✗ denied",
+                ToolStatus::PendingApproval { .. } => " (waiting for approval)",
+                ToolStatus::TimedOut => " ✗ timed out",
+            };
+            format!("[{tool_name}] {content}{status}")
+        }
+        NormalizedEntryType::SystemMessage => format!("# {content}"),
+        NormalizedEntryType::ErrorMessage { .. } => format!("error: {content}"),
+        NormalizedEntryType::Thinking
+        | NormalizedEntryType::Loading
+        | NormalizedEntryType::NextAction { .. }
+        | NormalizedEntryType::TokenUsageInfo(_) => return None,
+    };
+    Some(line)
+}
+
+/// Print the normalized conversation of an execution process, following it until it finishes
+pub async fn follow_process(client: &VkClient, process_id: Uuid, json: bool) -> anyhow::Result<()> {
+    let request = client.websocket_request(&format!(
+        "/api/execution-processes/{process_id}/normalized-logs/ws"
+    ))?;
+    let (mut socket, _) = connect_async(request)
+        .await
+        .context("Failed to open the log stream")?;
+
+    let mut printer = EntryPrinter::new(process_id, json);
+    while let Some(message) = socket.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let Ok(value) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        if value.get("finished").is_some() {
+            break;
+        }
+        if let Some(patch) = value.get("JsonPatch") {
+            printer.apply(patch);
+        }
+    }
+    printer.finish();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::ActionType;
+    use serde_json::json;
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn tool(status: ToolStatus) -> NormalizedEntry {
+        entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: "bash".to_string(),
+                action_type: ActionType::Other {
+                    description: "cargo test".to_string(),
+                },
+                status,
+            },
+            "cargo test",
+        )
+    }
+
+    fn patch(op: &str, index: usize, entry: &NormalizedEntry) -> Value {
+        json!([{
+            "op": op,
+            "path": format!("/entries/{index}"),
+            "value": { "type": "NORMALIZED_ENTRY", "content": entry },
+        }])
+    }
+
+    #[test]
+    fn entries_render_as_lines() {
+        assert_eq!(
+            render_entry(&entry(NormalizedEntryType::UserMessage, "Fix it\n")).as_deref(),
+            Some("> Fix it")
+        );
+        assert_eq!(
+            render_entry(&tool(ToolStatus::Success)).as_deref(),
+            Some("[bash] cargo test ✓")
+        );
+        assert_eq!(
+            render_entry(&tool(ToolStatus::Failed)).as_deref(),
+            Some("[bash] cargo test ✗ failed")
+        );
+        assert_eq!(
+            render_entry(&entry(NormalizedEntryType::Thinking, "hmm")),
+            None
+        );
+    }
+
+    #[test]
+    fn entries_are_printed_once_settled() {
+        let mut printer = EntryPrinter::new(Uuid::new_v4(), true);
+        let message = entry(NormalizedEntryType::AssistantMessage, "Looking");
+
+        printer.apply(&patch("add", 0, &message));
+        assert_eq!(printer.next_to_print, 0);
+
+        // The latest entry is still being streamed
+        printer.apply(&patch("add", 1, &tool(ToolStatus::Created)));
+        assert_eq!(printer.next_to_print, 1);
+        printer.apply(&patch("replace", 1, &tool(ToolStatus::Success)));
+        assert_eq!(printer.next_to_print, 1);
+        assert!(matches!(
+            printer.entries[&1].entry_type,
+            NormalizedEntryType::ToolUse {
+                status: ToolStatus::Success,
+                ..
+            }
+        ));
+
+        // Anything that is not a conversation entry is skipped
+        printer.apply(&json!([
+            { "op": "add", "path": "/entries/2", "value": { "type": "STDOUT", "content": "x" } },
+            { "op": "replace", "path": "/finished", "value": true },
+        ]));
+        assert_eq!(printer.entries.len(), 2);
+
+        printer.finish();
+        assert_eq!(printer.next_to_print, usize::MAX);
+    }
+}
@@ -0,0 +1,868 @@
+mod client;
+mod logs;
+
+use std::{io::Write, path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::{Context, bail};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    project::Project,
+    repo::Repo,
+    session::Session,
+    task::{CreateTask, Task, TaskWithAttemptStatus},
+    workspace::Workspace,
+    workspace_repo::RepoWithTargetBranch,
+};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use server::routes::{
+    sessions::CreateFollowUpAttempt,
+    task_attempts::{
+        CreateTaskAttemptBody, MergeTaskAttemptRequest, PushTaskAttemptRequest, WorkspaceRepoInput,
+        pr::CreatePrApiRequest,
+    },
+    tasks::CreateAndStartTaskRequest,
+};
+use utils::approvals::{ApprovalResponse, ApprovalStatus};
+use uuid::Uuid;
+
+use crate::client::VkClient;
+
+/// How long `--follow` waits for the coding agent process of a new attempt to appear
+const PROCESS_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Script a running Vibe Kanban server from the terminal
+#[derive(Parser, Debug)]
+#[command(name = "vk", version)]
+struct Cli {
+    /// Backend URL; defaults to the port of the locally running server
+    #[arg(long, global = true, env = "VIBE_BACKEND_URL")]
+    backend_url: Option<String>,
+
+    /// API token, needed when the server requires authentication
+    #[arg(long, global = true, env = "VIBE_API_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// Print JSON instead of human readable output
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List projects
+    Projects,
+    /// List the tasks of a project
+    Tasks {
+        /// Project id or name
+        project: String,
+    },
+    /// Create a task, optionally starting an attempt on it
+    Create {
+        /// Project id or name
+        project: String,
+        title: String,
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Start an attempt right away
+        #[arg(long)]
+        start: bool,
+        #[command(flatten)]
+        attempt: AttemptArgs,
+    },
+    /// Start a new attempt on an existing task
+    Start {
+        task: Uuid,
+        #[command(flatten)]
+        attempt: AttemptArgs,
+    },
+    /// Print the conversation of an attempt's latest coding agent run, following it while it runs
+    Logs {
+        workspace: Uuid,
+        /// Show this execution process instead of the latest one
+        #[arg(long)]
+        process: Option<Uuid>,
+    },
+    /// Export the conversation of an attempt's latest session
+    Transcript {
+        workspace: Uuid,
+        /// Export this session instead of the latest one
+        #[arg(long)]
+        session: Option<Uuid>,
+        #[arg(short, long, value_enum, default_value = "markdown")]
+        format: TranscriptFormatArg,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Send a follow-up prompt to an attempt
+    FollowUp {
+        workspace: Uuid,
+        prompt: String,
+        /// Executor variant to use for this turn
+        #[arg(long)]
+        variant: Option<String>,
+        /// Follow the agent's output until it finishes
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Approve or deny a pending tool call
+    Approve {
+        approval_id: String,
+        /// Execution process that requested the approval
+        #[arg(long)]
+        process: Uuid,
+        #[arg(long)]
+        deny: bool,
+        /// Reason given to the agent when denying
+        #[arg(long, requires = "deny")]
+        reason: Option<String>,
+    },
+    /// Merge an attempt's branch into its target branch
+    Merge {
+        workspace: Uuid,
+        #[arg(long)]
+        repo: Option<Uuid>,
+    },
+    /// Push an attempt's branch to its remote
+    Push {
+        workspace: Uuid,
+        #[arg(long)]
+        repo: Option<Uuid>,
+    },
+    /// Open a pull request for an attempt
+    Pr {
+        workspace: Uuid,
+        #[arg(long)]
+        repo: Option<Uuid>,
+        /// Defaults to the task title
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        body: Option<String>,
+        #[arg(long)]
+        target_branch: Option<String>,
+        #[arg(long)]
+        draft: bool,
+        /// Let the coding agent write the description
+        #[arg(long)]
+        auto_description: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+struct AttemptArgs {
+    /// Executor profile as EXECUTOR[:VARIANT]; defaults to the one named in a repository's
+    /// config file, then to the configured profile
+    #[arg(short, long)]
+    executor: Option<String>,
+    /// Repository as REPO_ID[@BRANCH]; defaults to every project repository
+    #[arg(long = "repo")]
+    repos: Vec<String>,
+    /// Follow the agent's output until it finishes
+    #[arg(long)]
+    follow: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TranscriptFormatArg {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl TranscriptFormatArg {
+    fn as_query(&self) -> &'static str {
+        match self {
+            TranscriptFormatArg::Markdown => "markdown",
+            TranscriptFormatArg::Json => "json",
+            TranscriptFormatArg::Html => "html",
+        }
+    }
+}
+
+/// `GitBranch` only serializes, so branches are read into this instead
+#[derive(Deserialize)]
+struct Branch {
+    name: String,
+    is_current: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    let cli = Cli::parse();
+    let client = VkClient::connect(cli.backend_url, cli.token).await?;
+    let json = cli.json;
+
+    match cli.command {
+        Command::Projects => {
+            let projects: Vec<Project> = client.get("/api/projects").await?;
+            print_list(json, &projects, |project| {
+                format!("{}  {}", project.id, project.name)
+            })
+        }
+        Command::Tasks { project } => {
+            let project = resolve_project(&client, &project).await?;
+            let tasks: Vec<TaskWithAttemptStatus> = client
+                .get(&format!("/api/tasks?project_id={}", project.id))
+                .await?;
+            print_list(json, &tasks, |task| {
+                let running = if task.has_in_progress_attempt {
+                    " (running)"
+                } else {
+                    ""
+                };
+                format!("{}  [{}] {}{}", task.id, task.status, task.title, running)
+            })
+        }
+        Command::Create {
+            project,
+            title,
+            description,
+            start,
+            attempt,
+        } => {
+            let project = resolve_project(&client, &project).await?;
+            let create_task = CreateTask::from_title_description(project.id, title, description);
+            if !start {
+                let task: Task = client.post("/api/tasks", &create_task).await?;
+                return print_item(json, &task, |task| format!("{}  {}", task.id, task.title));
+            }
+
+            let repos = workspace_repos(&client, project.id, &attempt.repos).await?;
+            let request = CreateAndStartTaskRequest {
+                task: create_task,
+                executor_profile_id: executor_profile(&client, attempt.executor.as_deref(), &repos)
+                    .await?,
+                repos,
+                linked_issue: None,
+            };
+            let task: TaskWithAttemptStatus =
+                client.post("/api/tasks/create-and-start", &request).await?;
+            let workspaces: Vec<Workspace> = client
+                .get(&format!("/api/task-attempts?task_id={}", task.id))
+                .await?;
+            let workspace = workspaces
+                .into_iter()
+                .next()
+                .context("The task was created but no attempt was started")?;
+            report_attempt(&client, json, &workspace, attempt.follow).await
+        }
+        Command::Start { task, attempt } => {
+            let task: Task = client.get(&format!("/api/tasks/{task}")).await?;
+            let repos = workspace_repos(&client, task.project_id, &attempt.repos).await?;
+            let body = CreateTaskAttemptBody {
+                task_id: task.id,
+                executor_profile_id: executor_profile(&client, attempt.executor.as_deref(), &repos)
+                    .await?,
+                repos,
+                fan_out: None,
+            };
+            let workspace: Workspace = client.post("/api/task-attempts", &body).await?;
+            report_attempt(&client, json, &workspace, attempt.follow).await
+        }
+        Command::Logs { workspace, process } => {
+            let process_id = match process {
+                Some(process_id) => process_id,
+                None => {
+                    latest_coding_agent_process(&client, workspace, None)
+                        .await?
+                        .context("This attempt has not run a coding agent yet")?
+                        .id
+                }
+            };
+            logs::follow_process(&client, process_id, json).await
+        }
+        Command::Transcript {
+            workspace,
+            session,
+            format,
+            output,
+        } => {
+            let session_id = match session {
+                Some(session_id) => session_id,
+                None => {
+                    latest_session(&client, workspace)
+                        .await?
+                        .context("This attempt has no session to export")?
+                        .id
+                }
+            };
+            let body = client
+                .get_raw(&format!(
+                    "/api/sessions/{}/transcript?format={}",
+                    session_id,
+                    format.as_query()
+                ))
+                .await?;
+            match output {
+                Some(path) => std::fs::write(&path, &body)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None => std::io::stdout().write_all(&body)?,
+            }
+            Ok(())
+        }
+        Command::FollowUp {
+            workspace,
+            prompt,
+            variant,
+            follow,
+        } => {
+            let session = latest_session(&client, workspace)
+                .await?
+                .context("This attempt has no session to follow up on")?;
+            let executor = session
+                .executor
+                .as_deref()
+                .context("The session has no executor to follow up with")?;
+            let body = CreateFollowUpAttempt {
+                prompt,
+                executor_profile_id: ExecutorProfileId {
+                    executor: parse_executor(executor)?,
+                    variant,
+                },
+                retry_process_id: None,
+                force_when_dirty: None,
+                perform_git_reset: None,
+            };
+            let process: ExecutionProcess = client
+                .post(&format!("/api/sessions/{}/follow-up", session.id), &body)
+                .await?;
+            if follow {
+                return logs::follow_process(&client, process.id, json).await;
+            }
+            print_item(json, &process, |process| {
+                format!("Started execution process {}", process.id)
+            })
+        }
+        Command::Approve {
+            approval_id,
+            process,
+            deny,
+            reason,
+        } => {
+            let status = if deny {
+                ApprovalStatus::Denied { reason }
+            } else {
+                ApprovalStatus::Approved
+            };
+            let body = ApprovalResponse {
+                execution_process_id: process,
+                status,
+            };
+            let status: ApprovalStatus = client
+                .post(&format!("/api/approvals/{approval_id}/respond"), &body)
+                .await?;
+            print_item(json, &status, |status| format!("{status:?}"))
+        }
+        Command::Merge { workspace, repo } => {
+            let repo = workspace_repo(&client, workspace, repo).await?;
+            let body = MergeTaskAttemptRequest {
+                repo_id: repo.repo.id,
+                strategy: None,
+            };
+            let _: () = client
+                .post(&format!("/api/task-attempts/{workspace}/merge"), &body)
+                .await?;
+            print_message(
+                json,
+                format!(
+                    "Merged into {} of {}",
+                    repo.target_branch, repo.repo.display_name
+                ),
+            )
+        }
+        Command::Push { workspace, repo } => {
+            let repo = workspace_repo(&client, workspace, repo).await?;
+            let body = PushTaskAttemptRequest {
+                repo_id: repo.repo.id,
+            };
+            let _: () = client
+                .post(&format!("/api/task-attempts/{workspace}/push"), &body)
+                .await?;
+            print_message(json, format!("Pushed {}", repo.repo.display_name))
+        }
+        Command::Pr {
+            workspace,
+            repo,
+            title,
+            body,
+            target_branch,
+            draft,
+            auto_description,
+        } => {
+            let repo = workspace_repo(&client, workspace, repo).await?;
+            let title = match title {
+                Some(title) => title,
+                None => {
+                    let attempt: Workspace = client
+                        .get(&format!("/api/task-attempts/{workspace}"))
+                        .await?;
+                    let task: Task = client
+                        .get(&format!("/api/tasks/{}", attempt.task_id))
+                        .await?;
+                    task.title
+                }
+            };
+            let request = CreatePrApiRequest {
+                title,
+                body,
+                target_branch,
+                draft: Some(draft),
+                repo_id: repo.repo.id,
+                auto_generate_description: auto_description,
+            };
+            let url: String = client
+                .post(&format!("/api/task-attempts/{workspace}/pr"), &request)
+                .await?;
+            print_message(json, url)
+        }
+    }
+}
+
+fn print_list<T: Serialize>(
+    json: bool,
+    items: &[T],
+    line: impl Fn(&T) -> String,
+) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(items)?);
+    } else {
+        for item in items {
+            println!("{}", line(item));
+        }
+    }
+    Ok(())
+}
+
+fn print_item<T: Serialize>(
+    json: bool,
+    item: &T,
+    line: impl Fn(&T) -> String,
+) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(item)?);
+    } else {
+        println!("{}", line(item));
+    }
+    Ok(())
+}
+
+fn print_message(json: bool, message: String) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::json!({ "message": message }));
+    } else {
+        println!("{message}");
+    }
+    Ok(())
+}
+
+async fn resolve_project(client: &VkClient, project: &str) -> anyhow::Result<Project> {
+    let projects: Vec<Project> = client.get("/api/projects").await?;
+    let id = Uuid::parse_str(project).ok();
+    let mut matches = projects.into_iter().filter(|candidate| {
+        Some(candidate.id) == id || candidate.name.eq_ignore_ascii_case(project)
+    });
+    match (matches.next(), matches.next()) {
+        (Some(project), None) => Ok(project),
+        (Some(_), Some(_)) => bail!("Several projects are named '{project}'; use its id"),
+        (None, _) => bail!("Project '{project}' not found"),
+    }
+}
+
+fn parse_executor(executor: &str) -> anyhow::Result<BaseCodingAgent> {
+    BaseCodingAgent::from_str(&executor.trim().to_uppercase().replace('-', "_"))
+        .map_err(|_| anyhow::anyhow!("Unknown executor '{executor}'"))
+}
+
+/// Parse `EXECUTOR[:VARIANT]`
+fn parse_executor_profile(executor: &str) -> anyhow::Result<ExecutorProfileId> {
+    let (executor, variant) = match executor.split_once(':') {
+        Some((executor, variant)) => (executor, Some(variant.to_string())),
+        None => (executor, None),
+    };
+    Ok(ExecutorProfileId {
+        executor: parse_executor(executor)?,
+        variant,
+    })
+}
+
+/// The default executor profile in the server's `/api/info`
+fn configured_executor_profile(info: &Value) -> anyhow::Result<ExecutorProfileId> {
+    serde_json::from_value(info["config"]["executor_profile"].clone())
+        .context("The server has no default executor profile; pass --executor")
+}
+
+/// The executor profile a repository's config file prefers, from its effective config
+fn repo_executor_profile(config: &Value) -> Option<ExecutorProfileId> {
+    serde_json::from_value(config["executor_profile"].clone()).ok()
+}
+
+/// Parse `EXECUTOR[:VARIANT]`, falling back to the profile preferred by the config file of the
+/// first repository that names one, and then to the profile configured on the server
+async fn executor_profile(
+    client: &VkClient,
+    executor: Option<&str>,
+    repos: &[WorkspaceRepoInput],
+) -> anyhow::Result<ExecutorProfileId> {
+    if let Some(executor) = executor {
+        return parse_executor_profile(executor);
+    }
+    for repo in repos {
+        let config: Value = client
+            .get(&format!("/api/repos/{}/config", repo.repo_id))
+            .await?;
+        if let Some(profile) = repo_executor_profile(&config) {
+            return Ok(profile);
+        }
+    }
+    configured_executor_profile(&client.get("/api/info").await?)
+}
+
+/// Parse `REPO_ID[@BRANCH]`
+fn parse_repo_arg(repo: &str) -> anyhow::Result<(Uuid, Option<String>)> {
+    let (repo_id, branch) = match repo.split_once('@') {
+        Some((repo_id, branch)) => (repo_id, Some(branch.to_string())),
+        None => (repo, None),
+    };
+    let repo_id =
+        Uuid::parse_str(repo_id).with_context(|| format!("Invalid repository id '{repo_id}'"))?;
+    Ok((repo_id, branch))
+}
+
+/// Parse `REPO_ID[@BRANCH]` arguments, defaulting to every repository of the project on its
+/// default target branch, or the branch it has checked out
+async fn workspace_repos(
+    client: &VkClient,
+    project_id: Uuid,
+    repos: &[String],
+) -> anyhow::Result<Vec<WorkspaceRepoInput>> {
+    let mut inputs = Vec::new();
+    if repos.is_empty() {
+        let project_repos: Vec<Repo> = client
+            .get(&format!("/api/projects/{project_id}/repositories"))
+            .await?;
+        for repo in project_repos {
+            // The effective config includes a default branch from the repo's config file
+            let config: Value = client
+                .get(&format!("/api/repos/{}/config", repo.id))
+                .await?;
+            let target_branch = match config["default_target_branch"].as_str() {
+                Some(branch) => branch.to_string(),
+                None => current_branch(client, repo.id).await?,
+            };
+            inputs.push(WorkspaceRepoInput {
+                repo_id: repo.id,
+                target_branch,
+            });
+        }
+    } else {
+        for repo in repos {
+            let (repo_id, branch) = parse_repo_arg(repo)?;
+            let target_branch = match branch {
+                Some(branch) => branch,
+                None => current_branch(client, repo_id).await?,
+            };
+            inputs.push(WorkspaceRepoInput {
+                repo_id,
+                target_branch,
+            });
+        }
+    }
+
+    if inputs.is_empty() {
+        bail!("The project has no repositories to start an attempt in");
+    }
+    Ok(inputs)
+}
+
+async fn current_branch(client: &VkClient, repo_id: Uuid) -> anyhow::Result<String> {
+    let branches: Vec<Branch> = client
+        .get(&format!("/api/repos/{repo_id}/branches"))
+        .await?;
+    branches
+        .into_iter()
+        .find(|branch| branch.is_current)
+        .map(|branch| branch.name)
+        .with_context(|| format!("Could not find the current branch of repository {repo_id}"))
+}
+
+/// The repository of an attempt to act on; required when the attempt spans several
+async fn workspace_repo(
+    client: &VkClient,
+    workspace_id: Uuid,
+    repo_id: Option<Uuid>,
+) -> anyhow::Result<RepoWithTargetBranch> {
+    let repos: Vec<RepoWithTargetBranch> = client
+        .get(&format!("/api/task-attempts/{workspace_id}/repos"))
+        .await?;
+    match repo_id {
+        Some(repo_id) => repos
+            .into_iter()
+            .find(|repo| repo.repo.id == repo_id)
+            .with_context(|| format!("Repository {repo_id} is not part of this attempt")),
+        None if repos.len() > 1 => {
+            let ids: Vec<String> = repos
+                .iter()
+                .map(|repo| format!("{} ({})", repo.repo.id, repo.repo.display_name))
+                .collect();
+            bail!(
+                "This attempt spans several repositories; pass --repo with one of: {}",
+                ids.join(", ")
+            )
+        }
+        None => repos
+            .into_iter()
+            .next()
+            .context("This attempt has no repositories"),
+    }
+}
+
+/// Sessions are listed most recently used first
+async fn latest_session(client: &VkClient, workspace_id: Uuid) -> anyhow::Result<Option<Session>> {
+    let sessions: Vec<Session> = client
+        .get(&format!("/api/sessions?workspace_id={workspace_id}"))
+        .await?;
+    Ok(sessions.into_iter().next())
+}
+
+async fn latest_coding_agent_process(
+    client: &VkClient,
+    workspace_id: Uuid,
+    wait: Option<Duration>,
+) -> anyhow::Result<Option<ExecutionProcess>> {
+    let deadline = wait.map(|wait| tokio::time::Instant::now() + wait);
+    loop {
+        if let Some(session) = latest_session(client, workspace_id).await? {
+            let processes: Vec<ExecutionProcess> = client
+                .get(&format!(
+                    "/api/execution-processes?session_id={}",
+                    session.id
+                ))
+                .await?;
+            let latest = processes
+                .into_iter()
+                .rev()
+                .find(|process| process.run_reason == ExecutionProcessRunReason::CodingAgent);
+            if latest.is_some() {
+                return Ok(latest);
+            }
+        }
+
+        match deadline {
+            Some(deadline) if tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            _ => return Ok(None),
+        }
+    }
+}
+
+async fn report_attempt(
+    client: &VkClient,
+    json: bool,
+    workspace: &Workspace,
+    follow: bool,
+) -> anyhow::Result<()> {
+    if !follow {
+        return print_item(json, workspace, |workspace| {
+            format!(
+                "Started attempt {} on branch {}",
+                workspace.id, workspace.branch
+            )
+        });
+    }
+
+    if !json {
+        println!(
+            "Started attempt {} on branch {}",
+            workspace.id, workspace.branch
+        );
+    }
+    // The setup script may run before the coding agent starts
+    let process = latest_coding_agent_process(client, workspace.id, Some(PROCESS_WAIT_TIMEOUT))
+        .await?
+        .context("The coding agent did not start; check the attempt in the UI")?;
+    logs::follow_process(client, process.id, json).await?;
+
+    let process: ExecutionProcess = client
+        .get(&format!("/api/execution-processes/{}", process.id))
+        .await?;
+    if process.status == ExecutionProcessStatus::Failed {
+        bail!("The coding agent failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    const REPO_ID: &str = "0b5a3c1e-7a4d-4c1f-9d2e-3f6a8b9c0d1e";
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("vk").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn create_takes_attempt_options_and_global_flags() {
+        let cli = parse(&[
+            "create",
+            "my-project",
+            "Fix the login page",
+            "-d",
+            "It crashes",
+            "--start",
+            "-e",
+            "claude-code:PLAN",
+            "--repo",
+            &format!("{REPO_ID}@main"),
+            "--follow",
+            "--json",
+            "--backend-url",
+            "http://10.0.0.2:3000",
+        ])
+        .unwrap();
+        assert!(cli.json);
+        assert_eq!(cli.backend_url.as_deref(), Some("http://10.0.0.2:3000"));
+        let Command::Create {
+            project,
+            title,
+            description,
+            start,
+            attempt,
+        } = cli.command
+        else {
+            panic!("expected the create command");
+        };
+        assert_eq!(project, "my-project");
+        assert_eq!(title, "Fix the login page");
+        assert_eq!(description.as_deref(), Some("It crashes"));
+        assert!(start);
+        assert_eq!(attempt.executor.as_deref(), Some("claude-code:PLAN"));
+        assert_eq!(attempt.repos, vec![format!("{REPO_ID}@main")]);
+        assert!(attempt.follow);
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        // Ids are validated while parsing
+        assert!(parse(&["start", "not-a-uuid"]).is_err());
+        assert!(parse(&["logs", REPO_ID, "--process", "latest"]).is_err());
+        // A reason only makes sense when denying
+        assert!(parse(&["approve", "a1", "--process", REPO_ID, "--reason", "no"]).is_err());
+        assert!(
+            parse(&[
+                "approve",
+                "a1",
+                "--process",
+                REPO_ID,
+                "--deny",
+                "--reason",
+                "no"
+            ])
+            .is_ok()
+        );
+        assert!(parse(&["tasks"]).is_err());
+    }
+
+    #[test]
+    fn executors_accept_cli_spellings() {
+        assert_eq!(
+            parse_executor("claude-code").unwrap(),
+            BaseCodingAgent::ClaudeCode
+        );
+        assert_eq!(
+            parse_executor(" CLAUDE_CODE ").unwrap(),
+            BaseCodingAgent::ClaudeCode
+        );
+        assert_eq!(parse_executor("codex").unwrap(), BaseCodingAgent::Codex);
+        assert_eq!(
+            parse_executor("vim").unwrap_err().to_string(),
+            "Unknown executor 'vim'"
+        );
+    }
+
+    #[test]
+    fn executor_profiles_take_an_optional_variant() {
+        assert_eq!(
+            parse_executor_profile("claude-code:PLAN").unwrap(),
+            ExecutorProfileId {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: Some("PLAN".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_executor_profile("codex").unwrap(),
+            ExecutorProfileId {
+                executor: BaseCodingAgent::Codex,
+                variant: None,
+            }
+        );
+        assert!(parse_executor_profile("vim:PLAN").is_err());
+    }
+
+    #[test]
+    fn default_executor_profile_comes_from_the_server_config() {
+        let info = serde_json::json!({
+            "config": { "executor_profile": { "executor": "GEMINI", "variant": "FLASH" } }
+        });
+        assert_eq!(
+            configured_executor_profile(&info).unwrap(),
+            ExecutorProfileId {
+                executor: BaseCodingAgent::Gemini,
+                variant: Some("FLASH".to_string()),
+            }
+        );
+        assert!(configured_executor_profile(&serde_json::json!({ "config": {} })).is_err());
+    }
+
+    #[test]
+    fn repo_config_files_can_prefer_an_executor_profile() {
+        let config = serde_json::json!({
+            "default_target_branch": "main",
+            "executor_profile": { "executor": "CLAUDE_CODE", "variant": "PLAN" },
+        });
+        assert_eq!(
+            repo_executor_profile(&config),
+            Some(ExecutorProfileId {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: Some("PLAN".to_string()),
+            })
+        );
+        assert_eq!(
+            repo_executor_profile(&serde_json::json!({ "executor_profile": null })),
+            None
+        );
+    }
+
+    #[test]
+    fn repo_arguments_take_an_optional_branch() {
+        let repo_id = Uuid::parse_str(REPO_ID).unwrap();
+        assert_eq!(parse_repo_arg(REPO_ID).unwrap(), (repo_id, None));
+        assert_eq!(
+            parse_repo_arg(&format!("{REPO_ID}@feature/login")).unwrap(),
+            (repo_id, Some("feature/login".to_string()))
+        );
+        assert_eq!(
+            parse_repo_arg("my-repo@main").unwrap_err().to_string(),
+            "Invalid repository id 'my-repo'"
+        );
+    }
+}
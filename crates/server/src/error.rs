@@ -5,8 +5,9 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use db::models::{
-    execution_process::ExecutionProcessError, project::ProjectError,
-    project_repo::ProjectRepoError, repo::RepoError, scratch::ScratchError, session::SessionError,
+    execution_process::ExecutionProcessError, merge_queue::MergeQueueError, project::ProjectError,
+    project_repo::ProjectRepoError, queued_message::QueuedMessageError, repo::RepoError,
+    scratch::ScratchError, session::SessionError, task_dependency::TaskDependencyError,
     workspace::WorkspaceError,
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
@@ -23,6 +24,8 @@ use services::services::{
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    repo_config::RepoConfigError,
+    transcript::TranscriptError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -42,6 +45,12 @@ pub enum ApiError {
     #[error(transparent)]
     ScratchError(#[from] ScratchError),
     #[error(transparent)]
+    QueuedMessage(#[from] QueuedMessageError),
+    #[error(transparent)]
+    MergeQueue(#[from] MergeQueueError),
+    #[error(transparent)]
+    TaskDependency(#[from] TaskDependencyError),
+    #[error(transparent)]
     ExecutionProcess(#[from] ExecutionProcessError),
     #[error(transparent)]
     GitService(#[from] GitServiceError),
@@ -83,6 +92,8 @@ pub enum ApiError {
     Pty(#[from] PtyError),
     #[error(transparent)]
     Migration(#[from] MigrationError),
+    #[error(transparent)]
+    Transcript(#[from] TranscriptError),
 }
 
 impl From<&'static str> for ApiError {
@@ -304,6 +315,41 @@ impl IntoResponse for ApiError {
                 )
             }
 
+            ApiError::QueuedMessage(QueuedMessageError::Database(_)) => {
+                ErrorInfo::internal("QueuedMessageError")
+            }
+            ApiError::QueuedMessage(QueuedMessageError::NotFound) => {
+                ErrorInfo::not_found("QueuedMessageError", "Queued message not found.")
+            }
+            ApiError::QueuedMessage(QueuedMessageError::InvalidOrder) => ErrorInfo::bad_request(
+                "QueuedMessageError",
+                "Reorder must list every queued message of the session exactly once.",
+            ),
+
+            ApiError::MergeQueue(MergeQueueError::Database(_)) => {
+                ErrorInfo::internal("MergeQueueError")
+            }
+            ApiError::MergeQueue(MergeQueueError::NotFound) => {
+                ErrorInfo::not_found("MergeQueueError", "Merge queue entry not found.")
+            }
+            ApiError::MergeQueue(MergeQueueError::AlreadyQueued) => ErrorInfo::conflict(
+                "MergeQueueError",
+                "Workspace is already queued for this repository.",
+            ),
+
+            ApiError::TaskDependency(TaskDependencyError::Database(_)) => {
+                ErrorInfo::internal("TaskDependencyError")
+            }
+            ApiError::TaskDependency(TaskDependencyError::NotFound) => {
+                ErrorInfo::not_found("TaskDependencyError", "Task dependency not found.")
+            }
+            ApiError::TaskDependency(
+                err @ (TaskDependencyError::SelfDependency | TaskDependencyError::DifferentProject),
+            ) => ErrorInfo::bad_request("TaskDependencyError", err.to_string()),
+            ApiError::TaskDependency(
+                err @ (TaskDependencyError::AlreadyExists | TaskDependencyError::Cycle),
+            ) => ErrorInfo::conflict("TaskDependencyError", err.to_string()),
+
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound) => {
                 ErrorInfo::not_found("ExecutionProcessError", "Execution process not found.")
             }
@@ -394,10 +440,13 @@ impl IntoResponse for ApiError {
             ApiError::Pty(PtyError::SessionNotFound(_)) => {
                 ErrorInfo::not_found("PtyError", "PTY session not found.")
             }
-            ApiError::Pty(PtyError::SessionClosed) => {
-                ErrorInfo::with_status(StatusCode::GONE, "PtyError", "PTY session closed.")
-            }
             ApiError::Pty(_) => ErrorInfo::internal("PtyError"),
+            ApiError::Transcript(TranscriptError::Timeout(_)) => ErrorInfo::with_status(
+                StatusCode::GATEWAY_TIMEOUT,
+                "TranscriptError",
+                format!("{}", self),
+            ),
+            ApiError::Transcript(_) => ErrorInfo::internal("TranscriptError"),
 
             ApiError::Unauthorized => ErrorInfo::with_status(
                 StatusCode::UNAUTHORIZED,
@@ -535,6 +584,17 @@ impl From<RepoServiceError> for ApiError {
     }
 }
 
+impl From<RepoConfigError> for ApiError {
+    fn from(err: RepoConfigError) -> Self {
+        match err {
+            RepoConfigError::Database(db_err) => ApiError::Database(db_err),
+            RepoConfigError::Io(io_err) => ApiError::Io(io_err),
+            RepoConfigError::Changed => ApiError::Conflict(err.to_string()),
+            RepoConfigError::Parse(_) => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
 impl From<ProjectRepoError> for ApiError {
     fn from(err: ProjectRepoError) -> Self {
         match err {
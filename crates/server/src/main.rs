@@ -1,6 +1,10 @@
 use anyhow::{self, Error as AnyhowError};
+use db::models::execution_process_logs::ExecutionProcessLogs;
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes};
+use server::{
+    DeploymentImpl, routes,
+    tls::{TlsListener, load_server_config},
+};
 use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
@@ -67,6 +71,21 @@ async fn main() -> Result<(), VibeKanbanError> {
         .backfill_repo_names()
         .await
         .map_err(DeploymentError::from)?;
+    // Recompress log chunks compacted by the chunked log storage migration
+    {
+        let pool = deployment.db().pool.clone();
+        tokio::spawn(async move {
+            match ExecutionProcessLogs::compress_pending_chunks(&pool).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Compressed {} legacy log chunks", count),
+                Err(e) => tracing::warn!("Failed to compress legacy log chunks: {}", e),
+            }
+        });
+    }
+    // Resume coding agent runs that were still queued when the server stopped
+    if let Err(e) = deployment.container().start_queued_executions().await {
+        tracing::warn!("Failed to start queued executions: {}", e);
+    }
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -99,10 +118,29 @@ async fn main() -> Result<(), VibeKanbanError> {
         }); // Use 0 to find free port if no specific port provided
 
     let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let tls_config = match (
+        std::env::var_os("TLS_CERT_PATH"),
+        std::env::var_os("TLS_KEY_PATH"),
+    ) {
+        (Some(cert_path), Some(key_path)) => {
+            Some(load_server_config(cert_path.as_ref(), key_path.as_ref())?)
+        }
+        (None, None) => None,
+        _ => {
+            return Err(
+                anyhow::anyhow!("TLS_CERT_PATH and TLS_KEY_PATH must be set together").into(),
+            );
+        }
+    };
+    let scheme = if tls_config.is_some() {
+        "https"
+    } else {
+        "http"
+    };
     let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
     let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
 
-    tracing::info!("Server running on http://{host}:{actual_port}");
+    tracing::info!("Server running on {scheme}://{host}:{actual_port}");
 
     // Production only: write port file for extension discovery and open browser
     if !cfg!(debug_assertions) {
@@ -111,19 +149,29 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
         tracing::info!("Opening browser...");
         tokio::spawn(async move {
-            if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
+            if let Err(e) = open_browser(&format!("{scheme}://127.0.0.1:{actual_port}")).await {
                 tracing::warn!(
-                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
+                    "Failed to open browser automatically: {}. Please open {}://127.0.0.1:{} manually.",
                     e,
+                    scheme,
                     actual_port
                 );
             }
         });
     }
 
-    axum::serve(listener, app_router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    match tls_config {
+        Some(tls_config) => {
+            axum::serve(TlsListener::new(listener, tls_config)?, app_router)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?
+        }
+        None => {
+            axum::serve(listener, app_router)
+                .with_graceful_shutdown(shutdown_signal())
+                .await?
+        }
+    }
 
     perform_cleanup_actions(&deployment).await;
 
@@ -169,7 +217,7 @@ pub async fn shutdown_signal() {
 pub async fn perform_cleanup_actions(deployment: &DeploymentImpl) {
     deployment
         .container()
-        .kill_all_running_processes()
+        .shutdown_running_processes()
         .await
-        .expect("Failed to cleanly kill running execution processes");
+        .expect("Failed to cleanly stop running execution processes");
 }
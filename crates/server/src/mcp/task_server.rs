@@ -8,7 +8,8 @@ use db::models::{
     project::Project,
     repo::Repo,
     tag::Tag,
-    task::{CreateTask, TaskWithAttemptStatus},
+    task::{CreateTask, Task, TaskWithAttemptStatus},
+    task_dependency::{TaskDependencies, TaskDependency},
     workspace::{Workspace, WorkspaceContext},
 };
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
@@ -275,7 +276,7 @@ pub struct StartWorkspaceSessionRequest {
     #[schemars(description = "A title for the workspace (used as the task name)")]
     pub title: String,
     #[schemars(
-        description = "The coding agent executor to run ('CLAUDE_CODE', 'AMP', 'GEMINI', 'CODEX', 'OPENCODE', 'CURSOR_AGENT', 'QWEN_CODE', 'COPILOT', 'DROID')"
+        description = "The coding agent executor to run ('CLAUDE_CODE', 'AMP', 'GEMINI', 'CODEX', 'OPENCODE', 'CURSOR_AGENT', 'QWEN_CODE', 'COPILOT', 'DROID', 'CUSTOM_ACP', 'AIDER')"
     )]
     pub executor: String,
     #[schemars(description = "Optional executor variant, if needed")]
@@ -311,6 +312,69 @@ pub struct McpLinkWorkspaceResponse {
     pub issue_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpAddTaskDependencyRequest {
+    #[schemars(
+        description = "The local task that should wait. Optional if running inside a workspace."
+    )]
+    pub task_id: Option<Uuid>,
+    #[schemars(description = "The local task that must be done first")]
+    pub depends_on_task_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpRemoveTaskDependencyRequest {
+    #[schemars(
+        description = "The local task that is waiting. Optional if running inside a workspace."
+    )]
+    pub task_id: Option<Uuid>,
+    #[schemars(description = "The local task it no longer waits on")]
+    pub depends_on_task_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpListTaskDependenciesRequest {
+    #[schemars(description = "The local task ID. Optional if running inside a workspace.")]
+    pub task_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpTaskSummary {
+    #[schemars(description = "The unique identifier of the local task")]
+    pub id: String,
+    #[schemars(description = "The title of the task")]
+    pub title: String,
+    #[schemars(description = "The current status of the task")]
+    pub status: String,
+}
+
+impl From<Task> for McpTaskSummary {
+    fn from(task: Task) -> Self {
+        Self {
+            id: task.id.to_string(),
+            title: task.title,
+            status: task.status.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpTaskDependencyResponse {
+    #[schemars(description = "The task that waits")]
+    pub task_id: String,
+    #[schemars(description = "The task it waits on")]
+    pub depends_on_task_id: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpListTaskDependenciesResponse {
+    pub task_id: String,
+    #[schemars(description = "Tasks that must be done before this task can start")]
+    pub blocked_by: Vec<McpTaskSummary>,
+    #[schemars(description = "Tasks waiting on this task")]
+    pub blocking: Vec<McpTaskSummary>,
+}
+
 // ── Server struct ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -339,6 +403,8 @@ pub struct McpContext {
     pub project_id: Option<Uuid>,
     #[schemars(description = "The remote issue ID (if workspace is linked to a remote issue)")]
     pub issue_id: Option<Uuid>,
+    #[schemars(description = "The local task that owns this workspace")]
+    pub task_id: Uuid,
     pub workspace_id: Uuid,
     pub workspace_branch: String,
     #[schemars(
@@ -347,10 +413,30 @@ pub struct McpContext {
     pub workspace_repos: Vec<McpRepoContext>,
 }
 
+/// HTTP client that authenticates with `VIBE_API_TOKEN` when the backend requires API tokens
+fn backend_client() -> reqwest::Client {
+    let Ok(token) = std::env::var("VIBE_API_TOKEN") else {
+        return reqwest::Client::new();
+    };
+    let Ok(mut value) = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+    else {
+        tracing::warn!("[MCP] Ignoring VIBE_API_TOKEN, it is not a valid header value");
+        return reqwest::Client::new();
+    };
+    value.set_sensitive(true);
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::AUTHORIZATION, value);
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap_or_default()
+}
+
 impl TaskServer {
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: backend_client(),
             base_url: base_url.to_string(),
             tool_router: Self::tool_router(),
             context: None,
@@ -411,6 +497,7 @@ impl TaskServer {
             })
             .collect();
 
+        let task_id = ctx.workspace.task_id;
         let workspace_id = ctx.workspace.id;
         let workspace_branch = ctx.workspace.branch.clone();
 
@@ -424,6 +511,7 @@ impl TaskServer {
             organization_id,
             project_id,
             issue_id,
+            task_id,
             workspace_id,
             workspace_branch,
             workspace_repos,
@@ -650,6 +738,20 @@ impl TaskServer {
         .unwrap())
     }
 
+    fn resolve_task_id(&self, explicit: Option<Uuid>) -> Result<Uuid, CallToolResult> {
+        if let Some(id) = explicit {
+            return Ok(id);
+        }
+        if let Some(ctx) = &self.context {
+            return Ok(ctx.task_id);
+        }
+        Err(Self::err(
+            "task_id is required (not available from workspace context)",
+            None::<&str>,
+        )
+        .unwrap())
+    }
+
     /// Fetches project statuses for a project, returning a map of status name → status.
     async fn fetch_project_statuses(
         &self,
@@ -1292,12 +1394,105 @@ impl TaskServer {
         let details = self.issue_to_details(&issue).await;
         TaskServer::success(&McpGetIssueResponse { issue: details })
     }
+
+    #[tool(
+        description = "Make a local task wait on another task. When auto-start is enabled, the waiting task starts automatically once everything it depends on is merged. `task_id` is optional if running inside a workspace."
+    )]
+    async fn add_task_dependency(
+        &self,
+        Parameters(McpAddTaskDependencyRequest {
+            task_id,
+            depends_on_task_id,
+        }): Parameters<McpAddTaskDependencyRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task_id = match self.resolve_task_id(task_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/tasks/{}/dependencies", task_id));
+        let payload = serde_json::json!({ "depends_on_task_id": depends_on_task_id });
+        let dependency: TaskDependency =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(d) => d,
+                Err(e) => return Ok(e),
+            };
+
+        TaskServer::success(&McpTaskDependencyResponse {
+            task_id: dependency.task_id.to_string(),
+            depends_on_task_id: dependency.depends_on_task_id.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "Remove a dependency between two local tasks. `task_id` is optional if running inside a workspace."
+    )]
+    async fn remove_task_dependency(
+        &self,
+        Parameters(McpRemoveTaskDependencyRequest {
+            task_id,
+            depends_on_task_id,
+        }): Parameters<McpRemoveTaskDependencyRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task_id = match self.resolve_task_id(task_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!(
+            "/api/tasks/{}/dependencies/{}",
+            task_id, depends_on_task_id
+        ));
+        if let Err(e) = self.send_empty_json(self.client.delete(&url)).await {
+            return Ok(e);
+        }
+
+        TaskServer::success(&McpTaskDependencyResponse {
+            task_id: task_id.to_string(),
+            depends_on_task_id: depends_on_task_id.to_string(),
+        })
+    }
+
+    #[tool(
+        description = "List the local tasks a task waits on and the tasks waiting on it. `task_id` is optional if running inside a workspace."
+    )]
+    async fn list_task_dependencies(
+        &self,
+        Parameters(McpListTaskDependenciesRequest { task_id }): Parameters<
+            McpListTaskDependenciesRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let task_id = match self.resolve_task_id(task_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/tasks/{}/dependencies", task_id));
+        let dependencies: TaskDependencies = match self.send_json(self.client.get(&url)).await {
+            Ok(d) => d,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&McpListTaskDependenciesResponse {
+            task_id: task_id.to_string(),
+            blocked_by: dependencies
+                .blocked_by
+                .into_iter()
+                .map(McpTaskSummary::from)
+                .collect(),
+            blocking: dependencies
+                .blocking
+                .into_iter()
+                .map(McpTaskSummary::from)
+                .collect(),
+        })
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or issues then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_issues` to fetch the `issue_ids` of all the issues in a project. TOOLS: 'list_organizations', 'list_projects', 'list_issues', 'create_issue', 'start_workspace_session', 'get_issue', 'update_issue', 'delete_issue', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `issue_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or issues then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_issues` to fetch the `issue_ids` of all the issues in a project. TOOLS: 'list_organizations', 'list_projects', 'list_issues', 'create_issue', 'start_workspace_session', 'get_issue', 'update_issue', 'delete_issue', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script', 'add_task_dependency', 'remove_task_dependency', 'list_task_dependencies'. Make sure to pass `project_id`, `issue_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/issue/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
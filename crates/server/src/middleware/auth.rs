@@ -0,0 +1,321 @@
+use std::{fmt::Write, net::SocketAddr};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, Method, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use deployment::Deployment;
+use rand::{Rng, distributions::Alphanumeric};
+use services::services::config::{ApiAuthConfig, ApiTokenScope};
+use sha2::{Digest, Sha256};
+
+use crate::DeploymentImpl;
+
+/// Prefix of generated tokens, so they are recognisable in scripts and secret scanners
+const TOKEN_PREFIX: &str = "vk_";
+
+/// Reachable without a token so load balancers and scripts can probe the server
+const PUBLIC_PATHS: &[&str] = &["/api/health"];
+
+/// Paths, and everything below them, that need a full-scope token even to read: they hand
+/// out a shell, reveal credentials, manage tokens or browse the host's filesystem
+const FULL_SCOPE_PATHS: &[&str] = &[
+    "/api/terminal",
+    "/api/auth/token",
+    "/api/auth/handoff",
+    "/api/api-tokens",
+    "/api/filesystem",
+    "/api/mcp-config",
+];
+
+/// Generate a new API token. Only its hash is stored, see [`hash_api_token`].
+pub fn generate_api_token() -> String {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    format!("{TOKEN_PREFIX}{secret}")
+}
+
+pub fn hash_api_token(token: &str) -> String {
+    let mut output = String::with_capacity(64);
+    for byte in Sha256::digest(token.as_bytes()) {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+/// Token presented as `Authorization: Bearer <token>` or as the password of basic auth
+fn presented_token(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?.trim();
+    let (scheme, credentials) = value.split_once(' ')?;
+    let credentials = credentials.trim();
+    if scheme.eq_ignore_ascii_case("bearer") {
+        return Some(credentials.to_string());
+    }
+    if scheme.eq_ignore_ascii_case("basic") {
+        let decoded = String::from_utf8(BASE64.decode(credentials).ok()?).ok()?;
+        // Any username is accepted, the token goes in the password
+        let (_, password) = decoded.split_once(':')?;
+        return Some(password.to_string());
+    }
+    None
+}
+
+fn is_full_scope_path(path: &str) -> bool {
+    FULL_SCOPE_PATHS.iter().any(|prefix| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+fn is_read_request(method: &Method, path: &str) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) && !is_full_scope_path(path)
+}
+
+/// Decide whether a request may go through under the configured API authentication.
+/// Returns the scope of the token it was authorized with, if one was needed.
+fn authorize(
+    auth: &ApiAuthConfig,
+    peer: &SocketAddr,
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+) -> Result<Option<ApiTokenScope>, StatusCode> {
+    if !auth.enabled
+        || (peer.ip().to_canonical().is_loopback() && !auth.require_for_loopback)
+        || PUBLIC_PATHS.contains(&path)
+    {
+        return Ok(None);
+    }
+
+    let token_hash = presented_token(headers)
+        .map(|token| hash_api_token(&token))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let token = auth
+        .tokens
+        .iter()
+        .find(|token| token.token_hash == token_hash)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    match token.scope {
+        ApiTokenScope::Full => Ok(Some(token.scope)),
+        ApiTokenScope::ReadOnly if is_read_request(method, path) => Ok(Some(token.scope)),
+        ApiTokenScope::ReadOnly => Err(StatusCode::FORBIDDEN),
+    }
+}
+
+/// Require an API token on every HTTP and websocket request when API authentication is enabled.
+/// The scope of the token is added to the request extensions for handlers that tailor their
+/// response to it.
+pub async fn require_api_token(
+    State(deployment): State<DeploymentImpl>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let result = {
+        let config = deployment.config().read().await;
+        authorize(
+            &config.api_auth,
+            &peer,
+            request.method(),
+            request.uri().path(),
+            request.headers(),
+        )
+    };
+
+    match result {
+        Ok(scope) => {
+            if let Some(scope) = scope {
+                request.extensions_mut().insert(scope);
+            }
+            next.run(request).await
+        }
+        Err(status) => {
+            tracing::debug!(
+                "Rejected {} {} from {}: {}",
+                request.method(),
+                request.uri().path(),
+                peer,
+                status
+            );
+            let mut response = Response::builder().status(status);
+            if status == StatusCode::UNAUTHORIZED {
+                // Lets browsers prompt for the token and reuse it for API and websocket requests
+                response = response.header(header::WWW_AUTHENTICATE, "Basic realm=\"vibe-kanban\"");
+            }
+            response
+                .body(Body::empty())
+                .unwrap_or_else(|_| Response::new(Body::empty()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use chrono::Utc;
+    use services::services::config::ApiToken;
+    use uuid::Uuid;
+
+    use super::*;
+
+    const READ_TOKEN: &str = "vk_read";
+    const FULL_TOKEN: &str = "vk_full";
+
+    fn auth_config() -> ApiAuthConfig {
+        let token = |name: &str, secret: &str, scope| ApiToken {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            scope,
+            token_hash: hash_api_token(secret),
+            created_at: Utc::now(),
+        };
+        ApiAuthConfig {
+            enabled: true,
+            require_for_loopback: false,
+            tokens: vec![
+                token("read", READ_TOKEN, ApiTokenScope::ReadOnly),
+                token("full", FULL_TOKEN, ApiTokenScope::Full),
+            ],
+        }
+    }
+
+    fn lan_peer() -> SocketAddr {
+        "192.168.1.20:52000".parse().unwrap()
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn generated_tokens_are_unique_and_prefixed() {
+        let a = generate_api_token();
+        let b = generate_api_token();
+        assert!(a.starts_with(TOKEN_PREFIX));
+        assert_ne!(a, b);
+        assert_eq!(hash_api_token(&a).len(), 64);
+    }
+
+    #[test]
+    fn disabled_auth_and_loopback_clients_need_no_token() {
+        let mut auth = auth_config();
+        let loopback: SocketAddr = "127.0.0.1:52000".parse().unwrap();
+        let headers = HeaderMap::new();
+        assert!(authorize(&auth, &loopback, &Method::POST, "/api/tasks", &headers).is_ok());
+        assert_eq!(
+            authorize(&auth, &lan_peer(), &Method::GET, "/api/tasks", &headers),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+        assert!(authorize(&auth, &lan_peer(), &Method::GET, "/api/health", &headers).is_ok());
+
+        auth.require_for_loopback = true;
+        assert_eq!(
+            authorize(&auth, &loopback, &Method::GET, "/api/tasks", &headers),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+
+        auth.enabled = false;
+        assert!(authorize(&auth, &lan_peer(), &Method::POST, "/api/tasks", &headers).is_ok());
+    }
+
+    #[test]
+    fn read_only_tokens_cannot_write_or_open_terminals() {
+        let auth = auth_config();
+        let peer = lan_peer();
+        let read = bearer(READ_TOKEN);
+        assert!(authorize(&auth, &peer, &Method::GET, "/api/tasks", &read).is_ok());
+        assert_eq!(
+            authorize(&auth, &peer, &Method::POST, "/api/tasks", &read),
+            Err(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(
+            authorize(&auth, &peer, &Method::GET, "/api/terminal/ws", &read),
+            Err(StatusCode::FORBIDDEN)
+        );
+
+        let full = bearer(FULL_TOKEN);
+        assert!(authorize(&auth, &peer, &Method::POST, "/api/tasks", &full).is_ok());
+        assert!(authorize(&auth, &peer, &Method::GET, "/api/terminal/ws", &full).is_ok());
+        assert_eq!(
+            authorize(
+                &auth,
+                &peer,
+                &Method::GET,
+                "/api/tasks",
+                &bearer("vk_revoked")
+            ),
+            Err(StatusCode::UNAUTHORIZED)
+        );
+    }
+
+    #[test]
+    fn read_only_tokens_cannot_read_credentials_tokens_or_files() {
+        let auth = auth_config();
+        let peer = lan_peer();
+        let read = bearer(READ_TOKEN);
+        let full = bearer(FULL_TOKEN);
+        for path in [
+            "/api/auth/token",
+            "/api/auth/handoff/complete",
+            "/api/api-tokens",
+            "/api/filesystem/directory",
+            "/api/filesystem/git-repos",
+            "/api/mcp-config",
+            "/api/terminal/sessions",
+        ] {
+            assert_eq!(
+                authorize(&auth, &peer, &Method::GET, path, &read),
+                Err(StatusCode::FORBIDDEN),
+                "{path}"
+            );
+            assert_eq!(
+                authorize(&auth, &peer, &Method::GET, path, &full),
+                Ok(Some(ApiTokenScope::Full)),
+                "{path}"
+            );
+        }
+
+        // Only whole path segments are matched
+        for path in ["/api/auth/status", "/api/auth/user", "/api/filesystems"] {
+            assert_eq!(
+                authorize(&auth, &peer, &Method::GET, path, &read),
+                Ok(Some(ApiTokenScope::ReadOnly)),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn basic_auth_password_is_the_token() {
+        let auth = auth_config();
+        let mut headers = HeaderMap::new();
+        let credentials = BASE64.encode(format!("anyone:{FULL_TOKEN}"));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Basic {credentials}")).unwrap(),
+        );
+        assert!(
+            authorize(
+                &auth,
+                &lan_peer(),
+                &Method::DELETE,
+                "/api/tasks/1",
+                &headers
+            )
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use chrono::Utc;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::config::{ApiToken, ApiTokenScope, save_config_to_file};
+use ts_rs::TS;
+use utils::{assets::config_path, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{generate_api_token, hash_api_token},
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+    pub scope: ApiTokenScope,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct CreateApiTokenResponse {
+    pub api_token: ApiToken,
+    /// The token to hand to clients. It is not stored and cannot be shown again.
+    pub token: String,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/api-tokens", get(list_api_tokens).post(create_api_token))
+        .route("/api-tokens/{id}", delete(revoke_api_token))
+}
+
+async fn list_api_tokens(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<ApiToken>>> {
+    let tokens = deployment.config().read().await.api_auth.tokens.clone();
+    ResponseJson(ApiResponse::success(tokens))
+}
+
+async fn create_api_token(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateApiTokenRequest>,
+) -> Result<ResponseJson<ApiResponse<CreateApiTokenResponse>>, ApiError> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("Token name is required".to_string()));
+    }
+
+    let token = generate_api_token();
+    let api_token = ApiToken {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        scope: payload.scope,
+        token_hash: hash_api_token(&token),
+        created_at: Utc::now(),
+    };
+
+    {
+        let mut config = deployment.config().write().await;
+        let mut updated = config.clone();
+        updated.api_auth.tokens.push(api_token.clone());
+        save_config_to_file(&updated, &config_path()).await?;
+        *config = updated;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "api_token_created",
+            serde_json::json!({ "scope": payload.scope }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreateApiTokenResponse {
+        api_token,
+        token,
+    })))
+}
+
+async fn revoke_api_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let mut config = deployment.config().write().await;
+    if !config.api_auth.tokens.iter().any(|token| token.id == id) {
+        return Err(ApiError::BadRequest(format!("API token {id} not found")));
+    }
+
+    let mut updated = config.clone();
+    updated.api_auth.tokens.retain(|token| token.id != id);
+    save_config_to_file(&updated, &config_path()).await?;
+    *config = updated;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use api_types::LoginStatus;
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     body::Body,
     extract::{
         Path, Query, State,
@@ -24,7 +24,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use services::services::{
     config::{
-        Config, ConfigError, SoundFile,
+        ApiTokenScope, Config, ConfigError, SoundFile,
         editor::{EditorConfig, EditorType},
         save_config_to_file,
     },
@@ -97,8 +97,12 @@ pub struct UserSystemInfo {
 #[axum::debug_handler]
 async fn get_user_system_info(
     State(deployment): State<DeploymentImpl>,
+    scope: Option<Extension<ApiTokenScope>>,
 ) -> ResponseJson<ApiResponse<UserSystemInfo>> {
-    let config = deployment.config().read().await;
+    let mut config = deployment.config().read().await.clone();
+    if matches!(scope, Some(Extension(ApiTokenScope::ReadOnly))) {
+        redact_credentials(&mut config);
+    }
     let login_status = tokio::time::timeout(
         std::time::Duration::from_secs(2),
         deployment.get_login_status(),
@@ -107,7 +111,7 @@ async fn get_user_system_info(
     .unwrap_or(LoginStatus::LoggedOut);
 
     let user_system_info = UserSystemInfo {
-        config: config.clone(),
+        config,
         analytics_user_id: deployment.user_id().to_string(),
         login_status,
         profiles: ExecutorConfigs::get_cached(),
@@ -127,9 +131,19 @@ async fn get_user_system_info(
     ResponseJson(ApiResponse::success(user_system_info))
 }
 
+/// Credentials in the config that a read-only API token doesn't get to see
+fn redact_credentials(config: &mut Config) {
+    config.github.pat = None;
+    config.github.oauth_token = None;
+    for webhook in &mut config.notifications.webhooks {
+        webhook.webhook_url.clear();
+        webhook.pushover_user_key = None;
+    }
+}
+
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
-    Json(new_config): Json<Config>,
+    Json(mut new_config): Json<Config>,
 ) -> ResponseJson<ApiResponse<Config>> {
     let config_path = config_path();
 
@@ -143,6 +157,9 @@ async fn update_config(
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
+    // API tokens are only created and revoked through their own endpoints
+    new_config.api_auth.tokens = old_config.api_auth.tokens.clone();
+
     match save_config_to_file(&new_config, &config_path).await {
         Ok(_) => {
             let mut config = deployment.config().write().await;
@@ -200,6 +217,16 @@ async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &C
             deployment_clone.trigger_auto_project_setup().await;
         });
     }
+
+    // Raised or removed limits may let queued coding agents start
+    if old.concurrency != new.concurrency {
+        let deployment_clone = deployment.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deployment_clone.container().start_queued_executions().await {
+                tracing::error!("Failed to start queued executions: {}", e);
+            }
+        });
+    }
 }
 
 async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
@@ -11,12 +11,13 @@ use axum::{
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_logs::{ExecutionProcessLogStats, ExecutionProcessLogs},
     execution_process_repo_state::ExecutionProcessRepoState,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
-use services::services::container::ContainerService;
+use services::services::container::{ContainerService, LogRange};
 use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
@@ -30,6 +31,19 @@ pub struct SessionExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+pub async fn get_execution_processes(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionExecutionProcessQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcess>>>, ApiError> {
+    let processes = ExecutionProcess::find_by_session_id(
+        &deployment.db().pool,
+        query.session_id,
+        query.show_soft_deleted.unwrap_or(false),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(processes)))
+}
+
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -41,18 +55,19 @@ pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(range): Query<LogRange>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Check if the stream exists before upgrading the WebSocket
     let _stream = deployment
         .container()
-        .stream_raw_logs(&exec_id)
+        .stream_raw_logs(&exec_id, range)
         .await
         .ok_or_else(|| {
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
+        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id, range).await {
             tracing::warn!("raw logs WS closed: {}", e);
         }
     }))
@@ -62,6 +77,7 @@ async fn handle_raw_logs_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     exec_id: Uuid,
+    range: LogRange,
 ) -> anyhow::Result<()> {
     use std::sync::{
         Arc,
@@ -74,11 +90,12 @@ async fn handle_raw_logs_ws(
     // Get the raw stream and convert to JSON patches on-the-fly
     let raw_stream = deployment
         .container()
-        .stream_raw_logs(&exec_id)
+        .stream_raw_logs(&exec_id, range)
         .await
         .ok_or_else(|| anyhow::anyhow!("Execution process not found"))?;
 
-    let counter = Arc::new(AtomicUsize::new(0));
+    // Patch indices are absolute so pages can be merged client-side
+    let counter = Arc::new(AtomicUsize::new(range.offset));
     let mut stream = raw_stream.map_ok({
         let counter = counter.clone();
         move |m| match m {
@@ -124,10 +141,11 @@ pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    Query(range): Query<LogRange>,
 ) -> Result<impl IntoResponse, ApiError> {
     let stream = deployment
         .container()
-        .stream_normalized_logs(&exec_id)
+        .stream_normalized_logs(&exec_id, range)
         .await
         .ok_or_else(|| {
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
@@ -233,6 +251,15 @@ async fn handle_execution_processes_by_session_ws(
     Ok(())
 }
 
+/// Stored log size, so clients know how many lines they can page through
+pub async fn get_execution_process_log_stats(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcessLogStats>>, ApiError> {
+    let stats = ExecutionProcessLogs::stats(&deployment.db().pool, execution_process.id).await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
 pub async fn get_execution_process_repo_states(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -248,6 +275,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
         .route("/repo-states", get(get_execution_process_repo_states))
+        .route("/raw-logs/stats", get(get_execution_process_log_stats))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
@@ -256,6 +284,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         ));
 
     let workspaces_router = Router::new()
+        .route("/", get(get_execution_processes))
         .route(
             "/stream/session/ws",
             get(stream_execution_processes_by_session_ws),
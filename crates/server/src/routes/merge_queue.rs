@@ -0,0 +1,180 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    merge::{Merge, MergeStatus},
+    merge_queue::{MergeQueueEntry, MergeQueueError, MergeQueueStatus},
+    repo::{Repo, RepoError},
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use git2::BranchType;
+use serde::Deserialize;
+use services::services::{
+    container::ContainerService,
+    merge_queue::{MergeQueueItem, list_merge_queue},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Request body for adding a workspace to a repo's merge queue
+#[derive(Debug, Deserialize, TS)]
+pub struct EnqueueMergeRequest {
+    pub workspace_id: Uuid,
+}
+
+async fn find_repo(deployment: &DeploymentImpl, repo_id: Uuid) -> Result<Repo, ApiError> {
+    Ok(Repo::find_by_id(&deployment.db().pool, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?)
+}
+
+async fn find_entry(
+    deployment: &DeploymentImpl,
+    repo_id: Uuid,
+    entry_id: Uuid,
+) -> Result<MergeQueueEntry, ApiError> {
+    Ok(MergeQueueEntry::find_by_id(&deployment.db().pool, entry_id)
+        .await?
+        .filter(|e| e.repo_id == repo_id)
+        .ok_or(MergeQueueError::NotFound)?)
+}
+
+async fn queue_response(
+    deployment: &DeploymentImpl,
+    repo: &Repo,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueItem>>>, ApiError> {
+    let items = list_merge_queue(&deployment.db().pool, deployment.git(), repo).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub async fn get_merge_queue(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueItem>>>, ApiError> {
+    let repo = find_repo(&deployment, repo_id).await?;
+    queue_response(&deployment, &repo).await
+}
+
+/// Append a workspace to the repo's merge queue. It is rebased, checked and merged
+/// into its target branch once every workspace queued before it has landed.
+pub async fn enqueue_merge(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    Json(payload): Json<EnqueueMergeRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueItem>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = find_repo(&deployment, repo_id).await?;
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, payload.workspace_id, repo.id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, payload.workspace_id, repo.id).await?;
+    if merges
+        .iter()
+        .any(|m| matches!(m, Merge::Pr(pr) if matches!(pr.pr_info.status, MergeStatus::Open)))
+    {
+        return Err(ApiError::BadRequest(
+            "Cannot queue a merge when a pull request is open for this repository.".to_string(),
+        ));
+    }
+    if deployment
+        .git()
+        .find_branch_type(&repo.path, &workspace_repo.target_branch)?
+        == BranchType::Remote
+    {
+        return Err(ApiError::BadRequest(
+            "Cannot queue a merge into a remote branch. Please create a pull request instead."
+                .to_string(),
+        ));
+    }
+
+    MergeQueueEntry::create(pool, repo.id, payload.workspace_id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "merge_queued",
+            serde_json::json!({
+                "repo_id": repo.id.to_string(),
+                "workspace_id": payload.workspace_id.to_string(),
+            }),
+        )
+        .await;
+
+    queue_response(&deployment, &repo).await
+}
+
+/// Remove an entry from the queue, stopping its check script if one is running.
+/// Removing a paused entry lets the rest of the queue continue.
+pub async fn remove_merge_queue_entry(
+    State(deployment): State<DeploymentImpl>,
+    Path((repo_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueItem>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = find_repo(&deployment, repo_id).await?;
+    let entry = find_entry(&deployment, repo_id, entry_id).await?;
+
+    MergeQueueEntry::delete(pool, entry.id).await?;
+    if entry.status == MergeQueueStatus::Checking
+        && let Some(process_id) = entry.execution_process_id
+        && let Some(process) = ExecutionProcess::find_by_id(pool, process_id).await?
+        && process.status == ExecutionProcessStatus::Running
+    {
+        deployment
+            .container()
+            .stop_execution(&process, ExecutionProcessStatus::Killed)
+            .await?;
+    }
+
+    queue_response(&deployment, &repo).await
+}
+
+/// Retry a failed or conflicted entry, resuming the queue. Conflicts must be resolved
+/// and the rebase continued in the workspace first.
+pub async fn retry_merge_queue_entry(
+    State(deployment): State<DeploymentImpl>,
+    Path((repo_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueItem>>>, ApiError> {
+    let repo = find_repo(&deployment, repo_id).await?;
+    let entry = find_entry(&deployment, repo_id, entry_id).await?;
+    if !entry.status.is_paused() {
+        return Err(ApiError::BadRequest(
+            "Only failed or conflicted entries can be retried.".to_string(),
+        ));
+    }
+
+    MergeQueueEntry::update_status(
+        &deployment.db().pool,
+        entry.id,
+        MergeQueueStatus::Queued,
+        None,
+        None,
+    )
+    .await?;
+
+    queue_response(&deployment, &repo).await
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/repos/{repo_id}/merge-queue",
+            get(get_merge_queue).post(enqueue_merge),
+        )
+        .route(
+            "/repos/{repo_id}/merge-queue/{entry_id}",
+            delete(remove_merge_queue_entry),
+        )
+        .route(
+            "/repos/{repo_id}/merge-queue/{entry_id}/retry",
+            post(retry_merge_queue_entry),
+        )
+}
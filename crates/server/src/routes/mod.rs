@@ -1,11 +1,14 @@
+use std::net::SocketAddr;
+
 use axum::{
-    Router,
-    routing::{IntoMakeService, get},
+    Router, extract::connect_info::IntoMakeServiceWithConnectInfo, middleware::from_fn_with_state,
+    routing::get,
 };
 use tower_http::validate_request::ValidateRequestHeaderLayer;
 
 use crate::{DeploymentImpl, middleware};
 
+pub mod api_tokens;
 pub mod approvals;
 pub mod config;
 pub mod containers;
@@ -16,6 +19,7 @@ pub mod execution_processes;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod merge_queue;
 pub mod migration;
 pub mod oauth;
 pub mod organizations;
@@ -29,8 +33,9 @@ pub mod tags;
 pub mod task_attempts;
 pub mod tasks;
 pub mod terminal;
+pub mod token_usage;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl) -> IntoMakeServiceWithConnectInfo<Router, SocketAddr> {
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
@@ -45,6 +50,7 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
+        .merge(merge_queue::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .merge(scratch::router(&deployment))
@@ -52,16 +58,22 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(migration::router())
         .merge(sessions::router(&deployment))
         .merge(terminal::router())
+        .merge(token_usage::router())
+        .merge(api_tokens::router())
         .nest("/remote", remote::router())
         .nest("/images", images::routes())
         .layer(ValidateRequestHeaderLayer::custom(
             middleware::validate_origin,
         ))
-        .with_state(deployment);
+        .with_state(deployment.clone());
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
+        .layer(from_fn_with_state(
+            deployment,
+            middleware::require_api_token,
+        ))
+        .into_make_service_with_connect_info::<SocketAddr>()
 }
@@ -10,11 +10,13 @@ use db::models::{
     repo::{Repo, UpdateRepo},
 };
 use deployment::Deployment;
-use git::{GitBranch, GitRemote};
+use git::{GitBranch, GitRemote, MergeStrategy};
 use serde::{Deserialize, Serialize};
 use services::services::{
+    approvals::policy::ApprovalPolicy,
     file_search::SearchQuery,
     git_host::{GitHostError, GitHostProvider, GitHostService, OpenPrInfo, ProviderKind},
+    repo_config::{EffectiveRepoConfig, trust_config_file, untrust_config_file},
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -135,11 +137,74 @@ pub async fn get_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+/// Settings the repository runs with, merging its `.vibe-kanban.toml` into the stored ones
+pub async fn get_repo_config(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<EffectiveRepoConfig>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(
+        EffectiveRepoConfig::for_repo(&deployment.db().pool, &repo).await?,
+    )))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TrustRepoConfigRequest {
+    /// Hash of the config file version the user reviewed
+    pub hash: String,
+}
+
+/// Approve running the scripts of the repository's `.vibe-kanban.toml`
+pub async fn trust_repo_config(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    ResponseJson(payload): ResponseJson<TrustRepoConfigRequest>,
+) -> Result<ResponseJson<ApiResponse<EffectiveRepoConfig>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    trust_config_file(pool, &repo, &payload.hash).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "repo_config_trusted",
+            serde_json::json!({ "repo_id": repo_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        EffectiveRepoConfig::for_repo(pool, &repo).await?,
+    )))
+}
+
+/// Withdraw the approval of the repository's `.vibe-kanban.toml` scripts
+pub async fn untrust_repo_config(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<EffectiveRepoConfig>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repo = deployment.repo().get_by_id(pool, repo_id).await?;
+    untrust_config_file(pool, &repo).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        EffectiveRepoConfig::for_repo(pool, &repo).await?,
+    )))
+}
+
 pub async fn update_repo(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
     ResponseJson(payload): ResponseJson<UpdateRepo>,
 ) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    if let Some(Some(policy)) = &payload.approval_policy {
+        ApprovalPolicy::parse(policy).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    }
+    if let Some(Some(strategy)) = &payload.default_merge_strategy {
+        strategy
+            .parse::<MergeStrategy>()
+            .map_err(ApiError::BadRequest)?;
+    }
     let repo = Repo::update(&deployment.db().pool, repo_id, &payload).await?;
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
@@ -297,6 +362,11 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/repos/init", post(init_repo))
         .route("/repos/batch", post(get_repos_batch))
         .route("/repos/{repo_id}", get(get_repo).put(update_repo))
+        .route("/repos/{repo_id}/config", get(get_repo_config))
+        .route(
+            "/repos/{repo_id}/config/trust",
+            post(trust_repo_config).delete(untrust_repo_config),
+        )
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
         .route("/repos/{repo_id}/remotes", get(get_repo_remotes))
         .route("/repos/{repo_id}/prs", get(list_open_prs))
@@ -4,8 +4,9 @@ pub mod review;
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
+    http::header,
     middleware::from_fn_with_state,
-    response::Json as ResponseJson,
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
 use db::models::{
@@ -23,8 +24,11 @@ use executors::{
     },
     profile::ExecutorProfileId,
 };
-use serde::Deserialize;
-use services::services::container::ContainerService;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    transcript::{TranscriptFormat, build_session_transcript},
+};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -36,6 +40,12 @@ pub struct SessionQuery {
     pub workspace_id: Uuid,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TranscriptQuery {
+    #[serde(default)]
+    pub format: TranscriptFormat,
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateSessionRequest {
     pub workspace_id: Uuid,
@@ -83,7 +93,7 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub executor_profile_id: ExecutorProfileId,
@@ -158,7 +168,10 @@ pub async fn follow_up(
     let prompt = payload.prompt;
 
     let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-    let cleanup_action = deployment.container().cleanup_actions_for_repos(&repos);
+    let cleanup_action = deployment
+        .container()
+        .cleanup_actions_for_repos(&repos)
+        .await;
 
     let working_dir = workspace
         .agent_working_dir
@@ -194,6 +207,7 @@ pub async fn follow_up(
             &session,
             &action,
             &ExecutionProcessRunReason::CodingAgent,
+            None,
         )
         .await?;
 
@@ -232,12 +246,39 @@ pub async fn reset_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Download the conversation of every coding agent turn in the session
+pub async fn export_transcript(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TranscriptQuery>,
+) -> Result<Response, ApiError> {
+    let transcript = build_session_transcript(deployment.container(), &session).await?;
+    let body = transcript.render(query.format)?;
+
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        transcript.file_name(query.format)
+    );
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
         .route("/reset", post(reset_process))
         .route("/review", post(review::start_review))
+        .route("/transcript", get(export_transcript))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
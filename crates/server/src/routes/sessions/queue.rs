@@ -1,6 +1,9 @@
 use axum::{
-    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
-    response::Json as ResponseJson, routing::get,
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
 };
 use db::models::{scratch::DraftFollowUpData, session::Session};
 use deployment::Deployment;
@@ -9,6 +12,7 @@ use serde::Deserialize;
 use services::services::queued_message::QueueStatus;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_session_middleware};
 
@@ -19,7 +23,15 @@ pub struct QueueMessageRequest {
     pub executor_profile_id: ExecutorProfileId,
 }
 
-/// Queue a follow-up message to be executed when the current execution finishes
+/// Request body for reordering a session's queued messages
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderQueueRequest {
+    /// Every queued message id of the session, in the desired send order
+    pub message_ids: Vec<Uuid>,
+}
+
+/// Append a follow-up message to be executed after the current execution and any
+/// messages queued before it
 pub async fn queue_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
@@ -30,9 +42,10 @@ pub async fn queue_message(
         executor_profile_id: payload.executor_profile_id,
     };
 
-    let queued = deployment
+    deployment
         .queued_message_service()
-        .queue_message(session.id, data);
+        .queue_message(session.id, data)
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -44,19 +57,23 @@ pub async fn queue_message(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(QueueStatus::Queued {
-        message: queued,
-    })))
+    let status = deployment
+        .queued_message_service()
+        .get_status(session.id)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(status)))
 }
 
-/// Cancel a queued follow-up message
+/// Cancel all queued follow-up messages
 pub async fn cancel_queued_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
     deployment
         .queued_message_service()
-        .cancel_queued(session.id);
+        .cancel_queued(session.id)
+        .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -76,21 +93,78 @@ pub async fn get_queue_status(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
-    let status = deployment.queued_message_service().get_status(session.id);
+    let status = deployment
+        .queued_message_service()
+        .get_status(session.id)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+/// Reorder the queued messages of a session
+pub async fn reorder_queued_messages(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderQueueRequest>,
+) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    let messages = deployment
+        .queued_message_service()
+        .reorder(session.id, &payload.message_ids)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(messages.into())))
+}
+
+/// Edit the content of a single queued message
+pub async fn update_queued_message(
+    Path((session_id, message_id)): Path<(Uuid, Uuid)>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<QueueMessageRequest>,
+) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    let data = DraftFollowUpData {
+        message: payload.message,
+        executor_profile_id: payload.executor_profile_id,
+    };
+
+    let service = deployment.queued_message_service();
+    service.update_queued(session_id, message_id, data).await?;
+    let status = service.get_status(session_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+/// Remove a single queued message
+pub async fn delete_queued_message(
+    Path((session_id, message_id)): Path<(Uuid, Uuid)>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    let service = deployment.queued_message_service();
+    service.remove_queued(session_id, message_id).await?;
+    let status = service.get_status(session_id).await?;
 
     Ok(ResponseJson(ApiResponse::success(status)))
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
-    Router::new()
+    let session_router = Router::new()
         .route(
             "/",
             get(get_queue_status)
                 .post(queue_message)
                 .delete(cancel_queued_message),
         )
+        .route("/reorder", post(reorder_queued_messages))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
-        ))
+        ));
+
+    // The session middleware only understands a single path parameter, so message
+    // routes check session ownership themselves
+    let message_router = Router::new().route(
+        "/{message_id}",
+        put(update_queued_message).delete(delete_queued_message),
+    );
+
+    session_router.merge(message_router)
 }
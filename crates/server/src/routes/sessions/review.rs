@@ -120,6 +120,7 @@ pub async fn start_review(
             &session,
             &action,
             &ExecutionProcessRunReason::CodingAgent,
+            None,
         )
         .await?;
 
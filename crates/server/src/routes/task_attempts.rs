@@ -1,5 +1,7 @@
 pub mod codex_setup;
+pub mod conflicts;
 pub mod cursor_setup;
+pub mod fan_out;
 pub mod gh_cli_setup;
 pub mod images;
 pub mod pr;
@@ -25,6 +27,7 @@ use axum::{
 use db::models::{
     coding_agent_turn::CodingAgentTurn,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    fan_out::FanOutWorkspace,
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
@@ -41,11 +44,17 @@ use executors::{
     executors::{CodingAgent, ExecutorError},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
-use git::{ConflictOp, GitCliError, GitService, GitServiceError};
+use git::{ConflictOp, GitCliError, GitService, GitServiceError, MergeStrategy};
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService, diff_stream, remote_client::RemoteClientError, remote_sync,
+    container::ContainerService,
+    diff_stream,
+    merge_queue::merge_commit_message,
+    remote_client::RemoteClientError,
+    remote_sync,
+    repo_config::{effective_repo, effective_repos},
+    task_scheduler,
     workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
@@ -194,6 +203,10 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Further executor profiles to run the task with side by side, one workspace each
+    #[serde(default)]
+    #[ts(optional)]
+    pub fan_out: Option<Vec<ExecutorProfileId>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
@@ -235,6 +248,7 @@ pub async fn create_task_attempt(
         let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
             .await?
             .ok_or(RepoError::NotFound)?;
+        let repo = effective_repo(pool, repo).await;
         match repo.default_working_dir {
             Some(subdir) => {
                 let path = PathBuf::from(&repo.name).join(&subdir);
@@ -246,6 +260,83 @@ pub async fn create_task_attempt(
         None
     };
 
+    // A fan-out starts one workspace per profile
+    let profiles = fan_out::attempt_profiles(executor_profile_id, payload.fan_out.as_deref())?;
+    let fan_out_id = (profiles.len() > 1).then(Uuid::new_v4);
+
+    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
+        .repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+        })
+        .collect();
+
+    let mut workspaces = Vec::with_capacity(profiles.len());
+    for (position, executor_profile_id) in profiles.into_iter().enumerate() {
+        let fan_out = fan_out_id.map(|id| (id, position as i64, &executor_profile_id));
+        let workspace = match create_attempt_workspace(
+            &deployment,
+            &task,
+            agent_working_dir.clone(),
+            &workspace_repos,
+            fan_out,
+        )
+        .await
+        {
+            Ok(workspace) => workspace,
+            Err(err) => {
+                // Don't leave a partial fan-out behind
+                discard_workspaces(&deployment, &workspaces).await;
+                return Err(err);
+            }
+        };
+        if let Err(err) = deployment
+            .container()
+            .start_workspace(&workspace, executor_profile_id.clone())
+            .await
+        {
+            tracing::error!("Failed to start task attempt: {}", err);
+        }
+
+        deployment
+            .track_if_analytics_allowed(
+                "task_attempt_started",
+                serde_json::json!({
+                    "task_id": workspace.task_id.to_string(),
+                    "variant": &executor_profile_id.variant,
+                    "executor": &executor_profile_id.executor,
+                    "workspace_id": workspace.id.to_string(),
+                    "repository_count": payload.repos.len(),
+                    "fan_out_id": fan_out_id.map(|id| id.to_string()),
+                }),
+            )
+            .await;
+
+        workspaces.push(workspace);
+    }
+
+    tracing::info!(
+        "Created {} attempt(s) for task {}",
+        workspaces.len(),
+        task.id
+    );
+
+    // Fan-out siblings are reachable through the fan-out comparison of the first workspace
+    let workspace = workspaces.swap_remove(0);
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
+
+/// Creates the workspace of one attempt along with its repos and fan-out membership
+async fn create_attempt_workspace(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    agent_working_dir: Option<String>,
+    workspace_repos: &[CreateWorkspaceRepo],
+    fan_out: Option<(Uuid, i64, &ExecutorProfileId)>,
+) -> Result<Workspace, ApiError> {
+    let pool = &deployment.db().pool;
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
@@ -255,48 +346,62 @@ pub async fn create_task_attempt(
     let workspace = Workspace::create(
         pool,
         &CreateWorkspace {
-            branch: git_branch_name.clone(),
+            branch: git_branch_name,
             agent_working_dir,
         },
         attempt_id,
-        payload.task_id,
+        task.id,
     )
     .await?;
 
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
-
-    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
-    if let Err(err) = deployment
-        .container()
-        .start_workspace(&workspace, executor_profile_id.clone())
-        .await
-    {
-        tracing::error!("Failed to start task attempt: {}", err);
+    let linked = async {
+        WorkspaceRepo::create_many(pool, workspace.id, workspace_repos).await?;
+        if let Some((fan_out_id, position, executor_profile_id)) = fan_out {
+            FanOutWorkspace::create(
+                pool,
+                fan_out_id,
+                task.id,
+                workspace.id,
+                position,
+                executor_profile_id,
+            )
+            .await?;
+        }
+        Ok::<_, ApiError>(())
     }
+    .await;
 
-    deployment
-        .track_if_analytics_allowed(
-            "task_attempt_started",
-            serde_json::json!({
-                "task_id": workspace.task_id.to_string(),
-                "variant": &executor_profile_id.variant,
-                "executor": &executor_profile_id.executor,
-                "workspace_id": workspace.id.to_string(),
-                "repository_count": payload.repos.len(),
-            }),
-        )
-        .await;
-
-    tracing::info!("Created attempt for task {}", task.id);
+    if let Err(err) = linked {
+        // Repos and fan-out rows of the workspace go with it (FK CASCADE)
+        if let Err(e) = Workspace::delete(pool, workspace.id).await {
+            tracing::error!("Failed to remove workspace {}: {}", workspace.id, e);
+        }
+        return Err(err);
+    }
+    Ok(workspace)
+}
 
-    Ok(ResponseJson(ApiResponse::success(workspace)))
+/// Stops and removes workspaces created by a request that failed part way, worktrees
+/// included
+async fn discard_workspaces(deployment: &DeploymentImpl, workspaces: &[Workspace]) {
+    let pool = &deployment.db().pool;
+    for workspace in workspaces {
+        // Starting the workspace may have set up its worktrees since it was created
+        let workspace = match Workspace::find_by_id(pool, workspace.id).await {
+            Ok(Some(workspace)) => workspace,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("Failed to load workspace {}: {}", workspace.id, e);
+                workspace.clone()
+            }
+        };
+        if let Err(e) = deployment.container().delete(&workspace).await {
+            tracing::error!("Failed to clean up workspace {}: {}", workspace.id, e);
+        }
+        if let Err(e) = Workspace::delete(pool, workspace.id).await {
+            tracing::error!("Failed to remove workspace {}: {}", workspace.id, e);
+        }
+    }
 }
 
 #[axum::debug_handler]
@@ -452,6 +557,10 @@ async fn handle_workspaces_ws(
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
+    /// Overrides the repo's default merge strategy
+    #[serde(default)]
+    #[ts(optional)]
+    pub strategy: Option<MergeStrategy>,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -509,25 +618,26 @@ pub async fn merge_task_attempt(
         .parent_task(pool)
         .await?
         .ok_or(ApiError::Workspace(WorkspaceError::TaskNotFound))?;
-    let task_uuid_str = task.id.to_string();
-    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
-
-    let mut commit_message = format!("{} (vibe-kanban {})", task.title, first_uuid_section);
-
-    // Add description on next line if it exists
-    if let Some(description) = &task.description
-        && !description.trim().is_empty()
-    {
-        commit_message.push_str("\n\n");
-        commit_message.push_str(description);
-    }
+    let commit_message = merge_commit_message(&task);
+
+    let strategy = match request.strategy {
+        Some(strategy) => strategy,
+        None => repo
+            .default_merge_strategy
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(ApiError::BadRequest)?
+            .unwrap_or_default(),
+    };
 
-    let merge_commit_id = deployment.git().merge_changes(
+    let merge_commit_id = deployment.git().merge_changes_with_strategy(
         &repo.path,
         &worktree_path,
         &workspace.branch,
         &workspace_repo.target_branch,
         &commit_message,
+        strategy,
     )?;
 
     Merge::create_direct(
@@ -545,12 +655,21 @@ pub async fn merge_task_attempt(
         tracing::error!("Failed to archive workspace {}: {}", workspace.id, e);
     }
 
+    let container = deployment.container().clone();
+    let task_id = task.id;
+    tokio::spawn(async move {
+        if let Err(e) = task_scheduler::start_unblocked_dependents(&container, task_id).await {
+            tracing::error!("Failed to start dependents of task {}: {}", task_id, e);
+        }
+    });
+
     deployment
         .track_if_analytics_allowed(
             "task_attempt_merged",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "workspace_id": workspace.id.to_string(),
+                "merge_strategy": strategy.as_str(),
             }),
         )
         .await;
@@ -1313,7 +1432,11 @@ pub async fn start_dev_server(
         }
     }
 
-    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let repos = effective_repos(
+        pool,
+        &WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?,
+    )
+    .await;
     let repos_with_dev_script: Vec<_> = repos
         .iter()
         .filter(|r| r.dev_server_script.as_ref().is_some_and(|s| !s.is_empty()))
@@ -1359,6 +1482,7 @@ pub async fn start_dev_server(
                 &session,
                 &executor_action,
                 &ExecutionProcessRunReason::DevServer,
+                None,
             )
             .await?;
         execution_processes.push(execution_process);
@@ -1466,7 +1590,7 @@ pub async fn run_setup_script(
         .ok_or(SqlxError::RowNotFound)?;
 
     let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-    let executor_action = match deployment.container().setup_actions_for_repos(&repos) {
+    let executor_action = match deployment.container().setup_actions_for_repos(&repos).await {
         Some(action) => action,
         None => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -1496,6 +1620,7 @@ pub async fn run_setup_script(
             &session,
             &executor_action,
             &ExecutionProcessRunReason::SetupScript,
+            None,
         )
         .await?;
 
@@ -1545,7 +1670,11 @@ pub async fn run_cleanup_script(
         .ok_or(SqlxError::RowNotFound)?;
 
     let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-    let executor_action = match deployment.container().cleanup_actions_for_repos(&repos) {
+    let executor_action = match deployment
+        .container()
+        .cleanup_actions_for_repos(&repos)
+        .await
+    {
         Some(action) => action,
         None => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -1575,6 +1704,7 @@ pub async fn run_cleanup_script(
             &session,
             &executor_action,
             &ExecutionProcessRunReason::CleanupScript,
+            None,
         )
         .await?;
 
@@ -1621,7 +1751,11 @@ pub async fn run_archive_script(
         .ok_or(SqlxError::RowNotFound)?;
 
     let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-    let executor_action = match deployment.container().archive_actions_for_repos(&repos) {
+    let executor_action = match deployment
+        .container()
+        .archive_actions_for_repos(&repos)
+        .await
+    {
         Some(action) => action,
         None => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
@@ -1649,6 +1783,7 @@ pub async fn run_archive_script(
             &session,
             &executor_action,
             &ExecutionProcessRunReason::ArchiveScript,
+            None,
         )
         .await?;
 
@@ -1961,6 +2096,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .route("/rebase", post(rebase_task_attempt))
                 .route("/rebase/continue", post(continue_rebase_task_attempt))
                 .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+                .route(
+                    "/conflicts/resolve",
+                    post(conflicts::resolve_conflicts_task_attempt),
+                )
                 .route("/pr", post(pr::create_pr))
                 .route("/pr/attach", post(pr::attach_existing_pr))
                 .route("/pr/comments", get(pr::get_pr_comments))
@@ -1973,6 +2112,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .route("/first-message", get(get_first_user_message))
                 .route("/mark-seen", put(mark_seen))
                 .route("/link", post(link_workspace))
+                .route("/fan-out", get(fan_out::get_fan_out_comparison))
+                .route("/fan-out/keep", post(fan_out::keep_fan_out_attempt))
                 .layer(from_fn_with_state(
                     deployment.clone(),
                     load_workspace_middleware,
@@ -70,6 +70,7 @@ pub async fn run_codex_setup(
             &session,
             &executor_action,
             &ExecutionProcessRunReason::SetupScript,
+            None,
         )
         .await?;
     Ok(execution_process)
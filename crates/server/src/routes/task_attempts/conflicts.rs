@@ -0,0 +1,226 @@
+use std::path::Path;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process_repo_state::ExecutionProcessRepoState,
+    repo::{Repo, RepoError},
+    session::{CreateSession, Session, SessionError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+        coding_agent_initial::CodingAgentInitialRequest,
+    },
+    profile::ExecutorProfileId,
+};
+use git::{ConflictOp, ConflictSides};
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct ResolveConflictsRequest {
+    pub repo_id: Uuid,
+    /// Session to resolve the conflicts in; defaults to the workspace's latest session
+    pub session_id: Option<Uuid>,
+    /// Defaults to the executor profile last used in the session
+    pub executor_profile_id: Option<ExecutorProfileId>,
+}
+
+fn op_label(op: &ConflictOp) -> &'static str {
+    match op {
+        ConflictOp::Rebase => "rebase",
+        ConflictOp::Merge => "merge",
+        ConflictOp::CherryPick => "cherry-pick",
+        ConflictOp::Revert => "revert",
+    }
+}
+
+fn build_resolve_conflicts_prompt(
+    repo_name: &str,
+    branch: &str,
+    target_branch: &str,
+    op: &ConflictOp,
+    conflicted_files: &[String],
+    sides: &ConflictSides,
+) -> String {
+    let label = op_label(op);
+    let files = conflicted_files
+        .iter()
+        .map(|file| format!("- `{file}`"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (head_role, incoming_role) = match op {
+        ConflictOp::Rebase => (
+            format!("the rebased history of `{target_branch}`"),
+            format!("the commit from `{branch}` being replayed"),
+        ),
+        _ => (
+            format!("`{branch}`"),
+            format!("the commit being applied by the {label}"),
+        ),
+    };
+    let describe = |summary: &Option<String>| summary.as_deref().unwrap_or("unknown").to_string();
+
+    format!(
+        "A {label} of `{branch}` onto `{target_branch}` in the `{repo_name}` repository stopped \
+         with conflicts.\n\n\
+         Conflicted files:\n{files}\n\n\
+         Commits on each side:\n\
+         - HEAD, {head_role}: {head}\n\
+         - Incoming, {incoming_role}: {incoming}\n\n\
+         Resolve every conflict so that the intent of both sides is kept, remove all conflict \
+         markers and stage each resolved file with `git add`. Do not commit, continue or abort \
+         the {label} yourself; it is completed automatically once you finish.",
+        head = describe(&sides.head),
+        incoming = describe(&sides.incoming),
+    )
+}
+
+/// Start a coding agent turn that resolves the conflicts of the repo's in-progress
+/// rebase, merge, cherry-pick or revert. Once the turn finishes with no conflict
+/// markers left, the operation is completed automatically.
+#[axum::debug_handler]
+pub async fn resolve_conflicts_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictsRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let worktree_path = Path::new(&container_ref).join(&repo.name);
+
+    let conflicted_files = deployment.git().get_conflicted_files(&worktree_path)?;
+    let op = match deployment.git().detect_conflict_op(&worktree_path)? {
+        Some(op) if !conflicted_files.is_empty() => op,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "There are no conflicts to resolve in this repository.".to_string(),
+            ));
+        }
+    };
+    let sides = deployment.git().get_conflict_sides(&worktree_path, &op)?;
+
+    let session = match payload.session_id {
+        Some(session_id) => Session::find_by_id(pool, session_id)
+            .await?
+            .filter(|s| s.workspace_id == workspace.id)
+            .ok_or(SessionError::NotFound)?,
+        None => match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(s) => s,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    workspace.id,
+                )
+                .await?
+            }
+        },
+    };
+
+    let latest_executor_profile =
+        ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
+    let Some(executor_profile_id) = payload
+        .executor_profile_id
+        .or_else(|| latest_executor_profile.clone())
+    else {
+        return Err(ApiError::BadRequest(
+            "Choose a coding agent to resolve the conflicts with.".to_string(),
+        ));
+    };
+    if let Some(expected) = latest_executor_profile
+        .map(|profile| profile.executor.to_string())
+        .or_else(|| session.executor.clone())
+    {
+        let actual = executor_profile_id.executor.to_string();
+        if expected != actual {
+            return Err(ApiError::Session(SessionError::ExecutorMismatch {
+                expected,
+                actual,
+            }));
+        }
+    }
+    if session.executor.is_none() {
+        Session::update_executor(pool, session.id, &executor_profile_id.executor.to_string())
+            .await?;
+    }
+
+    let prompt = build_resolve_conflicts_prompt(
+        &repo.name,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+        &op,
+        &conflicted_files,
+        &sides,
+    );
+    let working_dir = workspace
+        .agent_working_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .cloned();
+
+    let action_type = match CodingAgentTurn::find_latest_session_info(pool, session.id).await? {
+        Some(info) => ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+            prompt,
+            session_id: info.session_id,
+            reset_to_message_id: None,
+            executor_profile_id,
+            working_dir,
+        }),
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id,
+            working_dir,
+        }),
+    };
+    let action = ExecutorAction::new(action_type, None);
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+            None,
+        )
+        .await?;
+    ExecutionProcessRepoState::set_resolves_conflicts(pool, execution_process.id, repo.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "conflict_resolution_started",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": repo.id.to_string(),
+                "conflict_op": op_label(&op),
+                "conflicted_files": conflicted_files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
@@ -0,0 +1,316 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{
+        CheckStatus, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
+    fan_out::FanOutWorkspace,
+    session::Session,
+    token_usage::TokenUsageScope,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::Serialize;
+use services::services::{
+    container::ContainerService,
+    diff_stream, remote_sync,
+    token_usage::{TokenUsageSummary, token_usage_report},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::workspace_summary::{DiffStats, compute_workspace_diff_stats},
+};
+
+/// One workspace of a fan-out and how its attempt went
+#[derive(Debug, Serialize, TS)]
+pub struct FanOutAttempt {
+    pub workspace: Workspace,
+    pub executor_profile_id: ExecutorProfileId,
+    pub is_running: bool,
+    pub diff_stats: Option<DiffStats>,
+    /// Check script result of the latest coding agent turn that ran the checks
+    pub check_status: Option<CheckStatus>,
+    pub token_usage: TokenUsageSummary,
+    /// Time the coding agent spent running, summed over its turns
+    #[ts(type = "number")]
+    pub duration_secs: i64,
+}
+
+/// The workspaces started together on a task with different executor profiles
+#[derive(Debug, Serialize, TS)]
+pub struct FanOutComparison {
+    pub fan_out_id: Uuid,
+    pub task_id: Uuid,
+    pub attempts: Vec<FanOutAttempt>,
+}
+
+/// The executor profiles to start a workspace with, the requested one first
+pub(super) fn attempt_profiles(
+    executor_profile_id: ExecutorProfileId,
+    fan_out: Option<&[ExecutorProfileId]>,
+) -> Result<Vec<ExecutorProfileId>, ApiError> {
+    let mut profiles = vec![executor_profile_id];
+    for profile in fan_out.into_iter().flatten() {
+        if profiles.contains(profile) {
+            return Err(ApiError::BadRequest(format!(
+                "Executor profile {profile} is requested more than once"
+            )));
+        }
+        profiles.push(profile.clone());
+    }
+    Ok(profiles)
+}
+
+/// Time spent running the coding agent turns, and the check result of the latest turn
+/// that ran the checks. Turns still running count up to `now`.
+fn summarize_turns(turns: &[ExecutionProcess], now: DateTime<Utc>) -> (i64, Option<CheckStatus>) {
+    let duration_secs = turns
+        .iter()
+        .map(|process| {
+            (process.completed_at.unwrap_or(now) - process.started_at)
+                .num_seconds()
+                .max(0)
+        })
+        .sum();
+    let check_status = turns.iter().rev().find_map(|process| process.check_status);
+    (duration_secs, check_status)
+}
+
+/// The fan-out the workspace belongs to and all of its workspaces
+async fn fan_out_members(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<(Uuid, Vec<FanOutWorkspace>), ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(member) = FanOutWorkspace::find_by_workspace_id(pool, workspace.id).await? else {
+        return Err(ApiError::BadRequest(
+            "This workspace was not started as part of a fan-out.".to_string(),
+        ));
+    };
+    let members = FanOutWorkspace::find_by_fan_out_id(pool, member.fan_out_id).await?;
+    Ok((member.fan_out_id, members))
+}
+
+async fn fan_out_attempt(
+    deployment: &DeploymentImpl,
+    member: FanOutWorkspace,
+) -> Result<Option<FanOutAttempt>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(workspace) = Workspace::find_by_id(pool, member.workspace_id).await? else {
+        return Ok(None);
+    };
+
+    let mut coding_agent_turns = Vec::new();
+    for session in Session::find_by_workspace_id(pool, workspace.id).await? {
+        coding_agent_turns.extend(
+            ExecutionProcess::find_by_session_id(pool, session.id, false)
+                .await?
+                .into_iter()
+                .filter(|process| {
+                    process.run_reason == ExecutionProcessRunReason::CodingAgent
+                        && process.status != ExecutionProcessStatus::Queued
+                }),
+        );
+    }
+    coding_agent_turns.sort_by_key(|process| process.created_at);
+
+    let (duration_secs, check_status) = summarize_turns(&coding_agent_turns, Utc::now());
+
+    let is_running =
+        ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+            .await?;
+    let prices = deployment.config().read().await.model_prices.clone();
+    let token_usage = token_usage_report(pool, TokenUsageScope::Workspace(workspace.id), &prices)
+        .await?
+        .total;
+    let diff_stats = compute_workspace_diff_stats(deployment, &workspace).await;
+
+    Ok(Some(FanOutAttempt {
+        workspace,
+        executor_profile_id: member.executor_profile_id.0,
+        is_running,
+        diff_stats,
+        check_status,
+        token_usage,
+        duration_secs,
+    }))
+}
+
+/// Compare the attempts of the fan-out the workspace belongs to: diff stats, check
+/// results, token usage and duration of each, in the order they were requested.
+pub async fn get_fan_out_comparison(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<FanOutComparison>>, ApiError> {
+    let (fan_out_id, members) = fan_out_members(&deployment, &workspace).await?;
+
+    let mut attempts = Vec::with_capacity(members.len());
+    for member in members {
+        if let Some(attempt) = fan_out_attempt(&deployment, member).await? {
+            attempts.push(attempt);
+        }
+    }
+
+    Ok(ResponseJson(ApiResponse::success(FanOutComparison {
+        fan_out_id,
+        task_id: workspace.task_id,
+        attempts,
+    })))
+}
+
+/// Keep this workspace's attempt and stop and archive the other workspaces of its fan-out
+pub async fn keep_fan_out_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (fan_out_id, members) = fan_out_members(&deployment, &workspace).await?;
+
+    let mut archived = 0;
+    for member in members {
+        if member.workspace_id == workspace.id {
+            continue;
+        }
+        let Some(other) = Workspace::find_by_id(pool, member.workspace_id).await? else {
+            continue;
+        };
+        if other.archived {
+            continue;
+        }
+
+        deployment.container().try_stop(&other, false).await;
+        if let Err(e) = deployment.container().archive_workspace(other.id).await {
+            tracing::error!("Failed to archive workspace {}: {}", other.id, e);
+            continue;
+        }
+        archived += 1;
+
+        if let Ok(client) = deployment.remote_client() {
+            let stats = diff_stream::compute_diff_stats(pool, deployment.git(), &other).await;
+            tokio::spawn(async move {
+                remote_sync::sync_workspace_to_remote(
+                    &client,
+                    other.id,
+                    None,
+                    Some(true),
+                    stats.as_ref(),
+                )
+                .await;
+            });
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "fan_out_attempt_kept",
+            serde_json::json!({
+                "fan_out_id": fan_out_id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "archived_workspaces": archived,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use db::test_support::{execution_process, script_action};
+    use executors::{actions::script::ScriptContext, executors::BaseCodingAgent};
+
+    use super::*;
+
+    fn profile(executor: BaseCodingAgent, variant: Option<&str>) -> ExecutorProfileId {
+        ExecutorProfileId {
+            executor,
+            variant: variant.map(str::to_string),
+        }
+    }
+
+    fn turn(
+        started_at: DateTime<Utc>,
+        completed_at: Option<DateTime<Utc>>,
+        check_status: Option<CheckStatus>,
+    ) -> ExecutionProcess {
+        let (status, exit_code) = match completed_at {
+            Some(_) => (ExecutionProcessStatus::Completed, Some(0)),
+            None => (ExecutionProcessStatus::Running, None),
+        };
+        ExecutionProcess {
+            check_status,
+            started_at,
+            completed_at,
+            created_at: started_at,
+            updated_at: started_at,
+            ..execution_process(
+                ExecutionProcessRunReason::CodingAgent,
+                script_action(ScriptContext::SetupScript),
+                status,
+                exit_code,
+            )
+        }
+    }
+
+    #[test]
+    fn attempt_profiles_start_with_the_requested_profile() {
+        let claude = profile(BaseCodingAgent::ClaudeCode, None);
+        let claude_plan = profile(BaseCodingAgent::ClaudeCode, Some("PLAN"));
+        let codex = profile(BaseCodingAgent::Codex, None);
+
+        assert_eq!(
+            attempt_profiles(claude.clone(), None).unwrap(),
+            vec![claude.clone()]
+        );
+        assert_eq!(
+            attempt_profiles(claude.clone(), Some(&[codex.clone(), claude_plan.clone()])).unwrap(),
+            vec![claude, codex, claude_plan]
+        );
+    }
+
+    #[test]
+    fn attempt_profiles_reject_duplicates() {
+        let claude = profile(BaseCodingAgent::ClaudeCode, None);
+        let codex = profile(BaseCodingAgent::Codex, None);
+
+        assert!(matches!(
+            attempt_profiles(claude.clone(), Some(&[claude.clone()])),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            attempt_profiles(claude, Some(&[codex.clone(), codex])),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn summarize_turns_sums_durations_and_takes_the_latest_check() {
+        let now = Utc::now();
+        let turns = vec![
+            turn(
+                now - Duration::seconds(300),
+                Some(now - Duration::seconds(240)),
+                Some(CheckStatus::Failed),
+            ),
+            turn(
+                now - Duration::seconds(200),
+                Some(now - Duration::seconds(170)),
+                Some(CheckStatus::Passed),
+            ),
+            turn(now - Duration::seconds(10), None, None),
+        ];
+
+        assert_eq!(
+            summarize_turns(&turns, now),
+            (100, Some(CheckStatus::Passed))
+        );
+        assert_eq!(summarize_turns(&[], now), (0, None));
+    }
+}
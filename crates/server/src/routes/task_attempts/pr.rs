@@ -29,7 +29,6 @@ use services::services::{
     container::ContainerService,
     git_host::{
         self, CreatePrRequest, GitHostError, GitHostProvider, ProviderKind, UnifiedPrComment,
-        github::GhCli,
     },
     remote_sync,
 };
@@ -177,6 +176,7 @@ async fn trigger_pr_description_follow_up(
             &session,
             &action,
             &ExecutionProcessRunReason::CodingAgent,
+            None,
         )
         .await?;
 
@@ -664,7 +664,7 @@ pub async fn create_workspace_from_pr(
         None => deployment.git().get_default_remote(&repo.path)?,
     };
 
-    // Use target branch initially - we'll switch to PR branch via gh pr checkout
+    // Use target branch initially - we'll switch to PR branch via the provider's PR checkout
     let target_branch_ref = format!("{}/{}", remote.name, payload.base_branch);
 
     let task_id = Uuid::new_v4();
@@ -714,40 +714,41 @@ pub async fn create_workspace_from_pr(
     // Update workspace with container_ref so start_execution can find it
     workspace.container_ref = Some(container_ref.clone());
 
-    // Use gh pr checkout to fetch and switch to the PR branch
+    // Use the provider CLI (e.g. gh pr checkout) to fetch and switch to the PR branch
     // This handles SSH/HTTPS auth correctly regardless of fork URL format
     let worktree_path = PathBuf::from(&container_ref).join(&repo.name);
-    match GhCli::new().get_repo_info(&remote.url, &worktree_path) {
-        Ok(repo_info) => {
-            if let Err(e) = GhCli::new().pr_checkout(
-                &worktree_path,
-                &repo_info.owner,
-                &repo_info.repo_name,
-                payload.pr_number,
-            ) {
-                tracing::error!("Failed to checkout PR branch: {e}");
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    CreateFromPrError::BranchFetchFailed {
-                        message: e.to_string(),
-                    },
-                )));
-            }
-            // Update workspace branch to the actual PR branch
-            Workspace::update_branch_name(pool, workspace.id, &payload.head_branch).await?;
-            workspace.branch = payload.head_branch.clone();
-        }
-        Err(e) => {
-            tracing::error!(
-                "Failed to get repo info for PR checkout (gh CLI may not be installed): {e}"
-            );
+    let git_host = match git_host::GitHostService::from_url(&remote.url) {
+        Ok(host) => host,
+        Err(GitHostError::UnsupportedProvider) => {
             return Ok(ResponseJson(ApiResponse::error_with_data(
-                CreateFromPrError::BranchFetchFailed {
-                    message: format!("Failed to get repository info: {e}"),
-                },
+                CreateFromPrError::UnsupportedProvider,
             )));
         }
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+
+    if let Err(e) = git_host
+        .checkout_pr(&worktree_path, &remote.url, payload.pr_number)
+        .await
+    {
+        tracing::error!("Failed to checkout PR branch: {e}");
+        let error = match e {
+            GitHostError::CliNotInstalled { provider } => {
+                CreateFromPrError::CliNotInstalled { provider }
+            }
+            GitHostError::AuthFailed(message) => CreateFromPrError::AuthFailed { message },
+            GitHostError::UnsupportedProvider => CreateFromPrError::UnsupportedProvider,
+            e => CreateFromPrError::BranchFetchFailed {
+                message: e.to_string(),
+            },
+        };
+        return Ok(ResponseJson(ApiResponse::error_with_data(error)));
     }
 
+    // Update workspace branch to the actual PR branch
+    Workspace::update_branch_name(pool, workspace.id, &payload.head_branch).await?;
+    workspace.branch = payload.head_branch.clone();
+
     Merge::create_pr(
         pool,
         workspace.id,
@@ -760,7 +761,7 @@ pub async fn create_workspace_from_pr(
 
     if payload.run_setup {
         let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-        if let Some(setup_action) = deployment.container().setup_actions_for_repos(&repos) {
+        if let Some(setup_action) = deployment.container().setup_actions_for_repos(&repos).await {
             let session = Session::create(
                 pool,
                 &CreateSession { executor: None },
@@ -776,6 +777,7 @@ pub async fn create_workspace_from_pr(
                     &session,
                     &setup_action,
                     &ExecutionProcessRunReason::SetupScript,
+                    None,
                 )
                 .await
             {
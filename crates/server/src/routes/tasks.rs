@@ -4,7 +4,7 @@ use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
@@ -16,6 +16,9 @@ use db::models::{
     image::TaskImage,
     repo::{Repo, RepoError},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task_dependency::{
+        CreateTaskDependency, TaskDependencies, TaskDependency, TaskDependencyError,
+    },
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -25,7 +28,7 @@ use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService, image::ImageService, remote_client::RemoteClient,
-    workspace_manager::WorkspaceManager,
+    repo_config::effective_repo, workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -312,6 +315,7 @@ pub async fn create_task_and_start(
         let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
             .await?
             .ok_or(RepoError::NotFound)?;
+        let repo = effective_repo(pool, repo).await;
         match repo.default_working_dir {
             Some(subdir) => {
                 let path = PathBuf::from(&repo.name).join(&subdir);
@@ -519,6 +523,61 @@ pub async fn delete_task(
     Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
 }
 
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let dependencies = TaskDependency::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+pub async fn add_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskDependency>,
+) -> Result<ResponseJson<ApiResponse<TaskDependency>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let depends_on = Task::find_by_id(pool, payload.depends_on_task_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Dependency task not found".to_string()))?;
+
+    let dependency = TaskDependency::create(pool, &task, &depends_on).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_dependency_added",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "depends_on_task_id": depends_on.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(dependency)))
+}
+
+/// Route of a single dependency of a task. It has two path params, so it is kept out of the
+/// routes behind `load_task_middleware`, which extracts only one.
+const TASK_DEPENDENCY_ROUTE: &str = "/{task_id}/dependencies/{depends_on_task_id}";
+
+#[derive(Debug, Deserialize)]
+pub struct TaskDependencyPath {
+    pub task_id: Uuid,
+    pub depends_on_task_id: Uuid,
+}
+
+pub async fn remove_task_dependency(
+    State(deployment): State<DeploymentImpl>,
+    Path(path): Path<TaskDependencyPath>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, path.task_id)
+        .await?
+        .ok_or(TaskDependencyError::NotFound)?;
+    TaskDependency::delete(pool, task.id, path.depends_on_task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
@@ -526,6 +585,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route(
+            "/dependencies",
+            get(get_task_dependencies).post(add_task_dependency),
+        )
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
@@ -533,8 +596,61 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
+        .route(TASK_DEPENDENCY_ROUTE, delete(remove_task_dependency))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, middleware::Next};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn dependency_route_extracts_both_ids() {
+        // Mirrors `router`: the single-id routes sit behind a loader that extracts one id
+        let single_id_routes = Router::new()
+            .route("/dependencies", get(|| async { "dependencies" }))
+            .layer(axum::middleware::from_fn(
+                |Path(_task_id): Path<Uuid>, request: Request<Body>, next: Next| async move {
+                    next.run(request).await
+                },
+            ));
+        let app = Router::new().nest(
+            "/tasks",
+            Router::new()
+                .route(
+                    TASK_DEPENDENCY_ROUTE,
+                    delete(|Path(path): Path<TaskDependencyPath>| async move {
+                        format!("{} {}", path.task_id, path.depends_on_task_id)
+                    }),
+                )
+                .nest("/{task_id}", single_id_routes),
+        );
+        let task_id = Uuid::new_v4();
+        let depends_on_task_id = Uuid::new_v4();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!(
+                        "/tasks/{task_id}/dependencies/{depends_on_task_id}"
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, format!("{task_id} {depends_on_task_id}"));
+    }
+}
@@ -1,32 +1,72 @@
 use std::path::PathBuf;
 
 use axum::{
-    Router,
+    Json, Router,
     extract::{
-        Query, State,
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
     },
-    response::IntoResponse,
-    routing::get,
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{delete, get},
 };
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
 use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt};
+use local_deployment::pty::{TerminalAttachment, TerminalSessionInfo};
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
 pub struct TerminalQuery {
-    pub workspace_id: Uuid,
+    /// Attach to this existing session
+    pub session_id: Option<Uuid>,
+    /// Otherwise start a new session in this workspace
+    pub workspace_id: Option<Uuid>,
     #[serde(default = "default_cols")]
     pub cols: u16,
     #[serde(default = "default_rows")]
     pub rows: u16,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TerminalSessionsQuery {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTerminalSession {
+    pub workspace_id: Uuid,
+    pub cols: Option<u16>,
+    pub rows: Option<u16>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TerminalSession {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// Number of terminals currently attached to the session
+    pub viewers: usize,
+}
+
+impl From<TerminalSessionInfo> for TerminalSession {
+    fn from(info: TerminalSessionInfo) -> Self {
+        Self {
+            id: info.id,
+            workspace_id: info.workspace_id,
+            created_at: info.created_at,
+            viewers: info.viewers,
+        }
+    }
+}
+
 fn default_cols() -> u16 {
     80
 }
@@ -45,16 +85,22 @@ enum TerminalCommand {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum TerminalMessage {
-    Output { data: String },
-    Error { message: String },
+    /// First message after attaching; reconnects should attach to this session
+    Session {
+        session_id: Uuid,
+    },
+    Output {
+        data: String,
+    },
+    /// The shell exited or the session was closed
+    Exit,
 }
 
-pub async fn terminal_ws(
-    ws: WebSocketUpgrade,
-    State(deployment): State<DeploymentImpl>,
-    Query(query): Query<TerminalQuery>,
-) -> Result<impl IntoResponse, ApiError> {
-    let attempt = Workspace::find_by_id(&deployment.db().pool, query.workspace_id)
+async fn workspace_working_dir(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<PathBuf, ApiError> {
+    let attempt = Workspace::find_by_id(&deployment.db().pool, workspace_id)
         .await?
         .ok_or_else(|| ApiError::BadRequest("Attempt not found".to_string()))?;
 
@@ -70,7 +116,7 @@ pub async fn terminal_ws(
     }
 
     let mut working_dir = base_dir.clone();
-    match WorkspaceRepo::find_repos_for_workspace(&deployment.db().pool, query.workspace_id).await {
+    match WorkspaceRepo::find_repos_for_workspace(&deployment.db().pool, workspace_id).await {
         Ok(repos) if repos.len() == 1 => {
             let repo_dir = base_dir.join(&repos[0].name);
             if repo_dir.exists() {
@@ -87,52 +133,128 @@ pub async fn terminal_ws(
         }
     }
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_terminal_ws(socket, deployment, working_dir, query.cols, query.rows)
-    }))
+    Ok(working_dir)
+}
+
+pub async fn list_terminal_sessions(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TerminalSessionsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TerminalSession>>>, ApiError> {
+    let sessions = deployment
+        .pty()
+        .list_sessions(query.workspace_id)
+        .into_iter()
+        .map(TerminalSession::from)
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+pub async fn create_terminal_session(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTerminalSession>,
+) -> Result<ResponseJson<ApiResponse<TerminalSession>>, ApiError> {
+    let working_dir = workspace_working_dir(&deployment, payload.workspace_id).await?;
+    let session_id = deployment
+        .pty()
+        .create_session(
+            payload.workspace_id,
+            working_dir,
+            payload.cols.unwrap_or_else(default_cols),
+            payload.rows.unwrap_or_else(default_rows),
+        )
+        .await?;
+    let session = deployment.pty().session_info(session_id)?;
+    Ok(ResponseJson(ApiResponse::success(session.into())))
+}
+
+pub async fn close_terminal_session(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment.pty().close_session(session_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn terminal_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TerminalQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let session_id = match (query.session_id, query.workspace_id) {
+        (Some(session_id), _) => session_id,
+        (None, Some(workspace_id)) => {
+            let working_dir = workspace_working_dir(&deployment, workspace_id).await?;
+            deployment
+                .pty()
+                .create_session(workspace_id, working_dir, query.cols, query.rows)
+                .await?
+        }
+        (None, None) => {
+            return Err(ApiError::BadRequest(
+                "Either session_id or workspace_id is required".to_string(),
+            ));
+        }
+    };
+
+    // Attach before upgrading so an unknown session is reported as a normal HTTP error
+    let attachment = deployment.pty().attach(session_id)?;
+
+    Ok(ws.on_upgrade(move |socket| handle_terminal_ws(socket, deployment, session_id, attachment)))
 }
 
 async fn handle_terminal_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
-    working_dir: PathBuf,
-    cols: u16,
-    rows: u16,
+    session_id: Uuid,
+    attachment: TerminalAttachment,
 ) {
-    let (session_id, mut output_rx) = match deployment
-        .pty()
-        .create_session(working_dir, cols, rows)
-        .await
-    {
-        Ok(result) => result,
-        Err(e) => {
-            tracing::error!("Failed to create PTY session: {}", e);
-            let _ = send_error(socket, &e.to_string()).await;
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+
+    let mut greeting = vec![TerminalMessage::Session { session_id }];
+    if !attachment.scrollback.is_empty() {
+        greeting.push(TerminalMessage::Output {
+            data: BASE64.encode(&attachment.scrollback),
+        });
+    }
+    for msg in greeting {
+        if send_message(&mut ws_sender, &msg).await.is_err() {
             return;
         }
-    };
-
-    let (mut ws_sender, mut ws_receiver) = socket.split();
+    }
 
-    let pty_service = deployment.pty().clone();
-    let session_id_for_input = session_id;
+    let Some(mut output_rx) = attachment.output else {
+        let _ = send_message(&mut ws_sender, &TerminalMessage::Exit).await;
+        let _ = ws_sender.send(normal_close()).await;
+        return;
+    };
 
     let output_task = tokio::spawn(async move {
-        while let Some(data) = output_rx.recv().await {
-            let msg = TerminalMessage::Output {
-                data: BASE64.encode(&data),
-            };
-            let json = match serde_json::to_string(&msg) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
-                break;
+        loop {
+            match output_rx.recv().await {
+                Ok(data) => {
+                    let msg = TerminalMessage::Output {
+                        data: BASE64.encode(&data),
+                    };
+                    if send_message(&mut ws_sender, &msg).await.is_err() {
+                        return;
+                    }
+                }
+                // A slow viewer misses some output rather than holding up the shell
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(
+                        "Terminal viewer of session {} skipped {} output chunks",
+                        session_id,
+                        skipped
+                    );
+                }
+                Err(RecvError::Closed) => break,
             }
         }
-        ws_sender
+        let _ = send_message(&mut ws_sender, &TerminalMessage::Exit).await;
+        let _ = ws_sender.send(normal_close()).await;
     });
 
+    let pty_service = deployment.pty().clone();
     while let Some(Ok(msg)) = ws_receiver.next().await {
         match msg {
             Message::Text(text) => {
@@ -140,11 +262,11 @@ async fn handle_terminal_ws(
                     match cmd {
                         TerminalCommand::Input { data } => {
                             if let Ok(bytes) = BASE64.decode(&data) {
-                                let _ = pty_service.write(session_id_for_input, &bytes).await;
+                                let _ = pty_service.write(session_id, &bytes).await;
                             }
                         }
                         TerminalCommand::Resize { cols, rows } => {
-                            let _ = pty_service.resize(session_id_for_input, cols, rows).await;
+                            let _ = pty_service.resize(session_id, cols, rows).await;
                         }
                     }
                 }
@@ -154,20 +276,31 @@ async fn handle_terminal_ws(
         }
     }
 
-    let _ = deployment.pty().close_session(session_id).await;
+    // Only this viewer goes away; the session keeps running until it is closed explicitly
     output_task.abort();
 }
 
-async fn send_error(mut socket: WebSocket, message: &str) -> Result<(), axum::Error> {
-    let msg = TerminalMessage::Error {
-        message: message.to_string(),
-    };
-    let json = serde_json::to_string(&msg).unwrap_or_default();
-    socket.send(Message::Text(json.into())).await?;
-    socket.close().await?;
-    Ok(())
+async fn send_message<S>(sender: &mut S, msg: &TerminalMessage) -> Result<(), axum::Error>
+where
+    S: SinkExt<Message, Error = axum::Error> + Unpin,
+{
+    let json = serde_json::to_string(msg).unwrap_or_default();
+    sender.send(Message::Text(json.into())).await
+}
+
+fn normal_close() -> Message {
+    Message::Close(Some(CloseFrame {
+        code: close_code::NORMAL,
+        reason: "terminal session ended".into(),
+    }))
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/terminal/ws", get(terminal_ws))
+    Router::new()
+        .route("/terminal/ws", get(terminal_ws))
+        .route(
+            "/terminal/sessions",
+            get(list_terminal_sessions).post(create_terminal_session),
+        )
+        .route("/terminal/sessions/{id}", delete(close_terminal_session))
 }
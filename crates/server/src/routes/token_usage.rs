@@ -0,0 +1,56 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::token_usage::TokenUsageScope;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::token_usage::{TokenUsageReport, token_usage_report};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// At most one scope may be given; without one, usage across all projects is returned
+#[derive(Debug, Deserialize, TS)]
+pub struct TokenUsageQuery {
+    #[serde(default)]
+    pub workspace_id: Option<Uuid>,
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+impl TryFrom<TokenUsageQuery> for TokenUsageScope {
+    type Error = ApiError;
+
+    fn try_from(query: TokenUsageQuery) -> Result<Self, Self::Error> {
+        match (query.workspace_id, query.task_id, query.project_id) {
+            (None, None, None) => Ok(TokenUsageScope::All),
+            (Some(id), None, None) => Ok(TokenUsageScope::Workspace(id)),
+            (None, Some(id), None) => Ok(TokenUsageScope::Task(id)),
+            (None, None, Some(id)) => Ok(TokenUsageScope::Project(id)),
+            _ => Err(ApiError::BadRequest(
+                "Specify at most one of workspace_id, task_id and project_id".to_string(),
+            )),
+        }
+    }
+}
+
+pub async fn get_token_usage(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TokenUsageQuery>,
+) -> Result<ResponseJson<ApiResponse<TokenUsageReport>>, ApiError> {
+    let scope = TokenUsageScope::try_from(query)?;
+    let prices = deployment.config().read().await.model_prices.clone();
+    let report = token_usage_report(&deployment.db().pool, scope, &prices).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/token-usage", get(get_token_usage))
+}
@@ -0,0 +1,101 @@
+use std::{io, net::SocketAddr, path::Path, sync::Arc, time::Duration};
+
+use rustls::{
+    ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+use tokio_rustls::{TlsAcceptor, server::TlsStream};
+
+/// Clients that have not finished the handshake by then are dropped
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Load a PEM certificate chain and private key for serving HTTPS
+pub fn load_server_config(cert_path: &Path, key_path: &Path) -> io::Result<Arc<ServerConfig>> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| {
+            io::Error::other(format!(
+                "Failed to read TLS certificate {}: {e}",
+                cert_path.display()
+            ))
+        })?;
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|e| {
+        io::Error::other(format!(
+            "Failed to read TLS private key {}: {e}",
+            key_path.display()
+        ))
+    })?;
+
+    let mut config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(io::Error::other)?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+/// A listener that hands out connections once their TLS handshake completed. Handshakes run in
+/// their own tasks so a slow client does not hold up the others.
+pub struct TlsListener {
+    incoming: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+    local_addr: SocketAddr,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, config: Arc<ServerConfig>) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let acceptor = TlsAcceptor::from(config);
+        let (tx, incoming) = mpsc::channel(64);
+
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept connection: {}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+
+                let acceptor = acceptor.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(stream)) => {
+                            let _ = tx.send((stream, peer)).await;
+                        }
+                        Ok(Err(e)) => tracing::debug!("TLS handshake with {} failed: {}", peer, e),
+                        Err(_) => tracing::debug!("TLS handshake with {} timed out", peer),
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            incoming,
+            local_addr,
+        })
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.incoming.recv().await {
+            Some(connection) => connection,
+            // The accept loop only stops once this listener is dropped
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod executor_approvals;
+pub mod policy;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -287,6 +288,34 @@ impl Approvals {
         }
     }
 
+    /// Record a decision made by an approval policy on the matching tool use entry.
+    pub(crate) async fn apply_policy_status(
+        &self,
+        execution_process_id: Uuid,
+        tool_call_id: &str,
+        status: ToolStatus,
+    ) {
+        let Some(store) = self.msg_store_by_id(&execution_process_id).await else {
+            tracing::warn!(
+                "No msg_store found for execution_process_id: {}",
+                execution_process_id
+            );
+            return;
+        };
+
+        if let Some((idx, entry)) = find_matching_tool_use(store.clone(), tool_call_id)
+            && let Some(updated_entry) = entry.with_tool_status(status)
+        {
+            store.push_patch(ConversationPatch::replace(idx, updated_entry));
+        } else {
+            tracing::warn!(
+                "No matching tool use entry found for policy decision: tool_call_id={}, execution_process_id={}",
+                tool_call_id,
+                execution_process_id
+            );
+        }
+    }
+
     /// Check which execution processes have pending approvals.
     /// Returns a set of execution_process_ids that have at least one pending approval.
     pub fn get_pending_execution_process_ids(
@@ -2,14 +2,22 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use db::{self, DBService, models::execution_process::ExecutionProcess};
-use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
+use executors::{
+    approvals::{ExecutorApprovalError, ExecutorApprovalService},
+    logs::ToolStatus,
+};
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
 use crate::services::{
-    approvals::Approvals, notification::NotificationService, webhook_notification::WebhookMetadata,
+    approvals::{
+        Approvals,
+        policy::{ApprovalPolicyEngine, PolicyDecision},
+    },
+    notification::NotificationService,
+    webhook_notification::WebhookMetadata,
 };
 
 pub struct ExecutorApprovalBridge {
@@ -17,6 +25,7 @@ pub struct ExecutorApprovalBridge {
     db: DBService,
     notification_service: NotificationService,
     execution_process_id: Uuid,
+    policy: Option<ApprovalPolicyEngine>,
 }
 
 impl ExecutorApprovalBridge {
@@ -25,14 +34,68 @@ impl ExecutorApprovalBridge {
         db: DBService,
         notification_service: NotificationService,
         execution_process_id: Uuid,
+        policy: Option<ApprovalPolicyEngine>,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
             execution_process_id,
+            policy,
         })
     }
+
+    /// Settle the request from the approval policy, if a rule allows or denies it.
+    async fn apply_policy(
+        &self,
+        tool_name: &str,
+        tool_input: &Value,
+        tool_call_id: &str,
+    ) -> Option<ApprovalStatus> {
+        let decision = self.policy.as_ref()?.evaluate(tool_name, tool_input);
+        let (status, tool_status) = match decision {
+            PolicyDecision::Allow { rule } => {
+                tracing::info!(
+                    "Approval policy rule '{}' allowed tool '{}' (call_id={})",
+                    rule,
+                    tool_name,
+                    tool_call_id
+                );
+                (ApprovalStatus::Approved, ToolStatus::AutoApproved { rule })
+            }
+            PolicyDecision::Deny { rule, reason } => {
+                tracing::info!(
+                    "Approval policy rule '{}' denied tool '{}' (call_id={})",
+                    rule,
+                    tool_name,
+                    tool_call_id
+                );
+                (
+                    ApprovalStatus::Denied {
+                        reason: Some(reason.clone()),
+                    },
+                    ToolStatus::Denied {
+                        reason: Some(reason),
+                    },
+                )
+            }
+            PolicyDecision::Ask { rule } => {
+                if let Some(rule) = rule {
+                    tracing::debug!(
+                        "Approval policy rule '{}' requires approval for tool '{}'",
+                        rule,
+                        tool_name
+                    );
+                }
+                return None;
+            }
+        };
+
+        self.approvals
+            .apply_policy_status(self.execution_process_id, tool_call_id, tool_status)
+            .await;
+        Some(status)
+    }
 }
 
 #[async_trait]
@@ -44,6 +107,13 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_call_id: &str,
         cancel: CancellationToken,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
+        if let Some(status) = self
+            .apply_policy(tool_name, &tool_input, tool_call_id)
+            .await
+        {
+            return Ok(status);
+        }
+
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
         let request = ApprovalRequest::from_create(
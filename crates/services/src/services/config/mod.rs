@@ -44,6 +44,14 @@ pub type ShowcaseState = versions::v8::ShowcaseState;
 pub type SendMessageShortcut = versions::v8::SendMessageShortcut;
 pub type WebhookProvider = versions::v8::WebhookProvider;
 pub type WebhookConfig = versions::v8::WebhookConfig;
+pub type ConcurrencyConfig = versions::v8::ConcurrencyConfig;
+pub type ChecksConfig = versions::v8::ChecksConfig;
+pub type ModelPrice = versions::v8::ModelPrice;
+pub type ApiAuthConfig = versions::v8::ApiAuthConfig;
+pub type ApiToken = versions::v8::ApiToken;
+pub type ApiTokenScope = versions::v8::ApiTokenScope;
+pub type SandboxConfig = executors::sandbox::SandboxConfig;
+pub type SandboxNetwork = executors::sandbox::SandboxNetwork;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
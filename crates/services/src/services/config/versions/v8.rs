@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use anyhow::Error;
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use chrono::{DateTime, Utc};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId, sandbox::SandboxConfig};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, ShowcaseState, SoundFile, ThemeMode, UiLanguage,
 };
@@ -71,6 +75,107 @@ impl Default for NotificationConfig {
     }
 }
 
+/// Limits on how many coding agents may run at once. Starts beyond the limit are queued.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ConcurrencyConfig {
+    /// Maximum coding agents running across all workspaces, `None` for unlimited
+    #[serde(default)]
+    pub max_concurrent_agents: Option<u32>,
+    /// Maximum coding agents running per agent type, on top of the global limit
+    #[serde(default)]
+    pub per_agent_limits: HashMap<BaseCodingAgent, u32>,
+}
+
+impl ConcurrencyConfig {
+    /// Whether another run of `agent` may start given the current running counts
+    pub fn allows(
+        &self,
+        running_total: usize,
+        agent: Option<BaseCodingAgent>,
+        running_for_agent: usize,
+    ) -> bool {
+        if self
+            .max_concurrent_agents
+            .is_some_and(|max| running_total >= max as usize)
+        {
+            return false;
+        }
+        agent
+            .and_then(|agent| self.per_agent_limits.get(&agent))
+            .is_none_or(|max| running_for_agent < *max as usize)
+    }
+}
+
+/// Check scripts run on a coding agent's changes before its task moves to review
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ChecksConfig {
+    /// Follow-ups sending failing check output back to the agent before giving up; 0 disables them
+    #[serde(default)]
+    pub auto_fix_attempts: u32,
+}
+
+/// Price of a model in USD per million tokens, used to estimate what agent runs cost
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    /// Defaults to the input price
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+    /// Defaults to the input price
+    #[serde(default)]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPrice {
+    pub fn cost_usd(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_tokens: u64,
+        cache_write_tokens: u64,
+    ) -> f64 {
+        let per_token = |price: f64| price / 1_000_000.0;
+        input_tokens as f64 * per_token(self.input)
+            + output_tokens as f64 * per_token(self.output)
+            + cache_read_tokens as f64 * per_token(self.cache_read.unwrap_or(self.input))
+            + cache_write_tokens as f64 * per_token(self.cache_write.unwrap_or(self.input))
+    }
+}
+
+/// What an API token may do. Read-only tokens are limited to read requests, and get no terminal,
+/// credentials, token management or filesystem browsing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    ReadOnly,
+    Full,
+}
+
+/// A token clients send as `Authorization: Bearer <token>` or as the basic auth password
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub name: String,
+    pub scope: ApiTokenScope,
+    /// SHA-256 of the token, the token itself is only shown when it is created
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Authentication for reaching the server from other machines
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct ApiAuthConfig {
+    /// Require an API token from clients on other machines
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also require an API token from clients on this machine
+    #[serde(default)]
+    pub require_for_loopback: bool,
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+}
+
 fn default_pr_auto_description_enabled() -> bool {
     true
 }
@@ -118,6 +223,20 @@ pub struct Config {
     pub commit_reminder_prompt: Option<String>,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    #[serde(default)]
+    pub auto_start_dependent_tasks: bool,
+    #[serde(default)]
+    pub concurrency: ConcurrencyConfig,
+    /// Prices keyed by model name or model name prefix, e.g. "claude-sonnet-4"
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+    /// Run coding agents confined to their workspace (Linux only)
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub checks: ChecksConfig,
+    #[serde(default)]
+    pub api_auth: ApiAuthConfig,
 }
 
 impl Config {
@@ -147,6 +266,12 @@ impl Config {
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
             send_message_shortcut: SendMessageShortcut::default(),
+            auto_start_dependent_tasks: false,
+            concurrency: ConcurrencyConfig::default(),
+            model_prices: HashMap::new(),
+            sandbox: SandboxConfig::default(),
+            checks: ChecksConfig::default(),
+            api_auth: ApiAuthConfig::default(),
         }
     }
 
@@ -201,6 +326,12 @@ impl Default for Config {
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
             send_message_shortcut: SendMessageShortcut::default(),
+            auto_start_dependent_tasks: false,
+            concurrency: ConcurrencyConfig::default(),
+            model_prices: HashMap::new(),
+            sandbox: SandboxConfig::default(),
+            checks: ChecksConfig::default(),
+            api_auth: ApiAuthConfig::default(),
         }
     }
 }
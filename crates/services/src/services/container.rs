@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -14,7 +15,7 @@ use db::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessError,
             ExecutionProcessRunReason, ExecutionProcessStatus,
         },
-        execution_process_logs::ExecutionProcessLogs,
+        execution_process_logs::{ExecutionProcessLogs, LogChunkWriter},
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
@@ -42,9 +43,13 @@ use executors::{
 use futures::{StreamExt, future, stream::BoxStream};
 use git::{GitService, GitServiceError};
 use json_patch::Patch;
+use serde::Deserialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+};
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
@@ -53,11 +58,69 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
-    notification::NotificationService, webhook_notification::WebhookMetadata,
+    config::ConcurrencyConfig, notification::NotificationService, repo_config::effective_repos,
+    webhook_notification::WebhookMetadata,
     workspace_manager::WorkspaceError as WorkspaceManagerError, worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// How often buffered log lines are written to the database while a process runs
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Window of log messages to stream, used to page through long histories.
+/// `offset` and `limit` count content messages; `Finished` is always delivered.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct LogRange {
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl LogRange {
+    fn is_full(&self) -> bool {
+        self.offset == 0 && self.limit.is_none()
+    }
+}
+
+/// Restrict a log stream to `range`, ending it with `Finished` once the limit is reached
+fn apply_log_range(
+    stream: BoxStream<'static, Result<LogMsg, std::io::Error>>,
+    range: LogRange,
+) -> BoxStream<'static, Result<LogMsg, std::io::Error>> {
+    if range.is_full() {
+        return stream;
+    }
+    if range.limit == Some(0) {
+        return futures::stream::once(future::ready(Ok(LogMsg::Finished))).boxed();
+    }
+
+    let end = range.limit.map(|limit| range.offset.saturating_add(limit));
+    stream
+        .scan((0usize, false), move |(index, done), msg| {
+            if *done {
+                return future::ready(None);
+            }
+            let items = match msg {
+                Ok(LogMsg::Finished) | Err(_) => vec![msg],
+                Ok(msg) => {
+                    let i = *index;
+                    *index += 1;
+                    if i < range.offset {
+                        vec![]
+                    } else if end.is_some_and(|end| i + 1 >= end) {
+                        *done = true;
+                        vec![Ok(msg), Ok(LogMsg::Finished)]
+                    } else {
+                        vec![Ok(msg)]
+                    }
+                }
+            };
+            future::ready(Some(items))
+        })
+        .flat_map(futures::stream::iter)
+        .boxed()
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -94,6 +157,9 @@ pub trait ContainerService {
 
     fn notification_service(&self) -> &NotificationService;
 
+    /// Serialises coding agent starts so capacity checks and queue draining don't race
+    fn execution_queue_lock(&self) -> &Arc<Mutex<()>>;
+
     async fn touch(&self, workspace: &Workspace) -> Result<(), ContainerError>;
 
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf;
@@ -157,7 +223,16 @@ pub trait ContainerService {
 
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError>;
 
-    async fn kill_all_running_processes(&self) -> Result<(), ContainerError>;
+    /// Stop every running process at shutdown. Detached processes are left running so
+    /// they can be reattached on the next start.
+    async fn shutdown_running_processes(&self) -> Result<(), ContainerError>;
+
+    /// Resume tracking a detached process left running by a previous server, replaying
+    /// its spooled output. Returns false when there is nothing to reattach to.
+    async fn reattach_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<bool, ContainerError>;
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError>;
 
@@ -185,14 +260,14 @@ pub trait ContainerService {
 
     /// A context is finalized when
     /// - Always when the execution process has failed or been killed
-    /// - Never when the run reason is DevServer
+    /// - Never when the run reason is DevServer or CheckScript
     /// - Never when a setup script has no next_action (parallel mode)
     /// - The next action is None (no follow-up actions)
     fn should_finalize(&self, ctx: &ExecutionContext) -> bool {
-        // Never finalize DevServer processes
+        // Never finalize DevServer processes, and leave merge queue checks to the queue
         if matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::DevServer
+            ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::CheckScript
         ) {
             return false;
         }
@@ -221,10 +296,16 @@ pub trait ContainerService {
         action.next_action.is_none()
     }
 
-    /// Finalize task execution by updating status to InReview and sending notifications
+    /// Finalize task execution by updating status to InReview and sending notifications.
+    /// Failing check scripts keep the task out of review.
     async fn finalize_task(&self, ctx: &ExecutionContext) {
-        if let Err(e) =
-            Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await
+        let checks_failed = ctx.execution_process.run_reason
+            == ExecutionProcessRunReason::CheckScript
+            && !(ctx.execution_process.status == ExecutionProcessStatus::Completed
+                && ctx.execution_process.exit_code == Some(0));
+        if !checks_failed
+            && let Err(e) =
+                Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await
         {
             tracing::error!("Failed to update task status to InReview: {e}");
         }
@@ -267,10 +348,25 @@ pub trait ContainerService {
         }
     }
 
-    /// Cleanup executions marked as running in the db, call at startup
+    /// Reattach to detached executions that are still running and clean up the rest of
+    /// the executions marked as running in the db, call at startup
     async fn cleanup_orphan_executions(&self) -> Result<(), ContainerError> {
         let running_processes = ExecutionProcess::find_running(&self.db().pool).await?;
         for process in running_processes {
+            match self.reattach_execution(&process).await {
+                Ok(true) => {
+                    tracing::info!("Reattached to detached execution process {}", process.id);
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to reattach to execution process {}: {}",
+                        process.id,
+                        e
+                    );
+                }
+            }
             tracing::info!(
                 "Found orphaned execution process {} for session {}",
                 process.id,
@@ -420,7 +516,8 @@ pub trait ContainerService {
         Ok(())
     }
 
-    fn cleanup_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+    async fn cleanup_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+        let repos = effective_repos(&self.db().pool, repos).await;
         let repos_with_cleanup: Vec<_> = repos
             .iter()
             .filter(|r| r.cleanup_script.is_some())
@@ -457,7 +554,44 @@ pub trait ContainerService {
         Some(root_action)
     }
 
-    fn archive_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+    /// Chain of check scripts run on a coding agent's changes, one per repo
+    async fn check_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+        let repos = effective_repos(&self.db().pool, repos).await;
+        let repos_with_check: Vec<_> = repos.iter().filter(|r| r.check_script.is_some()).collect();
+
+        if repos_with_check.is_empty() {
+            return None;
+        }
+
+        let mut iter = repos_with_check.iter();
+        let first = iter.next()?;
+        let mut root_action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: first.check_script.clone().unwrap(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::CheckScript,
+                working_dir: Some(first.name.clone()),
+            }),
+            None,
+        );
+
+        for repo in iter {
+            root_action = root_action.append_action(ExecutorAction::new(
+                ExecutorActionType::ScriptRequest(ScriptRequest {
+                    script: repo.check_script.clone().unwrap(),
+                    language: ScriptRequestLanguage::Bash,
+                    context: ScriptContext::CheckScript,
+                    working_dir: Some(repo.name.clone()),
+                }),
+                None,
+            ));
+        }
+
+        Some(root_action)
+    }
+
+    async fn archive_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+        let repos = effective_repos(&self.db().pool, repos).await;
         let repos_with_archive: Vec<_> = repos
             .iter()
             .filter(|r| r.archive_script.is_some())
@@ -511,7 +645,7 @@ pub trait ContainerService {
             return Ok(());
         }
         let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
-        let Some(action) = self.archive_actions_for_repos(&repos) else {
+        let Some(action) = self.archive_actions_for_repos(&repos).await else {
             return Ok(());
         };
         let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
@@ -531,13 +665,19 @@ pub trait ContainerService {
             &session,
             &action,
             &ExecutionProcessRunReason::ArchiveScript,
+            None,
         )
         .await?;
 
         Ok(())
     }
 
-    /// Archive a workspace: set archived flag, stop running dev servers, and run archive script.
+    /// End the interactive terminal sessions of a workspace. Deployments without terminals
+    /// keep the default.
+    async fn close_workspace_terminals(&self, _workspace_id: Uuid) {}
+
+    /// Archive a workspace: set archived flag, stop running dev servers, close terminals, and run
+    /// archive script.
     async fn archive_workspace(&self, workspace_id: Uuid) -> Result<(), ContainerError> {
         let pool = &self.db().pool;
 
@@ -562,6 +702,8 @@ pub trait ContainerService {
             }
         }
 
+        self.close_workspace_terminals(workspace_id).await;
+
         // Run archive script (silently skips if not configured)
         if let Err(e) = self.try_run_archive_script(workspace_id).await {
             tracing::error!(
@@ -574,7 +716,8 @@ pub trait ContainerService {
         Ok(())
     }
 
-    fn setup_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+    async fn setup_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
+        let repos = effective_repos(&self.db().pool, repos).await;
         let repos_with_setup: Vec<_> = repos.iter().filter(|r| r.setup_script.is_some()).collect();
 
         if repos_with_setup.is_empty() {
@@ -736,7 +879,10 @@ pub trait ContainerService {
                     {
                         continue;
                     }
-                    if process.status == ExecutionProcessStatus::Running {
+                    if matches!(
+                        process.status,
+                        ExecutionProcessStatus::Running | ExecutionProcessStatus::Queued
+                    ) {
                         self.stop_execution(&process, ExecutionProcessStatus::Killed)
                             .await
                             .unwrap_or_else(|e| {
@@ -797,6 +943,115 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
+    /// Executor profile for tasks started automatically once their dependencies are done,
+    /// or `None` when auto-start is disabled.
+    async fn auto_start_executor_profile(&self) -> Option<ExecutorProfileId>;
+
+    async fn concurrency_config(&self) -> ConcurrencyConfig;
+
+    /// Whether a coding agent for `executor_action` may start now without exceeding the
+    /// configured concurrency limits. Callers must hold `execution_queue_lock`.
+    async fn has_agent_capacity(
+        &self,
+        executor_action: &ExecutorAction,
+    ) -> Result<bool, ContainerError> {
+        let limits = self.concurrency_config().await;
+        if limits == ConcurrencyConfig::default() {
+            return Ok(true);
+        }
+
+        let agent = executor_action.base_executor();
+        let running: Vec<_> = ExecutionProcess::find_running(&self.db().pool)
+            .await?
+            .into_iter()
+            .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+            .collect();
+        let running_for_agent = running
+            .iter()
+            .filter(|p| {
+                agent.is_some()
+                    && p.executor_action()
+                        .is_ok_and(|action| action.base_executor() == agent)
+            })
+            .count();
+
+        Ok(limits.allows(running.len(), agent, running_for_agent))
+    }
+
+    /// Start queued coding agent executions, oldest first, while capacity allows.
+    /// Call whenever a slot may have been freed or the limits were raised.
+    async fn start_queued_executions(&self) -> Result<(), ContainerError> {
+        let _guard = self.execution_queue_lock().lock().await;
+
+        for process in ExecutionProcess::find_queued(&self.db().pool).await? {
+            let executor_action = match process.executor_action() {
+                Ok(action) => action.clone(),
+                Err(e) => {
+                    tracing::error!("Dropping queued execution {}: {}", process.id, e);
+                    ExecutionProcess::update_completion(
+                        &self.db().pool,
+                        process.id,
+                        ExecutionProcessStatus::Failed,
+                        None,
+                    )
+                    .await?;
+                    continue;
+                }
+            };
+
+            // A later entry for a different agent may still fit under its own limit
+            if !self.has_agent_capacity(&executor_action).await? {
+                continue;
+            }
+
+            if !ExecutionProcess::mark_dequeued(&self.db().pool, process.id).await? {
+                continue;
+            }
+
+            let Some(process) = ExecutionProcess::find_by_id(&self.db().pool, process.id).await?
+            else {
+                continue;
+            };
+            let Some((workspace, _)) = process
+                .parent_workspace_and_session(&self.db().pool)
+                .await?
+            else {
+                continue;
+            };
+
+            tracing::info!(
+                "Starting queued execution {} for workspace {}",
+                process.id,
+                workspace.id
+            );
+            // The worktree may have been cleaned up while the run was waiting
+            if let Err(e) = self.ensure_container_exists(&workspace).await {
+                tracing::error!(
+                    "Failed to prepare workspace {} for queued execution {}: {}",
+                    workspace.id,
+                    process.id,
+                    e
+                );
+                ExecutionProcess::update_completion(
+                    &self.db().pool,
+                    process.id,
+                    ExecutionProcessStatus::Failed,
+                    None,
+                )
+                .await?;
+                continue;
+            }
+            if let Err(e) = self
+                .launch_execution(&workspace, &process, &executor_action)
+                .await
+            {
+                tracing::error!("Failed to start queued execution {}: {}", process.id, e);
+            }
+        }
+
+        Ok(())
+    }
+
     async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
         let task_title_id = git_branch_id(task_title);
         let prefix = self.git_branch_prefix().await;
@@ -811,36 +1066,42 @@ pub trait ContainerService {
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
+        range: LogRange,
     ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
         if let Some(store) = self.get_msg_store_by_id(id).await {
             // First try in-memory store
-            return Some(
-                store
-                    .history_plus_stream()
-                    .filter(|msg| {
-                        future::ready(matches!(
-                            msg,
-                            Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished)
-                        ))
-                    })
-                    .boxed(),
-            );
+            let stream = store
+                .history_plus_stream()
+                .filter(|msg| {
+                    future::ready(matches!(
+                        msg,
+                        Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished)
+                    ))
+                })
+                .boxed();
+            return Some(apply_log_range(stream, range));
         } else {
-            // Fallback: load from DB and create direct stream
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            // Fallback: load from DB, decompressing only the chunks in range
+            match ExecutionProcessLogs::stats(&self.db().pool, *id).await {
+                Ok(stats) if stats.line_count > 0 => {}
+                Ok(_) => return None, // No logs exist
+                Err(e) => {
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
+                    return None;
+                }
+            }
 
-            let messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            let messages = match ExecutionProcessLogs::read_lines(
+                &self.db().pool,
+                *id,
+                range.offset,
+                range.limit,
+            )
+            .await
+            {
                 Ok(msgs) => msgs,
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to read logs for execution {}: {}", id, e);
                     return None;
                 }
             };
@@ -862,173 +1123,171 @@ pub trait ContainerService {
     async fn stream_normalized_logs(
         &self,
         id: &Uuid,
+        range: LogRange,
     ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
         // First try in-memory store (existing behavior)
         if let Some(store) = self.get_msg_store_by_id(id).await {
-            Some(
-                store
-                    .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
+            let stream = store
+                .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
+                .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                .chain(futures::stream::once(async {
+                    Ok::<_, std::io::Error>(LogMsg::Finished)
+                }))
+                .boxed();
+            Some(apply_log_range(stream, range))
         } else {
-            // Fallback: load from DB and normalize
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            let temp_store = self.normalize_stored_logs(id).await?;
+            // Normalization needs the whole history, so the range applies to its output
+            let stream = temp_store
+                .history_plus_stream()
+                .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                .chain(futures::stream::once(async {
+                    Ok::<_, std::io::Error>(LogMsg::Finished)
+                }))
+                .boxed();
+            Some(apply_log_range(stream, range))
+        }
+    }
 
-            let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
+    /// Replay the stored raw logs of a process into a new store and start normalizing
+    /// them. The normalizers hold a clone of the store until they have processed all of it.
+    async fn normalize_stored_logs(&self, id: &Uuid) -> Option<Arc<MsgStore>> {
+        let log_records =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(records) if !records.is_empty() => records,
+                Ok(_) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Create temporary store and populate
-            // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(
-                    msg,
-                    LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
-                    temp_store.push(msg);
-                }
+        let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
+            }
+        };
+
+        // Create temporary store and populate
+        // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
+        let temp_store = Arc::new(MsgStore::new());
+        for msg in raw_messages {
+            if matches!(
+                msg,
+                LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+            ) {
+                temp_store.push(msg);
+            }
+        }
+        temp_store.push_finished();
+
+        let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!("No execution process found for ID: {}", id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                return None;
             }
-            temp_store.push_finished();
+        };
 
-            let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
-                Ok(Some(process)) => process,
+        // Get the workspace to determine correct directory
+        let (workspace, _session) =
+            match process.parent_workspace_and_session(&self.db().pool).await {
+                Ok(Some((workspace, session))) => (workspace, session),
                 Ok(None) => {
-                    tracing::error!("No execution process found for ID: {}", id);
+                    tracing::error!(
+                        "No workspace/session found for session ID: {}",
+                        process.session_id
+                    );
                     return None;
                 }
                 Err(e) => {
-                    tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                    tracing::error!(
+                        "Failed to fetch workspace for session {}: {}",
+                        process.session_id,
+                        e
+                    );
                     return None;
                 }
             };
 
-            // Get the workspace to determine correct directory
-            let (workspace, _session) =
-                match process.parent_workspace_and_session(&self.db().pool).await {
-                    Ok(Some((workspace, session))) => (workspace, session),
-                    Ok(None) => {
-                        tracing::error!(
-                            "No workspace/session found for session ID: {}",
-                            process.session_id
-                        );
-                        return None;
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to fetch workspace for session {}: {}",
-                            process.session_id,
-                            e
-                        );
-                        return None;
-                    }
-                };
-
-            if let Err(err) = self.ensure_container_exists(&workspace).await {
-                tracing::warn!(
-                    "Failed to recreate worktree before log normalization for workspace {}: {}",
-                    workspace.id,
-                    err
-                );
-            }
+        if let Err(err) = self.ensure_container_exists(&workspace).await {
+            tracing::warn!(
+                "Failed to recreate worktree before log normalization for workspace {}: {}",
+                workspace.id,
+                err
+            );
+        }
 
-            let current_dir = self.workspace_to_current_dir(&workspace);
+        let current_dir = self.workspace_to_current_dir(&workspace);
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
-                    process.executor_action()
-                );
-                return None;
-            };
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
 
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    #[cfg(feature = "qa-mode")]
-                    {
-                        let executor = QaMockExecutor;
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
-                    #[cfg(not(feature = "qa-mode"))]
-                    {
-                        let executor = ExecutorConfigs::get_cached()
-                            .get_coding_agent_or_default(&request.executor_profile_id);
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
+        // Spawn normalizer on populated store
+        match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                #[cfg(feature = "qa-mode")]
+                {
+                    let executor = QaMockExecutor;
+                    executor
+                        .normalize_logs(temp_store.clone(), &request.effective_dir(&current_dir));
                 }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    #[cfg(feature = "qa-mode")]
-                    {
-                        let executor = QaMockExecutor;
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
-                    #[cfg(not(feature = "qa-mode"))]
-                    {
-                        let executor = ExecutorConfigs::get_cached()
-                            .get_coding_agent_or_default(&request.executor_profile_id);
-                        executor.normalize_logs(
-                            temp_store.clone(),
-                            &request.effective_dir(&current_dir),
-                        );
-                    }
+                #[cfg(not(feature = "qa-mode"))]
+                {
+                    let executor = ExecutorConfigs::get_cached()
+                        .get_coding_agent_or_default(&request.executor_profile_id);
+                    executor
+                        .normalize_logs(temp_store.clone(), &request.effective_dir(&current_dir));
                 }
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
                 #[cfg(feature = "qa-mode")]
-                ExecutorActionType::ReviewRequest(_request) => {
+                {
                     let executor = QaMockExecutor;
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
+                    executor
+                        .normalize_logs(temp_store.clone(), &request.effective_dir(&current_dir));
                 }
                 #[cfg(not(feature = "qa-mode"))]
-                ExecutorActionType::ReviewRequest(request) => {
+                {
                     let executor = ExecutorConfigs::get_cached()
                         .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
+                    executor
+                        .normalize_logs(temp_store.clone(), &request.effective_dir(&current_dir));
                 }
             }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
+            #[cfg(feature = "qa-mode")]
+            ExecutorActionType::ReviewRequest(_request) => {
+                let executor = QaMockExecutor;
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            #[cfg(not(feature = "qa-mode"))]
+            ExecutorActionType::ReviewRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
+                    process.executor_action()
+                );
+                return None;
+            }
         }
+        Some(temp_store)
     }
 
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
@@ -1045,25 +1304,38 @@ pub trait ContainerService {
 
             if let Some(store) = store {
                 let mut stream = store.history_plus_stream();
+                // Lines are batched into compressed chunks, flushed when full or periodically
+                let mut writer = LogChunkWriter::new(execution_id);
+                let mut flush_interval = tokio::time::interval(LOG_FLUSH_INTERVAL);
+
+                loop {
+                    let msg = tokio::select! {
+                        msg = stream.next() => match msg {
+                            Some(Ok(msg)) => msg,
+                            _ => break,
+                        },
+                        _ = flush_interval.tick() => {
+                            if let Err(e) = writer.flush(&db.pool).await {
+                                tracing::error!(
+                                    "Failed to write log chunk for execution {}: {}",
+                                    execution_id,
+                                    e
+                                );
+                            }
+                            continue;
+                        }
+                    };
 
-                while let Some(Ok(msg)) = stream.next().await {
                     match &msg {
                         LogMsg::Stdout(_) | LogMsg::Stderr(_) => {
                             // Serialize this individual message as a JSONL line
                             match serde_json::to_string(&msg) {
                                 Ok(jsonl_line) => {
-                                    let jsonl_line_with_newline = format!("{jsonl_line}\n");
-
-                                    // Append this line to the database
-                                    if let Err(e) = ExecutionProcessLogs::append_log_line(
-                                        &db.pool,
-                                        execution_id,
-                                        &jsonl_line_with_newline,
-                                    )
-                                    .await
+                                    if let Err(e) =
+                                        writer.push(&db.pool, format!("{jsonl_line}\n")).await
                                     {
                                         tracing::error!(
-                                            "Failed to append log line for execution {}: {}",
+                                            "Failed to write log chunk for execution {}: {}",
                                             execution_id,
                                             e
                                         );
@@ -1111,12 +1383,32 @@ pub trait ContainerService {
                                 );
                             }
                         }
+                        LogMsg::TokenUsage(usage) => {
+                            if let Err(e) =
+                                CodingAgentTurn::update_token_usage(&db.pool, execution_id, usage)
+                                    .await
+                            {
+                                tracing::error!(
+                                    "Failed to update token usage for execution process {}: {}",
+                                    execution_id,
+                                    e
+                                );
+                            }
+                        }
                         LogMsg::Finished => {
                             break;
                         }
                         LogMsg::JsonPatch(_) | LogMsg::Ready => continue,
                     }
                 }
+
+                if let Err(e) = writer.flush(&db.pool).await {
+                    tracing::error!(
+                        "Failed to write final log chunk for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
             }
         })
     }
@@ -1135,7 +1427,11 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
 
-        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
+        let repos = effective_repos(
+            &self.db().pool,
+            &WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?,
+        )
+        .await;
 
         let workspace = Workspace::find_by_id(&self.db().pool, workspace.id)
             .await?
@@ -1158,7 +1454,7 @@ pub trait ContainerService {
 
         let all_parallel = repos_with_setup.iter().all(|r| r.parallel_setup_script);
 
-        let cleanup_action = self.cleanup_actions_for_repos(&repos);
+        let cleanup_action = self.cleanup_actions_for_repos(&repos).await;
 
         let working_dir = workspace
             .agent_working_dir
@@ -1185,6 +1481,7 @@ pub trait ContainerService {
                             &session,
                             &action,
                             &ExecutionProcessRunReason::SetupScript,
+                            None,
                         )
                         .await
                 {
@@ -1196,6 +1493,7 @@ pub trait ContainerService {
                 &session,
                 &coding_action,
                 &ExecutionProcessRunReason::CodingAgent,
+                None,
             )
             .await?
         } else {
@@ -1206,6 +1504,7 @@ pub trait ContainerService {
                 &session,
                 &main_action,
                 &ExecutionProcessRunReason::SetupScript,
+                None,
             )
             .await?
         };
@@ -1213,12 +1512,15 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    /// Start `executor_action` in the workspace. `checked_process_id` links a check script
+    /// to the coding agent turn whose changes it verifies.
     async fn start_execution(
         &self,
         workspace: &Workspace,
         session: &Session,
         executor_action: &ExecutorAction,
         run_reason: &ExecutionProcessRunReason,
+        checked_process_id: Option<Uuid>,
     ) -> Result<ExecutionProcess, ContainerError> {
         // Update task status to InProgress when starting an execution
         let task = workspace
@@ -1226,7 +1528,10 @@ pub trait ContainerService {
             .await?
             .ok_or(SqlxError::RowNotFound)?;
         if task.status != TaskStatus::InProgress
-            && run_reason != &ExecutionProcessRunReason::DevServer
+            && !matches!(
+                run_reason,
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::CheckScript
+            )
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
         }
@@ -1257,10 +1562,34 @@ pub trait ContainerService {
                 merge_commit: None,
             });
         }
+        // Coding agents wait in the queue while the concurrency limit is reached. While others
+        // are already waiting, a new one joins the back of the queue instead of taking a slot
+        // that frees up ahead of them.
+        let queue_guard = if *run_reason == ExecutionProcessRunReason::CodingAgent {
+            Some(self.execution_queue_lock().lock().await)
+        } else {
+            None
+        };
+        let mut joins_waiting_queue = false;
+        let status = if queue_guard.is_some() {
+            joins_waiting_queue = !ExecutionProcess::find_queued(&self.db().pool)
+                .await?
+                .is_empty();
+            if joins_waiting_queue || !self.has_agent_capacity(executor_action).await? {
+                ExecutionProcessStatus::Queued
+            } else {
+                ExecutionProcessStatus::Running
+            }
+        } else {
+            ExecutionProcessStatus::Running
+        };
+
         let create_execution_process = CreateExecutionProcess {
             session_id: session.id,
             executor_action: executor_action.clone(),
             run_reason: run_reason.clone(),
+            status: status.clone(),
+            checked_process_id,
         };
 
         let execution_process = ExecutionProcess::create(
@@ -1270,6 +1599,7 @@ pub trait ContainerService {
             &repo_states,
         )
         .await?;
+        drop(queue_guard);
         if *run_reason != ExecutionProcessRunReason::ArchiveScript {
             Workspace::set_archived(&self.db().pool, workspace.id, false).await?;
         }
@@ -1301,8 +1631,38 @@ pub trait ContainerService {
             .await?;
         }
 
+        if status == ExecutionProcessStatus::Queued {
+            tracing::info!(
+                "Concurrency limit reached, queued execution {} for workspace {}",
+                execution_process.id,
+                workspace.id
+            );
+            if !joins_waiting_queue {
+                return Ok(execution_process);
+            }
+            // There may be room after all; start whatever is first in line, possibly this one
+            self.start_queued_executions().await?;
+            return Ok(
+                ExecutionProcess::find_by_id(&self.db().pool, execution_process.id)
+                    .await?
+                    .unwrap_or(execution_process),
+            );
+        }
+
+        self.launch_execution(workspace, &execution_process, executor_action)
+            .await?;
+        Ok(execution_process)
+    }
+
+    /// Spawn the executor for an execution process record and wire up log streaming
+    async fn launch_execution(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+    ) -> Result<(), ContainerError> {
         if let Err(start_error) = self
-            .start_execution_inner(workspace, &execution_process, executor_action)
+            .start_execution_inner(workspace, execution_process, executor_action)
             .await
         {
             // Mark process as failed
@@ -1320,7 +1680,7 @@ pub trait ContainerService {
                     update_error
                 );
             }
-            Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            Task::update_status(&self.db().pool, workspace.task_id, TaskStatus::InReview).await?;
 
             // Emit stderr error message
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
@@ -1359,6 +1719,20 @@ pub trait ContainerService {
             return Err(start_error);
         }
 
+        self.start_log_processing(workspace, execution_process, executor_action)
+            .await;
+
+        Ok(())
+    }
+
+    /// Normalize the logs of coding agent executions and persist raw logs to the db
+    /// as they arrive in the execution's MsgStore
+    async fn start_log_processing(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+    ) {
         // Start processing normalised logs for executor requests and follow ups
         let workspace_root = self.workspace_to_current_dir(workspace);
         #[cfg_attr(feature = "qa-mode", allow(unused_variables))]
@@ -1402,7 +1776,6 @@ pub trait ContainerService {
         let db_stream_handle = self.spawn_stream_raw_logs_to_db(&execution_process.id);
         self.store_db_stream_handle(execution_process.id, db_stream_handle)
             .await;
-        Ok(execution_process)
     }
 
     async fn try_start_next_action(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
@@ -1416,8 +1789,12 @@ pub trait ContainerService {
 
         // Determine the run reason of the next action
         let next_run_reason = match (action.typ(), next_action.typ()) {
-            (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
-                ExecutionProcessRunReason::SetupScript
+            (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(next)) => {
+                match next.context {
+                    ScriptContext::CheckScript => ExecutionProcessRunReason::CheckScript,
+                    ScriptContext::CleanupScript => ExecutionProcessRunReason::CleanupScript,
+                    _ => ExecutionProcessRunReason::SetupScript,
+                }
             }
             (
                 ExecutorActionType::CodingAgentInitialRequest(_)
@@ -1433,8 +1810,21 @@ pub trait ContainerService {
             ) => ExecutionProcessRunReason::CodingAgent,
         };
 
-        self.start_execution(&ctx.workspace, &ctx.session, next_action, &next_run_reason)
-            .await?;
+        // Checks chained after one another verify the same coding agent turn
+        let checked_process_id = if next_run_reason == ExecutionProcessRunReason::CheckScript {
+            ExecutionProcess::find_checked_process_id(&self.db().pool, ctx.execution_process.id)
+                .await?
+        } else {
+            None
+        };
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            next_action,
+            &next_run_reason,
+            checked_process_id,
+        )
+        .await?;
 
         tracing::debug!("Started next action: {:?}", next_action);
         Ok(())
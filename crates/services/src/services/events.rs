@@ -1,10 +1,14 @@
-use std::{str::FromStr, sync::Arc};
+use std::{collections::HashSet, str::FromStr, sync::Arc};
 
 use db::{
     DBService,
     models::{
-        execution_process::ExecutionProcess, project::Project, scratch::Scratch, session::Session,
-        task::Task, workspace::Workspace,
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+        project::Project,
+        scratch::Scratch,
+        session::Session,
+        task::Task,
+        workspace::Workspace,
     },
 };
 use serde_json::json;
@@ -90,6 +94,26 @@ impl EventService {
         Ok(())
     }
 
+    /// Queue positions shift for every waiting workspace when a coding agent enters or
+    /// leaves the queue, so refresh all of them
+    async fn push_queued_workspace_updates(
+        pool: &SqlitePool,
+        msg_store: Arc<MsgStore>,
+    ) -> Result<(), SqlxError> {
+        let mut seen = HashSet::new();
+        for process in ExecutionProcess::find_queued(pool).await? {
+            if seen.insert(process.session_id) {
+                Self::push_workspace_update_for_session(
+                    pool,
+                    msg_store.clone(),
+                    process.session_id,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
     /// Creates the hook function that should be used with DBService::new_with_after_connect
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
@@ -420,6 +444,20 @@ impl EventService {
                                         );
                                     }
 
+                                    if process.run_reason == ExecutionProcessRunReason::CodingAgent
+                                        && let Err(err) =
+                                            EventService::push_queued_workspace_updates(
+                                                &db.pool,
+                                                msg_store_for_hook.clone(),
+                                            )
+                                            .await
+                                    {
+                                        tracing::error!(
+                                            "Failed to push queued workspace updates: {:?}",
+                                            err
+                                        );
+                                    }
+
                                     return;
                                 }
                                 RecordTypes::DeletedExecutionProcess {
@@ -15,7 +15,7 @@ use serde::Deserialize;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::git_host::types::{CreatePrRequest, UnifiedPrComment};
+use crate::services::git_host::types::{CreatePrRequest, OpenPrInfo, UnifiedPrComment};
 
 #[derive(Debug, Clone)]
 pub struct AzureRepoInfo {
@@ -36,6 +36,17 @@ struct AzPrResponse {
     last_merge_commit: Option<AzCommit>,
 }
 
+/// Pull request item from `az repos pr list --status active`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzOpenPrResponse {
+    pull_request_id: i64,
+    title: String,
+    source_ref_name: String,
+    target_ref_name: String,
+    repository: Option<AzRepository>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AzRepository {
@@ -364,6 +375,58 @@ impl AzCli {
         Self::parse_pr_list_response(&raw)
     }
 
+    pub fn list_open_prs(
+        &self,
+        organization_url: &str,
+        project: &str,
+        repo_name: &str,
+    ) -> Result<Vec<OpenPrInfo>, AzCliError> {
+        let raw = self.run(
+            [
+                "repos",
+                "pr",
+                "list",
+                "--organization",
+                organization_url,
+                "--project",
+                project,
+                "--repository",
+                repo_name,
+                "--status",
+                "active",
+                "--top",
+                "100",
+                "--output",
+                "json",
+            ],
+            None,
+        )?;
+
+        Self::parse_open_pr_list(&raw)
+    }
+
+    /// Check out the source branch of a pull request in `repo_path`.
+    pub fn pr_checkout(
+        &self,
+        repo_path: &Path,
+        organization_url: &str,
+        pr_id: i64,
+    ) -> Result<(), AzCliError> {
+        self.run(
+            [
+                "repos",
+                "pr",
+                "checkout",
+                "--id",
+                &pr_id.to_string(),
+                "--organization",
+                organization_url,
+            ],
+            Some(repo_path),
+        )?;
+        Ok(())
+    }
+
     pub fn get_pr_threads(
         &self,
         organization_url: &str,
@@ -450,13 +513,40 @@ impl AzCli {
         Ok(prs.into_iter().map(Self::az_pr_to_info).collect())
     }
 
+    fn parse_open_pr_list(raw: &str) -> Result<Vec<OpenPrInfo>, AzCliError> {
+        let prs: Vec<AzOpenPrResponse> = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!("Failed to parse PR list: {e}; raw: {raw}"))
+        })?;
+        Ok(prs
+            .into_iter()
+            .map(|pr| OpenPrInfo {
+                number: pr.pull_request_id,
+                url: Self::pr_web_url(pr.repository, pr.pull_request_id),
+                title: pr.title,
+                head_branch: Self::branch_name(&pr.source_ref_name),
+                base_branch: Self::branch_name(&pr.target_ref_name),
+            })
+            .collect())
+    }
+
+    fn pr_web_url(repository: Option<AzRepository>, pr_id: i64) -> String {
+        repository
+            .and_then(|r| r.web_url)
+            .map(|u| format!("{}/pullrequest/{}", u, pr_id))
+            .unwrap_or_else(|| format!("pullrequest/{}", pr_id))
+    }
+
+    /// Azure reports branches as full refs (`refs/heads/main`).
+    fn branch_name(ref_name: &str) -> String {
+        ref_name
+            .strip_prefix("refs/heads/")
+            .unwrap_or(ref_name)
+            .to_string()
+    }
+
     /// Convert Azure PR response to PullRequestInfo.
     fn az_pr_to_info(pr: AzPrResponse) -> PullRequestInfo {
-        let url = pr
-            .repository
-            .and_then(|r| r.web_url)
-            .map(|u| format!("{}/pullrequest/{}", u, pr.pull_request_id))
-            .unwrap_or_else(|| format!("pullrequest/{}", pr.pull_request_id));
+        let url = Self::pr_web_url(pr.repository, pr.pull_request_id);
 
         let status = pr.status.as_deref().unwrap_or("active");
         let merged_at = pr
@@ -589,6 +679,28 @@ mod tests {
         assert!(AzCli::parse_pr_url("https://dev.azure.com/myorg/myproject/_git/myrepo").is_none());
     }
 
+    #[test]
+    fn test_parse_open_pr_list() {
+        let raw = r#"[{
+            "pullRequestId": 42,
+            "title": "Add login page",
+            "sourceRefName": "refs/heads/feature/login",
+            "targetRefName": "refs/heads/main",
+            "repository": {"webUrl": "https://dev.azure.com/myorg/myproject/_git/myrepo"}
+        }]"#;
+
+        let prs = AzCli::parse_open_pr_list(raw).unwrap();
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 42);
+        assert_eq!(prs[0].title, "Add login page");
+        assert_eq!(prs[0].head_branch, "feature/login");
+        assert_eq!(prs[0].base_branch, "main");
+        assert_eq!(
+            prs[0].url,
+            "https://dev.azure.com/myorg/myproject/_git/myrepo/pullrequest/42"
+        );
+    }
+
     #[test]
     fn test_map_azure_status() {
         assert!(matches!(
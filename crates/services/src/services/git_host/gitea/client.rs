@@ -0,0 +1,599 @@
+//! Minimal client for the Gitea/Forgejo REST API (`/api/v1`).
+//!
+//! Unlike GitHub and Azure DevOps there is no ubiquitous CLI for Gitea, so this
+//! talks to the instance directly using a personal access token.
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+use crate::services::git_host::types::{CreatePrRequest, OpenPrInfo, UnifiedPrComment};
+
+/// Gitea caps `limit` at 50 by default (`MAX_RESPONSE_ITEMS`).
+const PAGE_SIZE: usize = 50;
+/// Upper bound on pages fetched when scanning PR lists.
+const MAX_PAGES: usize = 20;
+
+/// PR last found for a head branch, keyed by the repository's pulls URL and the branch, so
+/// polling an open PR fetches it directly instead of scanning the PR list again.
+static BRANCH_PRS: LazyLock<Mutex<HashMap<(String, String), i64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaRepoInfo {
+    /// Web root of the instance, including any sub-path (e.g. `https://example.com/gitea`).
+    pub base_url: String,
+    pub owner: String,
+    pub repo_name: String,
+}
+
+impl GiteaRepoInfo {
+    /// Parse an HTTP(S), `ssh://` or scp-style remote URL.
+    ///
+    /// SSH remotes are assumed to be served over HTTPS on the same hostname.
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (base, path) = if let Some((scheme, rest)) = url.split_once("://") {
+            let (authority, path) = rest.split_once('/')?;
+            let authority = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+            if scheme == "http" || scheme == "https" {
+                (format!("{scheme}://{authority}"), path)
+            } else {
+                (format!("https://{}", authority.split(':').next()?), path)
+            }
+        } else {
+            let (authority, path) = url.split_once(':')?;
+            let host = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+            (format!("https://{host}"), path)
+        };
+
+        let path = path.trim_matches('/').trim_end_matches(".git");
+        let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let repo_name = segments.pop()?;
+        let owner = segments.pop()?;
+
+        // Anything left over is the sub-path the instance is served under
+        let base_url = if segments.is_empty() {
+            base
+        } else {
+            format!("{base}/{}", segments.join("/"))
+        };
+
+        Some(Self {
+            base_url,
+            owner: owner.to_string(),
+            repo_name: repo_name.to_string(),
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "{}/api/v1/repos/{}/{}{}",
+            self.base_url, self.owner, self.repo_name, path
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct GiteaCreatePullRequest<'a> {
+    head: &'a str,
+    base: &'a str,
+    title: &'a str,
+    body: &'a str,
+}
+
+#[derive(Deserialize)]
+struct GiteaPullRequest {
+    number: i64,
+    html_url: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    head: Option<GiteaBranchRef>,
+    base: Option<GiteaBranchRef>,
+}
+
+#[derive(Deserialize)]
+struct GiteaBranchRef {
+    #[serde(rename = "ref", default)]
+    ref_name: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GiteaIssueComment {
+    id: i64,
+    #[serde(default)]
+    body: String,
+    user: Option<GiteaUser>,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaReview {
+    id: i64,
+    #[serde(default)]
+    body: String,
+    user: Option<GiteaUser>,
+    submitted_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    comments_count: i64,
+}
+
+#[derive(Deserialize)]
+struct GiteaReviewComment {
+    id: i64,
+    #[serde(default)]
+    body: String,
+    user: Option<GiteaUser>,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    path: String,
+    /// Line in the new file; 0 when the comment is on a removed line.
+    #[serde(default)]
+    position: i64,
+    /// Line in the old file; 0 when the comment is on an added line.
+    #[serde(default)]
+    original_position: i64,
+    diff_hunk: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum GiteaApiError {
+    #[error("Gitea API request failed: {0}")]
+    Transport(String),
+    #[error("Gitea API authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("Gitea API denied access: {0}")]
+    Forbidden(String),
+    #[error("Gitea API resource not found: {0}")]
+    NotFound(String),
+    #[error("Gitea API returned {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("Gitea API returned unexpected output: {0}")]
+    UnexpectedOutput(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct GiteaClient {
+    http: Client,
+    token: Option<String>,
+}
+
+impl GiteaClient {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(token: Option<String>) -> Result<Self, GiteaApiError> {
+        let http = Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .user_agent(concat!("vibe-kanban/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| GiteaApiError::Transport(e.to_string()))?;
+        Ok(Self { http, token })
+    }
+
+    /// Build a client using the token from `GITEA_TOKEN` or `FORGEJO_TOKEN`.
+    pub fn from_env() -> Result<Self, GiteaApiError> {
+        let token = ["GITEA_TOKEN", "FORGEJO_TOKEN"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .filter(|token| !token.trim().is_empty());
+        Self::new(token)
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.header("Authorization", format!("token {token}")),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, GiteaApiError> {
+        let response = self
+            .authorize(request)
+            .send()
+            .await
+            .map_err(|e| GiteaApiError::Transport(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| GiteaApiError::Transport(e.to_string()))?;
+
+        if !status.is_success() {
+            return Err(match status {
+                StatusCode::UNAUTHORIZED => GiteaApiError::AuthFailed(if self.token.is_some() {
+                    body
+                } else {
+                    "no access token configured; set GITEA_TOKEN".to_string()
+                }),
+                StatusCode::FORBIDDEN => GiteaApiError::Forbidden(body),
+                StatusCode::NOT_FOUND => GiteaApiError::NotFound(body),
+                _ => GiteaApiError::Http {
+                    status: status.as_u16(),
+                    body,
+                },
+            });
+        }
+
+        serde_json::from_str(&body).map_err(|e| {
+            GiteaApiError::UnexpectedOutput(format!("Failed to parse response: {e}; raw: {body}"))
+        })
+    }
+
+    /// Fetch every page of a list endpoint (bounded by `MAX_PAGES`).
+    async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, GiteaApiError> {
+        self.get_pages_until(url, query, |_| false).await
+    }
+
+    /// Fetch pages of a list endpoint until `found` holds for one of them, or the list ends.
+    async fn get_pages_until<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+        found: impl Fn(&[T]) -> bool,
+    ) -> Result<Vec<T>, GiteaApiError> {
+        let mut items = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let page = page.to_string();
+            let limit = PAGE_SIZE.to_string();
+            let batch: Vec<T> = self
+                .send(
+                    self.http
+                        .get(url)
+                        .query(query)
+                        .query(&[("page", page.as_str()), ("limit", limit.as_str())]),
+                )
+                .await?;
+            let done = batch.len() < PAGE_SIZE || found(&batch);
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub async fn create_pr(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GiteaApiError> {
+        // Gitea has no draft flag; a WIP title prefix blocks merging the same way
+        let title = if request.draft.unwrap_or(false) {
+            format!("WIP: {}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = GiteaCreatePullRequest {
+            head: &request.head_branch,
+            base: &request.base_branch,
+            title: &title,
+            body: request.body.as_deref().unwrap_or(""),
+        };
+        let pr: GiteaPullRequest = self
+            .send(self.http.post(repo_info.api_url("/pulls")).json(&body))
+            .await?;
+        Ok(Self::pr_to_info(pr))
+    }
+
+    pub async fn get_pr(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        number: i64,
+    ) -> Result<PullRequestInfo, GiteaApiError> {
+        let pr: GiteaPullRequest = self
+            .send(
+                self.http
+                    .get(repo_info.api_url(&format!("/pulls/{number}"))),
+            )
+            .await?;
+        Ok(Self::pr_to_info(pr))
+    }
+
+    /// Name of the PR's head branch, as needed to check it out locally.
+    pub async fn get_pr_head_branch(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        number: i64,
+    ) -> Result<String, GiteaApiError> {
+        let pr: GiteaPullRequest = self
+            .send(
+                self.http
+                    .get(repo_info.api_url(&format!("/pulls/{number}"))),
+            )
+            .await?;
+        pr.head
+            .map(|head| head.ref_name)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| {
+                GiteaApiError::UnexpectedOutput(format!("PR #{number} has no head branch"))
+            })
+    }
+
+    /// List pull requests for a head branch (includes closed/merged), most recently updated
+    /// first.
+    ///
+    /// The API can't filter by head branch, so the list is scanned newest first and scanning
+    /// stops at the first page with a match. A PR found this way is remembered and, while it
+    /// stays open, fetched directly on later calls.
+    pub async fn list_prs_for_branch(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, GiteaApiError> {
+        let pulls_url = repo_info.api_url("/pulls");
+        let key = (pulls_url.clone(), branch.to_string());
+        let cached = BRANCH_PRS.lock().unwrap().get(&key).copied();
+        if let Some(number) = cached {
+            match self
+                .send::<GiteaPullRequest>(self.http.get(format!("{pulls_url}/{number}")))
+                .await
+            {
+                Ok(pr) if Self::is_head(&pr, branch) && pr.state == "open" => {
+                    return Ok(vec![Self::pr_to_info(pr)]);
+                }
+                // Closed, merged or gone; a newer PR may have been opened for the branch
+                Ok(_) | Err(GiteaApiError::NotFound(_)) => {
+                    BRANCH_PRS.lock().unwrap().remove(&key);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let prs: Vec<GiteaPullRequest> = self
+            .get_pages_until(
+                &pulls_url,
+                &[("state", "all"), ("sort", "recentupdate")],
+                |page: &[GiteaPullRequest]| page.iter().any(|pr| Self::is_head(pr, branch)),
+            )
+            .await?;
+        let prs: Vec<PullRequestInfo> = prs
+            .into_iter()
+            .filter(|pr| Self::is_head(pr, branch))
+            .map(Self::pr_to_info)
+            .collect();
+        if let Some(pr) = prs.first() {
+            BRANCH_PRS.lock().unwrap().insert(key, pr.number);
+        }
+        Ok(prs)
+    }
+
+    fn is_head(pr: &GiteaPullRequest, branch: &str) -> bool {
+        pr.head.as_ref().is_some_and(|h| h.ref_name == branch)
+    }
+
+    pub async fn list_open_prs(
+        &self,
+        repo_info: &GiteaRepoInfo,
+    ) -> Result<Vec<OpenPrInfo>, GiteaApiError> {
+        let prs: Vec<GiteaPullRequest> = self
+            .get_all_pages(&repo_info.api_url("/pulls"), &[("state", "open")])
+            .await?;
+        Ok(prs
+            .into_iter()
+            .map(|pr| OpenPrInfo {
+                number: pr.number,
+                url: pr.html_url,
+                title: pr.title,
+                head_branch: pr.head.map(|h| h.ref_name).unwrap_or_default(),
+                base_branch: pr.base.map(|b| b.ref_name).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Fetch conversation comments, review summaries and inline review comments.
+    pub async fn get_pr_comments(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GiteaApiError> {
+        let issue_comments: Vec<GiteaIssueComment> = self
+            .get_all_pages(
+                &repo_info.api_url(&format!("/issues/{number}/comments")),
+                &[],
+            )
+            .await?;
+        let reviews: Vec<GiteaReview> = self
+            .get_all_pages(&repo_info.api_url(&format!("/pulls/{number}/reviews")), &[])
+            .await?;
+
+        let mut comments: Vec<UnifiedPrComment> = issue_comments
+            .into_iter()
+            .map(|c| UnifiedPrComment::General {
+                id: c.id.to_string(),
+                author: Self::login(c.user),
+                author_association: None,
+                body: c.body,
+                created_at: c.created_at.unwrap_or_else(Utc::now),
+                url: Some(c.html_url).filter(|u| !u.is_empty()),
+            })
+            .collect();
+
+        for review in reviews {
+            if review.comments_count > 0 {
+                let review_comments: Vec<GiteaReviewComment> = self
+                    .send(
+                        self.http.get(
+                            repo_info.api_url(&format!(
+                                "/pulls/{number}/reviews/{}/comments",
+                                review.id
+                            )),
+                        ),
+                    )
+                    .await?;
+                comments.extend(
+                    review_comments
+                        .into_iter()
+                        .map(Self::review_comment_to_unified),
+                );
+            }
+
+            if !review.body.trim().is_empty() {
+                comments.push(UnifiedPrComment::General {
+                    id: format!("review-{}", review.id),
+                    author: Self::login(review.user),
+                    author_association: None,
+                    body: review.body,
+                    created_at: review.submitted_at.unwrap_or_else(Utc::now),
+                    url: Some(review.html_url).filter(|u| !u.is_empty()),
+                });
+            }
+        }
+
+        comments.sort_by_key(|c| c.created_at());
+        Ok(comments)
+    }
+}
+
+impl GiteaClient {
+    fn login(user: Option<GiteaUser>) -> String {
+        user.and_then(|u| u.login)
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    fn review_comment_to_unified(c: GiteaReviewComment) -> UnifiedPrComment {
+        let (line, side) = match (c.position, c.original_position) {
+            (new, _) if new > 0 => (Some(new), Some("RIGHT".to_string())),
+            (_, old) if old > 0 => (Some(old), Some("LEFT".to_string())),
+            _ => (None, None),
+        };
+        UnifiedPrComment::Review {
+            id: c.id,
+            author: Self::login(c.user),
+            author_association: None,
+            body: c.body,
+            created_at: c.created_at.unwrap_or_else(Utc::now),
+            url: Some(c.html_url).filter(|u| !u.is_empty()),
+            path: c.path,
+            line,
+            side,
+            diff_hunk: c.diff_hunk,
+        }
+    }
+
+    fn pr_to_info(pr: GiteaPullRequest) -> PullRequestInfo {
+        PullRequestInfo {
+            number: pr.number,
+            url: pr.html_url,
+            status: Self::map_gitea_state(&pr.state, pr.merged),
+            merged_at: pr.merged_at,
+            merge_commit_sha: pr.merge_commit_sha,
+        }
+    }
+
+    /// Map Gitea PR state to MergeStatus. Merged PRs report `state: "closed"` with `merged: true`.
+    fn map_gitea_state(state: &str, merged: bool) -> MergeStatus {
+        if merged {
+            return MergeStatus::Merged;
+        }
+        match state.to_ascii_lowercase().as_str() {
+            "open" => MergeStatus::Open,
+            "closed" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        }
+    }
+
+    /// Parse a PR web URL into its repository and number.
+    ///
+    /// Format: `https://{host}[/{sub-path}]/{owner}/{repo}/pulls/{number}`
+    pub fn parse_pr_url(url: &str) -> Option<(GiteaRepoInfo, i64)> {
+        let (repo_url, rest) = url.rsplit_once("/pulls/")?;
+        let number = rest.split(['/', '?', '#']).next()?.parse::<i64>().ok()?;
+        Some((GiteaRepoInfo::from_remote_url(repo_url)?, number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_info_from_remote_urls() {
+        let https = GiteaRepoInfo::from_remote_url("https://codeberg.org/owner/repo.git").unwrap();
+        assert_eq!(https.base_url, "https://codeberg.org");
+        assert_eq!(https.owner, "owner");
+        assert_eq!(https.repo_name, "repo");
+
+        let sub_path =
+            GiteaRepoInfo::from_remote_url("http://127.0.0.1:3000/gitea/owner/repo").unwrap();
+        assert_eq!(sub_path.base_url, "http://127.0.0.1:3000/gitea");
+        assert_eq!(
+            sub_path.api_url("/pulls"),
+            "http://127.0.0.1:3000/gitea/api/v1/repos/owner/repo/pulls"
+        );
+
+        let ssh = GiteaRepoInfo::from_remote_url("ssh://git@gitea.acme.corp:2222/owner/repo.git")
+            .unwrap();
+        assert_eq!(ssh.base_url, "https://gitea.acme.corp");
+
+        let scp = GiteaRepoInfo::from_remote_url("git@gitea.acme.corp:owner/repo.git").unwrap();
+        assert_eq!(scp.base_url, "https://gitea.acme.corp");
+        assert_eq!(scp.owner, "owner");
+
+        assert!(GiteaRepoInfo::from_remote_url("https://gitea.acme.corp/repo").is_none());
+    }
+
+    #[test]
+    fn test_parse_pr_url() {
+        let (repo, number) =
+            GiteaClient::parse_pr_url("https://gitea.acme.corp/owner/repo/pulls/12").unwrap();
+        assert_eq!(repo.owner, "owner");
+        assert_eq!(repo.repo_name, "repo");
+        assert_eq!(number, 12);
+
+        let (_, number) =
+            GiteaClient::parse_pr_url("https://gitea.acme.corp/owner/repo/pulls/3/files").unwrap();
+        assert_eq!(number, 3);
+
+        assert!(GiteaClient::parse_pr_url("https://github.com/owner/repo/pull/1").is_none());
+    }
+
+    #[test]
+    fn test_map_gitea_state() {
+        assert!(matches!(
+            GiteaClient::map_gitea_state("open", false),
+            MergeStatus::Open
+        ));
+        assert!(matches!(
+            GiteaClient::map_gitea_state("closed", true),
+            MergeStatus::Merged
+        ));
+        assert!(matches!(
+            GiteaClient::map_gitea_state("closed", false),
+            MergeStatus::Closed
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+//! Gitea and Forgejo hosting service implementation (REST API).
+
+mod client;
+
+use std::{path::Path, time::Duration};
+
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+pub use client::{GiteaApiError, GiteaClient, GiteaRepoInfo};
+use db::models::merge::PullRequestInfo;
+use git::GitCli;
+use tokio::task;
+use tracing::info;
+
+use super::{
+    GitHostProvider,
+    types::{CreatePrRequest, GitHostError, OpenPrInfo, ProviderKind, UnifiedPrComment},
+};
+
+#[derive(Debug, Clone)]
+pub struct GiteaProvider {
+    client: GiteaClient,
+}
+
+impl GiteaProvider {
+    pub fn new() -> Result<Self, GitHostError> {
+        Ok(Self::with_client(GiteaClient::from_env()?))
+    }
+
+    pub fn with_client(client: GiteaClient) -> Self {
+        Self { client }
+    }
+
+    fn get_repo_info(remote_url: &str) -> Result<GiteaRepoInfo, GitHostError> {
+        GiteaRepoInfo::from_remote_url(remote_url).ok_or_else(|| {
+            GitHostError::Repository(format!(
+                "Could not parse Gitea repository from remote URL: {remote_url}"
+            ))
+        })
+    }
+}
+
+impl From<GiteaApiError> for GitHostError {
+    fn from(error: GiteaApiError) -> Self {
+        match error {
+            GiteaApiError::AuthFailed(msg) => GitHostError::AuthFailed(msg),
+            GiteaApiError::Forbidden(msg) => GitHostError::InsufficientPermissions(msg),
+            GiteaApiError::NotFound(msg) => GitHostError::RepoNotFoundOrNoAccess(msg),
+            GiteaApiError::UnexpectedOutput(msg) => GitHostError::UnexpectedOutput(msg),
+            e @ (GiteaApiError::Transport(_) | GiteaApiError::Http { .. }) => {
+                GitHostError::PullRequest(e.to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for GiteaProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let target_repo_info = Self::get_repo_info(remote_url)?;
+
+        // For cross-fork PRs, Gitea expects the head as "owner:branch".
+        let mut request = request.clone();
+        if let Some(head_url) = &request.head_repo_url {
+            let head_repo_info = Self::get_repo_info(head_url)?;
+            if head_repo_info.owner != target_repo_info.owner {
+                request.head_branch = format!("{}:{}", head_repo_info.owner, request.head_branch);
+            }
+        }
+
+        (|| async {
+            let pr = self
+                .client
+                .create_pr(&target_repo_info, &request)
+                .await
+                .map_err(GitHostError::from)?;
+
+            info!(
+                "Created Gitea PR #{} for branch {}",
+                pr.number, request.head_branch
+            );
+
+            Ok(pr)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Gitea API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        let (repo_info, number) = GiteaClient::parse_pr_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Could not parse Gitea PR URL: {pr_url}"))
+        })?;
+
+        (|| async {
+            self.client
+                .get_pr(&repo_info, number)
+                .await
+                .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|err: &GitHostError| err.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Gitea API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let repo_info = Self::get_repo_info(remote_url)?;
+
+        (|| async {
+            self.client
+                .list_prs_for_branch(&repo_info, branch_name)
+                .await
+                .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Gitea API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let repo_info = Self::get_repo_info(remote_url)?;
+
+        (|| async {
+            self.client
+                .get_pr_comments(&repo_info, pr_number)
+                .await
+                .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Gitea API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn list_open_prs(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<OpenPrInfo>, GitHostError> {
+        let repo_info = Self::get_repo_info(remote_url)?;
+
+        (|| async {
+            self.client
+                .list_open_prs(&repo_info)
+                .await
+                .map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Gitea API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn checkout_pr(
+        &self,
+        worktree_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<(), GitHostError> {
+        let repo_info = Self::get_repo_info(remote_url)?;
+        let head_branch = self
+            .client
+            .get_pr_head_branch(&repo_info, pr_number)
+            .await?;
+
+        // Gitea exposes every PR head as refs/pull/{number}/head, including fork PRs
+        let path = worktree_path.to_path_buf();
+        let url = remote_url.to_string();
+        task::spawn_blocking(move || {
+            let git = GitCli::new();
+            git.fetch_with_refspec(&path, &url, &format!("refs/pull/{pr_number}/head"))?;
+            git.git(
+                &path,
+                ["checkout", "-B", head_branch.as_str(), "FETCH_HEAD"],
+            )?;
+            Ok::<_, git::GitCliError>(())
+        })
+        .await
+        .map_err(|err| GitHostError::PullRequest(format!("Failed to check out Gitea PR: {err}")))?
+        .map_err(|err| GitHostError::Repository(err.to_string()))
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Gitea
+    }
+}
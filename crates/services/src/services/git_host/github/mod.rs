@@ -389,6 +389,28 @@ impl GitHostProvider for GitHubProvider {
         .await
     }
 
+    async fn checkout_pr(
+        &self,
+        worktree_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, worktree_path).await?;
+        let cli = self.gh_cli.clone();
+        let path = worktree_path.to_path_buf();
+
+        task::spawn_blocking(move || {
+            cli.pr_checkout(&path, &repo_info.owner, &repo_info.repo_name, pr_number)
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute GitHub CLI for PR checkout: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::GitHub
     }
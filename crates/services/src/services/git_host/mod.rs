@@ -2,7 +2,9 @@ mod detection;
 mod types;
 
 pub mod azure;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
 
 use std::path::Path;
 
@@ -15,7 +17,10 @@ pub use types::{
     ProviderKind, ReviewCommentUser, UnifiedPrComment,
 };
 
-use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
+use self::{
+    azure::AzureDevOpsProvider, gitea::GiteaProvider, github::GitHubProvider,
+    gitlab::GitLabProvider,
+};
 
 #[async_trait]
 #[enum_dispatch(GitHostService)]
@@ -49,6 +54,14 @@ pub trait GitHostProvider: Send + Sync {
         remote_url: &str,
     ) -> Result<Vec<OpenPrInfo>, GitHostError>;
 
+    /// Fetch a PR's head branch and check it out in the given worktree.
+    async fn checkout_pr(
+        &self,
+        worktree_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<(), GitHostError>;
+
     fn provider_kind(&self) -> ProviderKind;
 }
 
@@ -56,6 +69,8 @@ pub trait GitHostProvider: Send + Sync {
 pub enum GitHostService {
     GitHub(GitHubProvider),
     AzureDevOps(AzureDevOpsProvider),
+    GitLab(GitLabProvider),
+    Gitea(GiteaProvider),
 }
 
 impl GitHostService {
@@ -63,6 +78,8 @@ impl GitHostService {
         match detect_provider_from_url(url) {
             ProviderKind::GitHub => Ok(Self::GitHub(GitHubProvider::new()?)),
             ProviderKind::AzureDevOps => Ok(Self::AzureDevOps(AzureDevOpsProvider::new()?)),
+            ProviderKind::GitLab => Ok(Self::GitLab(GitLabProvider::new()?)),
+            ProviderKind::Gitea => Ok(Self::Gitea(GiteaProvider::new()?)),
             ProviderKind::Unknown => Err(GitHostError::UnsupportedProvider),
         }
     }
@@ -8,6 +8,8 @@ use ts_rs::TS;
 pub enum ProviderKind {
     GitHub,
     AzureDevOps,
+    GitLab,
+    Gitea,
     Unknown,
 }
 
@@ -16,6 +18,8 @@ impl std::fmt::Display for ProviderKind {
         match self {
             ProviderKind::GitHub => write!(f, "GitHub"),
             ProviderKind::AzureDevOps => write!(f, "Azure DevOps"),
+            ProviderKind::GitLab => write!(f, "GitLab"),
+            ProviderKind::Gitea => write!(f, "Gitea"),
             ProviderKind::Unknown => write!(f, "Unknown"),
         }
     }
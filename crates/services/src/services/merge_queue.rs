@@ -0,0 +1,846 @@
+//! Lands queued workspaces on their repo's target branch one at a time.
+//!
+//! The head of each repo's queue is rebased onto the latest target branch, verified
+//! with the repo's check script and merged only if the script passes. A failed check
+//! or a conflicting rebase pauses that repo's queue until the entry is retried or removed.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::anyhow;
+use db::{
+    DBService,
+    models::{
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+        merge::Merge,
+        merge_queue::{MergeQueueEntry, MergeQueueStatus},
+        repo::Repo,
+        session::{CreateSession, Session},
+        task::{Task, TaskStatus},
+        workspace::{Workspace, WorkspaceError},
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use executors::actions::{
+    ExecutorAction, ExecutorActionType,
+    script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+};
+use git::{ConflictOp, GitService, GitServiceError, MergeStrategy};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::time::interval;
+use tracing::{error, info};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    repo_config::effective_repo,
+    task_scheduler,
+};
+
+/// A queue entry along with the conflict state of its worktree, when it is paused on one
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MergeQueueItem {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub entry: MergeQueueEntry,
+    pub conflict_op: Option<ConflictOp>,
+    pub conflicted_files: Vec<String>,
+}
+
+/// Commit message used when a task's branch is merged locally
+pub fn merge_commit_message(task: &Task) -> String {
+    let task_uuid_str = task.id.to_string();
+    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
+
+    let mut commit_message = format!("{} (vibe-kanban {})", task.title, first_uuid_section);
+
+    // Add description on next line if it exists
+    if let Some(description) = &task.description
+        && !description.trim().is_empty()
+    {
+        commit_message.push_str("\n\n");
+        commit_message.push_str(description);
+    }
+    commit_message
+}
+
+/// A repo's queue in merge order, with the conflicts of entries paused on a rebase
+pub async fn list_merge_queue(
+    pool: &SqlitePool,
+    git: &GitService,
+    repo: &Repo,
+) -> Result<Vec<MergeQueueItem>, sqlx::Error> {
+    let entries = MergeQueueEntry::find_by_repo_id(pool, repo.id).await?;
+    let mut items = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let mut item = MergeQueueItem {
+            entry,
+            conflict_op: None,
+            conflicted_files: Vec::new(),
+        };
+        if item.entry.status == MergeQueueStatus::Conflict
+            && let Some(container_ref) = Workspace::find_by_id(pool, item.entry.workspace_id)
+                .await?
+                .and_then(|w| w.container_ref)
+        {
+            let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+            item.conflict_op = git.detect_conflict_op(&worktree_path).unwrap_or(None);
+            item.conflicted_files = git.get_conflicted_files(&worktree_path).unwrap_or_default();
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// The entry the queue works on next: its head, unless a failure or conflict pauses it
+fn next_entry(entries: Vec<MergeQueueEntry>) -> Option<MergeQueueEntry> {
+    entries.into_iter().next().filter(|entry| {
+        matches!(
+            entry.status,
+            MergeQueueStatus::Queued | MergeQueueStatus::Checking
+        )
+    })
+}
+
+/// Whether a failed rebase pauses the queue on a conflict rather than failing the entry
+fn is_rebase_conflict(err: &GitServiceError) -> bool {
+    matches!(
+        err,
+        GitServiceError::MergeConflicts { .. } | GitServiceError::RebaseInProgress
+    )
+}
+
+/// Whether a failed merge means the entry has to be rebased and checked again
+fn needs_requeue(err: &GitServiceError) -> bool {
+    matches!(err, GitServiceError::BranchesDiverged(_))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CheckOutcome {
+    Running,
+    Passed,
+    Failed(String),
+}
+
+fn check_outcome(status: &ExecutionProcessStatus, exit_code: Option<i64>) -> CheckOutcome {
+    match status {
+        ExecutionProcessStatus::Queued | ExecutionProcessStatus::Running => CheckOutcome::Running,
+        ExecutionProcessStatus::Completed if exit_code == Some(0) => CheckOutcome::Passed,
+        _ => CheckOutcome::Failed(match exit_code {
+            Some(code) => format!("Check script failed with exit code {code}"),
+            None => "Check script did not complete".to_string(),
+        }),
+    }
+}
+
+/// Everything needed to rebase, check and merge one queue entry
+struct QueuedWorkspace {
+    workspace: Workspace,
+    target_branch: String,
+    repo: Repo,
+    worktree_path: PathBuf,
+}
+
+/// Service that works through every repo's merge queue in the background
+pub struct MergeQueueService<C: ContainerService> {
+    db: DBService,
+    poll_interval: Duration,
+    container: C,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> MergeQueueService<C> {
+    pub async fn spawn(db: DBService, container: C) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(5),
+            container,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting merge queue service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            let repo_ids = match MergeQueueEntry::find_repo_ids(&self.db.pool).await {
+                Ok(repo_ids) => repo_ids,
+                Err(e) => {
+                    error!("Error loading merge queues: {}", e);
+                    continue;
+                }
+            };
+            for repo_id in repo_ids {
+                if let Err(e) = self.advance(repo_id).await {
+                    error!("Error advancing merge queue of repo {}: {}", repo_id, e);
+                }
+            }
+        }
+    }
+
+    /// Move the head of a repo's queue forward by one step
+    async fn advance(&self, repo_id: Uuid) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let Some(entry) = next_entry(MergeQueueEntry::find_by_repo_id(pool, repo_id).await?) else {
+            return Ok(());
+        };
+
+        let result = match entry.status {
+            MergeQueueStatus::Checking => self.finish_check(&entry).await,
+            _ => self.rebase_and_check(&entry).await,
+        };
+        if let Err(e) = result {
+            error!("Merge queue entry {} failed: {}", entry.id, e);
+            MergeQueueEntry::update_status(
+                pool,
+                entry.id,
+                MergeQueueStatus::Failed,
+                entry.execution_process_id,
+                Some(&e.to_string()),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn load(&self, entry: &MergeQueueEntry) -> Result<QueuedWorkspace, ContainerError> {
+        let pool = &self.db.pool;
+        let workspace = Workspace::find_by_id(pool, entry.workspace_id)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Workspace not found")))?;
+        let workspace_repo =
+            WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, entry.repo_id)
+                .await?
+                .ok_or(ContainerError::Other(anyhow!(
+                    "Workspace no longer uses this repository"
+                )))?;
+        let repo = Repo::find_by_id(pool, entry.repo_id)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!("Repository not found")))?;
+        let container_ref = self.container.ensure_container_exists(&workspace).await?;
+        let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+
+        Ok(QueuedWorkspace {
+            workspace,
+            target_branch: workspace_repo.target_branch,
+            repo,
+            worktree_path,
+        })
+    }
+
+    /// Rebase the entry onto its target branch, then start the check script or,
+    /// when the repo has none, merge right away
+    async fn rebase_and_check(&self, entry: &MergeQueueEntry) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let queued = self.load(entry).await?;
+        let git = self.container.git();
+
+        match git.rebase_branch(
+            &queued.repo.path,
+            &queued.worktree_path,
+            &queued.target_branch,
+            &queued.target_branch,
+            &queued.workspace.branch,
+        ) {
+            Ok(_) => {}
+            Err(e) if is_rebase_conflict(&e) => {
+                info!(
+                    "Pausing merge queue of repo {}: workspace {} conflicts with {}",
+                    entry.repo_id, entry.workspace_id, queued.target_branch
+                );
+                MergeQueueEntry::update_status(
+                    pool,
+                    entry.id,
+                    MergeQueueStatus::Conflict,
+                    None,
+                    Some(&e.to_string()),
+                )
+                .await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let Some(check_script) = effective_repo(pool, queued.repo.clone()).await.check_script
+        else {
+            return self.merge(entry, &queued).await;
+        };
+
+        let session = match Session::find_latest_by_workspace_id(pool, queued.workspace.id).await? {
+            Some(s) => s,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    queued.workspace.id,
+                )
+                .await?
+            }
+        };
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: check_script,
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::CheckScript,
+                working_dir: Some(queued.repo.name.clone()),
+            }),
+            None,
+        );
+        let process = self
+            .container
+            .start_execution(
+                &queued.workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::CheckScript,
+                None,
+            )
+            .await?;
+
+        MergeQueueEntry::update_status(
+            pool,
+            entry.id,
+            MergeQueueStatus::Checking,
+            Some(process.id),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Merge the entry once its check script has passed
+    async fn finish_check(&self, entry: &MergeQueueEntry) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let process = match entry.execution_process_id {
+            Some(id) => ExecutionProcess::find_by_id(pool, id).await?,
+            None => None,
+        };
+        let Some(process) = process else {
+            // The check was deleted along with its session; start over
+            MergeQueueEntry::update_status(pool, entry.id, MergeQueueStatus::Queued, None, None)
+                .await?;
+            return Ok(());
+        };
+
+        match check_outcome(&process.status, process.exit_code) {
+            CheckOutcome::Running => Ok(()),
+            CheckOutcome::Passed => {
+                let queued = self.load(entry).await?;
+                self.merge(entry, &queued).await
+            }
+            CheckOutcome::Failed(reason) => {
+                info!(
+                    "Pausing merge queue of repo {}: {} for workspace {}",
+                    entry.repo_id, reason, entry.workspace_id
+                );
+                MergeQueueEntry::update_status(
+                    pool,
+                    entry.id,
+                    MergeQueueStatus::Failed,
+                    Some(process.id),
+                    Some(&reason),
+                )
+                .await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn merge(
+        &self,
+        entry: &MergeQueueEntry,
+        queued: &QueuedWorkspace,
+    ) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let workspace = &queued.workspace;
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(WorkspaceError::TaskNotFound)?;
+        let strategy = queued
+            .repo
+            .default_merge_strategy
+            .as_deref()
+            .and_then(|s| s.parse::<MergeStrategy>().ok())
+            .unwrap_or_default();
+
+        let merge_commit_id = match self.container.git().merge_changes_with_strategy(
+            &queued.repo.path,
+            &queued.worktree_path,
+            &workspace.branch,
+            &queued.target_branch,
+            &merge_commit_message(&task),
+            strategy,
+        ) {
+            Ok(id) => id,
+            Err(e) if needs_requeue(&e) => {
+                // The target moved while the check ran; rebase and check again
+                MergeQueueEntry::update_status(
+                    pool,
+                    entry.id,
+                    MergeQueueStatus::Queued,
+                    None,
+                    None,
+                )
+                .await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        Merge::create_direct(
+            pool,
+            workspace.id,
+            queued.repo.id,
+            &queued.target_branch,
+            &merge_commit_id,
+        )
+        .await?;
+        MergeQueueEntry::delete(pool, entry.id).await?;
+        info!(
+            "Merge queue landed workspace {} on {} as {}",
+            workspace.id, queued.target_branch, merge_commit_id
+        );
+
+        Task::update_status(pool, task.id, TaskStatus::Done).await?;
+        if !workspace.pinned
+            && let Err(e) = self.container.archive_workspace(workspace.id).await
+        {
+            error!("Failed to archive workspace {}: {}", workspace.id, e);
+        }
+        if let Err(e) = task_scheduler::start_unblocked_dependents(&self.container, task.id).await {
+            error!("Failed to start dependents of task {}: {}", task.id, e);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::Path, sync::Arc};
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use db::{
+        models::{
+            execution_process::{CreateExecutionProcess, ExecutionContext},
+            project::{CreateProject, Project},
+            task::CreateTask,
+            workspace::CreateWorkspace,
+        },
+        test_support::script_action,
+    };
+    use executors::profile::ExecutorProfileId;
+    use futures::stream::BoxStream;
+    use tokio::{
+        sync::{Mutex, RwLock},
+        task::JoinHandle,
+    };
+    use utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+    use super::*;
+    use crate::services::{
+        config::{ConcurrencyConfig, Config},
+        container::ContainerRef,
+        notification::NotificationService,
+    };
+
+    fn entry(status: MergeQueueStatus) -> MergeQueueEntry {
+        MergeQueueEntry {
+            id: Uuid::new_v4(),
+            repo_id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            status,
+            execution_process_id: None,
+            error: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn task(description: Option<&str>) -> Task {
+        Task {
+            id: Uuid::parse_str("1b4e28ba-2fa1-11d2-883f-0016d3cca427").unwrap(),
+            project_id: Uuid::new_v4(),
+            title: "Add login page".to_string(),
+            description: description.map(str::to_string),
+            status: TaskStatus::InReview,
+            parent_workspace_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn merge_commit_message_includes_description_when_present() {
+        assert_eq!(
+            merge_commit_message(&task(None)),
+            "Add login page (vibe-kanban 1b4e28ba)"
+        );
+        assert_eq!(
+            merge_commit_message(&task(Some("  "))),
+            "Add login page (vibe-kanban 1b4e28ba)"
+        );
+        assert_eq!(
+            merge_commit_message(&task(Some("Uses the new auth flow"))),
+            "Add login page (vibe-kanban 1b4e28ba)\n\nUses the new auth flow"
+        );
+    }
+
+    #[test]
+    fn queue_advances_to_the_next_entry_once_the_head_lands() {
+        let first = entry(MergeQueueStatus::Checking);
+        let second = entry(MergeQueueStatus::Queued);
+        let entries = vec![first.clone(), second.clone()];
+
+        assert_eq!(next_entry(entries.clone()).map(|e| e.id), Some(first.id));
+        // Landing deletes the head, so the following entry is picked up next
+        assert_eq!(
+            next_entry(entries[1..].to_vec()).map(|e| e.id),
+            Some(second.id)
+        );
+        assert!(next_entry(vec![]).is_none());
+    }
+
+    #[test]
+    fn failed_or_conflicting_head_pauses_the_queue() {
+        for status in [MergeQueueStatus::Failed, MergeQueueStatus::Conflict] {
+            let entries = vec![entry(status), entry(MergeQueueStatus::Queued)];
+            assert!(next_entry(entries).is_none(), "{status:?}");
+        }
+    }
+
+    #[test]
+    fn check_script_result_decides_merge_or_failure() {
+        assert_eq!(
+            check_outcome(&ExecutionProcessStatus::Running, None),
+            CheckOutcome::Running
+        );
+        assert_eq!(
+            check_outcome(&ExecutionProcessStatus::Completed, Some(0)),
+            CheckOutcome::Passed
+        );
+        assert_eq!(
+            check_outcome(&ExecutionProcessStatus::Completed, Some(2)),
+            CheckOutcome::Failed("Check script failed with exit code 2".to_string())
+        );
+        assert_eq!(
+            check_outcome(&ExecutionProcessStatus::Killed, None),
+            CheckOutcome::Failed("Check script did not complete".to_string())
+        );
+    }
+
+    /// Container without worktrees; enough for the queue steps that only touch the database
+    struct TestContainer {
+        db: DBService,
+        git: GitService,
+        msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+        notification_service: NotificationService,
+        execution_queue_lock: Arc<Mutex<()>>,
+    }
+
+    #[async_trait]
+    impl ContainerService for TestContainer {
+        fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>> {
+            &self.msg_stores
+        }
+
+        fn db(&self) -> &DBService {
+            &self.db
+        }
+
+        fn git(&self) -> &GitService {
+            &self.git
+        }
+
+        fn notification_service(&self) -> &NotificationService {
+            &self.notification_service
+        }
+
+        fn execution_queue_lock(&self) -> &Arc<Mutex<()>> {
+            &self.execution_queue_lock
+        }
+
+        async fn touch(&self, _workspace: &Workspace) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        fn workspace_to_current_dir(&self, _workspace: &Workspace) -> PathBuf {
+            unimplemented!()
+        }
+
+        async fn store_db_stream_handle(&self, _id: Uuid, _handle: JoinHandle<()>) {}
+
+        async fn take_db_stream_handle(&self, _id: &Uuid) -> Option<JoinHandle<()>> {
+            None
+        }
+
+        async fn create(&self, _workspace: &Workspace) -> Result<ContainerRef, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn shutdown_running_processes(&self) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn reattach_execution(
+            &self,
+            _execution_process: &ExecutionProcess,
+        ) -> Result<bool, ContainerError> {
+            Ok(false)
+        }
+
+        async fn delete(&self, _workspace: &Workspace) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn ensure_container_exists(
+            &self,
+            _workspace: &Workspace,
+        ) -> Result<ContainerRef, ContainerError> {
+            Err(ContainerError::Other(anyhow!("Worktree unavailable")))
+        }
+
+        async fn is_container_clean(&self, _workspace: &Workspace) -> Result<bool, ContainerError> {
+            Ok(true)
+        }
+
+        async fn start_execution_inner(
+            &self,
+            _workspace: &Workspace,
+            _execution_process: &ExecutionProcess,
+            _executor_action: &ExecutorAction,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn stop_execution(
+            &self,
+            _execution_process: &ExecutionProcess,
+            _status: ExecutionProcessStatus,
+        ) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn try_commit_changes(
+            &self,
+            _ctx: &ExecutionContext,
+        ) -> Result<bool, ContainerError> {
+            Ok(false)
+        }
+
+        async fn copy_project_files(
+            &self,
+            _source_dir: &Path,
+            _target_dir: &Path,
+            _copy_files: &str,
+        ) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn stream_diff(
+            &self,
+            _workspace: &Workspace,
+            _stats_only: bool,
+        ) -> Result<BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn git_branch_prefix(&self) -> String {
+            "vk".to_string()
+        }
+
+        async fn auto_start_executor_profile(&self) -> Option<ExecutorProfileId> {
+            None
+        }
+
+        async fn concurrency_config(&self) -> ConcurrencyConfig {
+            ConcurrencyConfig::default()
+        }
+    }
+
+    async fn service() -> MergeQueueService<TestContainer> {
+        let db = db::test_support::db().await;
+        let container = TestContainer {
+            db: db.clone(),
+            git: GitService::new(),
+            msg_stores: Arc::default(),
+            notification_service: NotificationService::new(Arc::new(
+                RwLock::new(Config::default()),
+            )),
+            execution_queue_lock: Arc::default(),
+        };
+        MergeQueueService {
+            db,
+            poll_interval: Duration::from_secs(5),
+            container,
+        }
+    }
+
+    async fn repo(pool: &SqlitePool) -> Repo {
+        Repo::find_or_create(pool, Path::new("/tmp/merge-queue-repo"), "repo")
+            .await
+            .unwrap()
+    }
+
+    /// Queue a new workspace of a new task on `repo`
+    async fn enqueue(pool: &SqlitePool, repo: &Repo) -> (MergeQueueEntry, Session) {
+        let project_id = Uuid::new_v4();
+        let project = CreateProject {
+            name: format!("project {project_id}"),
+            repositories: vec![],
+        };
+        Project::create(pool, &project, project_id).await.unwrap();
+        let task = CreateTask::from_title_description(project_id, "task".to_string(), None);
+        let task = Task::create(pool, &task, Uuid::new_v4()).await.unwrap();
+        let workspace_id = Uuid::new_v4();
+        let workspace = CreateWorkspace {
+            branch: format!("vk/{workspace_id}"),
+            agent_working_dir: None,
+        };
+        let workspace = Workspace::create(pool, &workspace, workspace_id, task.id)
+            .await
+            .unwrap();
+        let session = Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap();
+        let entry = MergeQueueEntry::create(pool, repo.id, workspace.id)
+            .await
+            .unwrap();
+        (entry, session)
+    }
+
+    /// Put `entry` in the checking state with a running check script
+    async fn start_check(
+        pool: &SqlitePool,
+        entry: &MergeQueueEntry,
+        session: &Session,
+    ) -> ExecutionProcess {
+        let process = ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: script_action(ScriptContext::CheckScript),
+                run_reason: ExecutionProcessRunReason::CheckScript,
+                status: ExecutionProcessStatus::Running,
+                checked_process_id: None,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await
+        .unwrap();
+        MergeQueueEntry::update_status(
+            pool,
+            entry.id,
+            MergeQueueStatus::Checking,
+            Some(process.id),
+            None,
+        )
+        .await
+        .unwrap();
+        process
+    }
+
+    async fn reload(pool: &SqlitePool, entry: &MergeQueueEntry) -> MergeQueueEntry {
+        MergeQueueEntry::find_by_id(pool, entry.id)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn failed_check_pauses_the_queue_behind_its_entry() {
+        let service = service().await;
+        let pool = &service.db.pool;
+        let repo = repo(pool).await;
+        let (head, session) = enqueue(pool, &repo).await;
+        let (next, _) = enqueue(pool, &repo).await;
+        let check = start_check(pool, &head, &session).await;
+
+        // Nothing moves while the check runs
+        service.advance(repo.id).await.unwrap();
+        assert_eq!(reload(pool, &head).await.status, MergeQueueStatus::Checking);
+
+        ExecutionProcess::update_completion(
+            pool,
+            check.id,
+            ExecutionProcessStatus::Completed,
+            Some(2),
+        )
+        .await
+        .unwrap();
+        service.advance(repo.id).await.unwrap();
+        let head = reload(pool, &head).await;
+        assert_eq!(head.status, MergeQueueStatus::Failed);
+        assert_eq!(head.execution_process_id, Some(check.id));
+        assert_eq!(
+            head.error.as_deref(),
+            Some("Check script failed with exit code 2")
+        );
+
+        service.advance(repo.id).await.unwrap();
+        assert_eq!(reload(pool, &next).await.status, MergeQueueStatus::Queued);
+    }
+
+    #[tokio::test]
+    async fn entry_whose_check_was_deleted_is_queued_again() {
+        let service = service().await;
+        let pool = &service.db.pool;
+        let repo = repo(pool).await;
+        let (entry, _) = enqueue(pool, &repo).await;
+        MergeQueueEntry::update_status(pool, entry.id, MergeQueueStatus::Checking, None, None)
+            .await
+            .unwrap();
+
+        service
+            .finish_check(&reload(pool, &entry).await)
+            .await
+            .unwrap();
+
+        let entry = reload(pool, &entry).await;
+        assert_eq!(entry.status, MergeQueueStatus::Queued);
+        assert_eq!(entry.execution_process_id, None);
+    }
+
+    #[tokio::test]
+    async fn passed_check_that_cannot_merge_fails_the_entry() {
+        let service = service().await;
+        let pool = &service.db.pool;
+        let repo = repo(pool).await;
+        let (entry, session) = enqueue(pool, &repo).await;
+        let check = start_check(pool, &entry, &session).await;
+        ExecutionProcess::update_completion(
+            pool,
+            check.id,
+            ExecutionProcessStatus::Completed,
+            Some(0),
+        )
+        .await
+        .unwrap();
+
+        // The workspace is not in the repo, so loading it for the merge fails
+        service.advance(repo.id).await.unwrap();
+
+        let entry = reload(pool, &entry).await;
+        assert_eq!(entry.status, MergeQueueStatus::Failed);
+        assert_eq!(
+            entry.error.as_deref(),
+            Some("Workspace no longer uses this repository")
+        );
+    }
+}
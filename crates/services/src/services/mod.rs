@@ -11,6 +11,7 @@ pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git_host;
 pub mod image;
+pub mod merge_queue;
 pub mod migration;
 pub mod notification;
 pub mod oauth_credentials;
@@ -22,6 +23,10 @@ pub mod queued_message;
 pub mod remote_client;
 pub mod remote_sync;
 pub mod repo;
+pub mod repo_config;
+pub mod task_scheduler;
+pub mod token_usage;
+pub mod transcript;
 pub mod webhook_notification;
 pub mod workspace_manager;
 pub mod worktree_manager;
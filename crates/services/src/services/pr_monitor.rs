@@ -21,7 +21,7 @@ use crate::services::{
     container::ContainerService,
     git_host::{self, GitHostError, GitHostProvider},
     remote_client::RemoteClient,
-    remote_sync,
+    remote_sync, task_scheduler,
 };
 
 #[derive(Debug, Error)]
@@ -139,6 +139,15 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
                 {
                     error!("Failed to archive workspace {}: {}", workspace.id, e);
                 }
+                if let Err(e) =
+                    task_scheduler::start_unblocked_dependents(&self.container, workspace.task_id)
+                        .await
+                {
+                    error!(
+                        "Failed to start dependents of task {}: {}",
+                        workspace.task_id, e
+                    );
+                }
 
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
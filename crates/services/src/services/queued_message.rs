@@ -1,90 +1,118 @@
-use std::sync::Arc;
-
-use chrono::{DateTime, Utc};
-use dashmap::DashMap;
-use db::models::scratch::DraftFollowUpData;
+use db::models::{
+    queued_message::{QueuedMessage, QueuedMessageError},
+    scratch::DraftFollowUpData,
+};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use ts_rs::TS;
 use uuid::Uuid;
 
-/// Represents a queued follow-up message for a session
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-pub struct QueuedMessage {
-    /// The session this message is queued for
-    pub session_id: Uuid,
-    /// The follow-up data (message + variant)
-    pub data: DraftFollowUpData,
-    /// Timestamp when the message was queued
-    pub queued_at: DateTime<Utc>,
-}
-
 /// Status of the queue for a session (for frontend display)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "status", rename_all = "snake_case")]
 pub enum QueueStatus {
     /// No message queued
     Empty,
-    /// Message is queued and waiting for execution to complete
-    Queued { message: QueuedMessage },
+    /// Messages are queued and will be sent in order as executions complete
+    Queued { messages: Vec<QueuedMessage> },
 }
 
-/// In-memory service for managing queued follow-up messages.
-/// One queued message per session.
+impl From<Vec<QueuedMessage>> for QueueStatus {
+    fn from(messages: Vec<QueuedMessage>) -> Self {
+        if messages.is_empty() {
+            QueueStatus::Empty
+        } else {
+            QueueStatus::Queued { messages }
+        }
+    }
+}
+
+/// Service for managing queued follow-up messages.
+/// Each session has an ordered list of messages persisted in the database.
 #[derive(Clone)]
 pub struct QueuedMessageService {
-    queue: Arc<DashMap<Uuid, QueuedMessage>>,
+    pool: SqlitePool,
 }
 
 impl QueuedMessageService {
-    pub fn new() -> Self {
-        Self {
-            queue: Arc::new(DashMap::new()),
-        }
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
     }
 
-    /// Queue a message for a session. Replaces any existing queued message.
-    pub fn queue_message(&self, session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
-        let queued = QueuedMessage {
-            session_id,
-            data,
-            queued_at: Utc::now(),
-        };
-        self.queue.insert(session_id, queued.clone());
-        queued
+    /// Append a message to the end of a session's queue
+    pub async fn queue_message(
+        &self,
+        session_id: Uuid,
+        data: DraftFollowUpData,
+    ) -> Result<QueuedMessage, sqlx::Error> {
+        QueuedMessage::create(&self.pool, session_id, &data).await
     }
 
-    /// Cancel/remove a queued message for a session
-    pub fn cancel_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&session_id).map(|(_, v)| v)
+    /// Replace the content of a queued message belonging to `session_id`
+    pub async fn update_queued(
+        &self,
+        session_id: Uuid,
+        message_id: Uuid,
+        data: DraftFollowUpData,
+    ) -> Result<QueuedMessage, QueuedMessageError> {
+        self.find_in_session(session_id, message_id).await?;
+        QueuedMessage::update(&self.pool, message_id, &data).await
     }
 
-    /// Get the queued message for a session (if any)
-    pub fn get_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.get(&session_id).map(|r| r.clone())
+    /// Reorder a session's queue. `message_ids` must contain every queued message once.
+    pub async fn reorder(
+        &self,
+        session_id: Uuid,
+        message_ids: &[Uuid],
+    ) -> Result<Vec<QueuedMessage>, QueuedMessageError> {
+        QueuedMessage::reorder(&self.pool, session_id, message_ids).await
     }
 
-    /// Take (remove and return) the queued message for a session.
-    /// Used by finalization flow to consume the queued message.
-    pub fn take_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&session_id).map(|(_, v)| v)
+    /// Remove a single queued message belonging to `session_id`
+    pub async fn remove_queued(
+        &self,
+        session_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<QueuedMessage, QueuedMessageError> {
+        let message = self.find_in_session(session_id, message_id).await?;
+        QueuedMessage::delete(&self.pool, message_id).await?;
+        Ok(message)
     }
 
-    /// Check if a session has a queued message
-    pub fn has_queued(&self, session_id: Uuid) -> bool {
-        self.queue.contains_key(&session_id)
+    /// Cancel/remove all queued messages for a session
+    pub async fn cancel_queued(&self, session_id: Uuid) -> Result<u64, sqlx::Error> {
+        QueuedMessage::delete_by_session_id(&self.pool, session_id).await
+    }
+
+    /// Get the queued messages for a session in send order
+    pub async fn get_queued(&self, session_id: Uuid) -> Result<Vec<QueuedMessage>, sqlx::Error> {
+        QueuedMessage::find_by_session_id(&self.pool, session_id).await
+    }
+
+    /// Take (remove and return) the next queued message for a session.
+    /// Used by finalization flow to consume the queue one message at a time.
+    pub async fn take_next(&self, session_id: Uuid) -> Result<Option<QueuedMessage>, sqlx::Error> {
+        QueuedMessage::take_next(&self.pool, session_id).await
+    }
+
+    /// Check if a session has any queued messages
+    pub async fn has_queued(&self, session_id: Uuid) -> Result<bool, sqlx::Error> {
+        QueuedMessage::exists_for_session(&self.pool, session_id).await
     }
 
     /// Get queue status for frontend display
-    pub fn get_status(&self, session_id: Uuid) -> QueueStatus {
-        match self.get_queued(session_id) {
-            Some(msg) => QueueStatus::Queued { message: msg },
-            None => QueueStatus::Empty,
-        }
+    pub async fn get_status(&self, session_id: Uuid) -> Result<QueueStatus, sqlx::Error> {
+        Ok(self.get_queued(session_id).await?.into())
     }
-}
 
-impl Default for QueuedMessageService {
-    fn default() -> Self {
-        Self::new()
+    async fn find_in_session(
+        &self,
+        session_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<QueuedMessage, QueuedMessageError> {
+        QueuedMessage::find_by_id(&self.pool, message_id)
+            .await?
+            .filter(|m| m.session_id == session_id)
+            .ok_or(QueuedMessageError::NotFound)
     }
 }
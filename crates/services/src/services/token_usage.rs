@@ -0,0 +1,220 @@
+//! Aggregates the token usage recorded on coding agent turns and estimates its cost.
+
+use std::collections::HashMap;
+
+use db::models::token_usage::{TokenUsageScope, TokenUsageTotals};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+
+use crate::services::config::ModelPrice;
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct TokenUsageSummary {
+    #[ts(type = "number")]
+    pub turns: u64,
+    #[ts(type = "number")]
+    pub input_tokens: u64,
+    #[ts(type = "number")]
+    pub output_tokens: u64,
+    #[ts(type = "number")]
+    pub cache_read_tokens: u64,
+    #[ts(type = "number")]
+    pub cache_write_tokens: u64,
+    /// Estimated cost in USD of the turns whose model has a configured price,
+    /// `None` when no turn could be priced
+    pub cost_usd: Option<f64>,
+    /// Turns left out of `cost_usd` because their model has no configured price
+    #[ts(type = "number")]
+    pub unpriced_turns: u64,
+}
+
+impl TokenUsageSummary {
+    fn add(&mut self, other: &TokenUsageSummary) {
+        self.turns += other.turns;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.unpriced_turns += other.unpriced_turns;
+        if let Some(cost) = other.cost_usd {
+            *self.cost_usd.get_or_insert(0.0) += cost;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ModelTokenUsage {
+    pub executor: String,
+    pub model: Option<String>,
+    pub usage: TokenUsageSummary,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutorTokenUsage {
+    pub executor: String,
+    pub usage: TokenUsageSummary,
+}
+
+/// Token usage and cost of a workspace, task, project or everything
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct TokenUsageReport {
+    pub total: TokenUsageSummary,
+    pub by_executor: Vec<ExecutorTokenUsage>,
+    pub by_model: Vec<ModelTokenUsage>,
+}
+
+/// Price for `model`: an exact match, else the longest configured prefix of it
+pub fn find_model_price<'a>(
+    prices: &'a HashMap<String, ModelPrice>,
+    model: &str,
+) -> Option<&'a ModelPrice> {
+    prices.get(model).or_else(|| {
+        prices
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| price)
+    })
+}
+
+pub async fn token_usage_report(
+    pool: &SqlitePool,
+    scope: TokenUsageScope,
+    prices: &HashMap<String, ModelPrice>,
+) -> Result<TokenUsageReport, sqlx::Error> {
+    let totals = TokenUsageTotals::find_by_scope(pool, scope).await?;
+    Ok(build_report(totals, prices))
+}
+
+fn build_report(
+    totals: Vec<TokenUsageTotals>,
+    prices: &HashMap<String, ModelPrice>,
+) -> TokenUsageReport {
+    let mut report = TokenUsageReport::default();
+    for row in totals {
+        let usage = summarize(&row, prices);
+        report.total.add(&usage);
+        // Rows are ordered by executor, so each executor's rows are adjacent
+        match report.by_executor.last_mut() {
+            Some(last) if last.executor == row.executor => last.usage.add(&usage),
+            _ => report.by_executor.push(ExecutorTokenUsage {
+                executor: row.executor.clone(),
+                usage: usage.clone(),
+            }),
+        }
+        report.by_model.push(ModelTokenUsage {
+            executor: row.executor,
+            model: row.model,
+            usage,
+        });
+    }
+    report
+}
+
+fn summarize(row: &TokenUsageTotals, prices: &HashMap<String, ModelPrice>) -> TokenUsageSummary {
+    let turns = row.turns.max(0) as u64;
+    let input_tokens = row.input_tokens.max(0) as u64;
+    let output_tokens = row.output_tokens.max(0) as u64;
+    let cache_read_tokens = row.cache_read_tokens.max(0) as u64;
+    let cache_write_tokens = row.cache_write_tokens.max(0) as u64;
+    let price = row
+        .model
+        .as_deref()
+        .and_then(|model| find_model_price(prices, model));
+
+    TokenUsageSummary {
+        turns,
+        input_tokens,
+        output_tokens,
+        cache_read_tokens,
+        cache_write_tokens,
+        cost_usd: price.map(|price| {
+            price.cost_usd(
+                input_tokens,
+                output_tokens,
+                cache_read_tokens,
+                cache_write_tokens,
+            )
+        }),
+        unpriced_turns: if price.is_some() { 0 } else { turns },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(input: f64, output: f64) -> ModelPrice {
+        ModelPrice {
+            input,
+            output,
+            cache_read: None,
+            cache_write: None,
+        }
+    }
+
+    fn row(executor: &str, model: Option<&str>, input: i64, output: i64) -> TokenUsageTotals {
+        TokenUsageTotals {
+            executor: executor.to_string(),
+            model: model.map(str::to_string),
+            turns: 1,
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+        }
+    }
+
+    #[test]
+    fn exact_price_wins_over_longest_prefix() {
+        let prices = HashMap::from([
+            ("claude".to_string(), price(1.0, 1.0)),
+            ("claude-sonnet".to_string(), price(3.0, 15.0)),
+            ("claude-sonnet-4-5-20250929".to_string(), price(4.0, 20.0)),
+        ]);
+        assert_eq!(
+            find_model_price(&prices, "claude-sonnet-4-5-20250929").map(|p| p.input),
+            Some(4.0)
+        );
+        assert_eq!(
+            find_model_price(&prices, "claude-sonnet-4-20250514").map(|p| p.input),
+            Some(3.0)
+        );
+        assert!(find_model_price(&prices, "gpt-5").is_none());
+    }
+
+    #[test]
+    fn cache_tokens_default_to_input_price() {
+        let price = ModelPrice {
+            input: 2.0,
+            output: 10.0,
+            cache_read: Some(0.5),
+            cache_write: None,
+        };
+        let cost = price.cost_usd(1_000_000, 1_000_000, 2_000_000, 1_000_000);
+        assert!((cost - (2.0 + 10.0 + 1.0 + 2.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_groups_by_executor_and_tracks_unpriced_turns() {
+        let prices = HashMap::from([("claude".to_string(), price(3.0, 15.0))]);
+        let report = build_report(
+            vec![
+                row("CLAUDE_CODE", Some("claude-haiku"), 1_000_000, 0),
+                row("CLAUDE_CODE", Some("claude-sonnet"), 0, 1_000_000),
+                row("CODEX", Some("gpt-5"), 500, 500),
+            ],
+            &prices,
+        );
+
+        assert_eq!(report.by_model.len(), 3);
+        assert_eq!(report.by_executor.len(), 2);
+        assert_eq!(report.by_executor[0].usage.turns, 2);
+        assert_eq!(report.by_executor[0].usage.cost_usd, Some(18.0));
+        assert_eq!(report.by_executor[1].usage.cost_usd, None);
+        assert_eq!(report.total.turns, 3);
+        assert_eq!(report.total.unpriced_turns, 1);
+        assert_eq!(report.total.cost_usd, Some(18.0));
+    }
+}
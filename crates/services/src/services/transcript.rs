@@ -0,0 +1,747 @@
+//! Reconstructs the conversation of a session across all of its coding agent runs and
+//! renders it as Markdown, JSON or a self-contained HTML page.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    session::Session,
+};
+use executors::{
+    actions::ExecutorActionType,
+    logs::{
+        ActionType, CommandExitStatus, FileChange, NormalizedConversation, NormalizedEntry,
+        NormalizedEntryType, TokenUsageInfo, ToolResult, ToolStatus,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use thiserror::Error;
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+use crate::services::container::ContainerService;
+
+/// How long the stored logs of a single process may take to normalize
+const NORMALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum TranscriptError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("Timed out normalizing the logs of execution process {0}")]
+    Timeout(Uuid),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    #[default]
+    Markdown,
+    Json,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Json => "json",
+            TranscriptFormat::Html => "html",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "text/markdown; charset=utf-8",
+            TranscriptFormat::Json => "application/json",
+            TranscriptFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+/// Every coding agent turn of a session, in the order they ran
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTranscript {
+    pub session_id: Uuid,
+    pub workspace_id: Uuid,
+    pub executor: Option<String>,
+    pub exported_at: DateTime<Utc>,
+    pub turns: Vec<TranscriptTurn>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptTurn {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub conversation: NormalizedConversation,
+    /// Last token usage reported by the agent during this turn
+    pub token_usage: Option<TokenUsageInfo>,
+}
+
+impl SessionTranscript {
+    pub fn render(&self, format: TranscriptFormat) -> Result<String, TranscriptError> {
+        Ok(match format {
+            TranscriptFormat::Markdown => render_markdown(self),
+            TranscriptFormat::Json => serde_json::to_string_pretty(self)?,
+            TranscriptFormat::Html => render_html(self),
+        })
+    }
+
+    pub fn file_name(&self, format: TranscriptFormat) -> String {
+        format!("session-{}.{}", self.session_id, format.extension())
+    }
+}
+
+/// Rebuild the conversation of every coding agent process of `session`. Dropped
+/// processes (reset away by the user) are left out.
+pub async fn build_session_transcript<C>(
+    container: &C,
+    session: &Session,
+) -> Result<SessionTranscript, TranscriptError>
+where
+    C: ContainerService + Sync,
+{
+    let pool = &container.db().pool;
+    let processes = ExecutionProcess::find_by_session_id(pool, session.id, false).await?;
+
+    let mut turns = Vec::new();
+    for process in processes {
+        if process.run_reason != ExecutionProcessRunReason::CodingAgent || process.dropped {
+            continue;
+        }
+
+        let turn = CodingAgentTurn::find_by_execution_process_id(pool, process.id).await?;
+        let action = process.executor_action().ok();
+        let prompt = action.and_then(|action| match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => Some(request.prompt.clone()),
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => Some(request.prompt.clone()),
+            ExecutorActionType::ReviewRequest(request) => Some(request.prompt.clone()),
+            ExecutorActionType::ScriptRequest(_) => None,
+        });
+        let executor_type = action
+            .and_then(|action| action.base_executor())
+            .map(|executor| executor.to_string())
+            .or_else(|| session.executor.clone())
+            .unwrap_or_default();
+
+        let entries = collect_normalized_entries(container, &process.id).await?;
+        let (entries, token_usage) = transcript_entries(entries, prompt.as_deref());
+
+        turns.push(TranscriptTurn {
+            execution_process_id: process.id,
+            status: process.status,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+            conversation: NormalizedConversation {
+                entries,
+                session_id: turn.as_ref().and_then(|t| t.agent_session_id.clone()),
+                executor_type,
+                prompt: prompt.or_else(|| turn.as_ref().and_then(|t| t.prompt.clone())),
+                summary: turn.and_then(|t| t.summary),
+            },
+            token_usage,
+        });
+    }
+
+    Ok(SessionTranscript {
+        session_id: session.id,
+        workspace_id: session.workspace_id,
+        executor: session.executor.clone(),
+        exported_at: Utc::now(),
+        turns,
+    })
+}
+
+/// Replay the normalized log patches of a process into its list of entries
+async fn collect_normalized_entries<C>(
+    container: &C,
+    execution_id: &Uuid,
+) -> Result<Vec<NormalizedEntry>, TranscriptError>
+where
+    C: ContainerService + Sync,
+{
+    // The normalizer of a process still in memory keeps up with its output, so the
+    // history already holds the conversation so far
+    let history = match container.get_msg_store_by_id(execution_id).await {
+        Some(store) => store.get_history(),
+        None => match container.normalize_stored_logs(execution_id).await {
+            Some(store) => {
+                wait_for_normalizers(&store, execution_id, NORMALIZE_TIMEOUT).await?;
+                store.get_history()
+            }
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    let mut document = json!({ "entries": [] });
+    for msg in history {
+        let LogMsg::JsonPatch(patch) = msg else {
+            continue;
+        };
+        if let Err(e) = json_patch::patch(&mut document, &patch) {
+            tracing::warn!(
+                "Skipping log patch for execution {} that does not apply: {}",
+                execution_id,
+                e
+            );
+        }
+    }
+
+    let Value::Array(values) = document["entries"].take() else {
+        return Ok(Vec::new());
+    };
+    let mut entries = Vec::new();
+    for value in values {
+        if value["type"] == "NORMALIZED_ENTRY" {
+            entries.push(serde_json::from_value(value["content"].clone())?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Normalizers hold a clone of the store until they have processed all of it, so the
+/// conversation is complete once ours is the only one left
+async fn wait_for_normalizers(
+    store: &Arc<MsgStore>,
+    execution_id: &Uuid,
+    timeout: Duration,
+) -> Result<(), TranscriptError> {
+    tokio::time::timeout(timeout, async {
+        while Arc::strong_count(store) > 1 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .map_err(|_| TranscriptError::Timeout(*execution_id))
+}
+
+/// Put the prompt that started the turn first and pull token usage out of the
+/// entry list. Executors echo the prompt inconsistently, so their own user messages
+/// are replaced by it, matching the conversation view.
+fn transcript_entries(
+    entries: Vec<NormalizedEntry>,
+    prompt: Option<&str>,
+) -> (Vec<NormalizedEntry>, Option<TokenUsageInfo>) {
+    let mut result = Vec::with_capacity(entries.len() + 1);
+    let mut token_usage = None;
+
+    if let Some(prompt) = prompt {
+        result.push(NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::UserMessage,
+            content: prompt.to_string(),
+            metadata: None,
+        });
+    }
+    for entry in entries {
+        match entry.entry_type {
+            NormalizedEntryType::UserMessage if prompt.is_some() => {}
+            NormalizedEntryType::Loading => {}
+            NormalizedEntryType::TokenUsageInfo(info) => token_usage = Some(info),
+            _ => result.push(entry),
+        }
+    }
+
+    (result, token_usage)
+}
+
+/// Format-neutral pieces of a transcript, shared by the Markdown and HTML renderers
+enum Block {
+    Title(String),
+    Turn(String),
+    Role(&'static str),
+    /// Free-form text written by the user or the agent, usually Markdown
+    Text(String),
+    /// Short line describing an action, e.g. a tool call
+    Note(String),
+    Code {
+        language: &'static str,
+        code: String,
+    },
+    Fields(Vec<(&'static str, String)>),
+}
+
+fn transcript_blocks(transcript: &SessionTranscript) -> Vec<Block> {
+    let mut blocks = vec![
+        Block::Title("Session transcript".to_string()),
+        Block::Fields(
+            [
+                Some(("Session", transcript.session_id.to_string())),
+                Some(("Workspace", transcript.workspace_id.to_string())),
+                transcript.executor.clone().map(|e| ("Executor", e)),
+                Some(("Exported", transcript.exported_at.to_rfc3339())),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        ),
+    ];
+
+    for (index, turn) in transcript.turns.iter().enumerate() {
+        blocks.push(Block::Turn(format!(
+            "Turn {} — {}",
+            index + 1,
+            turn.conversation.executor_type
+        )));
+
+        let mut fields = vec![
+            ("Status", format!("{:?}", turn.status).to_lowercase()),
+            ("Started", turn.started_at.to_rfc3339()),
+        ];
+        if let Some(completed_at) = turn.completed_at {
+            fields.push(("Completed", completed_at.to_rfc3339()));
+        }
+        if let Some(usage) = &turn.token_usage {
+            fields.push((
+                "Tokens",
+                format!(
+                    "{} / {} context window",
+                    usage.total_tokens, usage.model_context_window
+                ),
+            ));
+        }
+        blocks.push(Block::Fields(fields));
+
+        for entry in &turn.conversation.entries {
+            entry_blocks(entry, &mut blocks);
+        }
+    }
+
+    blocks
+}
+
+fn entry_blocks(entry: &NormalizedEntry, blocks: &mut Vec<Block>) {
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => {
+            blocks.push(Block::Role("User"));
+            blocks.push(Block::Text(entry.content.clone()));
+        }
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            blocks.push(Block::Role("User"));
+            blocks.push(Block::Note(format!("Denied `{denied_tool}`")));
+            blocks.push(Block::Text(entry.content.clone()));
+        }
+        NormalizedEntryType::AssistantMessage => {
+            blocks.push(Block::Role("Assistant"));
+            blocks.push(Block::Text(entry.content.clone()));
+        }
+        NormalizedEntryType::Thinking => {
+            blocks.push(Block::Role("Thinking"));
+            blocks.push(Block::Text(entry.content.clone()));
+        }
+        NormalizedEntryType::SystemMessage => {
+            blocks.push(Block::Role("System"));
+            blocks.push(Block::Text(entry.content.clone()));
+        }
+        NormalizedEntryType::ErrorMessage { .. } => {
+            blocks.push(Block::Role("Error"));
+            blocks.push(Block::Code {
+                language: "text",
+                code: entry.content.clone(),
+            });
+        }
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => {
+            blocks.push(Block::Role("Tool"));
+            blocks.push(Block::Note(format!(
+                "`{}` — {}{}",
+                tool_name,
+                entry.content,
+                status_suffix(status)
+            )));
+            action_blocks(action_type, blocks);
+        }
+        NormalizedEntryType::Loading
+        | NormalizedEntryType::NextAction { .. }
+        | NormalizedEntryType::TokenUsageInfo(_) => {}
+    }
+}
+
+fn action_blocks(action: &ActionType, blocks: &mut Vec<Block>) {
+    match action {
+        ActionType::FileEdit { path, changes } => {
+            for change in changes {
+                match change {
+                    FileChange::Edit { unified_diff, .. } => blocks.push(Block::Code {
+                        language: "diff",
+                        code: unified_diff.clone(),
+                    }),
+                    FileChange::Write { content } => {
+                        blocks.push(Block::Note(format!("Wrote `{path}`")));
+                        blocks.push(Block::Code {
+                            language: "text",
+                            code: content.clone(),
+                        });
+                    }
+                    FileChange::Delete => blocks.push(Block::Note(format!("Deleted `{path}`"))),
+                    FileChange::Rename { new_path } => {
+                        blocks.push(Block::Note(format!("Renamed `{path}` to `{new_path}`")))
+                    }
+                }
+            }
+        }
+        ActionType::CommandRun { command, result } => {
+            blocks.push(Block::Code {
+                language: "sh",
+                code: command.clone(),
+            });
+            if let Some(result) = result {
+                if let Some(output) = result.output.as_ref().filter(|o| !o.trim().is_empty()) {
+                    blocks.push(Block::Code {
+                        language: "text",
+                        code: output.clone(),
+                    });
+                }
+                match result.exit_status {
+                    Some(CommandExitStatus::ExitCode { code }) => {
+                        blocks.push(Block::Note(format!("Exit code {code}")))
+                    }
+                    Some(CommandExitStatus::Success { success: false }) => {
+                        blocks.push(Block::Note("Command failed".to_string()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            if let Some(arguments) = arguments {
+                blocks.push(Block::Code {
+                    language: "json",
+                    code: serde_json::to_string_pretty(arguments).unwrap_or_default(),
+                });
+            }
+            if let Some(result) = result {
+                tool_result_blocks(result, blocks);
+            }
+        }
+        ActionType::TaskCreate { result, .. } => {
+            if let Some(result) = result {
+                tool_result_blocks(result, blocks);
+            }
+        }
+        ActionType::PlanPresentation { plan } => blocks.push(Block::Text(plan.clone())),
+        ActionType::TodoManagement { todos, .. } => {
+            let list = todos
+                .iter()
+                .map(|todo| {
+                    let mark = if todo.status == "completed" { "x" } else { " " };
+                    format!("- [{}] {}", mark, todo.content)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            blocks.push(Block::Text(list));
+        }
+        ActionType::FileRead { .. }
+        | ActionType::Search { .. }
+        | ActionType::WebFetch { .. }
+        | ActionType::Other { .. } => {}
+    }
+}
+
+fn tool_result_blocks(result: &ToolResult, blocks: &mut Vec<Block>) {
+    match &result.value {
+        Value::String(text) => blocks.push(Block::Text(text.clone())),
+        value => blocks.push(Block::Code {
+            language: "json",
+            code: serde_json::to_string_pretty(value).unwrap_or_default(),
+        }),
+    }
+}
+
+fn status_suffix(status: &ToolStatus) -> String {
+    match status {
+        ToolStatus::Created | ToolStatus::Success => String::new(),
+        ToolStatus::Failed => " (failed)".to_string(),
+        ToolStatus::Denied {
+            reason: Some(reason),
+        } => format!(" (denied: {reason})"),
+        ToolStatus::Denied { reason: None } => " (denied)".to_string(),
+        ToolStatus::PendingApproval { .. } => " (pending approval)".to_string(),
+        ToolStatus::AutoApproved { rule } => format!(" (auto-approved by {rule})"),
+        ToolStatus::TimedOut => " (approval timed out)".to_string(),
+    }
+}
+
+pub fn render_markdown(transcript: &SessionTranscript) -> String {
+    let mut out = String::new();
+    for block in transcript_blocks(transcript) {
+        match block {
+            Block::Title(title) => out.push_str(&format!("# {title}\n\n")),
+            Block::Turn(heading) => out.push_str(&format!("---\n\n## {heading}\n\n")),
+            Block::Role(role) => out.push_str(&format!("### {role}\n\n")),
+            Block::Text(text) | Block::Note(text) => {
+                out.push_str(text.trim_end());
+                out.push_str("\n\n");
+            }
+            Block::Code { language, code } => {
+                // Use a fence longer than any backtick run inside the code
+                let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+                let fence = "`".repeat(longest.max(2) + 1);
+                out.push_str(&format!(
+                    "{fence}{language}\n{}\n{fence}\n\n",
+                    code.trim_end_matches('\n')
+                ));
+            }
+            Block::Fields(fields) => {
+                for (name, value) in fields {
+                    out.push_str(&format!("- **{name}:** {value}\n"));
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:960px;\
+margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}\
+h2{border-top:1px solid #d0d7de;padding-top:1.5rem;margin-top:2rem}\
+h3{font-size:.85rem;text-transform:uppercase;letter-spacing:.05em;color:#59636e;margin-bottom:.25rem}\
+dl{display:grid;grid-template-columns:max-content auto;gap:.15rem 1rem;color:#59636e;font-size:.9rem}\
+dt{font-weight:600}dd{margin:0}\
+.text{white-space:pre-wrap}.note{color:#59636e;font-size:.9rem}\
+pre{background:#f6f8fa;border-radius:6px;padding:.75rem;overflow-x:auto;font-size:.85rem}\
+.add{color:#1a7f37}.del{color:#cf222e}.hunk{color:#8250df}";
+
+pub fn render_html(transcript: &SessionTranscript) -> String {
+    let mut body = String::new();
+    for block in transcript_blocks(transcript) {
+        match block {
+            Block::Title(title) => body.push_str(&format!("<h1>{}</h1>\n", escape_html(&title))),
+            Block::Turn(heading) => body.push_str(&format!("<h2>{}</h2>\n", escape_html(&heading))),
+            Block::Role(role) => body.push_str(&format!("<h3>{role}</h3>\n")),
+            Block::Text(text) => body.push_str(&format!(
+                "<div class=\"text\">{}</div>\n",
+                escape_html(text.trim_end())
+            )),
+            Block::Note(text) => {
+                body.push_str(&format!("<p class=\"note\">{}</p>\n", escape_html(&text)))
+            }
+            Block::Code { language, code } => {
+                let code = code.trim_end_matches('\n');
+                let inner = if language == "diff" {
+                    code.lines()
+                        .map(diff_line_html)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                } else {
+                    escape_html(code)
+                };
+                body.push_str(&format!(
+                    "<pre><code class=\"language-{language}\">{inner}</code></pre>\n"
+                ));
+            }
+            Block::Fields(fields) => {
+                body.push_str("<dl>\n");
+                for (name, value) in fields {
+                    body.push_str(&format!(
+                        "<dt>{}</dt><dd>{}</dd>\n",
+                        name,
+                        escape_html(&value)
+                    ));
+                }
+                body.push_str("</dl>\n");
+            }
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Session {}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        transcript.session_id, HTML_STYLE, body
+    )
+}
+
+fn diff_line_html(line: &str) -> String {
+    let class = if line.starts_with("+++") || line.starts_with("---") {
+        None
+    } else if line.starts_with('+') {
+        Some("add")
+    } else if line.starts_with('-') {
+        Some("del")
+    } else if line.starts_with("@@") {
+        Some("hunk")
+    } else {
+        None
+    };
+    match class {
+        Some(class) => format!("<span class=\"{class}\">{}</span>", escape_html(line)),
+        None => escape_html(line),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn transcript(entries: Vec<NormalizedEntry>) -> SessionTranscript {
+        let (entries, token_usage) = transcript_entries(entries, Some("Fix the <bug>"));
+        SessionTranscript {
+            session_id: Uuid::nil(),
+            workspace_id: Uuid::nil(),
+            executor: Some("CLAUDE_CODE".to_string()),
+            exported_at: Utc::now(),
+            turns: vec![TranscriptTurn {
+                execution_process_id: Uuid::nil(),
+                status: ExecutionProcessStatus::Completed,
+                started_at: Utc::now(),
+                completed_at: None,
+                conversation: NormalizedConversation {
+                    entries,
+                    session_id: None,
+                    executor_type: "CLAUDE_CODE".to_string(),
+                    prompt: Some("Fix the <bug>".to_string()),
+                    summary: None,
+                },
+                token_usage,
+            }],
+        }
+    }
+
+    fn sample() -> SessionTranscript {
+        transcript(vec![
+            entry(NormalizedEntryType::UserMessage, "echoed prompt"),
+            entry(NormalizedEntryType::AssistantMessage, "Done."),
+            entry(
+                NormalizedEntryType::ToolUse {
+                    tool_name: "Edit".to_string(),
+                    action_type: ActionType::FileEdit {
+                        path: "src/lib.rs".to_string(),
+                        changes: vec![FileChange::Edit {
+                            unified_diff: "@@ -1 +1 @@\n-old\n+new\n".to_string(),
+                            has_line_numbers: true,
+                        }],
+                    },
+                    status: ToolStatus::Success,
+                },
+                "src/lib.rs",
+            ),
+            entry(
+                NormalizedEntryType::TokenUsageInfo(TokenUsageInfo {
+                    total_tokens: 1200,
+                    model_context_window: 200000,
+                }),
+                "",
+            ),
+        ])
+    }
+
+    #[test]
+    fn prompt_replaces_echoed_user_messages_and_usage_is_extracted() {
+        let transcript = sample();
+        let turn = &transcript.turns[0];
+        let users: Vec<_> = turn
+            .conversation
+            .entries
+            .iter()
+            .filter(|e| matches!(e.entry_type, NormalizedEntryType::UserMessage))
+            .collect();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].content, "Fix the <bug>");
+        assert_eq!(turn.token_usage.as_ref().unwrap().total_tokens, 1200);
+    }
+
+    #[test]
+    fn markdown_includes_messages_diffs_and_usage() {
+        let markdown = render_markdown(&sample());
+        assert!(markdown.contains("### User\n\nFix the <bug>"));
+        assert!(markdown.contains("### Assistant\n\nDone."));
+        assert!(markdown.contains("```diff\n@@ -1 +1 @@\n-old\n+new\n```"));
+        assert!(markdown.contains("1200 / 200000 context window"));
+    }
+
+    #[test]
+    fn markdown_fence_outgrows_backticks_in_code() {
+        let markdown = render_markdown(&transcript(vec![entry(
+            NormalizedEntryType::ErrorMessage {
+                error_type: executors::logs::NormalizedEntryError::Other,
+            },
+            "```nested```",
+        )]));
+        assert!(markdown.contains("````text\n```nested```\n````"));
+    }
+
+    #[test]
+    fn html_is_escaped_and_highlights_diffs() {
+        let html = render_html(&sample());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Fix the &lt;bug&gt;"));
+        assert!(!html.contains("<bug>"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+        assert!(html.contains("<span class=\"del\">-old</span>"));
+    }
+
+    #[tokio::test]
+    async fn waits_until_normalizers_release_the_store() {
+        let store = Arc::new(MsgStore::new());
+        let normalizer = store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            normalizer.push_patch(json_patch::Patch(vec![]));
+        });
+
+        wait_for_normalizers(&store, &Uuid::nil(), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(store.get_history().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stuck_normalizers_time_out() {
+        let store = Arc::new(MsgStore::new());
+        let _stuck = store.clone();
+
+        let err = wait_for_normalizers(&store, &Uuid::nil(), Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TranscriptError::Timeout(id) if id == Uuid::nil()));
+    }
+
+    #[test]
+    fn json_round_trips_entries() {
+        let json = sample().render(TranscriptFormat::Json).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["turns"][0]["conversation"]["entries"][0]["entry_type"]["type"],
+            "user_message"
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use db::models::merge::MergeStatus;
+use services::services::git_host::{
+    CreatePrRequest, GitHostError, GitHostProvider, UnifiedPrComment,
+    gitea::{GiteaClient, GiteaProvider},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: String,
+}
+
+/// Minimal HTTP/1.1 server that answers with canned Gitea API responses.
+struct MockGitea {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockGitea {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let request = read_request(&mut stream).await;
+                let (status, body) = respond(&request);
+                recorded.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    fn remote_url(&self) -> String {
+        format!("{}/owner/repo.git", self.base_url)
+    }
+
+    fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> RecordedRequest {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if n == 0 {
+            break buf.len();
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    RecordedRequest {
+        method,
+        path,
+        authorization,
+        body: String::from_utf8_lossy(&buf[header_end..]).to_string(),
+    }
+}
+
+fn respond(request: &RecordedRequest) -> (&'static str, String) {
+    if request.authorization.is_none() {
+        return (
+            "401 Unauthorized",
+            r#"{"message":"token is required"}"#.into(),
+        );
+    }
+
+    let path = request.path.split('?').next().unwrap_or_default();
+    let body = match (request.method.as_str(), path) {
+        ("POST", "/api/v1/repos/owner/repo/pulls") => {
+            return (
+                "201 Created",
+                r#"{"number":7,"html_url":"http://gitea.test/owner/repo/pulls/7","state":"open","merged":false}"#.into(),
+            );
+        }
+        ("GET", "/api/v1/repos/owner/repo/pulls/7") => {
+            r#"{"number":7,"html_url":"http://gitea.test/owner/repo/pulls/7","state":"closed","merged":true,"merged_at":"2025-01-02T03:04:05Z","merge_commit_sha":"abc123","head":{"ref":"feature"}}"#
+        }
+        ("GET", "/api/v1/repos/owner/repo/pulls/8") => {
+            r#"{"number":8,"html_url":"http://gitea.test/owner/repo/pulls/8","state":"open","merged":false,"head":{"ref":"other"},"base":{"ref":"main"}}"#
+        }
+        ("GET", "/api/v1/repos/owner/repo/pulls") => {
+            r#"[
+                {"number":7,"html_url":"http://gitea.test/owner/repo/pulls/7","state":"open","merged":false,"head":{"ref":"feature"},"base":{"ref":"main"}},
+                {"number":8,"html_url":"http://gitea.test/owner/repo/pulls/8","state":"open","merged":false,"head":{"ref":"other"},"base":{"ref":"main"}}
+            ]"#
+        }
+        ("GET", "/api/v1/repos/owner/repo/issues/7/comments") => {
+            r#"[{"id":1,"body":"second","user":{"login":"alice"},"created_at":"2025-01-02T00:00:00Z","html_url":"http://gitea.test/c/1"}]"#
+        }
+        ("GET", "/api/v1/repos/owner/repo/pulls/7/reviews") => {
+            r#"[{"id":5,"body":"looks good","user":{"login":"bob"},"submitted_at":"2025-01-03T00:00:00Z","comments_count":1}]"#
+        }
+        ("GET", "/api/v1/repos/owner/repo/pulls/7/reviews/5/comments") => {
+            r#"[{"id":9,"body":"first","user":{"login":"bob"},"created_at":"2025-01-01T00:00:00Z","path":"src/lib.rs","position":12,"original_position":0}]"#
+        }
+        _ => return ("404 Not Found", r#"{"message":"not found"}"#.into()),
+    };
+    ("200 OK", body.to_string())
+}
+
+fn provider(token: Option<&str>) -> GiteaProvider {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    GiteaProvider::with_client(GiteaClient::new(token.map(str::to_string)).unwrap())
+}
+
+#[tokio::test]
+async fn test_create_pr_posts_to_pulls_endpoint() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    let request = CreatePrRequest {
+        title: "Add feature".to_string(),
+        body: Some("Body".to_string()),
+        head_branch: "feature".to_string(),
+        base_branch: "main".to_string(),
+        draft: Some(true),
+        head_repo_url: None,
+    };
+    let pr = provider
+        .create_pr(Path::new("."), &server.remote_url(), &request)
+        .await
+        .unwrap();
+
+    assert_eq!(pr.number, 7);
+    assert!(matches!(pr.status, MergeStatus::Open));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/api/v1/repos/owner/repo/pulls");
+    assert_eq!(requests[0].authorization.as_deref(), Some("token secret"));
+
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["head"], "feature");
+    assert_eq!(body["base"], "main");
+    assert_eq!(body["title"], "WIP: Add feature");
+}
+
+#[tokio::test]
+async fn test_get_pr_status_reports_merged() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    let pr = provider
+        .get_pr_status(&format!("{}/owner/repo/pulls/7", server.base_url))
+        .await
+        .unwrap();
+
+    assert!(matches!(pr.status, MergeStatus::Merged));
+    assert_eq!(pr.merge_commit_sha.as_deref(), Some("abc123"));
+    assert!(pr.merged_at.is_some());
+}
+
+#[tokio::test]
+async fn test_list_prs_for_branch_filters_by_head() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    let prs = provider
+        .list_prs_for_branch(Path::new("."), &server.remote_url(), "feature")
+        .await
+        .unwrap();
+
+    assert_eq!(prs.len(), 1);
+    assert_eq!(prs[0].number, 7);
+
+    let query = server.requests()[0].path.clone();
+    assert!(query.contains("state=all"), "unexpected query: {query}");
+    assert!(
+        query.contains("sort=recentupdate"),
+        "unexpected query: {query}"
+    );
+}
+
+#[tokio::test]
+async fn test_list_prs_for_branch_fetches_known_open_pr_directly() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    for _ in 0..2 {
+        let prs = provider
+            .list_prs_for_branch(Path::new("."), &server.remote_url(), "other")
+            .await
+            .unwrap();
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 8);
+    }
+
+    let paths: Vec<String> = server
+        .requests()
+        .into_iter()
+        .map(|r| r.path.split('?').next().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/api/v1/repos/owner/repo/pulls",
+            "/api/v1/repos/owner/repo/pulls/8"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_list_prs_for_branch_rescans_when_known_pr_is_closed() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    for _ in 0..2 {
+        provider
+            .list_prs_for_branch(Path::new("."), &server.remote_url(), "feature")
+            .await
+            .unwrap();
+    }
+
+    // PR 7 is merged by the time it is fetched again, so the list is scanned once more
+    let paths: Vec<String> = server
+        .requests()
+        .into_iter()
+        .map(|r| r.path.split('?').next().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/api/v1/repos/owner/repo/pulls",
+            "/api/v1/repos/owner/repo/pulls/7",
+            "/api/v1/repos/owner/repo/pulls",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_get_pr_comments_merges_and_sorts() {
+    let server = MockGitea::start().await;
+    let provider = provider(Some("secret"));
+
+    let comments = provider
+        .get_pr_comments(Path::new("."), &server.remote_url(), 7)
+        .await
+        .unwrap();
+
+    let bodies: Vec<&str> = comments
+        .iter()
+        .map(|c| match c {
+            UnifiedPrComment::General { body, .. } | UnifiedPrComment::Review { body, .. } => {
+                body.as_str()
+            }
+        })
+        .collect();
+    assert_eq!(bodies, vec!["first", "second", "looks good"]);
+
+    match &comments[0] {
+        UnifiedPrComment::Review {
+            path, line, side, ..
+        } => {
+            assert_eq!(path, "src/lib.rs");
+            assert_eq!(*line, Some(12));
+            assert_eq!(side.as_deref(), Some("RIGHT"));
+        }
+        other => panic!("expected review comment, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_missing_token_maps_to_auth_failed() {
+    let server = MockGitea::start().await;
+    let provider = provider(None);
+
+    let err = provider
+        .list_open_prs(Path::new("."), &server.remote_url())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, GitHostError::AuthFailed(_)), "got {err:?}");
+    // Auth failures are not retried
+    assert_eq!(server.requests().len(), 1);
+}
@@ -7,9 +7,21 @@ pub const EV_STDERR: &str = "stderr";
 pub const EV_JSON_PATCH: &str = "json_patch";
 pub const EV_SESSION_ID: &str = "session_id";
 pub const EV_MESSAGE_ID: &str = "message_id";
+pub const EV_TOKEN_USAGE: &str = "token_usage";
 pub const EV_READY: &str = "ready";
 pub const EV_FINISHED: &str = "finished";
 
+/// Tokens consumed by a coding agent run so far. Each report replaces the previous one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Main model of the run, when the agent reports it
+    pub model: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum LogMsg {
     Stdout(String),
@@ -17,6 +29,7 @@ pub enum LogMsg {
     JsonPatch(Patch),
     SessionId(String),
     MessageId(String),
+    TokenUsage(TokenUsage),
     Ready,
     Finished,
 }
@@ -29,6 +42,7 @@ impl LogMsg {
             LogMsg::JsonPatch(_) => EV_JSON_PATCH,
             LogMsg::SessionId(_) => EV_SESSION_ID,
             LogMsg::MessageId(_) => EV_MESSAGE_ID,
+            LogMsg::TokenUsage(_) => EV_TOKEN_USAGE,
             LogMsg::Ready => EV_READY,
             LogMsg::Finished => EV_FINISHED,
         }
@@ -44,6 +58,10 @@ impl LogMsg {
             }
             LogMsg::SessionId(s) => Event::default().event(EV_SESSION_ID).data(s.clone()),
             LogMsg::MessageId(s) => Event::default().event(EV_MESSAGE_ID).data(s.clone()),
+            LogMsg::TokenUsage(usage) => {
+                let data = serde_json::to_string(usage).unwrap_or_else(|_| "{}".to_string());
+                Event::default().event(EV_TOKEN_USAGE).data(data)
+            }
             LogMsg::Ready => Event::default().event(EV_READY).data(""),
             LogMsg::Finished => Event::default().event(EV_FINISHED).data(""),
         }
@@ -83,6 +101,12 @@ impl LogMsg {
             }
             LogMsg::SessionId(s) => EV_SESSION_ID.len() + s.len() + OVERHEAD,
             LogMsg::MessageId(s) => EV_MESSAGE_ID.len() + s.len() + OVERHEAD,
+            LogMsg::TokenUsage(usage) => {
+                EV_TOKEN_USAGE.len()
+                    + usage.model.as_ref().map_or(0, String::len)
+                    + 4 * std::mem::size_of::<u64>()
+                    + OVERHEAD
+            }
             LogMsg::Ready => EV_READY.len() + OVERHEAD,
             LogMsg::Finished => EV_FINISHED.len() + OVERHEAD,
         }
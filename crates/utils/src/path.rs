@@ -3,6 +3,9 @@ use std::path::{Path, PathBuf};
 /// Directory name for storing images in worktrees
 pub const VIBE_IMAGES_DIR: &str = ".vibe-images";
 
+/// Directory name for the output spools of detached executions in workspaces
+pub const VIBE_SPOOL_DIR: &str = ".vibe-spool";
+
 /// Directories that should always be skipped regardless of gitignore.
 /// .git is not in .gitignore but should never be watched.
 pub const ALWAYS_SKIP_DIRS: &[&str] = &[".git", "node_modules"];
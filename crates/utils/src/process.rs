@@ -1,6 +1,7 @@
 use command_group::AsyncGroupChild;
 #[cfg(unix)]
 use nix::{
+    errno::Errno,
     sys::signal::{Signal, killpg},
     unistd::{Pid, getpgid},
 };
@@ -39,3 +40,130 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> std::io::Result<
     let _ = child.wait().await;
     Ok(())
 }
+
+/// Whether any process of the process group `pgid` is still running
+pub fn process_group_alive(pgid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        matches!(
+            killpg(Pid::from_raw(pgid as i32), None),
+            Ok(()) | Err(Errno::EPERM)
+        )
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pgid;
+        false
+    }
+}
+
+/// Start time of the running process `pid`, qualified by the boot it started in. Unlike the
+/// pid, which is reused once the process exits, it identifies one process.
+pub fn process_start_time(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // The command name may contain spaces, so count fields from its closing parenthesis;
+        // starttime is the 22nd field, the 20th after the name
+        let (_, fields) = stat.rsplit_once(')')?;
+        let start_time = fields.split_whitespace().nth(19)?;
+        let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id").ok()?;
+        Some(format!("{}:{}", boot_id.trim(), start_time))
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "lstart=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        let start_time = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !start_time.is_empty()).then_some(start_time)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// A process group that is not a child of this process, e.g. a detached execution
+/// reattached after a restart. The leader's start time tells it apart from an unrelated
+/// group that got the same id after it exited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedProcessGroup {
+    pub pgid: u32,
+    pub leader_start_time: String,
+}
+
+impl DetachedProcessGroup {
+    /// The group led by the running process `pid`
+    pub fn of_leader(pid: u32) -> Option<Self> {
+        Some(Self {
+            pgid: pid,
+            leader_start_time: process_start_time(pid)?,
+        })
+    }
+
+    /// Whether the group is still running and is still the same group
+    pub fn is_alive(&self) -> bool {
+        let same_group = match process_start_time(self.pgid) {
+            Some(start_time) => start_time == self.leader_start_time,
+            // The leader has exited; its pid is not reused while the group has members
+            None => true,
+        };
+        same_group && process_group_alive(self.pgid)
+    }
+
+    pub async fn kill(&self) -> std::io::Result<()> {
+        #[cfg(unix)]
+        {
+            let pgid = Pid::from_raw(self.pgid as i32);
+            for sig in [Signal::SIGINT, Signal::SIGTERM, Signal::SIGKILL] {
+                if !self.is_alive() {
+                    break;
+                }
+                tracing::info!("Sending {:?} to process group {}", sig, pgid);
+                match killpg(pgid, sig) {
+                    Ok(()) => {}
+                    Err(Errno::ESRCH) => break,
+                    Err(e) => return Err(std::io::Error::other(e.to_string())),
+                }
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                if !process_group_alive(self.pgid) {
+                    tracing::info!("Process group {} exited after {:?}", pgid, sig);
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::CommandExt;
+
+    use super::*;
+
+    #[test]
+    fn detached_group_is_identified_by_its_leader_start_time() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let group = DetachedProcessGroup::of_leader(child.id()).unwrap();
+        assert!(group.is_alive());
+
+        // The same id with another start time is a different group, e.g. after pid reuse
+        let reused = DetachedProcessGroup {
+            leader_start_time: format!("{}-earlier", group.leader_start_time),
+            ..group.clone()
+        };
+        assert!(!reused.is_alive());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(!group.is_alive());
+    }
+}
@@ -40,6 +40,19 @@ pub fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     &content[..cutoff]
 }
 
+/// The last `max_len` bytes of `content` or fewer, starting on a char boundary
+pub fn tail_to_char_boundary(content: &str, max_len: usize) -> &str {
+    if content.len() <= max_len {
+        return content;
+    }
+
+    let mut start = content.len() - max_len;
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    &content[start..]
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -57,4 +70,17 @@ mod tests {
         assert_eq!(truncate_to_char_boundary(input, 5), "🔥");
         assert_eq!(truncate_to_char_boundary(input, 3), "");
     }
+
+    #[test]
+    fn test_tail_to_char_boundary() {
+        use super::tail_to_char_boundary;
+
+        let input = "abcdefghij";
+        assert_eq!(tail_to_char_boundary(input, 3), "hij");
+        assert_eq!(tail_to_char_boundary(input, 20), input);
+
+        let input = "🔥🔥🔥";
+        assert_eq!(tail_to_char_boundary(input, 5), "🔥");
+        assert_eq!(tail_to_char_boundary(input, 3), "");
+    }
 }
@@ -38,6 +38,10 @@ export function getAgentName(
       return 'Copilot';
     case BaseCodingAgent.DROID:
       return 'Droid';
+    case BaseCodingAgent.CUSTOM_ACP:
+      return 'Custom ACP';
+    case BaseCodingAgent.AIDER:
+      return 'Aider';
   }
 }
 
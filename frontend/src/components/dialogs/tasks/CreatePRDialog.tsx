@@ -183,13 +183,20 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
           // Only show setup dialog for GitHub CLI on Mac
           if (result.error.provider === 'git_hub' && isMacEnvironment) {
             await showGhCliSetupDialog();
+          } else if (result.error.provider === 'gitea') {
+            setError(
+              'Gitea access token is missing or invalid (set GITEA_TOKEN)'
+            );
+            setGhCliHelp(null);
           } else {
             const providerName =
               result.error.provider === 'git_hub'
                 ? 'GitHub'
                 : result.error.provider === 'azure_dev_ops'
                   ? 'Azure DevOps'
-                  : 'Git host';
+                  : result.error.provider === 'git_lab'
+                    ? 'GitLab'
+                    : 'Git host';
             const action =
               result.error.type === 'cli_not_installed'
                 ? 'not installed'
@@ -254,9 +254,10 @@ export function TaskFollowUpSection({
   });
 
   const isQueued = queueStatus.status === 'queued';
-  const queuedMessage = isQueued
-    ? (queueStatus as Extract<QueueStatus, { status: 'queued' }>).message
-    : null;
+  const queuedMessages = isQueued
+    ? (queueStatus as Extract<QueueStatus, { status: 'queued' }>).messages
+    : [];
+  const queuedMessage = queuedMessages[queuedMessages.length - 1] ?? null;
 
   const queueMutation = useMutation({
     mutationFn: ({
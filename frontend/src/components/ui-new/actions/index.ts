@@ -38,6 +38,7 @@ import {
   SpinnerIcon,
   GitPullRequestIcon,
   GitMergeIcon,
+  QueueIcon,
   GitForkIcon,
   ArrowsClockwiseIcon,
   CrosshairIcon,
@@ -62,7 +63,7 @@ import {
   RIGHT_MAIN_PANEL_MODES,
 } from '@/stores/useUiPreferencesStore';
 
-import { attemptsApi, tasksApi, repoApi } from '@/lib/api';
+import { attemptsApi, tasksApi, repoApi, mergeQueueApi } from '@/lib/api';
 import { bulkUpdateIssues } from '@/lib/remoteApi';
 import { attemptKeys } from '@/hooks/useAttempt';
 import { taskKeys } from '@/hooks/useTask';
@@ -1028,14 +1029,10 @@ export const Actions = {
         if (isRunning) return;
 
         // Show resolve conflicts dialog
-        const workspace = await getWorkspace(ctx.queryClient, workspaceId);
         const result = await ResolveConflictsDialog.show({
           workspaceId,
-          conflictOp: repoStatus.conflict_op ?? 'merge',
-          sourceBranch: workspace.branch,
-          targetBranch: repoStatus.target_branch_name,
+          repoId,
           conflictedFiles: repoStatus.conflicted_files ?? [],
-          repoName: repoStatus.repo_name,
         });
 
         if (result.action === 'resolved') {
@@ -1080,6 +1077,29 @@ export const Actions = {
     },
   },
 
+  GitQueueMerge: {
+    id: 'git-queue-merge',
+    label: 'Add to Merge Queue',
+    icon: QueueIcon,
+    shortcut: 'X Q',
+    requiresTarget: ActionTargetType.GIT,
+    isVisible: (ctx) => ctx.hasWorkspace && ctx.hasGitRepos,
+    execute: async (ctx, workspaceId, repoId) => {
+      const confirmResult = await ConfirmDialog.show({
+        title: 'Add to Merge Queue',
+        message:
+          'Once the workspaces queued before it have landed, this branch will be rebased onto the target branch, verified with the repository check script and merged.',
+        confirmText: 'Add to Queue',
+        cancelText: 'Cancel',
+      });
+
+      if (confirmResult === 'confirmed') {
+        await mergeQueueApi.enqueue(repoId, { workspace_id: workspaceId });
+        invalidateWorkspaceQueries(ctx.queryClient, workspaceId);
+      }
+    },
+  },
+
   GitRebase: {
     id: 'git-rebase',
     label: 'Rebase',
@@ -6,6 +6,7 @@ import { useUserSystem } from '@/components/ConfigProvider';
 import { useCreateWorkspace } from '@/hooks/useCreateWorkspace';
 import { useCreateAttachments } from '@/hooks/useCreateAttachments';
 import { useMultiRepoBranches } from '@/hooks/useRepoBranches';
+import { useRepoConfigs } from '@/hooks/useRepoConfigs';
 import { getVariantOptions, areProfilesEqual } from '@/utils/executor';
 import { splitMessageToTitleDescription } from '@/utils/string';
 import type { ExecutorProfileId, BaseCodingAgent, Repo } from 'shared/types';
@@ -62,19 +63,25 @@ export function CreateChatBoxContainer({
   // Auto-select branch for repos that don't have one yet
   const repoIds = useMemo(() => repos.map((r) => r.id), [repos]);
   const { branchesByRepo } = useMultiRepoBranches(repoIds);
+  const { configsByRepo: repoConfigs, isLoading: isLoadingRepoConfigs } =
+    useRepoConfigs(repoIds);
 
   useEffect(() => {
+    // A config file may name the default branch, so wait for it rather than
+    // locking in the current branch
+    if (isLoadingRepoConfigs) return;
     repos.forEach((repo) => {
       if (targetBranches[repo.id]) return;
       const branches = branchesByRepo[repo.id];
       if (!branches) return;
 
-      // Priority 1: default_target_branch if configured
-      if (
-        repo.default_target_branch &&
-        branches.some((b) => b.name === repo.default_target_branch)
-      ) {
-        setTargetBranch(repo.id, repo.default_target_branch);
+      // Priority 1: default_target_branch if configured, here or in the repo's
+      // config file
+      const defaultBranch =
+        repo.default_target_branch ||
+        repoConfigs[repo.id]?.default_target_branch;
+      if (defaultBranch && branches.some((b) => b.name === defaultBranch)) {
+        setTargetBranch(repo.id, defaultBranch);
         return;
       }
 
@@ -84,7 +91,14 @@ export function CreateChatBoxContainer({
         setTargetBranch(repo.id, currentBranch.name);
       }
     });
-  }, [repos, branchesByRepo, targetBranches, setTargetBranch]);
+  }, [
+    repos,
+    branchesByRepo,
+    repoConfigs,
+    isLoadingRepoConfigs,
+    targetBranches,
+    setTargetBranch,
+  ]);
 
   // Attachment handling - insert markdown and track image IDs
   const handleInsertMarkdown = useCallback(
@@ -120,9 +134,19 @@ export function CreateChatBoxContainer({
     noKeyboard: true,
   });
 
-  // Default to user's config profile or first available executor
+  // Executor profile preferred by the selected repos' config files
+  const repoProfile = useMemo<ExecutorProfileId | null>(() => {
+    for (const repoId of repoIds) {
+      const profile = repoConfigs[repoId]?.executor_profile;
+      if (profile && profiles?.[profile.executor]) return profile;
+    }
+    return null;
+  }, [repoIds, repoConfigs, profiles]);
+
+  // Default to the repo's profile, the user's profile or the first executor
   const effectiveProfile = useMemo<ExecutorProfileId | null>(() => {
     if (selectedProfile) return selectedProfile;
+    if (repoProfile) return repoProfile;
     if (config?.executor_profile) return config.executor_profile;
     if (profiles) {
       const firstExecutor = Object.keys(profiles)[0] as BaseCodingAgent;
@@ -135,7 +159,7 @@ export function CreateChatBoxContainer({
       }
     }
     return null;
-  }, [selectedProfile, config?.executor_profile, profiles]);
+  }, [selectedProfile, repoProfile, config?.executor_profile, profiles]);
 
   // Get variant options for the current executor
   const variantOptions = useMemo(
@@ -25,7 +25,6 @@ import { useSessionSend } from '@/hooks/useSessionSend';
 import { useSessionAttachments } from '@/hooks/useSessionAttachments';
 import { useMessageEditRetry } from '@/hooks/useMessageEditRetry';
 import { useBranchStatus } from '@/hooks/useBranchStatus';
-import { useAttemptBranch } from '@/hooks/useAttemptBranch';
 import { useApprovalMutation } from '@/hooks/useApprovalMutation';
 import { ResolveConflictsDialog } from '@/components/ui-new/dialogs/ResolveConflictsDialog';
 import { workspaceSummaryKeys } from '@/components/ui-new/hooks/useWorkspaces';
@@ -252,9 +251,6 @@ export function SessionChatBoxContainer(props: SessionChatBoxContainerProps) {
     );
   }, [branchStatus]);
 
-  // Get workspace branch for conflict resolution dialog
-  const { branch: attemptBranch } = useAttemptBranch(workspaceId);
-
   // Find the first repo with conflicts (for the resolve dialog)
   const repoWithConflicts = useMemo(
     () =>
@@ -268,13 +264,10 @@ export function SessionChatBoxContainer(props: SessionChatBoxContainerProps) {
     if (!workspaceId || !repoWithConflicts) return;
     ResolveConflictsDialog.show({
       workspaceId,
-      conflictOp: repoWithConflicts.conflict_op ?? 'rebase',
-      sourceBranch: attemptBranch,
-      targetBranch: repoWithConflicts.target_branch_name,
+      repoId: repoWithConflicts.repo_id,
       conflictedFiles: repoWithConflicts.conflicted_files ?? [],
-      repoName: repoWithConflicts.repo_name,
     });
-  }, [workspaceId, repoWithConflicts, attemptBranch]);
+  }, [workspaceId, repoWithConflicts]);
 
   // User profiles, config preference, and latest executor from processes
   const { profiles, config } = useUserSystem();
@@ -1,4 +1,5 @@
-import { useEffect, useRef } from 'react';
+import { useEffect, useRef, useState } from 'react';
+import { terminalApi } from '@/lib/api';
 import { useWorkspaceContext } from '@/contexts/WorkspaceContext';
 import { useTerminal } from '@/contexts/TerminalContext';
 import { TerminalPanel } from '../views/TerminalPanel';
@@ -21,6 +22,10 @@ export function TerminalPanelContainer() {
 
   const creatingRef = useRef(false);
   const prevWorkspaceIdRef = useRef<string | null>(null);
+  // Workspace whose running sessions have been reattached as tabs
+  const [restoredWorkspaceId, setRestoredWorkspaceId] = useState<
+    string | null
+  >(null);
 
   // Clean up terminals when workspace changes
   useEffect(() => {
@@ -33,11 +38,48 @@ export function TerminalPanelContainer() {
     prevWorkspaceIdRef.current = workspaceId ?? null;
   }, [workspaceId, clearWorkspaceTabs]);
 
+  // Reattach to sessions that are still running from an earlier visit
+  useEffect(() => {
+    if (!workspaceId || !containerRef || restoredWorkspaceId === workspaceId) {
+      return;
+    }
+    let cancelled = false;
+    const finish = () => {
+      if (!cancelled) setRestoredWorkspaceId(workspaceId);
+    };
+    if (getTabsForWorkspace(workspaceId).length > 0) {
+      finish();
+      return;
+    }
+    terminalApi
+      .listSessions(workspaceId)
+      .then((sessions) => {
+        if (cancelled) return;
+        sessions.forEach((session) =>
+          createTab(workspaceId, containerRef, session.id)
+        );
+      })
+      .catch((err) => {
+        console.error('Failed to load terminal sessions:', err);
+      })
+      .finally(finish);
+    return () => {
+      cancelled = true;
+    };
+  }, [
+    workspaceId,
+    containerRef,
+    restoredWorkspaceId,
+    getTabsForWorkspace,
+    createTab,
+  ]);
+
   // Auto-create first tab when workspace is selected and terminal mode is active
   useEffect(() => {
     if (
       workspaceId &&
       containerRef &&
+      restoredWorkspaceId === workspaceId &&
       tabs.length === 0 &&
       !creatingRef.current
     ) {
@@ -47,7 +89,13 @@ export function TerminalPanelContainer() {
     if (tabs.length > 0) {
       creatingRef.current = false;
     }
-  }, [workspaceId, containerRef, tabs.length, createTab]);
+  }, [
+    workspaceId,
+    containerRef,
+    restoredWorkspaceId,
+    tabs.length,
+    createTab,
+  ]);
 
   return (
     <TerminalPanel
@@ -16,7 +16,6 @@ import NiceModal, { useModal } from '@ebay/nice-modal-react';
 import { defineModal } from '@/lib/modals';
 import { GitOperationsProvider } from '@/contexts/GitOperationsContext';
 import { useGitOperations } from '@/hooks/useGitOperations';
-import { useAttempt } from '@/hooks/useAttempt';
 import { useRepoBranches } from '@/hooks/useRepoBranches';
 import { useAttemptRepo } from '@/hooks/useAttemptRepo';
 import { useBranchStatus } from '@/hooks/useBranchStatus';
@@ -46,7 +45,6 @@ function RebaseDialogContent({ attemptId, repoId }: RebaseDialogContentProps) {
   const [hasInitializedBranches, setHasInitializedBranches] = useState(false);
 
   const git = useGitOperations(attemptId, repoId);
-  const { data: workspace } = useAttempt(attemptId);
   const { workspaceId: activeWorkspaceId } = useWorkspaceContext();
   const { workspaces } = useWorkspaces();
   const isWorkspaceRunning =
@@ -95,11 +93,8 @@ function RebaseDialogContent({ attemptId, repoId }: RebaseDialogContentProps) {
         // Rebase in progress WITH conflicts -> show resolve conflicts dialog
         ResolveConflictsDialog.show({
           workspaceId: attemptId,
-          conflictOp: repoStatus.conflict_op ?? 'rebase',
-          sourceBranch: workspace?.branch ?? null,
-          targetBranch: repoStatus.target_branch_name,
+          repoId,
           conflictedFiles: repoStatus.conflicted_files ?? [],
-          repoName: repoStatus.repo_name,
         });
       } else {
         // Rebase in progress WITHOUT conflicts -> show simpler dialog
@@ -117,7 +112,6 @@ function RebaseDialogContent({ attemptId, repoId }: RebaseDialogContentProps) {
     repoStatus,
     attemptId,
     repoId,
-    workspace?.branch,
     modal,
     activeWorkspaceId,
     isWorkspaceRunning,
@@ -161,11 +155,8 @@ function RebaseDialogContent({ attemptId, repoId }: RebaseDialogContentProps) {
         if (activeWorkspaceId === attemptId) {
           await ResolveConflictsDialog.show({
             workspaceId: attemptId,
-            conflictOp: errorData.op,
-            sourceBranch: workspace?.branch ?? null,
-            targetBranch: errorData.target_branch,
+            repoId,
             conflictedFiles: errorData.conflicted_files,
-            repoName: undefined,
           });
         }
         return;
@@ -15,24 +15,16 @@ import { AgentSelector } from '@/components/tasks/AgentSelector';
 import { ConfigSelector } from '@/components/tasks/ConfigSelector';
 import { useUserSystem } from '@/components/ConfigProvider';
 import { useWorkspaceContext } from '@/contexts/WorkspaceContext';
-import { sessionsApi } from '@/lib/api';
+import { attemptsApi, sessionsApi } from '@/lib/api';
 import { useQueryClient } from '@tanstack/react-query';
 import NiceModal, { useModal } from '@ebay/nice-modal-react';
 import { defineModal } from '@/lib/modals';
-import { buildResolveConflictsInstructions } from '@/lib/conflicts';
-import type {
-  BaseCodingAgent,
-  ExecutorProfileId,
-  ConflictOp,
-} from 'shared/types';
+import type { BaseCodingAgent, ExecutorProfileId } from 'shared/types';
 
 export interface ResolveConflictsDialogProps {
   workspaceId: string;
-  conflictOp: ConflictOp;
-  sourceBranch: string | null;
-  targetBranch: string;
+  repoId: string;
   conflictedFiles: string[];
-  repoName?: string;
 }
 
 export type ResolveConflictsDialogResult =
@@ -41,14 +33,7 @@ export type ResolveConflictsDialogResult =
 
 const ResolveConflictsDialogImpl =
   NiceModal.create<ResolveConflictsDialogProps>(
-    ({
-      workspaceId,
-      conflictOp,
-      sourceBranch,
-      targetBranch,
-      conflictedFiles,
-      repoName,
-    }) => {
+    ({ workspaceId, repoId, conflictedFiles }) => {
       const modal = useModal();
       const queryClient = useQueryClient();
       const { profiles, config } = useUserSystem();
@@ -104,19 +89,6 @@ const ResolveConflictsDialogImpl =
       const effectiveProfile = userSelectedProfile ?? resolvedDefaultProfile;
       const canSubmit = Boolean(effectiveProfile && !isSubmitting);
 
-      // Build the conflict resolution instructions
-      const conflictInstructions = useMemo(
-        () =>
-          buildResolveConflictsInstructions(
-            sourceBranch,
-            targetBranch,
-            conflictedFiles,
-            conflictOp,
-            repoName
-          ),
-        [sourceBranch, targetBranch, conflictedFiles, conflictOp, repoName]
-      );
-
       const handleSubmit = useCallback(async () => {
         if (!effectiveProfile) return;
 
@@ -142,13 +114,12 @@ const ResolveConflictsDialogImpl =
             return;
           }
 
-          // Send follow-up with conflict resolution instructions
-          await sessionsApi.followUp(targetSessionId, {
-            prompt: conflictInstructions,
+          // The server builds the prompt and continues the operation once
+          // the agent has resolved every conflict
+          await attemptsApi.resolveConflicts(workspaceId, {
+            repo_id: repoId,
+            session_id: targetSessionId,
             executor_profile_id: effectiveProfile,
-            retry_process_id: null,
-            force_when_dirty: null,
-            perform_git_reset: null,
           });
 
           // Invalidate queries and wait for them to complete
@@ -186,7 +157,7 @@ const ResolveConflictsDialogImpl =
         selectedSessionId,
         createNewSession,
         workspaceId,
-        conflictInstructions,
+        repoId,
         queryClient,
         selectSession,
         modal,
@@ -467,6 +467,64 @@ export function GeneralSettingsSection() {
             ) : null}
           </div>
         </SettingsField>
+
+        <SettingsCheckbox
+          id="auto-start-dependent-tasks"
+          label={t('settings.general.taskExecution.autoStartDependents.label')}
+          description={t(
+            'settings.general.taskExecution.autoStartDependents.helper'
+          )}
+          checked={draft?.auto_start_dependent_tasks ?? false}
+          onChange={(checked) =>
+            updateDraft({ auto_start_dependent_tasks: checked })
+          }
+        />
+
+        <SettingsField
+          label={t('settings.general.taskExecution.maxConcurrentAgents.label')}
+          description={t(
+            'settings.general.taskExecution.maxConcurrentAgents.helper'
+          )}
+        >
+          <SettingsInput
+            value={draft?.concurrency.max_concurrent_agents?.toString() ?? ''}
+            onChange={(value) => {
+              const parsed = parseInt(value, 10);
+              updateDraft({
+                concurrency: {
+                  ...draft!.concurrency,
+                  max_concurrent_agents:
+                    Number.isNaN(parsed) || parsed < 1 ? null : parsed,
+                },
+              });
+            }}
+            placeholder={t(
+              'settings.general.taskExecution.maxConcurrentAgents.placeholder'
+            )}
+          />
+        </SettingsField>
+
+        <SettingsField
+          label={t('settings.general.taskExecution.checkFixAttempts.label')}
+          description={t(
+            'settings.general.taskExecution.checkFixAttempts.helper'
+          )}
+        >
+          <SettingsInput
+            value={draft?.checks.auto_fix_attempts.toString() ?? ''}
+            onChange={(value) => {
+              const parsed = parseInt(value, 10);
+              updateDraft({
+                checks: {
+                  ...draft!.checks,
+                  auto_fix_attempts:
+                    Number.isNaN(parsed) || parsed < 0 ? 0 : parsed,
+                },
+              });
+            }}
+            placeholder="0"
+          />
+        </SettingsField>
       </SettingsCard>
 
       {/* Git */}
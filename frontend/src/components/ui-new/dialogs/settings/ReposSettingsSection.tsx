@@ -4,9 +4,10 @@ import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { isEqual } from 'lodash';
 import { GitBranchIcon, SpinnerIcon } from '@phosphor-icons/react';
 import { useRepoBranches } from '@/hooks/useRepoBranches';
+import { repoConfigKeys } from '@/hooks/useRepoConfigs';
 import { useScriptPlaceholders } from '@/hooks/useScriptPlaceholders';
 import { repoApi } from '@/lib/api';
-import type { Repo, UpdateRepo } from 'shared/types';
+import type { MergeStrategy, Repo, UpdateRepo } from 'shared/types';
 import { SearchableDropdownContainer } from '../../containers/SearchableDropdownContainer';
 import {
   DropdownMenu,
@@ -15,6 +16,7 @@ import {
   DropdownMenuTrigger,
   DropdownMenuTriggerButton,
 } from '../../primitives/Dropdown';
+import { PrimaryButton } from '../../primitives/PrimaryButton';
 import {
   SettingsCard,
   SettingsField,
@@ -22,8 +24,25 @@ import {
   SettingsTextarea,
   SettingsCheckbox,
   SettingsSaveBar,
+  SettingsSelect,
 } from './SettingsComponents';
 
+const APPROVAL_POLICY_PLACEHOLDER = `{
+  "rules": [
+    { "name": "tests", "action": "allow", "tool": "bash", "command": "cargo test*" },
+    { "name": "push", "action": "ask", "command": "git push*" }
+  ]
+}`;
+
+// Config file settings that run commands, and so need the user's approval
+const CONFIG_FILE_SCRIPTS = [
+  'setup_script',
+  'cleanup_script',
+  'archive_script',
+  'dev_server_script',
+  'check_script',
+];
+
 interface RepoScriptsFormState {
   display_name: string;
   default_working_dir: string;
@@ -32,8 +51,11 @@ interface RepoScriptsFormState {
   parallel_setup_script: boolean;
   cleanup_script: string;
   archive_script: string;
+  check_script: string;
   copy_files: string;
   dev_server_script: string;
+  approval_policy: string;
+  default_merge_strategy: MergeStrategy;
 }
 
 function repoToFormState(repo: Repo): RepoScriptsFormState {
@@ -45,8 +67,11 @@ function repoToFormState(repo: Repo): RepoScriptsFormState {
     parallel_setup_script: repo.parallel_setup_script,
     cleanup_script: repo.cleanup_script ?? '',
     archive_script: repo.archive_script ?? '',
+    check_script: repo.check_script ?? '',
     copy_files: repo.copy_files ?? '',
     dev_server_script: repo.dev_server_script ?? '',
+    approval_policy: repo.approval_policy ?? '',
+    default_merge_strategy: repo.default_merge_strategy ?? 'squash',
   };
 }
 
@@ -94,6 +119,17 @@ export function ReposSettingsSection({
 
   const [selectedRepo, setSelectedRepo] = useState<Repo | null>(null);
 
+  // Effective settings, to review the scripts of the repo's config file
+  const { data: repoConfig } = useQuery({
+    queryKey: repoConfigKeys.byRepo(selectedRepoId),
+    queryFn: () => repoApi.getConfig(selectedRepoId),
+    enabled: !!selectedRepoId,
+  });
+  const [approving, setApproving] = useState(false);
+  const hasApprovedScripts = !!repoConfig?.from_config_file.some((field) =>
+    CONFIG_FILE_SCRIPTS.includes(field)
+  );
+
   // Form state
   const [draft, setDraft] = useState<RepoScriptsFormState | null>(null);
   const [saving, setSaving] = useState(false);
@@ -103,6 +139,13 @@ export function ReposSettingsSection({
   // Get OS-appropriate script placeholders
   const placeholders = useScriptPlaceholders();
 
+  const mergeStrategyOptions = (
+    ['squash', 'merge_commit', 'rebase', 'fast_forward'] as MergeStrategy[]
+  ).map((strategy) => ({
+    value: strategy,
+    label: t(`settings.repos.general.mergeStrategy.options.${strategy}`),
+  }));
+
   // Check for unsaved changes
   const hasUnsavedChanges = useMemo(() => {
     if (!draft || !selectedRepo) return false;
@@ -167,9 +210,12 @@ export function ReposSettingsSection({
         setup_script: draft.setup_script.trim() || null,
         cleanup_script: draft.cleanup_script.trim() || null,
         archive_script: draft.archive_script.trim() || null,
+        check_script: draft.check_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
         parallel_setup_script: draft.parallel_setup_script,
         dev_server_script: draft.dev_server_script.trim() || null,
+        approval_policy: draft.approval_policy.trim() || null,
+        default_merge_strategy: draft.default_merge_strategy,
       };
 
       const updatedRepo = await repoApi.update(selectedRepo.id, updateData);
@@ -178,6 +224,9 @@ export function ReposSettingsSection({
       queryClient.setQueryData(['repos'], (old: Repo[] | undefined) =>
         old?.map((r) => (r.id === updatedRepo.id ? updatedRepo : r))
       );
+      queryClient.invalidateQueries({
+        queryKey: repoConfigKeys.byRepo(updatedRepo.id),
+      });
       setSuccess(true);
       setTimeout(() => setSuccess(false), 3000);
     } catch (err) {
@@ -189,6 +238,36 @@ export function ReposSettingsSection({
     }
   };
 
+  const handleConfigApproval = async (approve: boolean) => {
+    if (!selectedRepo || !repoConfig?.config_file_hash) return;
+
+    setApproving(true);
+    setError(null);
+
+    try {
+      const updatedConfig = approve
+        ? await repoApi.trustConfig(selectedRepo.id, {
+            hash: repoConfig.config_file_hash,
+          })
+        : await repoApi.untrustConfig(selectedRepo.id);
+      queryClient.setQueryData(
+        repoConfigKeys.byRepo(selectedRepo.id),
+        updatedConfig
+      );
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.repos.configFile.error')
+      );
+      queryClient.invalidateQueries({
+        queryKey: repoConfigKeys.byRepo(selectedRepo.id),
+      });
+    } finally {
+      setApproving(false);
+    }
+  };
+
   const handleDiscard = () => {
     if (!selectedRepo) return;
     setDraft(repoToFormState(selectedRepo));
@@ -373,8 +452,75 @@ export function ReposSettingsSection({
                 }
               />
             </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.general.mergeStrategy.label')}
+              description={t('settings.repos.general.mergeStrategy.helper')}
+            >
+              <SettingsSelect
+                value={draft.default_merge_strategy}
+                options={mergeStrategyOptions}
+                onChange={(value) =>
+                  updateDraft({ default_merge_strategy: value })
+                }
+              />
+            </SettingsField>
           </SettingsCard>
 
+          {/* Committed config file */}
+          {repoConfig?.config_file && (
+            <SettingsCard
+              title={t('settings.repos.configFile.title')}
+              description={t('settings.repos.configFile.description')}
+            >
+              <SettingsField
+                label={t('settings.repos.configFile.path')}
+                description=""
+                error={repoConfig.config_file_error}
+              >
+                <div className="text-sm text-low font-mono bg-secondary px-base py-half rounded-sm">
+                  {repoConfig.config_file}
+                </div>
+              </SettingsField>
+
+              {repoConfig.unapproved_scripts.length > 0 ? (
+                <>
+                  <div className="bg-warning/10 border border-warning/50 rounded-sm p-4 text-sm text-normal">
+                    {t('settings.repos.configFile.unapproved')}
+                  </div>
+                  {repoConfig.unapproved_scripts.map((script) => (
+                    <SettingsField key={script.name} label={script.name}>
+                      <pre className="text-sm font-mono bg-secondary px-base py-half rounded-sm whitespace-pre-wrap break-all">
+                        {script.script}
+                      </pre>
+                    </SettingsField>
+                  ))}
+                  <PrimaryButton
+                    value={t('settings.repos.configFile.approve')}
+                    actionIcon={approving ? 'spinner' : undefined}
+                    onClick={() => handleConfigApproval(true)}
+                    disabled={approving}
+                  />
+                </>
+              ) : (
+                hasApprovedScripts && (
+                  <div className="flex items-center justify-between gap-base">
+                    <p className="text-sm text-low">
+                      {t('settings.repos.configFile.approved')}
+                    </p>
+                    <PrimaryButton
+                      variant="tertiary"
+                      value={t('settings.repos.configFile.revoke')}
+                      actionIcon={approving ? 'spinner' : undefined}
+                      onClick={() => handleConfigApproval(false)}
+                      disabled={approving}
+                    />
+                  </div>
+                )
+              )}
+            </SettingsCard>
+          )}
+
           {/* Scripts settings */}
           <SettingsCard
             title={t('settings.repos.scripts.title')}
@@ -439,6 +585,18 @@ export function ReposSettingsSection({
               />
             </SettingsField>
 
+            <SettingsField
+              label={t('settings.repos.scripts.check.label')}
+              description={t('settings.repos.scripts.check.helper')}
+            >
+              <SettingsTextarea
+                value={draft.check_script}
+                onChange={(value) => updateDraft({ check_script: value })}
+                placeholder={placeholders.check}
+                monospace
+              />
+            </SettingsField>
+
             <SettingsField
               label={t('settings.repos.scripts.copyFiles.label')}
               description={t('settings.repos.scripts.copyFiles.helper')}
@@ -450,6 +608,19 @@ export function ReposSettingsSection({
                 rows={3}
               />
             </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.scripts.approvalPolicy.label')}
+              description={t('settings.repos.scripts.approvalPolicy.helper')}
+            >
+              <SettingsTextarea
+                value={draft.approval_policy}
+                onChange={(value) => updateDraft({ approval_policy: value })}
+                placeholder={APPROVAL_POLICY_PLACEHOLDER}
+                rows={6}
+                monospace
+              />
+            </SettingsField>
           </SettingsCard>
 
           <SettingsSaveBar
@@ -104,6 +104,7 @@ export const useConversationHistory = ({
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
         ep.run_reason === 'archivescript' ||
+        ep.run_reason === 'checkscript' ||
         ep.run_reason === 'codingagent'
     );
   }, [executionProcessesRaw]);
@@ -331,6 +332,9 @@ export const useConversationHistory = ({
               case 'ArchiveScript':
                 toolName = 'Archive Script';
                 break;
+              case 'CheckScript':
+                toolName = 'Check Script';
+                break;
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
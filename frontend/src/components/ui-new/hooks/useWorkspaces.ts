@@ -25,7 +25,12 @@ export interface SidebarWorkspace {
   hasRunningDevServer?: boolean;
   hasUnseenActivity?: boolean;
   latestProcessCompletedAt?: string;
-  latestProcessStatus?: 'running' | 'completed' | 'failed' | 'killed';
+  latestProcessStatus?:
+    | 'queued'
+    | 'running'
+    | 'completed'
+    | 'failed'
+    | 'killed';
   prStatus?: 'open' | 'merged' | 'closed' | 'unknown';
 }
 
@@ -26,6 +26,7 @@ const RUN_REASON_LABELS: Record<ExecutionProcessRunReason, string> = {
   setupscript: 'Setup Script',
   cleanupscript: 'Cleanup Script',
   archivescript: 'Archive Script',
+  checkscript: 'Check Script',
   devserver: 'Dev Server',
 };
 
@@ -35,10 +36,12 @@ const RUN_REASON_ICONS: Record<ExecutionProcessRunReason, typeof TerminalIcon> =
     setupscript: GearIcon,
     cleanupscript: GearIcon,
     archivescript: GearIcon,
+    checkscript: GearIcon,
     devserver: GlobeIcon,
   };
 
 const STATUS_COLORS: Record<ExecutionProcessStatus, string> = {
+  queued: 'bg-low',
   running: 'bg-info',
   completed: 'bg-success',
   failed: 'bg-destructive',
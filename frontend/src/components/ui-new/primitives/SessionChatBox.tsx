@@ -194,6 +194,10 @@ export function SessionChatBox({
 
   const supportsContextUsage =
     agent && capabilities?.[agent]?.includes(BaseAgentCapability.CONTEXT_USAGE);
+  const agentCapabilities = agent ? capabilities?.[agent] : undefined;
+  const stoppedOnRestart =
+    agentCapabilities !== undefined &&
+    !agentCapabilities.includes(BaseAgentCapability.SURVIVES_RESTART);
 
   // Determine if in feedback mode, edit mode, or approval mode
   const isInFeedbackMode = feedbackMode?.isActive ?? false;
@@ -501,6 +505,21 @@ export function SessionChatBox({
       );
     }
 
+    // Agents the server talks to after starting them die with the server
+    if (status === 'running' && stoppedOnRestart) {
+      banners.push(
+        <div
+          key="stopped-on-restart"
+          className="bg-secondary border-b px-double py-base flex items-center gap-base"
+        >
+          <WarningIcon className="h-4 w-4 text-low" />
+          <span className="text-sm text-low">
+            {t('conversation.stoppedOnRestart')}
+          </span>
+        </div>
+      );
+    }
+
     return banners.length > 0 ? <>{banners}</> : null;
   };
 
@@ -216,6 +216,7 @@ export function ChatAggregatedDiffEntries({
         denied: 5,
         timed_out: 4,
         pending_approval: 3,
+        auto_approved: 2,
         created: 2,
         success: 1,
       };
@@ -51,7 +51,9 @@ export function ChatSubagentEntry({
       statusType === 'denied' ||
       statusType === 'timed_out';
     const isPending =
-      statusType === 'created' || statusType === 'pending_approval';
+      statusType === 'created' ||
+      statusType === 'pending_approval' ||
+      statusType === 'auto_approved';
 
     if (isSuccess) {
       return (
@@ -16,7 +16,9 @@ export function ToolStatusDot({ status, className }: ToolStatusDotProps) {
     statusType === 'denied' ||
     statusType === 'timed_out';
   const isPending =
-    statusType === 'created' || statusType === 'pending_approval';
+    statusType === 'created' ||
+    statusType === 'pending_approval' ||
+    statusType === 'auto_approved';
 
   return (
     <span className={cn('inline-flex', className)}>
@@ -11,6 +11,8 @@ import { useTerminal } from '@/contexts/TerminalContext';
 interface XTermInstanceProps {
   tabId: string;
   workspaceId: string;
+  /** Existing session to attach to; a new shell is started otherwise */
+  sessionId?: string;
   isActive: boolean;
   onClose?: () => void;
 }
@@ -18,6 +20,7 @@ interface XTermInstanceProps {
 export function XTermInstance({
   tabId,
   workspaceId,
+  sessionId,
   isActive,
   onClose,
 }: XTermInstanceProps) {
@@ -26,17 +29,23 @@ export function XTermInstance({
   const terminalRef = useRef<Terminal | null>(null);
   const fitAddonRef = useRef<FitAddon | null>(null);
   const initialSizeRef = useRef({ cols: 80, rows: 24 });
+  // Later session ids come from this terminal's own connection
+  const initialSessionIdRef = useRef(sessionId);
   const { theme } = useTheme();
   const {
     registerTerminalInstance,
     getTerminalInstance,
     createTerminalConnection,
     getTerminalConnection,
+    setTabSession,
   } = useTerminal();
 
   const endpoint = useMemo(() => {
     const protocol = window.location.protocol === 'https:' ? 'https:' : 'http:';
     const host = window.location.host;
+    if (initialSessionIdRef.current) {
+      return `${protocol}//${host}/api/terminal/ws?session_id=${initialSessionIdRef.current}`;
+    }
     return `${protocol}//${host}/api/terminal/ws?workspace_id=${workspaceId}&cols=${initialSizeRef.current.cols}&rows=${initialSizeRef.current.rows}`;
   }, [workspaceId]);
 
@@ -90,7 +99,13 @@ export function XTermInstance({
         tabId,
         endpoint,
         (data) => terminal?.write(data),
-        onClose
+        onClose,
+        (attachedSessionId) => {
+          // The scrollback replay that follows redraws the screen
+          terminal.reset();
+          getTerminalConnection(tabId)?.resize(terminal.cols, terminal.rows);
+          setTabSession(workspaceId, tabId, attachedSessionId);
+        }
       );
     }
 
@@ -110,12 +125,14 @@ export function XTermInstance({
     };
   }, [
     tabId,
+    workspaceId,
     endpoint,
     onClose,
     getTerminalInstance,
     registerTerminalInstance,
     createTerminalConnection,
     getTerminalConnection,
+    setTabSession,
   ]);
 
   useEffect(() => {
@@ -24,6 +24,7 @@ export function TerminalPanel({
           key={tab.id}
           tabId={tab.id}
           workspaceId={workspaceId}
+          sessionId={tab.sessionId}
           isActive={tab.id === activeTabId}
           onClose={() => onTabClose(tab.id)}
         />
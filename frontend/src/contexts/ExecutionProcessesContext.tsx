@@ -55,7 +55,8 @@ export const ExecutionProcessesProvider: React.FC<{
           (process.run_reason === 'codingagent' ||
             process.run_reason === 'setupscript' ||
             process.run_reason === 'cleanupscript' ||
-            process.run_reason === 'archivescript') &&
+            process.run_reason === 'archivescript' ||
+            process.run_reason === 'checkscript') &&
           process.status === 'running'
       ),
     [visible]
@@ -7,6 +7,7 @@ import {
   ReactNode,
 } from 'react';
 import { createHmrContext } from '@/lib/hmrContext.ts';
+import { terminalApi } from '@/lib/api';
 import type { Terminal } from '@xterm/xterm';
 import type { FitAddon } from '@xterm/addon-fit';
 
@@ -20,6 +21,8 @@ export interface TerminalTab {
   title: string;
   workspaceId: string;
   cwd: string;
+  /** Server side session, known once the terminal has attached */
+  sessionId?: string;
 }
 
 interface TerminalConnection {
@@ -34,7 +37,12 @@ interface TerminalState {
 }
 
 type TerminalAction =
-  | { type: 'CREATE_TAB'; workspaceId: string; cwd: string }
+  | {
+      type: 'CREATE_TAB';
+      workspaceId: string;
+      cwd: string;
+      sessionId?: string;
+    }
   | { type: 'CLOSE_TAB'; workspaceId: string; tabId: string }
   | { type: 'SET_ACTIVE_TAB'; workspaceId: string; tabId: string }
   | {
@@ -43,6 +51,12 @@ type TerminalAction =
       tabId: string;
       title: string;
     }
+  | {
+      type: 'SET_TAB_SESSION';
+      workspaceId: string;
+      tabId: string;
+      sessionId: string;
+    }
   | { type: 'CLEAR_WORKSPACE_TABS'; workspaceId: string };
 
 function generateTabId(): string {
@@ -67,13 +81,14 @@ function terminalReducer(
 ): TerminalState {
   switch (action.type) {
     case 'CREATE_TAB': {
-      const { workspaceId, cwd } = action;
+      const { workspaceId, cwd, sessionId } = action;
       const existingTabs = state.tabsByWorkspace[workspaceId] || [];
       const newTab: TerminalTab = {
         id: generateTabId(),
         title: `Terminal ${existingTabs.length + 1}`,
         workspaceId,
         cwd,
+        sessionId,
       };
       return {
         ...state,
@@ -141,6 +156,20 @@ function terminalReducer(
       };
     }
 
+    case 'SET_TAB_SESSION': {
+      const { workspaceId, tabId, sessionId } = action;
+      const tabs = state.tabsByWorkspace[workspaceId] || [];
+      return {
+        ...state,
+        tabsByWorkspace: {
+          ...state.tabsByWorkspace,
+          [workspaceId]: tabs.map((t) =>
+            t.id === tabId ? { ...t, sessionId } : t
+          ),
+        },
+      };
+    }
+
     case 'CLEAR_WORKSPACE_TABS': {
       const { workspaceId } = action;
       const restTabs = Object.fromEntries(
@@ -167,10 +196,15 @@ function terminalReducer(
 interface TerminalContextType {
   getTabsForWorkspace: (workspaceId: string) => TerminalTab[];
   getActiveTab: (workspaceId: string) => TerminalTab | null;
-  createTab: (workspaceId: string, cwd: string) => void;
+  createTab: (workspaceId: string, cwd: string, sessionId?: string) => void;
   closeTab: (workspaceId: string, tabId: string) => void;
   setActiveTab: (workspaceId: string, tabId: string) => void;
   updateTabTitle: (workspaceId: string, tabId: string, title: string) => void;
+  setTabSession: (
+    workspaceId: string,
+    tabId: string,
+    sessionId: string
+  ) => void;
   clearWorkspaceTabs: (workspaceId: string) => void;
   // Terminal instance management
   registerTerminalInstance: (
@@ -185,7 +219,8 @@ interface TerminalContextType {
     tabId: string,
     endpoint: string,
     onData: (data: string) => void,
-    onExit?: () => void
+    onExit?: () => void,
+    onAttach?: (sessionId: string) => void
   ) => {
     send: (data: string) => void;
     resize: (cols: number, rows: number) => void;
@@ -218,7 +253,14 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
 
   // Store callback refs for each connection to prevent stale closures
   const connectionCallbacksRef = useRef<
-    Map<string, { onData: (data: string) => void; onExit?: () => void }>
+    Map<
+      string,
+      {
+        onData: (data: string) => void;
+        onExit?: () => void;
+        onAttach?: (sessionId: string) => void;
+      }
+    >
   >(new Map());
 
   // Store reconnection state for each connection
@@ -251,9 +293,12 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
     [state.tabsByWorkspace, state.activeTabByWorkspace]
   );
 
-  const createTab = useCallback((workspaceId: string, cwd: string) => {
-    dispatch({ type: 'CREATE_TAB', workspaceId, cwd });
-  }, []);
+  const createTab = useCallback(
+    (workspaceId: string, cwd: string, sessionId?: string) => {
+      dispatch({ type: 'CREATE_TAB', workspaceId, cwd, sessionId });
+    },
+    []
+  );
 
   const closeTerminalConnection = useCallback((tabId: string) => {
     // Mark as intentionally closed to prevent reconnection
@@ -276,6 +321,15 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
 
   const closeTab = useCallback(
     (workspaceId: string, tabId: string) => {
+      // Closing a tab ends its shell; detaching alone would leave it running
+      const tab = state.tabsByWorkspace[workspaceId]?.find(
+        (t) => t.id === tabId
+      );
+      if (tab?.sessionId) {
+        terminalApi.closeSession(tab.sessionId).catch(() => {
+          // The shell may already have exited
+        });
+      }
       // Dispose the terminal instance when closing the tab
       const instance = terminalInstancesRef.current.get(tabId);
       if (instance) {
@@ -286,7 +340,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       closeTerminalConnection(tabId);
       dispatch({ type: 'CLOSE_TAB', workspaceId, tabId });
     },
-    [closeTerminalConnection]
+    [state.tabsByWorkspace, closeTerminalConnection]
   );
 
   const setActiveTab = useCallback((workspaceId: string, tabId: string) => {
@@ -300,9 +354,17 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
     []
   );
 
+  const setTabSession = useCallback(
+    (workspaceId: string, tabId: string, sessionId: string) => {
+      dispatch({ type: 'SET_TAB_SESSION', workspaceId, tabId, sessionId });
+    },
+    []
+  );
+
   const clearWorkspaceTabs = useCallback(
     (workspaceId: string) => {
-      // Dispose all terminal instances for this workspace
+      // Detach from this workspace's terminals; their sessions keep running on
+      // the server and are restored when the workspace is opened again
       const tabs = state.tabsByWorkspace[workspaceId] || [];
       tabs.forEach((tab) => {
         const instance = terminalInstancesRef.current.get(tab.id);
@@ -310,7 +372,6 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
           instance.terminal.dispose();
           terminalInstancesRef.current.delete(tab.id);
         }
-        // Close WebSocket connections
         closeTerminalConnection(tab.id);
       });
       dispatch({ type: 'CLEAR_WORKSPACE_TABS', workspaceId });
@@ -341,7 +402,8 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       tabId: string,
       endpoint: string,
       onData: (data: string) => void,
-      onExit?: () => void
+      onExit?: () => void,
+      onAttach?: (sessionId: string) => void
     ) => {
       // Close existing connection if any
       const existing = terminalConnectionsRef.current.get(tabId);
@@ -350,7 +412,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       }
 
       // Store callbacks in ref so they can be updated without recreating connection
-      connectionCallbacksRef.current.set(tabId, { onData, onExit });
+      connectionCallbacksRef.current.set(tabId, { onData, onExit, onAttach });
 
       // Initialize or reset reconnection state
       const existingReconnectState = reconnectStateRef.current.get(tabId);
@@ -371,7 +433,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
         }
 
         // Create new WebSocket
-        const wsEndpoint = endpoint.replace(/^http/, 'ws');
+        const wsEndpoint = reconnectState.endpoint.replace(/^http/, 'ws');
         const ws = new WebSocket(wsEndpoint);
 
         ws.onopen = () => {
@@ -386,7 +448,16 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
           try {
             const msg = JSON.parse(event.data);
             const callbacks = connectionCallbacksRef.current.get(tabId);
-            if (msg.type === 'output' && msg.data && callbacks) {
+            if (msg.type === 'session' && msg.session_id) {
+              // Reconnects attach to this session instead of a new shell
+              const state = reconnectStateRef.current.get(tabId);
+              if (state) {
+                const url = new URL(state.endpoint);
+                url.search = `?session_id=${msg.session_id}`;
+                state.endpoint = url.toString();
+              }
+              callbacks?.onAttach?.(msg.session_id);
+            } else if (msg.type === 'output' && msg.data && callbacks) {
               callbacks.onData(decodeBase64(msg.data));
             } else if (msg.type === 'exit' && callbacks) {
               callbacks.onExit?.();
@@ -474,6 +545,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       closeTab,
       setActiveTab,
       updateTabTitle,
+      setTabSession,
       clearWorkspaceTabs,
       registerTerminalInstance,
       getTerminalInstance,
@@ -488,6 +560,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       closeTab,
       setActiveTab,
       updateTabTitle,
+      setTabSession,
       clearWorkspaceTabs,
       registerTerminalInstance,
       getTerminalInstance,
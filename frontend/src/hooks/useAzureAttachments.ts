@@ -4,7 +4,7 @@ import {
   computeFileHash,
   confirmAttachmentUpload,
   initAttachmentUpload,
-  uploadToAzure,
+  uploadToStorage,
 } from '@/lib/remoteApi';
 
 // ---------------------------------------------------------------------------
@@ -133,7 +133,7 @@ export function useAzureAttachments({
           });
 
           if (!initResult.skip_upload) {
-            await uploadToAzure(initResult.upload_url, file, (pct) => {
+            await uploadToStorage(initResult.upload_url, file, (pct) => {
               setPendingAttachments((prev) =>
                 prev.map((p) => (p.file === file ? { ...p, progress: pct } : p))
               );
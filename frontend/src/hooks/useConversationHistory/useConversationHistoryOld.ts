@@ -53,6 +53,7 @@ export const useConversationHistoryOld = ({
         ep.run_reason === 'setupscript' ||
         ep.run_reason === 'cleanupscript' ||
         ep.run_reason === 'archivescript' ||
+        ep.run_reason === 'checkscript' ||
         ep.run_reason === 'codingagent'
     );
   }, [executionProcessesRaw]);
@@ -264,6 +265,9 @@ export const useConversationHistoryOld = ({
               case 'ArchiveScript':
                 toolName = 'Archive Script';
                 break;
+              case 'CheckScript':
+                toolName = 'Check Script';
+                break;
               case 'ToolInstallScript':
                 toolName = 'Tool Install Script';
                 break;
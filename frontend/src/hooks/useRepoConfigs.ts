@@ -0,0 +1,44 @@
+import { useQueries } from '@tanstack/react-query';
+import { useMemo } from 'react';
+import { repoApi } from '@/lib/api';
+import type { EffectiveRepoConfig } from 'shared/types';
+
+export const repoConfigKeys = {
+  all: ['repoConfig'] as const,
+  byRepo: (repoId: string) => ['repoConfig', repoId] as const,
+};
+
+interface UseRepoConfigsResult {
+  configsByRepo: Record<string, EffectiveRepoConfig>;
+  isLoading: boolean;
+  isError: boolean;
+}
+
+/**
+ * Effective settings of each repo, including values from its committed
+ * `.vibe-kanban.toml`
+ */
+export function useRepoConfigs(repoIds: string[]): UseRepoConfigsResult {
+  const queries = useQueries({
+    queries: repoIds.map((repoId) => ({
+      queryKey: repoConfigKeys.byRepo(repoId),
+      queryFn: () => repoApi.getConfig(repoId),
+      staleTime: 60_000,
+    })),
+  });
+
+  const configsByRepo = useMemo(() => {
+    const result: Record<string, EffectiveRepoConfig> = {};
+    repoIds.forEach((repoId, idx) => {
+      if (queries[idx]?.data) {
+        result[repoId] = queries[idx].data;
+      }
+    });
+    return result;
+  }, [repoIds, queries]);
+
+  const isLoading = queries.some((q) => q.isLoading);
+  const isError = queries.some((q) => q.isError);
+
+  return { configsByRepo, isLoading, isError };
+}
@@ -9,9 +9,9 @@ interface UseSessionQueueInteractionOptions {
 }
 
 interface UseSessionQueueInteractionResult {
-  /** Whether a message is currently queued */
+  /** Whether any message is currently queued */
   isQueued: boolean;
-  /** The queued message content, if any */
+  /** The most recently queued message content, if any */
   queuedMessage: string | null;
   /** Whether a queue operation is in progress */
   isQueueLoading: boolean;
@@ -20,7 +20,7 @@ interface UseSessionQueueInteractionResult {
     message: string,
     executorProfileId: ExecutorProfileId
   ) => Promise<void>;
-  /** Cancel the queued message */
+  /** Remove the most recently queued message */
   cancelQueue: () => Promise<void>;
   /** Refresh queue status from server */
   refreshQueueStatus: () => Promise<void>;
@@ -46,10 +46,11 @@ export function useSessionQueueInteraction({
     });
 
   const isQueued = queueStatus.status === 'queued';
-  const queuedMessageData = isQueued
-    ? (queueStatus as Extract<QueueStatus, { status: 'queued' }>).message
-    : null;
-  const queuedMessage = queuedMessageData?.data.message ?? null;
+  const queuedMessages = isQueued
+    ? (queueStatus as Extract<QueueStatus, { status: 'queued' }>).messages
+    : [];
+  const lastQueued = queuedMessages[queuedMessages.length - 1] ?? null;
+  const queuedMessage = lastQueued?.data.message ?? null;
 
   // Mutation for queueing a message
   const queueMutation = useMutation({
@@ -65,9 +66,9 @@ export function useSessionQueueInteraction({
     },
   });
 
-  // Mutation for cancelling the queue
+  // Mutation for removing a single queued message
   const cancelMutation = useMutation({
-    mutationFn: () => queueApi.cancel(sessionId!),
+    mutationFn: (messageId: string) => queueApi.remove(sessionId!, messageId),
     onSuccess: (status) => {
       queryClient.setQueryData([QUEUE_STATUS_KEY, sessionId], status);
     },
@@ -85,9 +86,9 @@ export function useSessionQueueInteraction({
   );
 
   const cancelQueue = useCallback(async () => {
-    if (!sessionId) return;
-    await cancelMutation.mutateAsync();
-  }, [sessionId, cancelMutation]);
+    if (!sessionId || !lastQueued) return;
+    await cancelMutation.mutateAsync(lastQueued.id);
+  }, [sessionId, lastQueued, cancelMutation]);
 
   const refreshQueueStatus = useCallback(async () => {
     if (!sessionId) return;
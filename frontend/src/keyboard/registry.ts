@@ -178,6 +178,14 @@ export const sequentialBindings: SequentialBinding[] = [
     group: 'Git',
     actionId: 'git-merge',
   },
+  {
+    id: 'seq-git-queue-merge',
+    keys: ['x', 'q'],
+    scopes: [Scope.WORKSPACE],
+    description: 'Add branch to merge queue',
+    group: 'Git',
+    actionId: 'git-queue-merge',
+  },
   {
     id: 'seq-git-rebase',
     keys: ['x', 'r'],
@@ -67,6 +67,7 @@ export function useWorkspaceShortcuts() {
 
   useHotkeys('x>p', () => execute(Actions.GitCreatePR), OPTIONS);
   useHotkeys('x>m', () => execute(Actions.GitMerge), OPTIONS);
+  useHotkeys('x>q', () => execute(Actions.GitQueueMerge), OPTIONS);
   useHotkeys('x>r', () => execute(Actions.GitRebase), OPTIONS);
   useHotkeys('x>u', () => execute(Actions.GitPush), OPTIONS);
 
@@ -9,13 +9,17 @@ import {
   EditorType,
   CreatePrApiRequest,
   CreateTask,
+  ApiToken,
   CreateAndStartTaskRequest,
+  CreateApiTokenRequest,
+  CreateApiTokenResponse,
   CreateTaskAttemptBody,
   CreateTag,
   DirectoryListResponse,
   DirectoryEntry,
   ExecutionProcess,
   ExecutionProcessRepoState,
+  FanOutComparison,
   GitBranch,
   Project,
   Repo,
@@ -24,6 +28,8 @@ import {
   CreateProjectRepo,
   UpdateRepo,
   SearchMode,
+  EffectiveRepoConfig,
+  TrustRepoConfigRequest,
   SearchResult,
   Task,
   TaskRelationships,
@@ -77,13 +83,20 @@ import {
   TokenResponse,
   CurrentUserResponse,
   QueueStatus,
+  ReorderQueueRequest,
+  EnqueueMergeRequest,
+  MergeQueueItem,
   PrCommentsResponse,
   MergeTaskAttemptRequest,
   PushTaskAttemptRequest,
   RepoBranchStatus,
   AbortConflictsRequest,
   ContinueRebaseRequest,
+  ResolveConflictsRequest,
   Session,
+  TranscriptFormat,
+  TokenUsageQuery,
+  TokenUsageReport,
   Workspace,
   StartReviewRequest,
   ReviewError,
@@ -95,6 +108,8 @@ import {
   CreateFromPrError,
   MigrationRequest,
   MigrationResponse,
+  CreateTerminalSession,
+  TerminalSession,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -428,6 +443,9 @@ export const sessionsApi = {
     });
     return handleApiResponse<void>(response);
   },
+
+  getTranscriptUrl: (sessionId: string, format: TranscriptFormat): string =>
+    `/api/sessions/${sessionId}/transcript?format=${format}`,
 };
 
 // Task Attempts APIs
@@ -483,6 +501,23 @@ export const attemptsApi = {
     return handleApiResponse<Workspace>(response);
   },
 
+  /** Compare the attempts of the fan-out the workspace was started in */
+  getFanOut: async (attemptId: string): Promise<FanOutComparison> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/fan-out`
+    );
+    return handleApiResponse<FanOutComparison>(response);
+  },
+
+  /** Keep this attempt and archive the other workspaces of its fan-out */
+  keepFanOut: async (attemptId: string): Promise<Workspace> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/fan-out/keep`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<Workspace>(response);
+  },
+
   stop: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
       method: 'POST',
@@ -688,6 +723,20 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  resolveConflicts: async (
+    attemptId: string,
+    data: ResolveConflictsRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
   createPR: async (
     attemptId: string,
     data: CreatePrApiRequest
@@ -884,6 +933,29 @@ export const repoApi = {
     return handleApiResponse<GitBranch[]>(response);
   },
 
+  getConfig: async (repoId: string): Promise<EffectiveRepoConfig> => {
+    const response = await makeRequest(`/api/repos/${repoId}/config`);
+    return handleApiResponse<EffectiveRepoConfig>(response);
+  },
+
+  trustConfig: async (
+    repoId: string,
+    data: TrustRepoConfigRequest
+  ): Promise<EffectiveRepoConfig> => {
+    const response = await makeRequest(`/api/repos/${repoId}/config/trust`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<EffectiveRepoConfig>(response);
+  },
+
+  untrustConfig: async (repoId: string): Promise<EffectiveRepoConfig> => {
+    const response = await makeRequest(`/api/repos/${repoId}/config/trust`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<EffectiveRepoConfig>(response);
+  },
+
   init: async (data: {
     parent_path: string;
     folder_name: string;
@@ -976,6 +1048,47 @@ export const configApi = {
   },
 };
 
+export const tokenUsageApi = {
+  get: async (
+    query: Partial<TokenUsageQuery> = {}
+  ): Promise<TokenUsageReport> => {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(query)) {
+      if (value) params.set(key, value);
+    }
+    const queryString = params.toString();
+    const response = await makeRequest(
+      `/api/token-usage${queryString ? `?${queryString}` : ''}`
+    );
+    return handleApiResponse<TokenUsageReport>(response);
+  },
+};
+
+// API tokens for clients on other machines
+export const apiTokensApi = {
+  list: async (): Promise<ApiToken[]> => {
+    const response = await makeRequest('/api/api-tokens');
+    return handleApiResponse<ApiToken[]>(response);
+  },
+
+  create: async (
+    data: CreateApiTokenRequest
+  ): Promise<CreateApiTokenResponse> => {
+    const response = await makeRequest('/api/api-tokens', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CreateApiTokenResponse>(response);
+  },
+
+  revoke: async (id: string): Promise<void> => {
+    const response = await makeRequest(`/api/api-tokens/${id}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
 // Task Tags APIs (all tags are global)
 export const tagsApi = {
   list: async (params?: TagSearchParams): Promise<Tag[]> => {
@@ -1409,7 +1522,7 @@ export const queueApi = {
   },
 
   /**
-   * Cancel a queued follow-up message
+   * Cancel all queued follow-up messages
    */
   cancel: async (sessionId: string): Promise<QueueStatus> => {
     const response = await makeRequest(`/api/sessions/${sessionId}/queue`, {
@@ -1418,6 +1531,57 @@ export const queueApi = {
     return handleApiResponse<QueueStatus>(response);
   },
 
+  /**
+   * Edit a single queued follow-up message
+   */
+  update: async (
+    sessionId: string,
+    messageId: string,
+    data: { message: string; executor_profile_id: ExecutorProfileId }
+  ): Promise<QueueStatus> => {
+    const response = await makeRequest(
+      `/api/sessions/${sessionId}/queue/${messageId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<QueueStatus>(response);
+  },
+
+  /**
+   * Remove a single queued follow-up message
+   */
+  remove: async (
+    sessionId: string,
+    messageId: string
+  ): Promise<QueueStatus> => {
+    const response = await makeRequest(
+      `/api/sessions/${sessionId}/queue/${messageId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<QueueStatus>(response);
+  },
+
+  /**
+   * Reorder the queued follow-up messages of a session
+   */
+  reorder: async (
+    sessionId: string,
+    data: ReorderQueueRequest
+  ): Promise<QueueStatus> => {
+    const response = await makeRequest(
+      `/api/sessions/${sessionId}/queue/reorder`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<QueueStatus>(response);
+  },
+
   /**
    * Get the current queue status for a session
    */
@@ -1427,6 +1591,63 @@ export const queueApi = {
   },
 };
 
+// Merge queue API: workspaces waiting to be rebased, checked and merged into a repo
+export const mergeQueueApi = {
+  /**
+   * Get a repo's merge queue in merge order
+   */
+  list: async (repoId: string): Promise<MergeQueueItem[]> => {
+    const response = await makeRequest(`/api/repos/${repoId}/merge-queue`);
+    return handleApiResponse<MergeQueueItem[]>(response);
+  },
+
+  /**
+   * Add a workspace to the end of a repo's merge queue
+   */
+  enqueue: async (
+    repoId: string,
+    data: EnqueueMergeRequest
+  ): Promise<MergeQueueItem[]> => {
+    const response = await makeRequest(`/api/repos/${repoId}/merge-queue`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<MergeQueueItem[]>(response);
+  },
+
+  /**
+   * Remove an entry, stopping its check script if it is running
+   */
+  remove: async (
+    repoId: string,
+    entryId: string
+  ): Promise<MergeQueueItem[]> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/merge-queue/${entryId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<MergeQueueItem[]>(response);
+  },
+
+  /**
+   * Retry a failed or conflicted entry, resuming the queue
+   */
+  retry: async (
+    repoId: string,
+    entryId: string
+  ): Promise<MergeQueueItem[]> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/merge-queue/${entryId}/retry`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<MergeQueueItem[]>(response);
+  },
+};
+
 // Migration API
 export const migrationApi = {
   start: async (data: MigrationRequest): Promise<MigrationResponse> => {
@@ -1438,6 +1659,33 @@ export const migrationApi = {
   },
 };
 
+// Terminal API (interactive shells that outlive their websocket)
+export const terminalApi = {
+  listSessions: async (workspaceId: string): Promise<TerminalSession[]> => {
+    const response = await makeRequest(
+      `/api/terminal/sessions?workspace_id=${encodeURIComponent(workspaceId)}`
+    );
+    return handleApiResponse<TerminalSession[]>(response);
+  },
+
+  createSession: async (
+    data: CreateTerminalSession
+  ): Promise<TerminalSession> => {
+    const response = await makeRequest('/api/terminal/sessions', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TerminalSession>(response);
+  },
+
+  closeSession: async (sessionId: string): Promise<void> => {
+    const response = await makeRequest(`/api/terminal/sessions/${sessionId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+};
+
 // Search API (multi-repo file search)
 export const searchApi = {
   searchFiles: async (
@@ -122,10 +122,10 @@ export async function computeFileHash(file: File): Promise<string> {
 }
 
 // ---------------------------------------------------------------------------
-// Utility: Upload to Azure Blob Storage with progress
+// Utility: Upload to the attachment storage's presigned URL with progress
 // ---------------------------------------------------------------------------
 
-export function uploadToAzure(
+export function uploadToStorage(
   uploadUrl: string,
   file: File,
   onProgress?: (pct: number) => void
@@ -133,6 +133,7 @@ export function uploadToAzure(
   return new Promise((resolve, reject) => {
     const xhr = new XMLHttpRequest();
     xhr.open('PUT', uploadUrl, true);
+    // Required by Azure Blob Storage, ignored by the other backends
     xhr.setRequestHeader('x-ms-blob-type', 'BlockBlob');
     xhr.setRequestHeader('Content-Type', file.type);
 
@@ -145,19 +146,20 @@ export function uploadToAzure(
     }
 
     xhr.onload = () => {
-      if (xhr.status === 201) {
+      // Azure answers 201, S3 compatible storage 200
+      if (xhr.status >= 200 && xhr.status < 300) {
         resolve();
       } else {
         reject(
           new Error(
-            `Azure upload failed with status ${xhr.status}: ${xhr.statusText}`
+            `Upload failed with status ${xhr.status}: ${xhr.statusText}`
           )
         );
       }
     };
 
     xhr.onerror = () => {
-      reject(new Error('Azure upload failed: network error'));
+      reject(new Error('Upload failed: network error'));
     };
 
     xhr.send(file);
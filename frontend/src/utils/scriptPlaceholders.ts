@@ -3,6 +3,7 @@ interface ScriptPlaceholders {
   dev: string;
   cleanup: string;
   archive: string;
+  check: string;
 }
 
 interface ScriptPlaceholderStrategy {
@@ -24,6 +25,9 @@ REM This runs after coding agent execution - only if changes were made`,
       archive: `@echo off
 REM Add archive commands here...
 REM This runs when the workspace is archived`,
+      check: `@echo off
+npm test
+REM A non-zero exit keeps the merge queue from merging the workspace`,
     };
   }
 }
@@ -39,6 +43,8 @@ class UnixScriptPlaceholderStrategy implements ScriptPlaceholderStrategy {
 # This runs after coding agent execution - only if changes were made`,
       archive: `# Add archive commands here...
 # This runs when the workspace is archived`,
+      check: `npm test
+# A non-zero exit keeps the merge queue from merging the workspace`,
     };
   }
 }
@@ -18,9 +18,21 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, 
+/**
+ * JSON-encoded approval policy applied to coding agents running in this repo.
+ */
+approval_policy: string | null, 
+/**
+ * How local merges land task branches when the request doesn't pick a strategy.
+ */
+default_merge_strategy: MergeStrategy | null, 
+/**
+ * Verifies a workspace before the merge queue lands it; a non-zero exit blocks the merge.
+ */
+check_script: string | null, created_at: Date, updated_at: Date, };
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, };
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, approval_policy?: string | null, default_merge_strategy?: MergeStrategy | null, check_script?: string | null, };
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
@@ -50,6 +62,12 @@ export type CreateTask = { project_id: string, title: string, description: strin
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
 
+export type TaskDependency = { id: string, task_id: string, depends_on_task_id: string, created_at: string, };
+
+export type CreateTaskDependency = { depends_on_task_id: string, };
+
+export type TaskDependencies = { blocked_by: Array<Task>, blocking: Array<Task>, };
+
 export type DraftFollowUpData = { message: string, executor_profile_id: ExecutorProfileId, };
 
 export type DraftWorkspaceData = { message: string, project_id: string | null, repos: Array<DraftWorkspaceRepo>, selected_profile: ExecutorProfileId | null, linked_issue: DraftWorkspaceLinkedIssue | null, };
@@ -136,7 +154,11 @@ export type CreateImage = { file_path: string, original_name: string, mime_type:
 
 export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, archived: boolean, pinned: boolean, name: string | null, };
 
-export type WorkspaceWithStatus = { is_running: boolean, is_errored: boolean, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, archived: boolean, pinned: boolean, name: string | null, };
+export type WorkspaceWithStatus = { is_running: boolean, is_errored: boolean, 
+/**
+ * 1-based position in the global coding agent queue, if waiting for a slot
+ */
+queue_position: number | null, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, archived: boolean, pinned: boolean, name: string | null, };
 
 export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
 
@@ -146,13 +168,37 @@ export type ExecutionProcess = { id: string, session_id: string, run_reason: Exe
  * history view (due to restore/trimming). Hidden from logs/timeline;
  * still listed in the Processes tab.
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+dropped: boolean, 
+/**
+ * Result of the check scripts run on this coding agent turn's changes, if any ran
+ */
+check_status: CheckStatus | null, 
+/**
+ * Automatic follow-ups in a row, including this turn, sent to fix failing checks
+ */
+check_fix_attempt: bigint, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export enum ExecutionProcessStatus { queued = "queued", running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "archivescript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "archivescript" | "checkscript" | "codingagent" | "devserver";
 
-export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+export type CheckStatus = "pending" | "passed" | "failed";
+
+export type ExecutionProcessLogStats = { line_count: number, chunk_count: number, 
+/**
+ * Uncompressed size of all lines
+ */
+byte_size: number, 
+/**
+ * Size on disk after compression
+ */
+stored_size: number, };
+
+export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, 
+/**
+ * Whether the process was started to resolve the repo's in-progress conflicts
+ */
+resolves_conflicts: boolean, created_at: Date, updated_at: Date, };
 
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
@@ -164,6 +210,18 @@ export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
 export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
 
+export type MergeQueueStatus = "queued" | "checking" | "failed" | "conflict";
+
+export type MergeQueueEntry = { id: string, repo_id: string, workspace_id: string, status: MergeQueueStatus, 
+/**
+ * The check script run for the latest attempt, if any
+ */
+execution_process_id: string | null, 
+/**
+ * Why the entry failed or which files conflict
+ */
+error: string | null, created_at: Date, updated_at: Date, };
+
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
@@ -252,6 +310,14 @@ export type RegisterRepoRequest = { path: string, display_name: string | null, }
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
+export type TrustRepoConfigRequest = { 
+/**
+ * Hash of the config file version the user reviewed
+ */
+hash: string, };
+
+export type EnqueueMergeRequest = { workspace_id: string, };
+
 export type TagSearchParams = { search: string | null, };
 
 export type TokenResponse = { access_token: string, expires_at: string | null, };
@@ -286,7 +352,11 @@ export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: st
 
 export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+export type MergeTaskAttemptRequest = { repo_id: string, 
+/**
+ * Overrides the repo's default merge strategy
+ */
+strategy?: MergeStrategy, };
 
 export type PushTaskAttemptRequest = { repo_id: string, };
 
@@ -294,10 +364,29 @@ export type RenameBranchRequest = { new_branch_name: string, };
 
 export type RenameBranchResponse = { branch: string, };
 
+export type ReorderQueueRequest = { 
+/**
+ * Every queued message id of the session, in the desired send order
+ */
+message_ids: Array<string>, };
+
 export type StartReviewRequest = { executor_profile_id: ExecutorProfileId, additional_prompt: string | null, use_all_workspace_commits: boolean, };
 
 export type ReviewError = { "type": "process_already_running" };
 
+/**
+ * At most one scope may be given; without one, usage across all projects is returned
+ */
+export type TokenUsageQuery = { workspace_id: string | null, task_id: string | null, project_id: string | null, };
+
+export type CreateApiTokenRequest = { name: string, scope: ApiTokenScope, };
+
+export type CreateApiTokenResponse = { api_token: ApiToken, 
+/**
+ * The token to hand to clients. It is not stored and cannot be shown again.
+ */
+token: string, };
+
 export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
 
 export type OpenEditorResponse = { url: string | null, };
@@ -312,7 +401,11 @@ export type ImageResponse = { id: string, file_path: string, original_name: stri
 
 export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Further executor profiles to run the task with side by side, one workspace each
+ */
+fan_out?: Array<ExecutorProfileId>, };
 
 export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
 
@@ -328,6 +421,16 @@ export type ContinueRebaseRequest = { repo_id: string, };
 
 export type AbortConflictsRequest = { repo_id: string, };
 
+export type ResolveConflictsRequest = { repo_id: string, 
+/**
+ * Session to resolve the conflicts in; defaults to the workspace's latest session
+ */
+session_id: string | null, 
+/**
+ * Defaults to the executor profile last used in the session
+ */
+executor_profile_id: ExecutorProfileId | null, };
+
 export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, target_branch: string, } | { "type": "rebase_in_progress" };
 
 export type PushError = { "type": "force_push_required" };
@@ -348,7 +451,7 @@ export type GetPrCommentsQuery = { repo_id: string, };
 
 export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
 
-export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
+export type ProviderKind = "git_hub" | "azure_dev_ops" | "git_lab" | "gitea" | "unknown";
 
 export type OpenPrInfo = { number: bigint, url: string, title: string, head_branch: string, base_branch: string, };
 
@@ -430,13 +533,71 @@ export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+/**
+ * One workspace of a fan-out and how its attempt went
+ */
+export type FanOutAttempt = { workspace: Workspace, executor_profile_id: ExecutorProfileId, is_running: boolean, diff_stats: DiffStats | null, 
+/**
+ * Check script result of the latest coding agent turn that ran the checks
+ */
+check_status: CheckStatus | null, token_usage: TokenUsageSummary, 
+/**
+ * Time the coding agent spent running, summed over its turns
+ */
+duration_secs: number, };
+
+/**
+ * The workspaces started together on a task with different executor profiles
+ */
+export type FanOutComparison = { fan_out_id: string, task_id: string, attempts: Array<FanOutAttempt>, };
+
+export type CreateTerminalSession = { workspace_id: string, cols: number | null, rows: number | null, };
+
+export type TerminalSession = { id: string, workspace_id: string, created_at: string, 
+/**
+ * Number of terminals currently attached to the session
+ */
+viewers: number, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, };
+export type UnapprovedScript = { name: string, script: string, };
+
+export type EffectiveRepoConfig = { 
+/**
+ * Location of the config file, when the repository has one
+ */
+config_file: string | null, 
+/**
+ * Why the config file is being ignored
+ */
+config_file_error: string | null, 
+/**
+ * Hash of the config file's current contents, to approve its scripts with
+ */
+config_file_hash: string | null, 
+/**
+ * Scripts of the config file that don't run until the user approves this version of it
+ */
+unapproved_scripts: Array<UnapprovedScript>, setup_script: string | null, parallel_setup_script: boolean, cleanup_script: string | null, archive_script: string | null, dev_server_script: string | null, check_script: string | null, copy_files: string | null, default_target_branch: string | null, default_working_dir: string | null, executor_profile: ExecutorProfileId | null, 
+/**
+ * Names of the fields above whose value comes from the config file
+ */
+from_config_file: Array<string>, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, auto_start_dependent_tasks: boolean, concurrency: ConcurrencyConfig, 
+/**
+ * Prices keyed by model name or model name prefix, e.g. "claude-sonnet-4"
+ */
+model_prices: { [key in string]?: ModelPrice }, 
+/**
+ * Run coding agents confined to their workspace (Linux only)
+ */
+sandbox: SandboxConfig, checks: ChecksConfig, api_auth: ApiAuthConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, webhook_notifications_enabled: boolean, webhooks: Array<WebhookConfig>, };
 
@@ -462,9 +623,80 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type SendMessageShortcut = "ModifierEnter" | "Enter";
 
+/**
+ * Limits on how many coding agents may run at once. Starts beyond the limit are queued.
+ */
+export type ConcurrencyConfig = { 
+/**
+ * Maximum coding agents running across all workspaces, `None` for unlimited
+ */
+max_concurrent_agents: number | null, 
+/**
+ * Maximum coding agents running per agent type, on top of the global limit
+ */
+per_agent_limits: { [key in BaseCodingAgent]?: number }, };
+
+/**
+ * Price of a model in USD per million tokens, used to estimate what agent runs cost
+ */
+export type ModelPrice = { input: number, output: number, 
+/**
+ * Defaults to the input price
+ */
+cache_read: number | null, 
+/**
+ * Defaults to the input price
+ */
+cache_write: number | null, };
+
+export type SandboxConfig = { enabled: boolean, network: SandboxNetwork, 
+/**
+ * Extra paths the agent may write to besides its workspace
+ */
+writable_paths: Array<string>, };
+
+export type SandboxNetwork = { "mode": "allow" } | { "mode": "deny" } | { "mode": "allowlist", hosts: Array<string>, };
+
+/**
+ * Check scripts run on a coding agent's changes before its task moves to review
+ */
+export type ChecksConfig = { 
+/**
+ * Follow-ups sending failing check output back to the agent before giving up; 0 disables them
+ */
+auto_fix_attempts: number, };
+
+/**
+ * Authentication for reaching the server from other machines
+ */
+export type ApiAuthConfig = { 
+/**
+ * Require an API token from clients on other machines
+ */
+enabled: boolean, 
+/**
+ * Also require an API token from clients on this machine
+ */
+require_for_loopback: boolean, tokens: Array<ApiToken>, };
+
+/**
+ * A token clients send as `Authorization: Bearer <token>` or as the basic auth password
+ */
+export type ApiToken = { id: string, name: string, scope: ApiTokenScope, 
+/**
+ * SHA-256 of the token, the token itself is only shown when it is created
+ */
+token_hash: string, created_at: string, };
+
+/**
+ * What an API token may do. Read-only tokens are limited to read requests, and get no terminal,
+ * credentials, token management or filesystem browsing.
+ */
+export type ApiTokenScope = "read_only" | "full";
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
-export type QueuedMessage = { 
+export type QueuedMessage = { id: string, 
 /**
  * The session this message is queued for
  */
@@ -478,17 +710,46 @@ data: DraftFollowUpData,
  */
 queued_at: string, };
 
-export type QueueStatus = { "status": "empty" } | { "status": "queued", message: QueuedMessage, };
+export type QueueStatus = { "status": "empty" } | { "status": "queued", messages: Array<QueuedMessage>, };
+
+export type MergeQueueItem = { conflict_op: ConflictOp | null, conflicted_files: Array<string>, id: string, repo_id: string, workspace_id: string, status: MergeQueueStatus, execution_process_id: string | null, error: string | null, created_at: Date, updated_at: Date, };
+
+export type TranscriptFormat = "markdown" | "json" | "html";
+
+export type TokenUsageSummary = { turns: number, input_tokens: number, output_tokens: number, cache_read_tokens: number, cache_write_tokens: number, 
+/**
+ * Estimated cost in USD of the turns whose model has a configured price,
+ * `None` when no turn could be priced
+ */
+cost_usd: number | null, 
+/**
+ * Turns left out of `cost_usd` because their model has no configured price
+ */
+unpriced_turns: number, };
+
+export type ModelTokenUsage = { executor: string, model: string | null, usage: TokenUsageSummary, };
+
+export type ExecutorTokenUsage = { executor: string, usage: TokenUsageSummary, };
+
+/**
+ * Token usage and cost of a workspace, task, project or everything
+ */
+export type TokenUsageReport = { total: TokenUsageSummary, by_executor: Array<ExecutorTokenUsage>, by_model: Array<ModelTokenUsage>, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
+/**
+ * How a task branch is landed on its base branch by a local merge
+ */
+export type MergeStrategy = "squash" | "merge_commit" | "rebase" | "fast_forward";
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest | { "type": "ReviewRequest" } & ReviewRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "ArchiveScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "ArchiveScript" | "CheckScript" | "DevServer" | "ToolInstallScript";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**
@@ -499,9 +760,9 @@ working_dir: string | null, };
 
 export type ScriptRequestLanguage = "Bash";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", CUSTOM_ACP = "CUSTOM_ACP", AIDER = "AIDER" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM_ACP": CustomAcp } | { "AIDER": Aider };
 
 export type SlashCommandDescription = { 
 /**
@@ -531,11 +792,11 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "CUSTOM_ACP": CustomAcp } | { "AIDER": Aider } };
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
-export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER", CONTEXT_USAGE = "CONTEXT_USAGE" }
+export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER", CONTEXT_USAGE = "CONTEXT_USAGE", SURVIVES_RESTART = "SURVIVES_RESTART" }
 
 export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
@@ -577,6 +838,10 @@ export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-
 
 export type DroidReasoningEffort = "none" | "dynamic" | "off" | "low" | "medium" | "high";
 
+export type CustomAcp = { append_prompt: AppendPrompt, command: string, args: Array<string>, session_namespace?: string | null, model?: string | null, mode?: string | null, mcp_config_path?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type Aider = { append_prompt: AppendPrompt, model?: string | null, edit_format?: string | null, auto_commits?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
 export type AppendPrompt = string | null;
 
 export type CodingAgentInitialRequest = { prompt: string, 
@@ -647,7 +912,7 @@ value: JsonValue, };
 
 export type ToolResultValueType = { "type": "markdown" } | { "type": "json" };
 
-export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "timed_out" };
+export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "auto_approved", rule: string, } | { "status": "timed_out" };
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 